@@ -0,0 +1,12 @@
+// proto/ のgRPC定義からサーバとクライアントのコードを生成する。
+// protoc はビルド環境に入れなくてよいよう、同梱版を使う
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path()?);
+    let well_known_types = protoc_bin_vendored::include_path()?;
+
+    tonic_build::configure().compile_protos(
+        &["proto/todo/v1/todo.proto"],
+        &[std::path::Path::new("proto"), well_known_types.as_path()],
+    )?;
+    Ok(())
+}
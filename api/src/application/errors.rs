@@ -1,13 +1,65 @@
+use std::borrow::Cow;
+
 #[derive(Debug, Clone)]
 pub enum AppError {
     NotFound,
-    Validation(String),
+    /// パスはあるが、そのメソッドを受け付けていない
+    MethodNotAllowed,
+    Validation(ErrorMessage),
+    /// 入力のどの項目がなぜ不正かを項目ごとに持つ
+    InvalidFields(Vec<FieldError>),
+    PayloadTooLarge(ErrorMessage),
+    UnsupportedMediaType(ErrorMessage),
+    /// 同じ対象への別の処理と重なった
+    Conflict(ErrorMessage),
+    /// 形式は正しいが、受け付けられない内容
+    UnprocessableEntity(ErrorMessage),
+    /// 短時間にリクエストを送りすぎた
+    TooManyRequests(ErrorMessage),
+    /// ログにだけ出す内部向けの説明
     Unexpected(String),
 }
 
+/// 利用者に見せるメッセージ。文言は表示する側でコードと言語から組み立てる
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorMessage {
+    pub code: Cow<'static, str>,
+    pub params: Vec<(Cow<'static, str>, String)>,
+    /// ライブラリが返した原文（英語のまま）。翻訳した文言には混ぜず、別の項目で返す
+    pub reason: Option<String>,
+}
+
+impl ErrorMessage {
+    pub fn new(code: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            code: code.into(),
+            params: Vec::new(),
+            reason: None,
+        }
+    }
+
+    /// 文言に埋め込む値を足す（テンプレートの `{name}` を置き換える）
+    pub fn with(mut self, name: impl Into<Cow<'static, str>>, value: impl ToString) -> Self {
+        self.params.push((name.into(), value.to_string()));
+        self
+    }
+
+    /// 翻訳しない原文の説明を添える
+    pub fn with_reason(mut self, reason: impl Into<String>) -> Self {
+        self.reason = Some(reason.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    pub field: String,
+    pub message: ErrorMessage,
+}
+
 impl AppError {
-    pub fn validation(message: impl Into<String>) -> Self {
-        Self::Validation(message.into())
+    pub fn validation(message: ErrorMessage) -> Self {
+        Self::Validation(message)
     }
 
     pub fn unexpected(message: impl Into<String>) -> Self {
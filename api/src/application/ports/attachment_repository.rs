@@ -0,0 +1,45 @@
+use async_trait::async_trait;
+
+use crate::application::errors::AppError;
+use crate::application::ports::blob_storage::BlobStorage;
+use crate::domain::entities::attachment::Attachment;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewAttachment {
+    pub todo_id: u32,
+    pub filename: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub sha256: String,
+}
+
+/// 添付のレコード。本体は同じ内容の添付で共有するので、本体の保存と削除もここで行い、
+/// レコードの書き込みと同じトランザクションの中に収める。
+/// こうしておけば、アップロードと同じ内容の削除が重なっても、本体のないレコードが残らない
+#[async_trait]
+pub trait AttachmentRepository: Send + Sync {
+    /// レコードを書き込み、`data` を `blobs` に保存する
+    async fn create(
+        &self,
+        attachment: NewAttachment,
+        data: &[u8],
+        blobs: &dyn BlobStorage,
+    ) -> Result<Attachment, AppError>;
+    async fn list_by_todo(&self, todo_id: u32) -> Result<Vec<Attachment>, AppError>;
+    async fn get_by_id(&self, todo_id: u32, id: u32) -> Result<Option<Attachment>, AppError>;
+    /// 削除したレコードを返す（存在しなければ `None`）。
+    /// どの添付からも参照されなくなった本体は `blobs` から消す
+    async fn delete(
+        &self,
+        todo_id: u32,
+        id: u32,
+        blobs: &dyn BlobStorage,
+    ) -> Result<Option<Attachment>, AppError>;
+    /// `keys` の本体のうち、どの添付からも参照されていないものを `blobs` から消す。
+    /// TODOと一緒に添付のレコードを削除した後（コミットの後）に呼ぶ
+    async fn delete_unreferenced_blobs(
+        &self,
+        keys: &[String],
+        blobs: &dyn BlobStorage,
+    ) -> Result<(), AppError>;
+}
@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+
+use crate::application::errors::AppError;
+
+/// 添付ファイル本体の保存先。キーは内容のSHA-256で、同じ内容は一度だけ保存される
+#[async_trait]
+pub trait BlobStorage: Send + Sync {
+    /// `data` を保存したときのキー。保存する前にレコードへ書くために使う
+    fn key_for(&self, data: &[u8]) -> String;
+    /// 保存してキー（SHA-256の16進数）を返す
+    async fn put(&self, data: &[u8]) -> Result<String, AppError>;
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, AppError>;
+    /// 存在しないキーの削除はエラーにしない
+    async fn delete(&self, key: &str) -> Result<(), AppError>;
+}
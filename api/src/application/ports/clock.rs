@@ -0,0 +1,6 @@
+use chrono::{DateTime, Utc};
+
+/// 現在時刻の取得元。テストでは時刻を進められる実装に差し替える
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
@@ -0,0 +1,39 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+
+use crate::application::errors::AppError;
+use crate::domain::entities::comment::Comment;
+
+#[async_trait]
+pub trait CommentRepository: Send + Sync {
+    async fn create(&self, todo_id: u32, author: String, body: String)
+        -> Result<Comment, AppError>;
+    /// `todo_id` のコメントを作成日時の昇順で返す。戻り値の2番目は総件数。
+    async fn list_by_todo(
+        &self,
+        todo_id: u32,
+        limit: u32,
+        offset: u32,
+    ) -> Result<(Vec<Comment>, u64), AppError>;
+    /// TODOごとのコメント数。コメントのないTODOは結果に含めない
+    async fn count_by_todos(&self, todo_ids: &[u32]) -> Result<HashMap<u32, u64>, AppError> {
+        let mut counts = HashMap::new();
+        for todo_id in todo_ids {
+            let (_, total) = self.list_by_todo(*todo_id, 0, 0).await?;
+            if total > 0 {
+                counts.insert(*todo_id, total);
+            }
+        }
+        Ok(counts)
+    }
+
+    async fn get_by_id(&self, todo_id: u32, id: u32) -> Result<Option<Comment>, AppError>;
+    async fn update(
+        &self,
+        todo_id: u32,
+        id: u32,
+        body: String,
+    ) -> Result<Option<Comment>, AppError>;
+    async fn delete(&self, todo_id: u32, id: u32) -> Result<bool, AppError>;
+}
@@ -0,0 +1,18 @@
+use crate::domain::entities::presence::EditLock;
+
+/// TODOの編集ロックの問い合わせ先。
+/// ロックはWebSocketの接続が取るが、どの経路からの更新・削除もこれで確かめる
+pub trait EditLocks: Send + Sync {
+    /// `editor` 以外の接続が有効なロックを持っていれば、そのロックを返す。
+    /// `editor` はWebSocketの接続ID。ロックを取れない経路（RESTなど）は `None`
+    fn held_by_other(&self, todo_id: i64, editor: Option<u64>) -> Option<EditLock>;
+}
+
+/// ロックを持つ接続がいない環境（サーバを通さずSQLiteを直接読み書きする場合など）
+pub struct NoEditLocks;
+
+impl EditLocks for NoEditLocks {
+    fn held_by_other(&self, _todo_id: i64, _editor: Option<u64>) -> Option<EditLock> {
+        None
+    }
+}
@@ -0,0 +1,10 @@
+use async_trait::async_trait;
+
+use crate::domain::events::TodoEvent;
+
+/// 変更の確定後にイベントを外へ流す。
+/// 変更自体は既に成功しているので、配信側の失敗は呼び出し元へ返さず実装側で記録する
+#[async_trait]
+pub trait EventPublisher: Send + Sync {
+    async fn publish(&self, event: TodoEvent);
+}
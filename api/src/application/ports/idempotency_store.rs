@@ -0,0 +1,50 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::application::errors::AppError;
+
+/// 保存しておき、同じリクエストの再送に返す応答
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredResponse {
+    pub status: u16,
+    /// (名前, 値)。同じ名前が複数あってもよい
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+/// キーを予約しようとした結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reservation {
+    /// 初めて使われたキー。処理したら `token` を添えて [`IdempotencyStore::complete`] で応答を保存する
+    Reserved { token: String },
+    /// 既に使われているキー。`response` が `None` ならまだ処理中
+    Existing {
+        fingerprint: String,
+        response: Option<StoredResponse>,
+    },
+}
+
+/// `Idempotency-Key` ごとのリクエストの指紋と応答の保存先
+#[async_trait]
+pub trait IdempotencyStore: Send + Sync {
+    /// キーを予約する。期限切れのものと、`stale_before` より前に予約されたまま
+    /// 応答が保存されていないもの（処理中に落ちたなど）は、なかったものとして扱う
+    async fn reserve(
+        &self,
+        key: &str,
+        fingerprint: &str,
+        now: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+        stale_before: DateTime<Utc>,
+    ) -> Result<Reservation, AppError>;
+    /// 応答を保存する。`token` の予約が（処理が止まったとみなされて）別のリクエストに
+    /// 取って代わられていたら何もしない
+    async fn complete(
+        &self,
+        key: &str,
+        token: &str,
+        response: &StoredResponse,
+    ) -> Result<(), AppError>;
+    /// 予約を取り消し、同じキーで処理し直せるようにする。`token` の予約でなければ何もしない
+    async fn release(&self, key: &str, token: &str) -> Result<(), AppError>;
+}
@@ -1 +1,13 @@
+pub mod attachment_repository;
+pub mod blob_storage;
+pub mod clock;
+pub mod comment_repository;
+pub mod edit_locks;
+pub mod event_publisher;
+pub mod idempotency_store;
+pub mod notifier;
+pub mod reminder_repository;
 pub mod todo_repository;
+pub mod webhook_outbox;
+pub mod webhook_repository;
+pub mod webhook_sender;
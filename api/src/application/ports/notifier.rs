@@ -0,0 +1,19 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::application::errors::AppError;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReminderNotification {
+    pub reminder_id: i64,
+    pub todo_id: i64,
+    pub todo_title: String,
+    pub due_at: Option<DateTime<Utc>>,
+    pub fire_at: DateTime<Utc>,
+}
+
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    async fn notify(&self, notification: &ReminderNotification) -> Result<(), AppError>;
+}
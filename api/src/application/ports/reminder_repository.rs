@@ -0,0 +1,34 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::application::errors::AppError;
+use crate::domain::entities::reminder::{Reminder, ReminderSchedule};
+
+/// 発火時刻を過ぎた未配信のリマインダーと、通知に必要なTODOの情報
+#[derive(Debug, Clone)]
+pub struct DueReminder {
+    pub reminder: Reminder,
+    pub todo_title: String,
+    pub due_at: Option<DateTime<Utc>>,
+}
+
+#[async_trait]
+pub trait ReminderRepository: Send + Sync {
+    async fn create(
+        &self,
+        todo_id: u32,
+        schedule: ReminderSchedule,
+        fire_at: Option<DateTime<Utc>>,
+    ) -> Result<Reminder, AppError>;
+    async fn list_by_todo(&self, todo_id: u32) -> Result<Vec<Reminder>, AppError>;
+    async fn delete(&self, todo_id: u32, id: u32) -> Result<bool, AppError>;
+    /// `now` 時点で配信すべきものを発火時刻の早い順に最大 `limit` 件返す
+    async fn due(&self, now: DateTime<Utc>, limit: u32) -> Result<Vec<DueReminder>, AppError>;
+    async fn mark_delivered(&self, id: i64, delivered_at: DateTime<Utc>) -> Result<(), AppError>;
+    async fn mark_failed(
+        &self,
+        id: i64,
+        error: String,
+        retry_at: DateTime<Utc>,
+    ) -> Result<(), AppError>;
+}
@@ -1,19 +1,145 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 
 use crate::application::errors::AppError;
 use crate::domain::entities::todo::Todo;
 
+/// 絞り込み条件。`None` の項目は条件にしない
+#[derive(Debug, Clone, Default)]
+pub struct TodoFilter {
+    pub completed: Option<bool>,
+    /// タイトルに含まれる文字列（英字の大文字小文字は区別しない）
+    pub title_contains: Option<String>,
+    pub due_before: Option<DateTime<Utc>>,
+    pub due_after: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TodoSortField {
+    /// 画面上の並び順
+    #[default]
+    Position,
+    Title,
+    /// 期限なしのTODOは昇順でも降順でも末尾に並べる
+    DueAt,
+    Id,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TodoSort {
+    pub field: TodoSortField,
+    pub direction: SortDirection,
+}
+
+#[derive(Debug, Clone)]
+pub struct TodoPage {
+    pub todos: Vec<Todo>,
+    /// 絞り込み後、ページングする前の件数
+    pub total: u64,
+}
+
 #[async_trait]
 pub trait TodoRepository: Send + Sync {
     async fn create(&self, title: String) -> Result<Todo, AppError>;
     async fn get_all(&self) -> Result<Vec<Todo>, AppError>;
     async fn get_by_id(&self, id: u32) -> Result<Option<Todo>, AppError>;
+    /// まとめて取得する。見つからないIDは結果に含めない（順序は問わない）
+    async fn get_by_ids(&self, ids: &[u32]) -> Result<Vec<Todo>, AppError> {
+        let mut todos = Vec::with_capacity(ids.len());
+        for id in ids {
+            if let Some(todo) = self.get_by_id(*id).await? {
+                todos.push(todo);
+            }
+        }
+        Ok(todos)
+    }
+
+    /// 絞り込み・並び替えたうえで、`offset` 件目から最大 `limit` 件返す。
+    /// 既定の実装は全件を読んでメモリ上で行うので、DBで行える実装は上書きする
+    async fn query(
+        &self,
+        filter: &TodoFilter,
+        sort: TodoSort,
+        limit: u32,
+        offset: u32,
+    ) -> Result<TodoPage, AppError> {
+        let mut todos: Vec<Todo> = self
+            .get_all()
+            .await?
+            .into_iter()
+            .filter(|todo| matches(filter, todo))
+            .collect();
+        todos.sort_by(|a, b| {
+            let ordering = match sort.field {
+                TodoSortField::Position => a.position.cmp(&b.position),
+                TodoSortField::Title => a.title.cmp(&b.title),
+                TodoSortField::Id => a.id.cmp(&b.id),
+                TodoSortField::DueAt => match (a.due_at, b.due_at) {
+                    (Some(a_due), Some(b_due)) => a_due.cmp(&b_due),
+                    (Some(_), None) => return std::cmp::Ordering::Less,
+                    (None, Some(_)) => return std::cmp::Ordering::Greater,
+                    (None, None) => std::cmp::Ordering::Equal,
+                },
+            };
+            let ordering = match sort.direction {
+                SortDirection::Asc => ordering,
+                SortDirection::Desc => ordering.reverse(),
+            };
+            ordering.then(a.id.cmp(&b.id))
+        });
+
+        let total = todos.len() as u64;
+        let todos = todos
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect();
+        Ok(TodoPage { todos, total })
+    }
+
     async fn update(
         &self,
         id: u32,
         title: Option<String>,
         completed: Option<bool>,
+        // `Some(None)` で期限を解除する
+        due_at: Option<Option<DateTime<Utc>>>,
     ) -> Result<Option<Todo>, AppError>;
-    async fn delete(&self, id: u32) -> Result<bool, AppError>;
+    /// TODOを、コメント・リマインダー・添付のレコードと一緒に削除する（存在しなければ `None`）。
+    /// 削除した添付が参照していた本体のキーを重複なしで返すので、コミットの後で片付ける
+    async fn delete(&self, id: u32) -> Result<Option<Vec<String>>, AppError>;
     async fn reorder(&self, todo_ids: Vec<i64>) -> Result<(), AppError>;
 }
+
+fn matches(filter: &TodoFilter, todo: &Todo) -> bool {
+    if filter
+        .completed
+        .is_some_and(|completed| todo.completed != completed)
+    {
+        return false;
+    }
+    if let Some(needle) = &filter.title_contains {
+        if !todo.title.to_lowercase().contains(&needle.to_lowercase()) {
+            return false;
+        }
+    }
+    // 期限で絞り込むときは期限のないTODOを含めない
+    if let Some(before) = filter.due_before {
+        if todo.due_at.is_none_or(|due_at| due_at >= before) {
+            return false;
+        }
+    }
+    if let Some(after) = filter.due_after {
+        if todo.due_at.is_none_or(|due_at| due_at <= after) {
+            return false;
+        }
+    }
+    true
+}
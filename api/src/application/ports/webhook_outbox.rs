@@ -0,0 +1,48 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::application::errors::AppError;
+use crate::domain::entities::webhook::WebhookDelivery;
+
+/// 送信待ちの配信と、送信に必要な購読先の情報
+#[derive(Debug, Clone)]
+pub struct PendingDelivery {
+    pub delivery: WebhookDelivery,
+    pub url: String,
+    pub secret: String,
+}
+
+/// Webhook配信のアウトボックス。イベントはまずここへ積み、ワーカーが送信する
+#[async_trait]
+pub trait WebhookOutbox: Send + Sync {
+    /// イベントを購読しているすべてのWebhook向けに配信を積み、積んだ件数を返す
+    async fn enqueue(
+        &self,
+        event: &str,
+        payload: &str,
+        now: DateTime<Utc>,
+    ) -> Result<usize, AppError>;
+    /// `now` 時点で送信すべきものを古い順に最大 `limit` 件返す
+    async fn due(&self, now: DateTime<Utc>, limit: u32) -> Result<Vec<PendingDelivery>, AppError>;
+    async fn mark_delivered(
+        &self,
+        id: i64,
+        response_status: u16,
+        delivered_at: DateTime<Utc>,
+    ) -> Result<(), AppError>;
+    /// `retry_at` が `None` の場合は再送を諦めて失敗扱いにする
+    async fn mark_failed(
+        &self,
+        id: i64,
+        response_status: Option<u16>,
+        error: String,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> Result<(), AppError>;
+    /// 新しい順に返す
+    async fn list_by_webhook(
+        &self,
+        webhook_id: u32,
+        limit: u32,
+        offset: u32,
+    ) -> Result<(Vec<WebhookDelivery>, u64), AppError>;
+}
@@ -0,0 +1,29 @@
+use async_trait::async_trait;
+
+use crate::application::errors::AppError;
+use crate::domain::entities::webhook::Webhook;
+
+#[derive(Debug, Clone)]
+pub struct NewWebhook {
+    pub url: String,
+    pub secret: String,
+    pub events: Vec<String>,
+}
+
+/// 指定された項目だけを更新する
+#[derive(Debug, Clone, Default)]
+pub struct WebhookPatch {
+    pub url: Option<String>,
+    pub secret: Option<String>,
+    pub events: Option<Vec<String>>,
+}
+
+#[async_trait]
+pub trait WebhookRepository: Send + Sync {
+    async fn create(&self, webhook: NewWebhook) -> Result<Webhook, AppError>;
+    async fn list(&self) -> Result<Vec<Webhook>, AppError>;
+    async fn get_by_id(&self, id: u32) -> Result<Option<Webhook>, AppError>;
+    async fn update(&self, id: u32, patch: WebhookPatch) -> Result<Option<Webhook>, AppError>;
+    /// 購読と一緒に配信記録も削除する
+    async fn delete(&self, id: u32) -> Result<bool, AppError>;
+}
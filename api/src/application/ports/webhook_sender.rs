@@ -0,0 +1,24 @@
+use async_trait::async_trait;
+
+/// 1回分の送信内容
+#[derive(Debug, Clone)]
+pub struct OutgoingWebhook {
+    pub delivery_id: i64,
+    pub event: String,
+    pub url: String,
+    pub secret: String,
+    pub payload: String,
+}
+
+/// 送信の失敗。相手から応答があった場合はそのステータスコードも持つ
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SendError {
+    pub status: Option<u16>,
+    pub message: String,
+}
+
+#[async_trait]
+pub trait WebhookSender: Send + Sync {
+    /// 2xxで応答されたらそのステータスコードを返す
+    async fn send(&self, webhook: &OutgoingWebhook) -> Result<u16, SendError>;
+}
@@ -0,0 +1,48 @@
+use crate::application::errors::AppError;
+use crate::application::ports::attachment_repository::AttachmentRepository;
+use crate::application::ports::blob_storage::BlobStorage;
+
+#[tracing::instrument(name = "usecase.attachment.delete", skip_all)]
+pub async fn execute(
+    attachments: &dyn AttachmentRepository,
+    blobs: &dyn BlobStorage,
+    todo_id: u32,
+    id: u32,
+) -> Result<bool, AppError> {
+    // 同じ内容を他の添付が参照している間は、本体はリポジトリが残す
+    Ok(attachments.delete(todo_id, id, blobs).await?.is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::execute;
+    use crate::application::ports::blob_storage::BlobStorage;
+    use crate::application::usecases::attachment::test_support::{
+        attachment, FakeAttachmentRepo, FakeBlobStorage,
+    };
+
+    #[tokio::test]
+    async fn delete_keeps_blob_while_still_referenced() {
+        let blobs = FakeBlobStorage::default();
+        let key = blobs.put(b"abc").await.unwrap();
+        let attachments =
+            FakeAttachmentRepo::with_rows(vec![attachment(1, 1, &key), attachment(2, 2, &key)]);
+
+        assert!(execute(&attachments, &blobs, 1, 1).await.unwrap());
+        assert_eq!(blobs.keys(), vec![key.clone()]);
+
+        assert!(execute(&attachments, &blobs, 2, 2).await.unwrap());
+        assert!(blobs.keys().is_empty());
+    }
+
+    #[tokio::test]
+    async fn delete_returns_false_when_missing() {
+        let attachments = FakeAttachmentRepo::default();
+
+        let result = execute(&attachments, &FakeBlobStorage::default(), 1, 1)
+            .await
+            .unwrap();
+
+        assert!(!result);
+    }
+}
@@ -0,0 +1,55 @@
+use crate::application::errors::AppError;
+use crate::application::ports::attachment_repository::AttachmentRepository;
+use crate::application::ports::blob_storage::BlobStorage;
+use crate::domain::entities::attachment::Attachment;
+
+#[tracing::instrument(name = "usecase.attachment.download", skip_all)]
+pub async fn execute(
+    attachments: &dyn AttachmentRepository,
+    blobs: &dyn BlobStorage,
+    todo_id: u32,
+    id: u32,
+) -> Result<Option<(Attachment, Vec<u8>)>, AppError> {
+    let attachment = match attachments.get_by_id(todo_id, id).await? {
+        Some(a) => a,
+        None => return Ok(None),
+    };
+    let data = blobs.get(&attachment.sha256).await?.ok_or_else(|| {
+        AppError::unexpected(format!(
+            "blob {} for attachment {} is missing",
+            attachment.sha256, attachment.id
+        ))
+    })?;
+    Ok(Some((attachment, data)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::execute;
+    use crate::application::errors::AppError;
+    use crate::application::ports::blob_storage::BlobStorage;
+    use crate::application::usecases::attachment::test_support::{
+        attachment, FakeAttachmentRepo, FakeBlobStorage,
+    };
+
+    #[tokio::test]
+    async fn download_returns_metadata_and_content() {
+        let blobs = FakeBlobStorage::default();
+        let key = blobs.put(b"abc").await.unwrap();
+        let attachments = FakeAttachmentRepo::with_rows(vec![attachment(1, 1, &key)]);
+
+        let (found, data) = execute(&attachments, &blobs, 1, 1).await.unwrap().unwrap();
+
+        assert_eq!(found.id, 1);
+        assert_eq!(data, b"abc");
+    }
+
+    #[tokio::test]
+    async fn download_reports_missing_blob_as_unexpected() {
+        let attachments = FakeAttachmentRepo::with_rows(vec![attachment(1, 1, "gone")]);
+
+        let result = execute(&attachments, &FakeBlobStorage::default(), 1, 1).await;
+
+        assert!(matches!(result, Err(AppError::Unexpected(_))));
+    }
+}
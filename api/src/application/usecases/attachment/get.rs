@@ -0,0 +1,29 @@
+use crate::application::errors::AppError;
+use crate::application::ports::attachment_repository::AttachmentRepository;
+use crate::domain::entities::attachment::Attachment;
+
+#[tracing::instrument(name = "usecase.attachment.get", skip_all)]
+pub async fn execute(
+    attachments: &dyn AttachmentRepository,
+    todo_id: u32,
+    id: u32,
+) -> Result<Option<Attachment>, AppError> {
+    attachments.get_by_id(todo_id, id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::execute;
+    use crate::application::usecases::attachment::test_support::{attachment, FakeAttachmentRepo};
+
+    #[tokio::test]
+    async fn get_is_scoped_to_the_todo() {
+        let attachments = FakeAttachmentRepo::with_rows(vec![attachment(1, 1, "a")]);
+
+        let found = execute(&attachments, 1, 1).await.unwrap();
+        let other_todo = execute(&attachments, 2, 1).await.unwrap();
+
+        assert_eq!(found.unwrap().id, 1);
+        assert!(other_todo.is_none());
+    }
+}
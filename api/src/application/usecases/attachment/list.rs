@@ -0,0 +1,40 @@
+use crate::application::errors::AppError;
+use crate::application::ports::attachment_repository::AttachmentRepository;
+use crate::application::ports::todo_repository::TodoRepository;
+use crate::domain::entities::attachment::Attachment;
+
+#[tracing::instrument(name = "usecase.attachment.list", skip_all)]
+pub async fn execute(
+    todos: &dyn TodoRepository,
+    attachments: &dyn AttachmentRepository,
+    todo_id: u32,
+) -> Result<Vec<Attachment>, AppError> {
+    if todos.get_by_id(todo_id).await?.is_none() {
+        return Err(AppError::NotFound);
+    }
+    attachments.list_by_todo(todo_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::execute;
+    use crate::application::usecases::attachment::test_support::{
+        attachment, FakeAttachmentRepo, FakeTodoRepo,
+    };
+
+    #[tokio::test]
+    async fn list_returns_only_attachments_of_the_todo() {
+        let attachments = FakeAttachmentRepo::with_rows(vec![
+            attachment(1, 1, "a"),
+            attachment(2, 2, "b"),
+            attachment(3, 1, "c"),
+        ]);
+
+        let result = execute(&FakeTodoRepo::with_todo(1), &attachments, 1)
+            .await
+            .unwrap();
+
+        let ids: Vec<i64> = result.iter().map(|a| a.id).collect();
+        assert_eq!(ids, vec![1, 3]);
+    }
+}
@@ -0,0 +1,9 @@
+pub mod delete;
+pub mod download;
+pub mod get;
+pub mod list;
+pub mod purge;
+pub mod upload;
+
+#[cfg(test)]
+pub(crate) mod test_support;
@@ -0,0 +1,40 @@
+use tracing::warn;
+
+use crate::application::ports::attachment_repository::AttachmentRepository;
+use crate::application::ports::blob_storage::BlobStorage;
+
+/// 削除したTODOの添付が参照していた本体（`keys`）のうち、どの添付からも参照されなくなったものを消す。
+/// レコードはもう削除してあるので、失敗しても本体が残るだけとしてログに残して続ける
+#[tracing::instrument(name = "usecase.attachment.purge", skip_all)]
+pub async fn execute(
+    attachments: &dyn AttachmentRepository,
+    blobs: &dyn BlobStorage,
+    keys: &[String],
+) {
+    if let Err(e) = attachments.delete_unreferenced_blobs(keys, blobs).await {
+        warn!(keys = ?keys, error = ?e, "failed to remove attachment blobs");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::execute;
+    use crate::application::ports::blob_storage::BlobStorage;
+    use crate::application::usecases::attachment::test_support::{
+        attachment, FakeAttachmentRepo, FakeBlobStorage,
+    };
+
+    #[tokio::test]
+    async fn purge_removes_only_unreferenced_blobs() {
+        let blobs = FakeBlobStorage::default();
+        let own = blobs.put(b"a").await.unwrap();
+        let shared = blobs.put(b"bb").await.unwrap();
+        // TODO 1 の添付のレコードはもう削除してあり、TODO 2 がまだ `shared` を参照している
+        let attachments = FakeAttachmentRepo::with_rows(vec![attachment(3, 2, &shared)]);
+
+        execute(&attachments, &blobs, &[own, shared.clone()]).await;
+
+        assert_eq!(blobs.keys(), vec![shared]);
+        assert_eq!(attachments.rows.lock().unwrap().len(), 1);
+    }
+}
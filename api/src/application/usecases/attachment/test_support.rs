@@ -0,0 +1,228 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::application::errors::AppError;
+use crate::application::ports::attachment_repository::{AttachmentRepository, NewAttachment};
+use crate::application::ports::blob_storage::BlobStorage;
+use crate::application::ports::todo_repository::TodoRepository;
+use crate::domain::entities::attachment::Attachment;
+use crate::domain::entities::todo::Todo;
+
+// 添付まわりのユースケースはポートを3つ使うので、テスト用のインメモリ実装をまとめておく
+
+pub struct FakeTodoRepo {
+    pub todo: Option<Todo>,
+    pub delete_result: bool,
+}
+
+impl FakeTodoRepo {
+    pub fn with_todo(id: i64) -> Self {
+        Self {
+            todo: Some(Todo {
+                id,
+                title: "with attachments".to_string(),
+                completed: false,
+                position: 1,
+                due_at: None,
+            }),
+            delete_result: true,
+        }
+    }
+}
+
+#[async_trait]
+impl TodoRepository for FakeTodoRepo {
+    async fn create(&self, _title: String) -> Result<Todo, AppError> {
+        unimplemented!("not needed for this test");
+    }
+
+    async fn get_all(&self) -> Result<Vec<Todo>, AppError> {
+        unimplemented!("not needed for this test");
+    }
+
+    async fn get_by_id(&self, _id: u32) -> Result<Option<Todo>, AppError> {
+        Ok(self.todo.clone())
+    }
+
+    async fn update(
+        &self,
+        _id: u32,
+        _title: Option<String>,
+        _completed: Option<bool>,
+        _due_at: Option<Option<DateTime<Utc>>>,
+    ) -> Result<Option<Todo>, AppError> {
+        unimplemented!("not needed for this test");
+    }
+
+    async fn delete(&self, _id: u32) -> Result<Option<Vec<String>>, AppError> {
+        Ok(self.delete_result.then(Vec::new))
+    }
+
+    async fn reorder(&self, _todo_ids: Vec<i64>) -> Result<(), AppError> {
+        unimplemented!("not needed for this test");
+    }
+}
+
+#[derive(Default)]
+pub struct FakeAttachmentRepo {
+    pub rows: Mutex<Vec<Attachment>>,
+}
+
+impl FakeAttachmentRepo {
+    pub fn with_rows(rows: Vec<Attachment>) -> Self {
+        Self {
+            rows: Mutex::new(rows),
+        }
+    }
+
+    fn rows(&self) -> std::sync::MutexGuard<'_, Vec<Attachment>> {
+        self.rows.lock().expect("failed to lock rows")
+    }
+
+    async fn delete_unreferenced(
+        &self,
+        blobs: &dyn BlobStorage,
+        key: &str,
+    ) -> Result<(), AppError> {
+        let referenced = self.rows().iter().any(|a| a.sha256 == key);
+        if !referenced {
+            blobs.delete(key).await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AttachmentRepository for FakeAttachmentRepo {
+    async fn create(
+        &self,
+        new: NewAttachment,
+        data: &[u8],
+        blobs: &dyn BlobStorage,
+    ) -> Result<Attachment, AppError> {
+        blobs.put(data).await?;
+        let mut rows = self.rows();
+        let attachment = Attachment {
+            id: rows.len() as i64 + 1,
+            todo_id: new.todo_id as i64,
+            filename: new.filename,
+            content_type: new.content_type,
+            size_bytes: new.size_bytes,
+            sha256: new.sha256,
+            created_at: Utc::now(),
+        };
+        rows.push(attachment.clone());
+        Ok(attachment)
+    }
+
+    async fn list_by_todo(&self, todo_id: u32) -> Result<Vec<Attachment>, AppError> {
+        Ok(self
+            .rows()
+            .iter()
+            .filter(|a| a.todo_id == todo_id as i64)
+            .cloned()
+            .collect())
+    }
+
+    async fn get_by_id(&self, todo_id: u32, id: u32) -> Result<Option<Attachment>, AppError> {
+        Ok(self
+            .rows()
+            .iter()
+            .find(|a| a.todo_id == todo_id as i64 && a.id == id as i64)
+            .cloned())
+    }
+
+    async fn delete(
+        &self,
+        todo_id: u32,
+        id: u32,
+        blobs: &dyn BlobStorage,
+    ) -> Result<Option<Attachment>, AppError> {
+        let removed = {
+            let mut rows = self.rows();
+            let index = rows
+                .iter()
+                .position(|a| a.todo_id == todo_id as i64 && a.id == id as i64);
+            index.map(|i| rows.remove(i))
+        };
+        if let Some(attachment) = &removed {
+            self.delete_unreferenced(blobs, &attachment.sha256).await?;
+        }
+        Ok(removed)
+    }
+
+    async fn delete_unreferenced_blobs(
+        &self,
+        keys: &[String],
+        blobs: &dyn BlobStorage,
+    ) -> Result<(), AppError> {
+        for key in keys {
+            self.delete_unreferenced(blobs, key).await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+pub struct FakeBlobStorage {
+    pub blobs: Mutex<HashMap<String, Vec<u8>>>,
+}
+
+impl FakeBlobStorage {
+    pub fn keys(&self) -> Vec<String> {
+        let mut keys: Vec<String> = self
+            .blobs
+            .lock()
+            .expect("failed to lock blobs")
+            .keys()
+            .cloned()
+            .collect();
+        keys.sort();
+        keys
+    }
+}
+
+#[async_trait]
+impl BlobStorage for FakeBlobStorage {
+    fn key_for(&self, data: &[u8]) -> String {
+        format!("blob-{}", data.len())
+    }
+
+    async fn put(&self, data: &[u8]) -> Result<String, AppError> {
+        let key = self.key_for(data);
+        self.blobs
+            .lock()
+            .expect("failed to lock blobs")
+            .insert(key.clone(), data.to_vec());
+        Ok(key)
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, AppError> {
+        Ok(self
+            .blobs
+            .lock()
+            .expect("failed to lock blobs")
+            .get(key)
+            .cloned())
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), AppError> {
+        self.blobs.lock().expect("failed to lock blobs").remove(key);
+        Ok(())
+    }
+}
+
+pub fn attachment(id: i64, todo_id: i64, sha256: &str) -> Attachment {
+    Attachment {
+        id,
+        todo_id,
+        filename: format!("file-{}.txt", id),
+        content_type: "text/plain".to_string(),
+        size_bytes: 3,
+        sha256: sha256.to_string(),
+        created_at: Utc::now(),
+    }
+}
@@ -0,0 +1,229 @@
+use crate::application::errors::{AppError, ErrorMessage};
+use crate::application::ports::attachment_repository::{AttachmentRepository, NewAttachment};
+use crate::application::ports::blob_storage::BlobStorage;
+use crate::application::ports::todo_repository::TodoRepository;
+use crate::domain::entities::attachment::Attachment;
+
+pub const DEFAULT_MAX_SIZE_BYTES: usize = 10 * 1024 * 1024;
+const MAX_FILENAME_CHARS: usize = 255;
+
+#[derive(Debug, Clone)]
+pub struct AttachmentLimits {
+    pub max_size_bytes: usize,
+    /// `image/*` のようにサブタイプをワイルドカードにできる
+    pub allowed_content_types: Vec<String>,
+}
+
+impl Default for AttachmentLimits {
+    fn default() -> Self {
+        Self {
+            max_size_bytes: DEFAULT_MAX_SIZE_BYTES,
+            allowed_content_types: [
+                "image/png",
+                "image/jpeg",
+                "image/gif",
+                "image/webp",
+                "application/pdf",
+                "text/plain",
+                "text/markdown",
+                "text/csv",
+            ]
+            .map(String::from)
+            .to_vec(),
+        }
+    }
+}
+
+impl AttachmentLimits {
+    pub fn allows(&self, content_type: &str) -> bool {
+        let (kind, _) = content_type.split_once('/').unwrap_or((content_type, ""));
+        self.allowed_content_types.iter().any(|allowed| {
+            allowed == content_type
+                || allowed
+                    .strip_suffix("/*")
+                    .is_some_and(|allowed_kind| allowed_kind == kind)
+        })
+    }
+}
+
+pub struct Upload {
+    pub filename: String,
+    pub content_type: String,
+    pub data: Vec<u8>,
+}
+
+#[tracing::instrument(name = "usecase.attachment.upload", skip_all)]
+pub async fn execute(
+    todos: &dyn TodoRepository,
+    attachments: &dyn AttachmentRepository,
+    blobs: &dyn BlobStorage,
+    limits: &AttachmentLimits,
+    todo_id: u32,
+    upload: Upload,
+) -> Result<Attachment, AppError> {
+    if upload.data.is_empty() {
+        return Err(AppError::validation(ErrorMessage::new("attachment_empty")));
+    }
+    if upload.data.len() > limits.max_size_bytes {
+        return Err(AppError::PayloadTooLarge(
+            ErrorMessage::new("attachment_too_large").with("max", limits.max_size_bytes),
+        ));
+    }
+    let content_type = normalize_content_type(&upload.content_type);
+    if !limits.allows(&content_type) {
+        return Err(AppError::UnsupportedMediaType(
+            ErrorMessage::new("attachment_type_not_allowed").with("content_type", &content_type),
+        ));
+    }
+    if todos.get_by_id(todo_id).await?.is_none() {
+        return Err(AppError::NotFound);
+    }
+
+    let attachment = NewAttachment {
+        todo_id,
+        filename: sanitize_filename(&upload.filename),
+        content_type,
+        size_bytes: upload.data.len() as i64,
+        sha256: blobs.key_for(&upload.data),
+    };
+    attachments.create(attachment, &upload.data, blobs).await
+}
+
+// `text/plain; charset=utf-8` のようなパラメータを落として小文字に揃える
+fn normalize_content_type(content_type: &str) -> String {
+    content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+// パス区切りや制御文字を取り除き、ファイル名部分だけを残す
+fn sanitize_filename(filename: &str) -> String {
+    let base = filename.rsplit(['/', '\\']).next().unwrap_or_default();
+    let cleaned: String = base
+        .chars()
+        .filter(|c| !c.is_control() && *c != '"')
+        .take(MAX_FILENAME_CHARS)
+        .collect();
+    let cleaned = cleaned.trim();
+    if cleaned.is_empty() || cleaned == "." || cleaned == ".." {
+        "file".to_string()
+    } else {
+        cleaned.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{execute, sanitize_filename, AttachmentLimits, Upload};
+    use crate::application::errors::AppError;
+    use crate::application::usecases::attachment::test_support::{
+        FakeAttachmentRepo, FakeBlobStorage, FakeTodoRepo,
+    };
+
+    fn upload(content_type: &str, data: &[u8]) -> Upload {
+        Upload {
+            filename: "../notes/report.txt".to_string(),
+            content_type: content_type.to_string(),
+            data: data.to_vec(),
+        }
+    }
+
+    #[tokio::test]
+    async fn upload_stores_blob_and_metadata() {
+        let attachments = FakeAttachmentRepo::default();
+        let blobs = FakeBlobStorage::default();
+
+        let result = execute(
+            &FakeTodoRepo::with_todo(1),
+            &attachments,
+            &blobs,
+            &AttachmentLimits::default(),
+            1,
+            upload("Text/Plain; charset=utf-8", b"abc"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.filename, "report.txt");
+        assert_eq!(result.content_type, "text/plain");
+        assert_eq!(result.size_bytes, 3);
+        assert_eq!(blobs.keys(), vec![result.sha256]);
+    }
+
+    #[tokio::test]
+    async fn upload_enforces_size_and_type_limits() {
+        let limits = AttachmentLimits {
+            max_size_bytes: 2,
+            allowed_content_types: vec!["image/*".to_string()],
+        };
+        let todos = FakeTodoRepo::with_todo(1);
+        let attachments = FakeAttachmentRepo::default();
+        let blobs = FakeBlobStorage::default();
+
+        let too_large = execute(
+            &todos,
+            &attachments,
+            &blobs,
+            &limits,
+            1,
+            upload("image/png", b"abc"),
+        )
+        .await;
+        let wrong_type = execute(
+            &todos,
+            &attachments,
+            &blobs,
+            &limits,
+            1,
+            upload("application/zip", b"a"),
+        )
+        .await;
+        let allowed = execute(
+            &todos,
+            &attachments,
+            &blobs,
+            &limits,
+            1,
+            upload("image/gif", b"a"),
+        )
+        .await;
+
+        assert!(matches!(too_large, Err(AppError::PayloadTooLarge(_))));
+        assert!(matches!(wrong_type, Err(AppError::UnsupportedMediaType(_))));
+        assert!(allowed.is_ok());
+        assert_eq!(blobs.keys().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn upload_returns_not_found_for_missing_todo() {
+        let todos = FakeTodoRepo {
+            todo: None,
+            delete_result: false,
+        };
+        let blobs = FakeBlobStorage::default();
+
+        let result = execute(
+            &todos,
+            &FakeAttachmentRepo::default(),
+            &blobs,
+            &AttachmentLimits::default(),
+            1,
+            upload("text/plain", b"abc"),
+        )
+        .await;
+
+        assert!(matches!(result, Err(AppError::NotFound)));
+        assert!(blobs.keys().is_empty());
+    }
+
+    #[test]
+    fn sanitize_filename_strips_paths_and_control_characters() {
+        assert_eq!(sanitize_filename("C:\\tmp\\a\"b.png"), "ab.png");
+        assert_eq!(sanitize_filename("dir/\u{0}x.txt"), "x.txt");
+        assert_eq!(sanitize_filename(".."), "file");
+        assert_eq!(sanitize_filename(""), "file");
+    }
+}
@@ -0,0 +1,166 @@
+use crate::application::errors::AppError;
+use crate::application::ports::comment_repository::CommentRepository;
+use crate::application::ports::todo_repository::TodoRepository;
+use crate::domain::entities::comment::Comment;
+
+#[tracing::instrument(name = "usecase.comment.create", skip_all)]
+pub async fn execute(
+    todos: &dyn TodoRepository,
+    comments: &dyn CommentRepository,
+    todo_id: u32,
+    author: String,
+    body: String,
+) -> Result<Comment, AppError> {
+    if todos.get_by_id(todo_id).await?.is_none() {
+        return Err(AppError::NotFound);
+    }
+    comments.create(todo_id, author, body).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+    use chrono::{DateTime, Utc};
+
+    use super::execute;
+    use crate::application::errors::AppError;
+    use crate::application::ports::comment_repository::CommentRepository;
+    use crate::application::ports::todo_repository::TodoRepository;
+    use crate::domain::entities::comment::Comment;
+    use crate::domain::entities::todo::Todo;
+
+    struct FakeTodoRepo {
+        todo: Option<Todo>,
+    }
+
+    #[async_trait]
+    impl TodoRepository for FakeTodoRepo {
+        async fn create(&self, _title: String) -> Result<Todo, AppError> {
+            unimplemented!("not needed for this test");
+        }
+
+        async fn get_all(&self) -> Result<Vec<Todo>, AppError> {
+            unimplemented!("not needed for this test");
+        }
+
+        async fn get_by_id(&self, _id: u32) -> Result<Option<Todo>, AppError> {
+            Ok(self.todo.clone())
+        }
+
+        async fn update(
+            &self,
+            _id: u32,
+            _title: Option<String>,
+            _completed: Option<bool>,
+            _due_at: Option<Option<DateTime<Utc>>>,
+        ) -> Result<Option<Todo>, AppError> {
+            unimplemented!("not needed for this test");
+        }
+
+        async fn delete(&self, _id: u32) -> Result<Option<Vec<String>>, AppError> {
+            unimplemented!("not needed for this test");
+        }
+
+        async fn reorder(&self, _todo_ids: Vec<i64>) -> Result<(), AppError> {
+            unimplemented!("not needed for this test");
+        }
+    }
+
+    struct FakeCommentRepo {
+        last_args: Mutex<Option<(u32, String, String)>>,
+    }
+
+    #[async_trait]
+    impl CommentRepository for FakeCommentRepo {
+        async fn create(
+            &self,
+            todo_id: u32,
+            author: String,
+            body: String,
+        ) -> Result<Comment, AppError> {
+            *self.last_args.lock().expect("failed to lock last_args") =
+                Some((todo_id, author.clone(), body.clone()));
+            Ok(Comment {
+                id: 1,
+                todo_id: todo_id as i64,
+                author,
+                body,
+                created_at: Utc::now(),
+                edited_at: None,
+            })
+        }
+
+        async fn list_by_todo(
+            &self,
+            _todo_id: u32,
+            _limit: u32,
+            _offset: u32,
+        ) -> Result<(Vec<Comment>, u64), AppError> {
+            unimplemented!("not needed for this test");
+        }
+
+        async fn get_by_id(&self, _todo_id: u32, _id: u32) -> Result<Option<Comment>, AppError> {
+            unimplemented!("not needed for this test");
+        }
+
+        async fn update(
+            &self,
+            _todo_id: u32,
+            _id: u32,
+            _body: String,
+        ) -> Result<Option<Comment>, AppError> {
+            unimplemented!("not needed for this test");
+        }
+
+        async fn delete(&self, _todo_id: u32, _id: u32) -> Result<bool, AppError> {
+            unimplemented!("not needed for this test");
+        }
+    }
+
+    #[tokio::test]
+    async fn create_delegates_to_repository_when_todo_exists() {
+        let todos = FakeTodoRepo {
+            todo: Some(Todo {
+                id: 3,
+                title: "discuss".to_string(),
+                completed: false,
+                position: 1,
+                due_at: None,
+            }),
+        };
+        let comments = FakeCommentRepo {
+            last_args: Mutex::new(None),
+        };
+
+        let result = execute(&todos, &comments, 3, "alice".to_string(), "hi".to_string())
+            .await
+            .unwrap();
+
+        let last_args = comments
+            .last_args
+            .lock()
+            .expect("failed to lock last_args")
+            .clone();
+        assert_eq!(last_args, Some((3, "alice".to_string(), "hi".to_string())));
+        assert_eq!(result.body, "hi");
+    }
+
+    #[tokio::test]
+    async fn create_returns_not_found_for_missing_todo() {
+        let todos = FakeTodoRepo { todo: None };
+        let comments = FakeCommentRepo {
+            last_args: Mutex::new(None),
+        };
+
+        let result = execute(&todos, &comments, 3, "alice".to_string(), "hi".to_string()).await;
+
+        assert!(matches!(result, Err(AppError::NotFound)));
+        assert!(comments
+            .last_args
+            .lock()
+            .expect("failed to lock last_args")
+            .is_none());
+    }
+}
@@ -0,0 +1,90 @@
+use crate::application::errors::AppError;
+use crate::application::ports::comment_repository::CommentRepository;
+use crate::domain::entities::comment::Comment;
+
+#[tracing::instrument(name = "usecase.comment.get", skip_all)]
+pub async fn execute(
+    comments: &dyn CommentRepository,
+    todo_id: u32,
+    id: u32,
+) -> Result<Option<Comment>, AppError> {
+    comments.get_by_id(todo_id, id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+    use chrono::Utc;
+
+    use super::execute;
+    use crate::application::errors::AppError;
+    use crate::application::ports::comment_repository::CommentRepository;
+    use crate::domain::entities::comment::Comment;
+
+    struct FakeRepo {
+        last_args: Mutex<Option<(u32, u32)>>,
+        comment: Option<Comment>,
+    }
+
+    #[async_trait]
+    impl CommentRepository for FakeRepo {
+        async fn create(
+            &self,
+            _todo_id: u32,
+            _author: String,
+            _body: String,
+        ) -> Result<Comment, AppError> {
+            unimplemented!("not needed for this test");
+        }
+
+        async fn list_by_todo(
+            &self,
+            _todo_id: u32,
+            _limit: u32,
+            _offset: u32,
+        ) -> Result<(Vec<Comment>, u64), AppError> {
+            unimplemented!("not needed for this test");
+        }
+
+        async fn get_by_id(&self, todo_id: u32, id: u32) -> Result<Option<Comment>, AppError> {
+            *self.last_args.lock().expect("failed to lock last_args") = Some((todo_id, id));
+            Ok(self.comment.clone())
+        }
+
+        async fn update(
+            &self,
+            _todo_id: u32,
+            _id: u32,
+            _body: String,
+        ) -> Result<Option<Comment>, AppError> {
+            unimplemented!("not needed for this test");
+        }
+
+        async fn delete(&self, _todo_id: u32, _id: u32) -> Result<bool, AppError> {
+            unimplemented!("not needed for this test");
+        }
+    }
+
+    #[tokio::test]
+    async fn get_delegates_to_repository() {
+        let repo = FakeRepo {
+            last_args: Mutex::new(None),
+            comment: Some(Comment {
+                id: 4,
+                todo_id: 2,
+                author: "alice".to_string(),
+                body: "first draft".to_string(),
+                created_at: Utc::now(),
+                edited_at: None,
+            }),
+        };
+
+        let result = execute(&repo, 2, 4).await.unwrap();
+
+        let last_args = *repo.last_args.lock().expect("failed to lock last_args");
+        assert_eq!(last_args, Some((2, 4)));
+        assert_eq!(result.unwrap().author, "alice");
+    }
+}
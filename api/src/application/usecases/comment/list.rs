@@ -0,0 +1,173 @@
+use crate::application::errors::{AppError, ErrorMessage};
+use crate::application::ports::comment_repository::CommentRepository;
+use crate::application::ports::todo_repository::TodoRepository;
+use crate::domain::entities::comment::Comment;
+
+pub const DEFAULT_PER_PAGE: u32 = 20;
+pub const MAX_PER_PAGE: u32 = 100;
+
+#[derive(Debug, Clone)]
+pub struct CommentPage {
+    pub comments: Vec<Comment>,
+    pub page: u32,
+    pub per_page: u32,
+    pub total: u64,
+}
+
+#[tracing::instrument(name = "usecase.comment.list", skip_all)]
+pub async fn execute(
+    todos: &dyn TodoRepository,
+    comments: &dyn CommentRepository,
+    todo_id: u32,
+    page: u32,
+    per_page: u32,
+) -> Result<CommentPage, AppError> {
+    if page == 0 {
+        return Err(AppError::validation(ErrorMessage::new("page_too_small")));
+    }
+    if per_page == 0 || per_page > MAX_PER_PAGE {
+        return Err(AppError::validation(
+            ErrorMessage::new("per_page_out_of_range").with("max", MAX_PER_PAGE),
+        ));
+    }
+    if todos.get_by_id(todo_id).await?.is_none() {
+        return Err(AppError::NotFound);
+    }
+
+    let offset = (page - 1).saturating_mul(per_page);
+    let (items, total) = comments.list_by_todo(todo_id, per_page, offset).await?;
+    Ok(CommentPage {
+        comments: items,
+        page,
+        per_page,
+        total,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+    use chrono::{DateTime, Utc};
+
+    use super::execute;
+    use crate::application::errors::AppError;
+    use crate::application::ports::comment_repository::CommentRepository;
+    use crate::application::ports::todo_repository::TodoRepository;
+    use crate::domain::entities::comment::Comment;
+    use crate::domain::entities::todo::Todo;
+
+    struct FakeTodoRepo;
+
+    #[async_trait]
+    impl TodoRepository for FakeTodoRepo {
+        async fn create(&self, _title: String) -> Result<Todo, AppError> {
+            unimplemented!("not needed for this test");
+        }
+
+        async fn get_all(&self) -> Result<Vec<Todo>, AppError> {
+            unimplemented!("not needed for this test");
+        }
+
+        async fn get_by_id(&self, id: u32) -> Result<Option<Todo>, AppError> {
+            Ok(Some(Todo {
+                id: id as i64,
+                title: "discuss".to_string(),
+                completed: false,
+                position: 1,
+                due_at: None,
+            }))
+        }
+
+        async fn update(
+            &self,
+            _id: u32,
+            _title: Option<String>,
+            _completed: Option<bool>,
+            _due_at: Option<Option<DateTime<Utc>>>,
+        ) -> Result<Option<Todo>, AppError> {
+            unimplemented!("not needed for this test");
+        }
+
+        async fn delete(&self, _id: u32) -> Result<Option<Vec<String>>, AppError> {
+            unimplemented!("not needed for this test");
+        }
+
+        async fn reorder(&self, _todo_ids: Vec<i64>) -> Result<(), AppError> {
+            unimplemented!("not needed for this test");
+        }
+    }
+
+    struct FakeCommentRepo {
+        last_args: Mutex<Option<(u32, u32, u32)>>,
+    }
+
+    #[async_trait]
+    impl CommentRepository for FakeCommentRepo {
+        async fn create(
+            &self,
+            _todo_id: u32,
+            _author: String,
+            _body: String,
+        ) -> Result<Comment, AppError> {
+            unimplemented!("not needed for this test");
+        }
+
+        async fn list_by_todo(
+            &self,
+            todo_id: u32,
+            limit: u32,
+            offset: u32,
+        ) -> Result<(Vec<Comment>, u64), AppError> {
+            *self.last_args.lock().expect("failed to lock last_args") =
+                Some((todo_id, limit, offset));
+            Ok((Vec::new(), 42))
+        }
+
+        async fn get_by_id(&self, _todo_id: u32, _id: u32) -> Result<Option<Comment>, AppError> {
+            unimplemented!("not needed for this test");
+        }
+
+        async fn update(
+            &self,
+            _todo_id: u32,
+            _id: u32,
+            _body: String,
+        ) -> Result<Option<Comment>, AppError> {
+            unimplemented!("not needed for this test");
+        }
+
+        async fn delete(&self, _todo_id: u32, _id: u32) -> Result<bool, AppError> {
+            unimplemented!("not needed for this test");
+        }
+    }
+
+    #[tokio::test]
+    async fn list_translates_page_into_offset() {
+        let comments = FakeCommentRepo {
+            last_args: Mutex::new(None),
+        };
+
+        let page = execute(&FakeTodoRepo, &comments, 7, 3, 10).await.unwrap();
+
+        let last_args = *comments.last_args.lock().expect("failed to lock last_args");
+        assert_eq!(last_args, Some((7, 10, 20)));
+        assert_eq!(page.total, 42);
+        assert_eq!(page.page, 3);
+        assert_eq!(page.per_page, 10);
+    }
+
+    #[tokio::test]
+    async fn list_rejects_out_of_range_paging() {
+        let comments = FakeCommentRepo {
+            last_args: Mutex::new(None),
+        };
+
+        let zero_page = execute(&FakeTodoRepo, &comments, 7, 0, 10).await;
+        let too_large = execute(&FakeTodoRepo, &comments, 7, 1, 101).await;
+
+        assert!(matches!(zero_page, Err(AppError::Validation(_))));
+        assert!(matches!(too_large, Err(AppError::Validation(_))));
+    }
+}
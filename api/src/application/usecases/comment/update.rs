@@ -0,0 +1,104 @@
+use crate::application::errors::AppError;
+use crate::application::ports::comment_repository::CommentRepository;
+use crate::domain::entities::comment::Comment;
+
+#[tracing::instrument(name = "usecase.comment.update", skip_all)]
+pub async fn execute(
+    comments: &dyn CommentRepository,
+    todo_id: u32,
+    id: u32,
+    body: String,
+) -> Result<Option<Comment>, AppError> {
+    comments.update(todo_id, id, body).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+    use chrono::Utc;
+
+    use super::execute;
+    use crate::application::errors::AppError;
+    use crate::application::ports::comment_repository::CommentRepository;
+    use crate::domain::entities::comment::Comment;
+
+    struct FakeRepo {
+        last_args: Mutex<Option<(u32, u32, String)>>,
+        comment: Option<Comment>,
+    }
+
+    #[async_trait]
+    impl CommentRepository for FakeRepo {
+        async fn create(
+            &self,
+            _todo_id: u32,
+            _author: String,
+            _body: String,
+        ) -> Result<Comment, AppError> {
+            unimplemented!("not needed for this test");
+        }
+
+        async fn list_by_todo(
+            &self,
+            _todo_id: u32,
+            _limit: u32,
+            _offset: u32,
+        ) -> Result<(Vec<Comment>, u64), AppError> {
+            unimplemented!("not needed for this test");
+        }
+
+        async fn get_by_id(&self, _todo_id: u32, _id: u32) -> Result<Option<Comment>, AppError> {
+            unimplemented!("not needed for this test");
+        }
+
+        async fn update(
+            &self,
+            todo_id: u32,
+            id: u32,
+            body: String,
+        ) -> Result<Option<Comment>, AppError> {
+            *self.last_args.lock().expect("failed to lock last_args") =
+                Some((todo_id, id, body.clone()));
+            Ok(self.comment.clone().map(|mut c| {
+                c.body = body;
+                c.edited_at = Some(Utc::now());
+                c
+            }))
+        }
+
+        async fn delete(&self, _todo_id: u32, _id: u32) -> Result<bool, AppError> {
+            unimplemented!("not needed for this test");
+        }
+    }
+
+    #[tokio::test]
+    async fn update_delegates_to_repository() {
+        let repo = FakeRepo {
+            last_args: Mutex::new(None),
+            comment: Some(Comment {
+                id: 4,
+                todo_id: 2,
+                author: "alice".to_string(),
+                body: "first draft".to_string(),
+                created_at: Utc::now(),
+                edited_at: None,
+            }),
+        };
+
+        let result = execute(&repo, 2, 4, "final".to_string())
+            .await
+            .unwrap()
+            .unwrap();
+
+        let last_args = repo
+            .last_args
+            .lock()
+            .expect("failed to lock last_args")
+            .clone();
+        assert_eq!(last_args, Some((2, 4, "final".to_string())));
+        assert_eq!(result.body, "final");
+        assert!(result.is_edited());
+    }
+}
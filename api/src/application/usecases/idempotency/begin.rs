@@ -0,0 +1,183 @@
+use chrono::Duration;
+
+use crate::application::errors::{AppError, ErrorMessage};
+use crate::application::ports::clock::Clock;
+use crate::application::ports::idempotency_store::{IdempotencyStore, Reservation, StoredResponse};
+
+/// リクエストをどう扱うか
+#[derive(Debug, PartialEq, Eq)]
+pub enum Begin {
+    /// 初めてのリクエスト。処理したら `token` を添えて `finish` で応答を保存する
+    Proceed { token: String },
+    /// 同じリクエストの再送。保存しておいた応答を返す
+    Replay(StoredResponse),
+}
+
+/// `key` を予約する。同じキーで別の内容（`fingerprint` が違う）なら422、
+/// 最初のリクエストがまだ処理中なら409にする。
+/// 予約から `in_progress_timeout` が過ぎても応答が保存されていなければ、処理が止まったものとみなす。
+/// 処理中のリクエストと二重に処理しないよう、処理を打ち切る時間より長くすること
+#[tracing::instrument(name = "usecase.idempotency.begin", skip_all)]
+pub async fn execute(
+    store: &dyn IdempotencyStore,
+    clock: &dyn Clock,
+    key: &str,
+    fingerprint: &str,
+    ttl: Duration,
+    in_progress_timeout: Duration,
+) -> Result<Begin, AppError> {
+    let now = clock.now();
+    let stale_before = now - in_progress_timeout;
+    match store
+        .reserve(key, fingerprint, now, now + ttl, stale_before)
+        .await?
+    {
+        Reservation::Reserved { token } => Ok(Begin::Proceed { token }),
+        Reservation::Existing {
+            fingerprint: stored,
+            ..
+        } if stored != fingerprint => Err(AppError::UnprocessableEntity(ErrorMessage::new(
+            "idempotency_key_reused",
+        ))),
+        Reservation::Existing {
+            response: Some(response),
+            ..
+        } => Ok(Begin::Replay(response)),
+        Reservation::Existing { response: None, .. } => Err(AppError::Conflict(ErrorMessage::new(
+            "idempotency_key_in_progress",
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use super::Begin;
+    use crate::application::errors::AppError;
+    use crate::application::ports::idempotency_store::StoredResponse;
+    use crate::application::usecases::idempotency::finish;
+    use crate::application::usecases::idempotency::test_support::FakeIdempotencyStore;
+    use crate::infrastructure::clock::ManualClock;
+
+    const IN_PROGRESS_TIMEOUT_SECS: i64 = 120;
+
+    async fn execute(
+        store: &FakeIdempotencyStore,
+        clock: &ManualClock,
+        key: &str,
+        fingerprint: &str,
+    ) -> Result<Begin, AppError> {
+        super::execute(
+            store,
+            clock,
+            key,
+            fingerprint,
+            Duration::hours(1),
+            Duration::seconds(IN_PROGRESS_TIMEOUT_SECS),
+        )
+        .await
+    }
+
+    fn token(begin: Begin) -> String {
+        match begin {
+            Begin::Proceed { token } => token,
+            Begin::Replay(_) => panic!("expected a new reservation"),
+        }
+    }
+
+    fn created() -> StoredResponse {
+        StoredResponse {
+            status: 200,
+            headers: vec![("content-type".to_string(), "application/json".to_string())],
+            body: br#"{"id":1}"#.to_vec(),
+        }
+    }
+
+    #[tokio::test]
+    async fn retries_replay_the_stored_response() {
+        let store = FakeIdempotencyStore::default();
+        let clock = ManualClock::new(Utc::now());
+
+        let first = token(execute(&store, &clock, "k1", "fp").await.unwrap());
+        // 最初のリクエストがまだ終わっていない
+        let error = execute(&store, &clock, "k1", "fp").await.unwrap_err();
+        assert!(matches!(error, AppError::Conflict(_)), "{:?}", error);
+
+        finish::execute(&store, "k1", &first, Some(&created()))
+            .await
+            .unwrap();
+        let retry = execute(&store, &clock, "k1", "fp").await.unwrap();
+        assert_eq!(retry, Begin::Replay(created()));
+
+        // 期限が過ぎたら新しいリクエストとして扱う
+        clock.advance(Duration::hours(1));
+        let later = execute(&store, &clock, "k1", "fp").await.unwrap();
+        assert!(matches!(later, Begin::Proceed { .. }), "{:?}", later);
+    }
+
+    #[tokio::test]
+    async fn reusing_a_key_for_another_request_is_rejected() {
+        let store = FakeIdempotencyStore::default();
+        let clock = ManualClock::new(Utc::now());
+
+        let first = token(execute(&store, &clock, "k1", "fp").await.unwrap());
+        finish::execute(&store, "k1", &first, Some(&created()))
+            .await
+            .unwrap();
+
+        let error = execute(&store, &clock, "k1", "other").await.unwrap_err();
+        let AppError::UnprocessableEntity(message) = error else {
+            panic!("expected 422, got {:?}", error);
+        };
+        assert_eq!(message.code, "idempotency_key_reused");
+    }
+
+    #[tokio::test]
+    async fn abandoned_reservations_can_be_retried() {
+        let store = FakeIdempotencyStore::default();
+        let clock = ManualClock::new(Utc::now());
+
+        let released = token(execute(&store, &clock, "released", "fp").await.unwrap());
+        finish::execute(&store, "released", &released, None)
+            .await
+            .unwrap();
+        let retry = execute(&store, &clock, "released", "fp").await.unwrap();
+        assert!(matches!(retry, Begin::Proceed { .. }), "{:?}", retry);
+
+        // 応答を保存しないまま止まったものは、in_progress_timeout が過ぎると処理し直せる
+        execute(&store, &clock, "stuck", "fp").await.unwrap();
+        clock.advance(Duration::seconds(IN_PROGRESS_TIMEOUT_SECS - 1));
+        let error = execute(&store, &clock, "stuck", "fp").await.unwrap_err();
+        assert!(matches!(error, AppError::Conflict(_)), "{:?}", error);
+        clock.advance(Duration::seconds(1));
+        let retry = execute(&store, &clock, "stuck", "fp").await.unwrap();
+        assert!(matches!(retry, Begin::Proceed { .. }), "{:?}", retry);
+    }
+
+    #[tokio::test]
+    async fn a_late_finish_does_not_touch_the_reservation_that_replaced_it() {
+        let store = FakeIdempotencyStore::default();
+        let clock = ManualClock::new(Utc::now());
+
+        let stale = token(execute(&store, &clock, "k1", "fp").await.unwrap());
+        clock.advance(Duration::seconds(IN_PROGRESS_TIMEOUT_SECS));
+        let current = token(execute(&store, &clock, "k1", "fp").await.unwrap());
+
+        // 止まったとみなされた最初のリクエストが遅れて終わっても、後の予約はそのまま
+        finish::execute(&store, "k1", &stale, None).await.unwrap();
+        let error = execute(&store, &clock, "k1", "fp").await.unwrap_err();
+        assert!(matches!(error, AppError::Conflict(_)), "{:?}", error);
+        finish::execute(&store, "k1", &stale, Some(&created()))
+            .await
+            .unwrap();
+        let error = execute(&store, &clock, "k1", "fp").await.unwrap_err();
+        assert!(matches!(error, AppError::Conflict(_)), "{:?}", error);
+
+        finish::execute(&store, "k1", &current, Some(&created()))
+            .await
+            .unwrap();
+        let retry = execute(&store, &clock, "k1", "fp").await.unwrap();
+        assert_eq!(retry, Begin::Replay(created()));
+    }
+}
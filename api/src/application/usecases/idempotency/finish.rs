@@ -0,0 +1,16 @@
+use crate::application::errors::AppError;
+use crate::application::ports::idempotency_store::{IdempotencyStore, StoredResponse};
+
+/// 処理を終えたキーに応答を保存する（`token` は `begin` で受け取ったもの）。`None` なら予約を取り消し、同じキーで送り直せるようにする
+#[tracing::instrument(name = "usecase.idempotency.finish", skip_all)]
+pub async fn execute(
+    store: &dyn IdempotencyStore,
+    key: &str,
+    token: &str,
+    response: Option<&StoredResponse>,
+) -> Result<(), AppError> {
+    match response {
+        Some(response) => store.complete(key, token, response).await,
+        None => store.release(key, token).await,
+    }
+}
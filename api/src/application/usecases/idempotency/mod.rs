@@ -0,0 +1,5 @@
+pub mod begin;
+pub mod finish;
+
+#[cfg(test)]
+pub(crate) mod test_support;
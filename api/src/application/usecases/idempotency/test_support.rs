@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::application::errors::AppError;
+use crate::application::ports::idempotency_store::{IdempotencyStore, Reservation, StoredResponse};
+
+// 冪等キーのユースケース用のインメモリ実装
+
+struct Entry {
+    token: String,
+    fingerprint: String,
+    created_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+    response: Option<StoredResponse>,
+}
+
+#[derive(Default)]
+pub struct FakeIdempotencyStore {
+    entries: Mutex<HashMap<String, Entry>>,
+    reserved: AtomicU64,
+}
+
+#[async_trait]
+impl IdempotencyStore for FakeIdempotencyStore {
+    async fn reserve(
+        &self,
+        key: &str,
+        fingerprint: &str,
+        now: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+        stale_before: DateTime<Utc>,
+    ) -> Result<Reservation, AppError> {
+        let mut entries = self.entries.lock().expect("failed to lock entries");
+        entries.retain(|_, entry| {
+            entry.expires_at > now
+                && !(entry.response.is_none() && entry.created_at <= stale_before)
+        });
+        match entries.get(key) {
+            Some(entry) => Ok(Reservation::Existing {
+                fingerprint: entry.fingerprint.clone(),
+                response: entry.response.clone(),
+            }),
+            None => {
+                let token = format!("token-{}", self.reserved.fetch_add(1, Ordering::Relaxed));
+                entries.insert(
+                    key.to_string(),
+                    Entry {
+                        token: token.clone(),
+                        fingerprint: fingerprint.to_string(),
+                        created_at: now,
+                        expires_at,
+                        response: None,
+                    },
+                );
+                Ok(Reservation::Reserved { token })
+            }
+        }
+    }
+
+    async fn complete(
+        &self,
+        key: &str,
+        token: &str,
+        response: &StoredResponse,
+    ) -> Result<(), AppError> {
+        if let Some(entry) = self
+            .entries
+            .lock()
+            .expect("failed to lock entries")
+            .get_mut(key)
+            .filter(|entry| entry.token == token)
+        {
+            entry.response = Some(response.clone());
+        }
+        Ok(())
+    }
+
+    async fn release(&self, key: &str, token: &str) -> Result<(), AppError> {
+        let mut entries = self.entries.lock().expect("failed to lock entries");
+        if entries
+            .get(key)
+            .is_some_and(|entry| entry.token == token && entry.response.is_none())
+        {
+            entries.remove(key);
+        }
+        Ok(())
+    }
+}
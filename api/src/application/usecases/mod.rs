@@ -1 +1,7 @@
+pub mod attachment;
+pub mod comment;
+pub mod idempotency;
+pub mod reminder;
+mod retry;
 pub mod todo;
+pub mod webhook;
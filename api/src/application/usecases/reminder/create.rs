@@ -0,0 +1,94 @@
+use crate::application::errors::{AppError, ErrorMessage};
+use crate::application::ports::reminder_repository::ReminderRepository;
+use crate::application::ports::todo_repository::TodoRepository;
+use crate::domain::entities::reminder::{Reminder, ReminderSchedule};
+
+#[tracing::instrument(name = "usecase.reminder.create", skip_all)]
+pub async fn execute(
+    todos: &dyn TodoRepository,
+    reminders: &dyn ReminderRepository,
+    todo_id: u32,
+    schedule: ReminderSchedule,
+) -> Result<Reminder, AppError> {
+    if let ReminderSchedule::BeforeDue(offset) = schedule {
+        if offset < chrono::Duration::zero() {
+            return Err(AppError::validation(ErrorMessage::new(
+                "reminder_offset_negative",
+            )));
+        }
+    }
+    let todo = todos.get_by_id(todo_id).await?.ok_or(AppError::NotFound)?;
+
+    let fire_at = schedule.fire_at(todo.due_at);
+    reminders.create(todo_id, schedule, fire_at).await
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone, Utc};
+
+    use super::execute;
+    use crate::application::errors::AppError;
+    use crate::application::usecases::reminder::test_support::{FakeReminderRepo, FakeTodoRepo};
+    use crate::domain::entities::reminder::ReminderSchedule;
+
+    #[tokio::test]
+    async fn create_computes_fire_time_from_due_date() {
+        let due = Utc.with_ymd_and_hms(2030, 1, 1, 9, 0, 0).unwrap();
+        let reminders = FakeReminderRepo::default();
+
+        let reminder = execute(
+            &FakeTodoRepo::with_due(Some(due)),
+            &reminders,
+            1,
+            ReminderSchedule::BeforeDue(Duration::hours(1)),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            reminder.fire_at,
+            Some(Utc.with_ymd_and_hms(2030, 1, 1, 8, 0, 0).unwrap())
+        );
+    }
+
+    #[tokio::test]
+    async fn create_keeps_offset_reminder_unscheduled_without_due_date() {
+        let reminders = FakeReminderRepo::default();
+
+        let reminder = execute(
+            &FakeTodoRepo::with_due(None),
+            &reminders,
+            1,
+            ReminderSchedule::BeforeDue(Duration::hours(1)),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(reminder.fire_at, None);
+    }
+
+    #[tokio::test]
+    async fn create_rejects_negative_offset_and_missing_todo() {
+        let reminders = FakeReminderRepo::default();
+
+        let negative = execute(
+            &FakeTodoRepo::with_due(None),
+            &reminders,
+            1,
+            ReminderSchedule::BeforeDue(Duration::minutes(-5)),
+        )
+        .await;
+        let missing = execute(
+            &FakeTodoRepo { todo: None },
+            &reminders,
+            1,
+            ReminderSchedule::At(Utc::now()),
+        )
+        .await;
+
+        assert!(matches!(negative, Err(AppError::Validation(_))));
+        assert!(matches!(missing, Err(AppError::NotFound)));
+        assert!(reminders.rows().is_empty());
+    }
+}
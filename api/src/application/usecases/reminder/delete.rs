@@ -0,0 +1,35 @@
+use crate::application::errors::AppError;
+use crate::application::ports::reminder_repository::ReminderRepository;
+
+#[tracing::instrument(name = "usecase.reminder.delete", skip_all)]
+pub async fn execute(
+    reminders: &dyn ReminderRepository,
+    todo_id: u32,
+    id: u32,
+) -> Result<bool, AppError> {
+    reminders.delete(todo_id, id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::execute;
+    use crate::application::ports::reminder_repository::ReminderRepository;
+    use crate::application::usecases::reminder::test_support::FakeReminderRepo;
+    use crate::domain::entities::reminder::ReminderSchedule;
+
+    #[tokio::test]
+    async fn delete_delegates_to_repository() {
+        let reminders = FakeReminderRepo::default();
+        let now = Utc::now();
+        reminders
+            .create(1, ReminderSchedule::At(now), Some(now))
+            .await
+            .unwrap();
+
+        assert!(!execute(&reminders, 2, 1).await.unwrap());
+        assert!(execute(&reminders, 1, 1).await.unwrap());
+        assert!(reminders.rows().is_empty());
+    }
+}
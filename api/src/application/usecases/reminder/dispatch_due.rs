@@ -0,0 +1,124 @@
+use crate::application::errors::AppError;
+use crate::application::ports::clock::Clock;
+use crate::application::ports::notifier::{Notifier, ReminderNotification};
+use crate::application::ports::reminder_repository::ReminderRepository;
+use crate::application::usecases::retry::retry_delay;
+
+// 30秒から倍々に伸ばし、最大1時間で頭打ちにする
+const BASE_RETRY_DELAY_SECS: i64 = 30;
+const MAX_RETRY_DELAY_SECS: i64 = 60 * 60;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DispatchSummary {
+    pub delivered: usize,
+    pub failed: usize,
+}
+
+/// 発火時刻を過ぎたリマインダーを1回分まとめて通知する。
+/// 通知に成功してから配信済みにするので、途中で落ちた場合は再送される（at-least-once）
+#[tracing::instrument(name = "usecase.reminder.dispatch_due", skip_all)]
+pub async fn execute(
+    reminders: &dyn ReminderRepository,
+    notifier: &dyn Notifier,
+    clock: &dyn Clock,
+    batch_size: u32,
+) -> Result<DispatchSummary, AppError> {
+    let now = clock.now();
+    let mut summary = DispatchSummary::default();
+
+    for due in reminders.due(now, batch_size).await? {
+        let reminder = &due.reminder;
+        let notification = ReminderNotification {
+            reminder_id: reminder.id,
+            todo_id: reminder.todo_id,
+            todo_title: due.todo_title.clone(),
+            due_at: due.due_at,
+            fire_at: reminder.fire_at.unwrap_or(now),
+        };
+
+        match notifier.notify(&notification).await {
+            Ok(()) => {
+                reminders.mark_delivered(reminder.id, clock.now()).await?;
+                summary.delivered += 1;
+            }
+            Err(e) => {
+                let retry_at = clock.now()
+                    + retry_delay(
+                        BASE_RETRY_DELAY_SECS,
+                        MAX_RETRY_DELAY_SECS,
+                        reminder.attempts,
+                    );
+                reminders
+                    .mark_failed(reminder.id, format!("{:?}", e), retry_at)
+                    .await?;
+                summary.failed += 1;
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone, Utc};
+
+    use super::{execute, DispatchSummary};
+    use crate::application::ports::reminder_repository::ReminderRepository;
+    use crate::application::usecases::reminder::test_support::{
+        FakeReminderRepo, RecordingNotifier,
+    };
+    use crate::domain::entities::reminder::ReminderSchedule;
+    use crate::infrastructure::clock::ManualClock;
+
+    #[tokio::test]
+    async fn dispatch_delivers_only_reminders_whose_time_has_come() {
+        let start = Utc.with_ymd_and_hms(2030, 1, 1, 9, 0, 0).unwrap();
+        let clock = ManualClock::new(start);
+        let reminders = FakeReminderRepo::default();
+        let notifier = RecordingNotifier::default();
+        let soon = start + Duration::minutes(5);
+        reminders
+            .create(1, ReminderSchedule::At(soon), Some(soon))
+            .await
+            .unwrap();
+
+        let summary = execute(&reminders, &notifier, &clock, 10).await.unwrap();
+        assert_eq!(summary, DispatchSummary::default());
+
+        clock.advance(Duration::minutes(5));
+        let summary = execute(&reminders, &notifier, &clock, 10).await.unwrap();
+        assert_eq!(summary.delivered, 1);
+        assert_eq!(reminders.rows()[0].delivered_at, Some(soon));
+
+        // 配信済みのものは再送しない
+        clock.advance(Duration::minutes(1));
+        let summary = execute(&reminders, &notifier, &clock, 10).await.unwrap();
+        assert_eq!(summary.delivered, 0);
+        assert_eq!(notifier.sent.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn dispatch_records_failures_for_retry() {
+        let start = Utc.with_ymd_and_hms(2030, 1, 1, 9, 0, 0).unwrap();
+        let clock = ManualClock::new(start);
+        let reminders = FakeReminderRepo::default();
+        let notifier = RecordingNotifier {
+            fail: true,
+            ..Default::default()
+        };
+        reminders
+            .create(1, ReminderSchedule::At(start), Some(start))
+            .await
+            .unwrap();
+
+        let summary = execute(&reminders, &notifier, &clock, 10).await.unwrap();
+
+        assert_eq!(summary.failed, 1);
+        let row = reminders.rows()[0].clone();
+        assert_eq!(row.attempts, 1);
+        assert!(row.delivered_at.is_none());
+        assert!(row.last_error.unwrap().contains("notifier is down"));
+        assert_eq!(row.fire_at, Some(start + Duration::seconds(30)));
+    }
+}
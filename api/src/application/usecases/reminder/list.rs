@@ -0,0 +1,47 @@
+use crate::application::errors::AppError;
+use crate::application::ports::reminder_repository::ReminderRepository;
+use crate::application::ports::todo_repository::TodoRepository;
+use crate::domain::entities::reminder::Reminder;
+
+#[tracing::instrument(name = "usecase.reminder.list", skip_all)]
+pub async fn execute(
+    todos: &dyn TodoRepository,
+    reminders: &dyn ReminderRepository,
+    todo_id: u32,
+) -> Result<Vec<Reminder>, AppError> {
+    if todos.get_by_id(todo_id).await?.is_none() {
+        return Err(AppError::NotFound);
+    }
+    reminders.list_by_todo(todo_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::execute;
+    use crate::application::ports::reminder_repository::ReminderRepository;
+    use crate::application::usecases::reminder::test_support::{FakeReminderRepo, FakeTodoRepo};
+    use crate::domain::entities::reminder::ReminderSchedule;
+
+    #[tokio::test]
+    async fn list_delegates_to_repository() {
+        let reminders = FakeReminderRepo::default();
+        let now = Utc::now();
+        reminders
+            .create(1, ReminderSchedule::At(now), Some(now))
+            .await
+            .unwrap();
+        reminders
+            .create(2, ReminderSchedule::At(now), Some(now))
+            .await
+            .unwrap();
+
+        let result = execute(&FakeTodoRepo::with_due(None), &reminders, 1)
+            .await
+            .unwrap();
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].todo_id, 1);
+    }
+}
@@ -0,0 +1,7 @@
+pub mod create;
+pub mod delete;
+pub mod dispatch_due;
+pub mod list;
+
+#[cfg(test)]
+pub(crate) mod test_support;
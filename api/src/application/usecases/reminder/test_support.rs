@@ -0,0 +1,171 @@
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::application::errors::AppError;
+use crate::application::ports::notifier::{Notifier, ReminderNotification};
+use crate::application::ports::reminder_repository::{DueReminder, ReminderRepository};
+use crate::application::ports::todo_repository::TodoRepository;
+use crate::domain::entities::reminder::{Reminder, ReminderSchedule};
+use crate::domain::entities::todo::Todo;
+
+// リマインダーのユースケース用のインメモリ実装
+
+pub struct FakeTodoRepo {
+    pub todo: Option<Todo>,
+}
+
+impl FakeTodoRepo {
+    pub fn with_due(due_at: Option<DateTime<Utc>>) -> Self {
+        Self {
+            todo: Some(Todo {
+                id: 1,
+                title: "remind me".to_string(),
+                completed: false,
+                position: 1,
+                due_at,
+            }),
+        }
+    }
+}
+
+#[async_trait]
+impl TodoRepository for FakeTodoRepo {
+    async fn create(&self, _title: String) -> Result<Todo, AppError> {
+        unimplemented!("not needed for this test");
+    }
+
+    async fn get_all(&self) -> Result<Vec<Todo>, AppError> {
+        unimplemented!("not needed for this test");
+    }
+
+    async fn get_by_id(&self, _id: u32) -> Result<Option<Todo>, AppError> {
+        Ok(self.todo.clone())
+    }
+
+    async fn update(
+        &self,
+        _id: u32,
+        _title: Option<String>,
+        _completed: Option<bool>,
+        _due_at: Option<Option<DateTime<Utc>>>,
+    ) -> Result<Option<Todo>, AppError> {
+        unimplemented!("not needed for this test");
+    }
+
+    async fn delete(&self, _id: u32) -> Result<Option<Vec<String>>, AppError> {
+        unimplemented!("not needed for this test");
+    }
+
+    async fn reorder(&self, _todo_ids: Vec<i64>) -> Result<(), AppError> {
+        unimplemented!("not needed for this test");
+    }
+}
+
+#[derive(Default)]
+pub struct FakeReminderRepo {
+    pub rows: Mutex<Vec<Reminder>>,
+}
+
+impl FakeReminderRepo {
+    pub fn rows(&self) -> std::sync::MutexGuard<'_, Vec<Reminder>> {
+        self.rows.lock().expect("failed to lock rows")
+    }
+}
+
+#[async_trait]
+impl ReminderRepository for FakeReminderRepo {
+    async fn create(
+        &self,
+        todo_id: u32,
+        schedule: ReminderSchedule,
+        fire_at: Option<DateTime<Utc>>,
+    ) -> Result<Reminder, AppError> {
+        let mut rows = self.rows();
+        let reminder = Reminder {
+            id: rows.len() as i64 + 1,
+            todo_id: todo_id as i64,
+            schedule,
+            fire_at,
+            created_at: Utc::now(),
+            delivered_at: None,
+            attempts: 0,
+            last_error: None,
+        };
+        rows.push(reminder.clone());
+        Ok(reminder)
+    }
+
+    async fn list_by_todo(&self, todo_id: u32) -> Result<Vec<Reminder>, AppError> {
+        Ok(self
+            .rows()
+            .iter()
+            .filter(|r| r.todo_id == todo_id as i64)
+            .cloned()
+            .collect())
+    }
+
+    async fn delete(&self, todo_id: u32, id: u32) -> Result<bool, AppError> {
+        let mut rows = self.rows();
+        let before = rows.len();
+        rows.retain(|r| !(r.todo_id == todo_id as i64 && r.id == id as i64));
+        Ok(rows.len() < before)
+    }
+
+    async fn due(&self, now: DateTime<Utc>, limit: u32) -> Result<Vec<DueReminder>, AppError> {
+        Ok(self
+            .rows()
+            .iter()
+            .filter(|r| r.delivered_at.is_none() && r.fire_at.is_some_and(|at| at <= now))
+            .take(limit as usize)
+            .map(|r| DueReminder {
+                reminder: r.clone(),
+                todo_title: "remind me".to_string(),
+                due_at: None,
+            })
+            .collect())
+    }
+
+    async fn mark_delivered(&self, id: i64, delivered_at: DateTime<Utc>) -> Result<(), AppError> {
+        if let Some(r) = self.rows().iter_mut().find(|r| r.id == id) {
+            r.delivered_at = Some(delivered_at);
+        }
+        Ok(())
+    }
+
+    async fn mark_failed(
+        &self,
+        id: i64,
+        error: String,
+        retry_at: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        // このフェイクでは再試行時刻を fire_at に入れて表現する
+        if let Some(r) = self.rows().iter_mut().find(|r| r.id == id) {
+            r.attempts += 1;
+            r.last_error = Some(error);
+            r.fire_at = Some(retry_at);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+pub struct RecordingNotifier {
+    pub sent: Mutex<Vec<ReminderNotification>>,
+    pub fail: bool,
+}
+
+#[async_trait]
+impl Notifier for RecordingNotifier {
+    async fn notify(&self, notification: &ReminderNotification) -> Result<(), AppError> {
+        if self.fail {
+            return Err(AppError::unexpected("notifier is down"));
+        }
+        self.sent
+            .lock()
+            .expect("failed to lock sent")
+            .push(notification.clone());
+        Ok(())
+    }
+}
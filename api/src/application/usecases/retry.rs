@@ -0,0 +1,26 @@
+use chrono::Duration;
+
+/// 失敗した送信を次に試すまでの待ち時間。`base_secs` から倍々に伸ばし、`max_secs` で頭打ちにする
+pub(crate) fn retry_delay(base_secs: i64, max_secs: i64, previous_attempts: u32) -> Duration {
+    let secs = base_secs
+        .saturating_mul(1_i64 << previous_attempts.min(16))
+        .min(max_secs);
+    Duration::seconds(secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::retry_delay;
+
+    #[test]
+    fn retry_delay_grows_exponentially_up_to_the_max() {
+        assert_eq!(retry_delay(30, 3600, 0), Duration::seconds(30));
+        assert_eq!(retry_delay(30, 3600, 1), Duration::seconds(60));
+        assert_eq!(retry_delay(10, 3600, 2), Duration::seconds(40));
+        assert_eq!(retry_delay(30, 3600, 3), Duration::seconds(240));
+        assert_eq!(retry_delay(10, 3600, 20), Duration::hours(1));
+        assert_eq!(retry_delay(30, 3600, u32::MAX), Duration::hours(1));
+    }
+}
@@ -1,9 +1,18 @@
 use crate::application::errors::AppError;
+use crate::application::ports::event_publisher::EventPublisher;
 use crate::application::ports::todo_repository::TodoRepository;
 use crate::domain::entities::todo::Todo;
+use crate::domain::events::TodoEvent;
 
-pub async fn execute(repo: &dyn TodoRepository, title: String) -> Result<Todo, AppError> {
-    repo.create(title).await
+#[tracing::instrument(name = "usecase.todo.create", skip_all)]
+pub async fn execute(
+    repo: &dyn TodoRepository,
+    events: &dyn EventPublisher,
+    title: String,
+) -> Result<Todo, AppError> {
+    let todo = repo.create(title).await?;
+    events.publish(TodoEvent::Created(todo.clone())).await;
+    Ok(todo)
 }
 
 #[cfg(test)]
@@ -11,10 +20,12 @@ mod tests {
     use std::sync::Mutex;
 
     use async_trait::async_trait;
+    use chrono::{DateTime, Utc};
 
     use super::execute;
     use crate::application::errors::AppError;
     use crate::application::ports::todo_repository::TodoRepository;
+    use crate::application::usecases::todo::test_support::RecordingPublisher;
     use crate::domain::entities::todo::Todo;
 
     struct FakeRepo {
@@ -42,11 +53,12 @@ mod tests {
             _id: u32,
             _title: Option<String>,
             _completed: Option<bool>,
+            _due_at: Option<Option<DateTime<Utc>>>,
         ) -> Result<Option<Todo>, AppError> {
             unimplemented!("not needed for this test");
         }
 
-        async fn delete(&self, _id: u32) -> Result<bool, AppError> {
+        async fn delete(&self, _id: u32) -> Result<Option<Vec<String>>, AppError> {
             unimplemented!("not needed for this test");
         }
 
@@ -64,10 +76,13 @@ mod tests {
                 title: "saved".to_string(),
                 completed: false,
                 position: 1,
+                due_at: None,
             },
         };
 
-        let result = execute(&repo, "write tests".to_string()).await;
+        let events = RecordingPublisher::default();
+
+        let result = execute(&repo, &events, "write tests".to_string()).await;
 
         let stored_title = repo
             .last_title
@@ -76,5 +91,6 @@ mod tests {
             .clone();
         assert_eq!(stored_title, Some("write tests".to_string()));
         assert_eq!(result.unwrap().title, "saved");
+        assert_eq!(events.names(), vec!["todo.created"]);
     }
 }
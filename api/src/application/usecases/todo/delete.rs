@@ -1,8 +1,29 @@
 use crate::application::errors::AppError;
+use crate::application::ports::attachment_repository::AttachmentRepository;
+use crate::application::ports::blob_storage::BlobStorage;
+use crate::application::ports::event_publisher::EventPublisher;
 use crate::application::ports::todo_repository::TodoRepository;
+use crate::application::usecases::attachment::purge as purge_attachments;
+use crate::application::usecases::todo::edit_lock::Editor;
+use crate::domain::events::TodoEvent;
 
-pub async fn execute(repo: &dyn TodoRepository, id: u32) -> Result<bool, AppError> {
-    repo.delete(id).await
+#[tracing::instrument(name = "usecase.todo.delete", skip_all)]
+pub async fn execute(
+    repo: &dyn TodoRepository,
+    attachments: &dyn AttachmentRepository,
+    blobs: &dyn BlobStorage,
+    events: &dyn EventPublisher,
+    editor: Editor<'_>,
+    id: u32,
+) -> Result<bool, AppError> {
+    editor.ensure_editable(id)?;
+    let Some(keys) = repo.delete(id).await? else {
+        return Ok(false);
+    };
+    // レコードはリポジトリ側で削除済み。添付のファイル本体はコミットの後でここで片付ける
+    purge_attachments::execute(attachments, blobs, &keys).await;
+    events.publish(TodoEvent::Deleted { id: id as i64 }).await;
+    Ok(true)
 }
 
 #[cfg(test)]
@@ -10,15 +31,24 @@ mod tests {
     use std::sync::Mutex;
 
     use async_trait::async_trait;
+    use chrono::{DateTime, Utc};
 
     use super::execute;
     use crate::application::errors::AppError;
+    use crate::application::ports::blob_storage::BlobStorage;
+    use crate::application::ports::edit_locks::NoEditLocks;
     use crate::application::ports::todo_repository::TodoRepository;
+    use crate::application::usecases::attachment::test_support::{
+        attachment, FakeAttachmentRepo, FakeBlobStorage,
+    };
+    use crate::application::usecases::todo::edit_lock::Editor;
+    use crate::application::usecases::todo::test_support::RecordingPublisher;
     use crate::domain::entities::todo::Todo;
 
     struct FakeRepo {
         last_id: Mutex<Option<u32>>,
-        result: bool,
+        /// 削除したときに返す本体のキー（`None` ならTODOがない）
+        result: Option<Vec<String>>,
     }
 
     #[async_trait]
@@ -40,13 +70,14 @@ mod tests {
             _id: u32,
             _title: Option<String>,
             _completed: Option<bool>,
+            _due_at: Option<Option<DateTime<Utc>>>,
         ) -> Result<Option<Todo>, AppError> {
             unimplemented!("not needed for this test");
         }
 
-        async fn delete(&self, id: u32) -> Result<bool, AppError> {
+        async fn delete(&self, id: u32) -> Result<Option<Vec<String>>, AppError> {
             *self.last_id.lock().expect("failed to lock last_id") = Some(id);
-            Ok(self.result)
+            Ok(self.result.clone())
         }
 
         async fn reorder(&self, _todo_ids: Vec<i64>) -> Result<(), AppError> {
@@ -58,13 +89,78 @@ mod tests {
     async fn delete_delegates_to_repository() {
         let repo = FakeRepo {
             last_id: Mutex::new(None),
-            result: true,
+            result: Some(Vec::new()),
         };
 
-        let result = execute(&repo, 9).await.unwrap();
+        let events = RecordingPublisher::default();
+
+        let result = execute(
+            &repo,
+            &FakeAttachmentRepo::default(),
+            &FakeBlobStorage::default(),
+            &events,
+            Editor::anonymous(&NoEditLocks),
+            9,
+        )
+        .await
+        .unwrap();
 
         let last_id = *repo.last_id.lock().expect("failed to lock last_id");
         assert_eq!(last_id, Some(9));
         assert!(result);
+        assert_eq!(events.names(), vec!["todo.deleted"]);
+    }
+
+    #[tokio::test]
+    async fn delete_removes_blobs_no_longer_referenced() {
+        let blobs = FakeBlobStorage::default();
+        let own = blobs.put(b"abc").await.unwrap();
+        let shared = blobs.put(b"de").await.unwrap();
+        let repo = FakeRepo {
+            last_id: Mutex::new(None),
+            result: Some(vec![own, shared.clone()]),
+        };
+        // 別のTODOの添付が同じ内容を参照している
+        let attachments = FakeAttachmentRepo::with_rows(vec![attachment(2, 3, &shared)]);
+
+        execute(
+            &repo,
+            &attachments,
+            &blobs,
+            &RecordingPublisher::default(),
+            Editor::anonymous(&NoEditLocks),
+            9,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(blobs.keys(), vec![shared]);
+    }
+
+    #[tokio::test]
+    async fn delete_leaves_blobs_when_todo_missing() {
+        let repo = FakeRepo {
+            last_id: Mutex::new(None),
+            result: None,
+        };
+        let blobs = FakeBlobStorage::default();
+        let key = blobs.put(b"abc").await.unwrap();
+
+        let events = RecordingPublisher::default();
+
+        let result = execute(
+            &repo,
+            &FakeAttachmentRepo::default(),
+            &blobs,
+            &events,
+            Editor::anonymous(&NoEditLocks),
+            9,
+        )
+        .await
+        .unwrap();
+
+        assert!(!result);
+        assert!(events.names().is_empty());
+        assert_eq!(blobs.keys(), vec![key]);
     }
 }
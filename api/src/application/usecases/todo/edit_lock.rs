@@ -0,0 +1,45 @@
+use crate::application::errors::{AppError, ErrorMessage};
+use crate::application::ports::edit_locks::EditLocks;
+use crate::domain::entities::presence::EditLock;
+
+/// TODOを更新・削除しようとしている側。
+/// WebSocketからなら接続IDを持ち、その接続が取ったロックは妨げにならない
+#[derive(Clone, Copy)]
+pub struct Editor<'a> {
+    locks: &'a dyn EditLocks,
+    connection_id: Option<u64>,
+}
+
+impl<'a> Editor<'a> {
+    /// ロックを取れない経路（REST・GraphQL・gRPCなど）
+    pub fn anonymous(locks: &'a dyn EditLocks) -> Self {
+        Self {
+            locks,
+            connection_id: None,
+        }
+    }
+
+    /// WebSocketの接続
+    pub fn connection(locks: &'a dyn EditLocks, connection_id: u64) -> Self {
+        Self {
+            locks,
+            connection_id: Some(connection_id),
+        }
+    }
+
+    /// 他の接続が有効なロックを持っていれば、更新も削除もさせない
+    pub fn ensure_editable(&self, id: u32) -> Result<(), AppError> {
+        match self.locks.held_by_other(id as i64, self.connection_id) {
+            Some(held) => Err(locked(&held)),
+            None => Ok(()),
+        }
+    }
+}
+
+pub fn locked(held: &EditLock) -> AppError {
+    AppError::Conflict(
+        ErrorMessage::new("todo_locked")
+            .with("id", held.todo_id)
+            .with("user", &held.user),
+    )
+}
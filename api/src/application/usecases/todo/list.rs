@@ -2,6 +2,7 @@ use crate::application::errors::AppError;
 use crate::application::ports::todo_repository::TodoRepository;
 use crate::domain::entities::todo::Todo;
 
+#[tracing::instrument(name = "usecase.todo.list", skip_all)]
 pub async fn execute(repo: &dyn TodoRepository) -> Result<Vec<Todo>, AppError> {
     repo.get_all().await
 }
@@ -11,6 +12,7 @@ mod tests {
     use std::sync::Mutex;
 
     use async_trait::async_trait;
+    use chrono::{DateTime, Utc};
 
     use super::execute;
     use crate::application::errors::AppError;
@@ -42,11 +44,12 @@ mod tests {
             _id: u32,
             _title: Option<String>,
             _completed: Option<bool>,
+            _due_at: Option<Option<DateTime<Utc>>>,
         ) -> Result<Option<Todo>, AppError> {
             unimplemented!("not needed for this test");
         }
 
-        async fn delete(&self, _id: u32) -> Result<bool, AppError> {
+        async fn delete(&self, _id: u32) -> Result<Option<Vec<String>>, AppError> {
             unimplemented!("not needed for this test");
         }
 
@@ -64,6 +67,7 @@ mod tests {
                 title: "first".to_string(),
                 completed: false,
                 position: 1,
+                due_at: None,
             }],
         };
 
@@ -1,6 +1,11 @@
 pub mod create;
 pub mod delete;
+pub mod edit_lock;
 pub mod get;
 pub mod list;
+pub mod query;
 pub mod reorder;
 pub mod update;
+
+#[cfg(test)]
+pub(crate) mod test_support;
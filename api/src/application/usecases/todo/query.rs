@@ -0,0 +1,170 @@
+use crate::application::errors::{AppError, ErrorMessage};
+use crate::application::ports::todo_repository::TodoRepository;
+pub use crate::application::ports::todo_repository::{
+    SortDirection, TodoFilter, TodoPage, TodoSort, TodoSortField,
+};
+
+pub const DEFAULT_LIMIT: u32 = 50;
+pub const MAX_LIMIT: u32 = 200;
+
+/// `limit` を確かめ、絞り込み・並び替え・ページングはリポジトリに任せる
+#[tracing::instrument(name = "usecase.todo.query", skip_all)]
+pub async fn execute(
+    repo: &dyn TodoRepository,
+    filter: &TodoFilter,
+    sort: TodoSort,
+    limit: u32,
+    offset: u32,
+) -> Result<TodoPage, AppError> {
+    if limit == 0 || limit > MAX_LIMIT {
+        return Err(AppError::validation(
+            ErrorMessage::new("limit_out_of_range").with("max", MAX_LIMIT),
+        ));
+    }
+
+    repo.query(filter, sort, limit, offset).await
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use chrono::{DateTime, TimeZone, Utc};
+
+    use super::{execute, SortDirection, TodoFilter, TodoSort, TodoSortField};
+    use crate::application::errors::AppError;
+    use crate::application::ports::todo_repository::TodoRepository;
+    use crate::domain::entities::todo::Todo;
+
+    struct FakeRepo {
+        todos: Vec<Todo>,
+    }
+
+    #[async_trait]
+    impl TodoRepository for FakeRepo {
+        async fn create(&self, _title: String) -> Result<Todo, AppError> {
+            unimplemented!("not needed for this test");
+        }
+
+        async fn get_all(&self) -> Result<Vec<Todo>, AppError> {
+            Ok(self.todos.clone())
+        }
+
+        async fn get_by_id(&self, _id: u32) -> Result<Option<Todo>, AppError> {
+            unimplemented!("not needed for this test");
+        }
+
+        async fn update(
+            &self,
+            _id: u32,
+            _title: Option<String>,
+            _completed: Option<bool>,
+            _due_at: Option<Option<DateTime<Utc>>>,
+        ) -> Result<Option<Todo>, AppError> {
+            unimplemented!("not needed for this test");
+        }
+
+        async fn delete(&self, _id: u32) -> Result<Option<Vec<String>>, AppError> {
+            unimplemented!("not needed for this test");
+        }
+
+        async fn reorder(&self, _todo_ids: Vec<i64>) -> Result<(), AppError> {
+            unimplemented!("not needed for this test");
+        }
+    }
+
+    fn todo(id: i64, title: &str, completed: bool, due_day: Option<u32>) -> Todo {
+        Todo {
+            id,
+            title: title.to_string(),
+            completed,
+            position: 10 - id,
+            due_at: due_day.map(|day| Utc.with_ymd_and_hms(2025, 1, day, 0, 0, 0).unwrap()),
+        }
+    }
+
+    fn repo() -> FakeRepo {
+        FakeRepo {
+            todos: vec![
+                todo(1, "Buy milk", false, Some(3)),
+                todo(2, "Write report", true, None),
+                todo(3, "buy bread", false, Some(1)),
+                todo(4, "Call mom", false, None),
+            ],
+        }
+    }
+
+    fn ids(todos: &[Todo]) -> Vec<i64> {
+        todos.iter().map(|todo| todo.id).collect()
+    }
+
+    #[tokio::test]
+    async fn default_sort_follows_position() {
+        let page = execute(&repo(), &TodoFilter::default(), TodoSort::default(), 50, 0)
+            .await
+            .unwrap();
+
+        assert_eq!(ids(&page.todos), vec![4, 3, 2, 1]);
+        assert_eq!(page.total, 4);
+    }
+
+    #[tokio::test]
+    async fn filters_are_combined() {
+        let filter = TodoFilter {
+            completed: Some(false),
+            title_contains: Some("BUY".to_string()),
+            ..TodoFilter::default()
+        };
+        let sort = TodoSort {
+            field: TodoSortField::Title,
+            direction: SortDirection::Asc,
+        };
+
+        let page = execute(&repo(), &filter, sort, 50, 0).await.unwrap();
+
+        assert_eq!(ids(&page.todos), vec![1, 3]);
+    }
+
+    #[tokio::test]
+    async fn due_sort_puts_undated_last_and_paginates() {
+        let sort = TodoSort {
+            field: TodoSortField::DueAt,
+            direction: SortDirection::Desc,
+        };
+
+        let page = execute(&repo(), &TodoFilter::default(), sort, 2, 1)
+            .await
+            .unwrap();
+
+        assert_eq!(ids(&page.todos), vec![3, 2]);
+        assert_eq!(page.total, 4);
+    }
+
+    #[tokio::test]
+    async fn due_range_excludes_undated() {
+        let filter = TodoFilter {
+            due_before: Some(Utc.with_ymd_and_hms(2025, 1, 2, 0, 0, 0).unwrap()),
+            ..TodoFilter::default()
+        };
+
+        let page = execute(&repo(), &filter, TodoSort::default(), 50, 0)
+            .await
+            .unwrap();
+
+        assert_eq!(ids(&page.todos), vec![3]);
+    }
+
+    #[tokio::test]
+    async fn rejects_out_of_range_limit() {
+        for limit in [0, 201] {
+            let result = execute(
+                &repo(),
+                &TodoFilter::default(),
+                TodoSort::default(),
+                limit,
+                0,
+            )
+            .await;
+            assert!(matches!(result, Err(AppError::Validation(_))));
+        }
+    }
+}
@@ -1,8 +1,24 @@
-use crate::application::errors::AppError;
+use crate::application::errors::{AppError, ErrorMessage, FieldError};
+use crate::application::ports::event_publisher::EventPublisher;
 use crate::application::ports::todo_repository::TodoRepository;
+use crate::domain::events::TodoEvent;
 
-pub async fn execute(repo: &dyn TodoRepository, ids: Vec<i64>) -> Result<(), AppError> {
-    repo.reorder(ids).await
+#[tracing::instrument(name = "usecase.todo.reorder", skip_all)]
+pub async fn execute(
+    repo: &dyn TodoRepository,
+    events: &dyn EventPublisher,
+    ids: Vec<i64>,
+    max_ids: usize,
+) -> Result<(), AppError> {
+    if ids.len() > max_ids {
+        return Err(AppError::InvalidFields(vec![FieldError {
+            field: "ids".to_string(),
+            message: ErrorMessage::new("too_many_items").with("max", max_ids),
+        }]));
+    }
+    repo.reorder(ids.clone()).await?;
+    events.publish(TodoEvent::Reordered { ids }).await;
+    Ok(())
 }
 
 #[cfg(test)]
@@ -10,10 +26,12 @@ mod tests {
     use std::sync::Mutex;
 
     use async_trait::async_trait;
+    use chrono::{DateTime, Utc};
 
     use super::execute;
     use crate::application::errors::AppError;
     use crate::application::ports::todo_repository::TodoRepository;
+    use crate::application::usecases::todo::test_support::RecordingPublisher;
     use crate::domain::entities::todo::Todo;
 
     struct FakeRepo {
@@ -39,11 +57,12 @@ mod tests {
             _id: u32,
             _title: Option<String>,
             _completed: Option<bool>,
+            _due_at: Option<Option<DateTime<Utc>>>,
         ) -> Result<Option<Todo>, AppError> {
             unimplemented!("not needed for this test");
         }
 
-        async fn delete(&self, _id: u32) -> Result<bool, AppError> {
+        async fn delete(&self, _id: u32) -> Result<Option<Vec<String>>, AppError> {
             unimplemented!("not needed for this test");
         }
 
@@ -60,7 +79,8 @@ mod tests {
         };
 
         let ids = vec![3, 1, 2];
-        execute(&repo, ids.clone()).await.unwrap();
+        let events = RecordingPublisher::default();
+        execute(&repo, &events, ids.clone(), 3).await.unwrap();
 
         let last_ids = repo
             .last_ids
@@ -68,5 +88,24 @@ mod tests {
             .expect("failed to lock last_ids")
             .clone();
         assert_eq!(last_ids, Some(ids));
+        assert_eq!(events.names(), vec!["todo.reordered"]);
+    }
+
+    #[tokio::test]
+    async fn reorder_rejects_too_many_ids() {
+        let repo = FakeRepo {
+            last_ids: Mutex::new(None),
+        };
+
+        let events = RecordingPublisher::default();
+        let error = execute(&repo, &events, vec![3, 1, 2], 2).await.unwrap_err();
+
+        let AppError::InvalidFields(fields) = error else {
+            panic!("expected field errors, got {:?}", error);
+        };
+        assert_eq!(fields[0].field, "ids");
+        assert_eq!(fields[0].message.code, "too_many_items");
+        assert!(repo.last_ids.lock().unwrap().is_none());
+        assert!(events.names().is_empty());
     }
 }
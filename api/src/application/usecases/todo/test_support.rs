@@ -0,0 +1,57 @@
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use chrono::{TimeZone, Utc};
+
+use crate::application::ports::edit_locks::EditLocks;
+use crate::application::ports::event_publisher::EventPublisher;
+use crate::domain::entities::presence::EditLock;
+use crate::domain::events::TodoEvent;
+
+// TODOのユースケース用のインメモリ実装
+
+#[derive(Default)]
+pub struct RecordingPublisher {
+    pub events: Mutex<Vec<TodoEvent>>,
+}
+
+impl RecordingPublisher {
+    pub fn names(&self) -> Vec<&'static str> {
+        self.events
+            .lock()
+            .expect("failed to lock events")
+            .iter()
+            .map(TodoEvent::name)
+            .collect()
+    }
+}
+
+#[async_trait]
+impl EventPublisher for RecordingPublisher {
+    async fn publish(&self, event: TodoEvent) {
+        self.events
+            .lock()
+            .expect("failed to lock events")
+            .push(event);
+    }
+}
+
+/// 決まったロックを1つだけ持つ（期限は考えない）
+pub struct FixedLocks(pub EditLock);
+
+impl FixedLocks {
+    pub fn held_by(todo_id: i64, connection_id: u64, user: &str) -> Self {
+        Self(EditLock {
+            todo_id,
+            connection_id,
+            user: user.to_string(),
+            expires_at: Utc.with_ymd_and_hms(2030, 1, 1, 9, 0, 0).unwrap(),
+        })
+    }
+}
+
+impl EditLocks for FixedLocks {
+    fn held_by_other(&self, todo_id: i64, editor: Option<u64>) -> Option<EditLock> {
+        (self.0.todo_id == todo_id && Some(self.0.connection_id) != editor).then(|| self.0.clone())
+    }
+}
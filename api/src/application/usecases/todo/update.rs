@@ -1,14 +1,28 @@
+use chrono::{DateTime, Utc};
+
 use crate::application::errors::AppError;
+use crate::application::ports::event_publisher::EventPublisher;
 use crate::application::ports::todo_repository::TodoRepository;
+use crate::application::usecases::todo::edit_lock::Editor;
 use crate::domain::entities::todo::Todo;
+use crate::domain::events::TodoEvent;
 
+#[tracing::instrument(name = "usecase.todo.update", skip_all)]
 pub async fn execute(
     repo: &dyn TodoRepository,
+    events: &dyn EventPublisher,
+    editor: Editor<'_>,
     id: u32,
     title: Option<String>,
     completed: Option<bool>,
+    due_at: Option<Option<DateTime<Utc>>>,
 ) -> Result<Option<Todo>, AppError> {
-    repo.update(id, title, completed).await
+    editor.ensure_editable(id)?;
+    let updated = repo.update(id, title, completed, due_at).await?;
+    if let Some(todo) = &updated {
+        events.publish(TodoEvent::Updated(todo.clone())).await;
+    }
+    Ok(updated)
 }
 
 #[cfg(test)]
@@ -16,13 +30,22 @@ mod tests {
     use std::sync::Mutex;
 
     use async_trait::async_trait;
+    use chrono::{DateTime, TimeZone, Utc};
 
     use super::execute;
     use crate::application::errors::AppError;
+    use crate::application::ports::edit_locks::NoEditLocks;
     use crate::application::ports::todo_repository::TodoRepository;
+    use crate::application::usecases::todo::edit_lock::Editor;
+    use crate::application::usecases::todo::test_support::{FixedLocks, RecordingPublisher};
     use crate::domain::entities::todo::Todo;
 
-    type UpdateArgs = (u32, Option<String>, Option<bool>);
+    type UpdateArgs = (
+        u32,
+        Option<String>,
+        Option<bool>,
+        Option<Option<DateTime<Utc>>>,
+    );
 
     struct FakeRepo {
         last_args: Mutex<Option<UpdateArgs>>,
@@ -48,13 +71,14 @@ mod tests {
             id: u32,
             title: Option<String>,
             completed: Option<bool>,
+            due_at: Option<Option<DateTime<Utc>>>,
         ) -> Result<Option<Todo>, AppError> {
             *self.last_args.lock().expect("failed to lock last_args") =
-                Some((id, title, completed));
+                Some((id, title, completed, due_at));
             Ok(self.todo.clone())
         }
 
-        async fn delete(&self, _id: u32) -> Result<bool, AppError> {
+        async fn delete(&self, _id: u32) -> Result<Option<Vec<String>>, AppError> {
             unimplemented!("not needed for this test");
         }
 
@@ -72,12 +96,23 @@ mod tests {
                 title: "updated".to_string(),
                 completed: true,
                 position: 2,
+                due_at: None,
             }),
         };
 
-        let result = execute(&repo, 5, Some("updated".to_string()), Some(true))
-            .await
-            .unwrap();
+        let due_at = Utc.with_ymd_and_hms(2030, 1, 2, 9, 0, 0).unwrap();
+        let events = RecordingPublisher::default();
+        let result = execute(
+            &repo,
+            &events,
+            Editor::anonymous(&NoEditLocks),
+            5,
+            Some("updated".to_string()),
+            Some(true),
+            Some(Some(due_at)),
+        )
+        .await
+        .unwrap();
 
         let last_args = repo
             .last_args
@@ -86,8 +121,53 @@ mod tests {
             .clone();
         assert_eq!(
             last_args,
-            Some((5, Some("updated".to_string()), Some(true)))
+            Some((
+                5,
+                Some("updated".to_string()),
+                Some(true),
+                Some(Some(due_at))
+            ))
         );
         assert_eq!(result.unwrap().title, "updated");
+        assert_eq!(events.names(), vec!["todo.updated"]);
+    }
+
+    #[tokio::test]
+    async fn update_is_rejected_while_another_connection_holds_the_lock() {
+        let repo = FakeRepo {
+            last_args: Mutex::new(None),
+            todo: None,
+        };
+        let events = RecordingPublisher::default();
+        let locks = FixedLocks::held_by(5, 1, "alice");
+
+        let error = execute(
+            &repo,
+            &events,
+            Editor::anonymous(&locks),
+            5,
+            None,
+            Some(true),
+            None,
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(error, AppError::Conflict(_)), "{:?}", error);
+        assert!(repo.last_args.lock().unwrap().is_none());
+        assert!(events.names().is_empty());
+
+        // ロックを持つ接続からなら更新できる
+        execute(
+            &repo,
+            &events,
+            Editor::connection(&locks, 1),
+            5,
+            None,
+            Some(true),
+            None,
+        )
+        .await
+        .unwrap();
+        assert!(repo.last_args.lock().unwrap().is_some());
     }
 }
@@ -0,0 +1,103 @@
+use crate::application::errors::{AppError, ErrorMessage};
+use crate::application::ports::webhook_repository::{NewWebhook, WebhookRepository};
+use crate::domain::entities::webhook::Webhook;
+use crate::domain::events::TodoEvent;
+
+#[tracing::instrument(name = "usecase.webhook.create", skip_all)]
+pub async fn execute(
+    webhooks: &dyn WebhookRepository,
+    url: String,
+    secret: String,
+    events: Vec<String>,
+) -> Result<Webhook, AppError> {
+    let url = validate_url(url)?;
+    let events = normalize_events(events)?;
+    webhooks
+        .create(NewWebhook {
+            url,
+            secret,
+            events,
+        })
+        .await
+}
+
+/// 送信先は http / https のみ受け付ける
+pub(crate) fn validate_url(url: String) -> Result<String, AppError> {
+    let url = url.trim().to_string();
+    let lower = url.to_ascii_lowercase();
+    if !(lower.starts_with("http://") || lower.starts_with("https://")) {
+        return Err(AppError::validation(ErrorMessage::new(
+            "webhook_url_scheme",
+        )));
+    }
+    Ok(url)
+}
+
+/// 未知のイベント名を弾き、重複を除いて並びを揃える
+pub(crate) fn normalize_events(events: Vec<String>) -> Result<Vec<String>, AppError> {
+    let mut normalized = Vec::with_capacity(events.len());
+    for event in events {
+        let event = event.trim().to_string();
+        if !TodoEvent::NAMES.contains(&event.as_str()) {
+            return Err(AppError::validation(
+                ErrorMessage::new("webhook_unknown_event")
+                    .with("event", event)
+                    .with("expected", TodoEvent::NAMES.join(", ")),
+            ));
+        }
+        normalized.push(event);
+    }
+    normalized.sort();
+    normalized.dedup();
+    Ok(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{execute, normalize_events};
+    use crate::application::errors::AppError;
+    use crate::application::usecases::webhook::test_support::FakeWebhookRepo;
+
+    #[tokio::test]
+    async fn create_stores_normalized_subscription() {
+        let webhooks = FakeWebhookRepo::default();
+
+        let webhook = execute(
+            &webhooks,
+            " https://example.com/hook ".to_string(),
+            "s3cret-s3cret".to_string(),
+            vec!["todo.updated".to_string(), "todo.created".to_string()],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(webhook.url, "https://example.com/hook");
+        assert_eq!(webhook.events, vec!["todo.created", "todo.updated"]);
+        assert_eq!(webhooks.rows().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn create_rejects_non_http_url() {
+        let webhooks = FakeWebhookRepo::default();
+
+        let result = execute(
+            &webhooks,
+            "ftp://example.com".to_string(),
+            "s3cret-s3cret".to_string(),
+            vec![],
+        )
+        .await;
+
+        assert!(matches!(result, Err(AppError::Validation(_))));
+        assert!(webhooks.rows().is_empty());
+    }
+
+    #[test]
+    fn normalize_rejects_unknown_events_and_dedups() {
+        assert!(normalize_events(vec!["todo.exploded".to_string()]).is_err());
+        assert_eq!(
+            normalize_events(vec!["todo.deleted".to_string(), "todo.deleted".to_string()]).unwrap(),
+            vec!["todo.deleted"]
+        );
+    }
+}
@@ -0,0 +1,7 @@
+use crate::application::errors::AppError;
+use crate::application::ports::webhook_repository::WebhookRepository;
+
+#[tracing::instrument(name = "usecase.webhook.delete", skip_all)]
+pub async fn execute(webhooks: &dyn WebhookRepository, id: u32) -> Result<bool, AppError> {
+    webhooks.delete(id).await
+}
@@ -0,0 +1,158 @@
+use crate::application::errors::AppError;
+use crate::application::ports::clock::Clock;
+use crate::application::ports::webhook_outbox::WebhookOutbox;
+use crate::application::ports::webhook_sender::{OutgoingWebhook, WebhookSender};
+use crate::application::usecases::retry::retry_delay;
+
+/// これ以上失敗したら再送を諦める
+pub const MAX_ATTEMPTS: u32 = 8;
+
+// 10秒から倍々に伸ばし、最大1時間で頭打ちにする
+const BASE_RETRY_DELAY_SECS: i64 = 10;
+const MAX_RETRY_DELAY_SECS: i64 = 60 * 60;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DispatchSummary {
+    pub delivered: usize,
+    pub retrying: usize,
+    pub failed: usize,
+}
+
+impl DispatchSummary {
+    pub fn total(&self) -> usize {
+        self.delivered + self.retrying + self.failed
+    }
+}
+
+/// 送信時刻が来た配信を1回分まとめて送る。
+/// 送信に成功してから配信済みにするので、途中で落ちた場合は再送される（at-least-once）
+#[tracing::instrument(name = "usecase.webhook.dispatch_pending", skip_all)]
+pub async fn execute(
+    outbox: &dyn WebhookOutbox,
+    sender: &dyn WebhookSender,
+    clock: &dyn Clock,
+    batch_size: u32,
+) -> Result<DispatchSummary, AppError> {
+    let mut summary = DispatchSummary::default();
+
+    for pending in outbox.due(clock.now(), batch_size).await? {
+        let delivery = &pending.delivery;
+        let outgoing = OutgoingWebhook {
+            delivery_id: delivery.id,
+            event: delivery.event.clone(),
+            url: pending.url.clone(),
+            secret: pending.secret.clone(),
+            payload: delivery.payload.clone(),
+        };
+
+        match sender.send(&outgoing).await {
+            Ok(status) => {
+                outbox
+                    .mark_delivered(delivery.id, status, clock.now())
+                    .await?;
+                summary.delivered += 1;
+            }
+            Err(e) => {
+                let attempts = delivery.attempts + 1;
+                let retry_at = if attempts < MAX_ATTEMPTS {
+                    summary.retrying += 1;
+                    Some(
+                        clock.now()
+                            + retry_delay(
+                                BASE_RETRY_DELAY_SECS,
+                                MAX_RETRY_DELAY_SECS,
+                                delivery.attempts,
+                            ),
+                    )
+                } else {
+                    summary.failed += 1;
+                    None
+                };
+                outbox
+                    .mark_failed(delivery.id, e.status, e.message, retry_at)
+                    .await?;
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone, Utc};
+
+    use super::{execute, MAX_ATTEMPTS};
+    use crate::application::ports::webhook_outbox::WebhookOutbox;
+    use crate::application::ports::webhook_repository::{NewWebhook, WebhookRepository};
+    use crate::application::usecases::webhook::test_support::{
+        FakeOutbox, FakeWebhookRepo, RecordingSender,
+    };
+    use crate::domain::entities::webhook::DeliveryStatus;
+    use crate::infrastructure::clock::ManualClock;
+
+    async fn outbox_with_one_delivery(now: chrono::DateTime<Utc>) -> FakeOutbox {
+        let webhooks = FakeWebhookRepo::default();
+        let webhook = webhooks
+            .create(NewWebhook {
+                url: "http://example.com/hook".to_string(),
+                secret: "top-secret".to_string(),
+                events: vec![],
+            })
+            .await
+            .unwrap();
+        let outbox = FakeOutbox::with_webhooks(vec![webhook]);
+        outbox.enqueue("todo.created", "{}", now).await.unwrap();
+        outbox
+    }
+
+    #[tokio::test]
+    async fn dispatch_sends_and_marks_delivered() {
+        let now = Utc.with_ymd_and_hms(2030, 1, 1, 9, 0, 0).unwrap();
+        let clock = ManualClock::new(now);
+        let outbox = outbox_with_one_delivery(now).await;
+        let sender = RecordingSender::default();
+
+        let summary = execute(&outbox, &sender, &clock, 10).await.unwrap();
+
+        assert_eq!(summary.delivered, 1);
+        let sent = sender.sent.lock().unwrap().clone();
+        assert_eq!(sent[0].secret, "top-secret");
+        assert_eq!(sent[0].event, "todo.created");
+        let row = outbox.rows()[0].clone();
+        assert_eq!(row.status, DeliveryStatus::Delivered);
+        assert_eq!(row.response_status, Some(200));
+
+        // 配信済みのものは再送しない
+        let summary = execute(&outbox, &sender, &clock, 10).await.unwrap();
+        assert_eq!(summary.total(), 0);
+    }
+
+    #[tokio::test]
+    async fn dispatch_backs_off_then_gives_up() {
+        let now = Utc.with_ymd_and_hms(2030, 1, 1, 9, 0, 0).unwrap();
+        let clock = ManualClock::new(now);
+        let outbox = outbox_with_one_delivery(now).await;
+        let sender = RecordingSender::failing(503);
+
+        let summary = execute(&outbox, &sender, &clock, 10).await.unwrap();
+        assert_eq!(summary.retrying, 1);
+        let row = outbox.rows()[0].clone();
+        assert_eq!(row.status, DeliveryStatus::Pending);
+        assert_eq!(row.response_status, Some(503));
+        assert_eq!(row.next_attempt_at, Some(now + Duration::seconds(10)));
+
+        // 再送時刻までは送らない
+        let summary = execute(&outbox, &sender, &clock, 10).await.unwrap();
+        assert_eq!(summary.total(), 0);
+
+        for _ in 1..MAX_ATTEMPTS {
+            clock.advance(Duration::hours(1));
+            execute(&outbox, &sender, &clock, 10).await.unwrap();
+        }
+        let row = outbox.rows()[0].clone();
+        assert_eq!(row.status, DeliveryStatus::Failed);
+        assert_eq!(row.attempts, MAX_ATTEMPTS);
+        assert_eq!(sender.sent.lock().unwrap().len(), MAX_ATTEMPTS as usize);
+    }
+}
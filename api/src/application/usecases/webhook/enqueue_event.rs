@@ -0,0 +1,96 @@
+use chrono::{DateTime, Utc};
+use serde_json::json;
+
+use crate::application::errors::AppError;
+use crate::application::ports::clock::Clock;
+use crate::application::ports::webhook_outbox::WebhookOutbox;
+use crate::domain::entities::todo::Todo;
+use crate::domain::events::TodoEvent;
+
+/// イベントをWebhookの送信ペイロードにして、購読しているWebhook分だけアウトボックスへ積む
+#[tracing::instrument(name = "usecase.webhook.enqueue_event", skip_all)]
+pub async fn execute(
+    outbox: &dyn WebhookOutbox,
+    clock: &dyn Clock,
+    event: &TodoEvent,
+) -> Result<usize, AppError> {
+    let now = clock.now();
+    outbox
+        .enqueue(event.name(), &payload(event, now), now)
+        .await
+}
+
+/// Webhookで送るJSON。変更と同じトランザクションで積むリポジトリもこれを使う
+pub fn payload(event: &TodoEvent, now: DateTime<Utc>) -> String {
+    let data = match event {
+        TodoEvent::Created(todo) | TodoEvent::Updated(todo) => todo_json(todo),
+        TodoEvent::Deleted { id } => json!({ "id": id }),
+        TodoEvent::Reordered { ids } => json!({ "ids": ids }),
+    };
+    json!({
+        "event": event.name(),
+        "occurred_at": now,
+        "data": data,
+    })
+    .to_string()
+}
+
+fn todo_json(todo: &Todo) -> serde_json::Value {
+    json!({
+        "id": todo.id,
+        "title": todo.title,
+        "completed": todo.completed,
+        "position": todo.position,
+        "due_at": todo.due_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::execute;
+    use crate::application::usecases::webhook::test_support::FakeOutbox;
+    use crate::domain::entities::todo::Todo;
+    use crate::domain::events::TodoEvent;
+    use crate::infrastructure::clock::ManualClock;
+
+    #[tokio::test]
+    async fn enqueue_wraps_todo_in_event_envelope() {
+        let outbox = FakeOutbox::default();
+        let clock = ManualClock::new(Utc.with_ymd_and_hms(2030, 1, 1, 9, 0, 0).unwrap());
+        let todo = Todo {
+            id: 7,
+            title: "ship it".to_string(),
+            completed: false,
+            position: 1,
+            due_at: None,
+        };
+
+        execute(&outbox, &clock, &TodoEvent::Created(todo))
+            .await
+            .unwrap();
+
+        let enqueued = outbox.enqueued.lock().unwrap().clone();
+        assert_eq!(enqueued.len(), 1);
+        assert_eq!(enqueued[0].0, "todo.created");
+        let payload: serde_json::Value = serde_json::from_str(&enqueued[0].1).unwrap();
+        assert_eq!(payload["event"], "todo.created");
+        assert_eq!(payload["occurred_at"], "2030-01-01T09:00:00Z");
+        assert_eq!(payload["data"]["title"], "ship it");
+    }
+
+    #[tokio::test]
+    async fn enqueue_reorder_lists_ids() {
+        let outbox = FakeOutbox::default();
+        let clock = ManualClock::new(Utc::now());
+
+        execute(&outbox, &clock, &TodoEvent::Reordered { ids: vec![3, 1] })
+            .await
+            .unwrap();
+
+        let enqueued = outbox.enqueued.lock().unwrap().clone();
+        let payload: serde_json::Value = serde_json::from_str(&enqueued[0].1).unwrap();
+        assert_eq!(payload["data"]["ids"], serde_json::json!([3, 1]));
+    }
+}
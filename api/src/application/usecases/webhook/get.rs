@@ -0,0 +1,8 @@
+use crate::application::errors::AppError;
+use crate::application::ports::webhook_repository::WebhookRepository;
+use crate::domain::entities::webhook::Webhook;
+
+#[tracing::instrument(name = "usecase.webhook.get", skip_all)]
+pub async fn execute(webhooks: &dyn WebhookRepository, id: u32) -> Result<Webhook, AppError> {
+    webhooks.get_by_id(id).await?.ok_or(AppError::NotFound)
+}
@@ -0,0 +1,8 @@
+use crate::application::errors::AppError;
+use crate::application::ports::webhook_repository::WebhookRepository;
+use crate::domain::entities::webhook::Webhook;
+
+#[tracing::instrument(name = "usecase.webhook.list", skip_all)]
+pub async fn execute(webhooks: &dyn WebhookRepository) -> Result<Vec<Webhook>, AppError> {
+    webhooks.list().await
+}
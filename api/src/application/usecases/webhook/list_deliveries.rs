@@ -0,0 +1,80 @@
+use crate::application::errors::{AppError, ErrorMessage};
+use crate::application::ports::webhook_outbox::WebhookOutbox;
+use crate::application::ports::webhook_repository::WebhookRepository;
+use crate::domain::entities::webhook::WebhookDelivery;
+
+pub const DEFAULT_PER_PAGE: u32 = 20;
+pub const MAX_PER_PAGE: u32 = 100;
+
+#[derive(Debug, Clone)]
+pub struct DeliveryPage {
+    pub deliveries: Vec<WebhookDelivery>,
+    pub page: u32,
+    pub per_page: u32,
+    pub total: u64,
+}
+
+#[tracing::instrument(name = "usecase.webhook.list_deliveries", skip_all)]
+pub async fn execute(
+    webhooks: &dyn WebhookRepository,
+    outbox: &dyn WebhookOutbox,
+    webhook_id: u32,
+    page: u32,
+    per_page: u32,
+) -> Result<DeliveryPage, AppError> {
+    if page == 0 {
+        return Err(AppError::validation(ErrorMessage::new("page_too_small")));
+    }
+    if per_page == 0 || per_page > MAX_PER_PAGE {
+        return Err(AppError::validation(
+            ErrorMessage::new("per_page_out_of_range").with("max", MAX_PER_PAGE),
+        ));
+    }
+    if webhooks.get_by_id(webhook_id).await?.is_none() {
+        return Err(AppError::NotFound);
+    }
+
+    let offset = (page - 1).saturating_mul(per_page);
+    let (deliveries, total) = outbox.list_by_webhook(webhook_id, per_page, offset).await?;
+    Ok(DeliveryPage {
+        deliveries,
+        page,
+        per_page,
+        total,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::execute;
+    use crate::application::errors::AppError;
+    use crate::application::usecases::webhook::test_support::{FakeOutbox, FakeWebhookRepo};
+
+    #[tokio::test]
+    async fn deliveries_of_missing_webhook_is_not_found() {
+        let result = execute(
+            &FakeWebhookRepo::default(),
+            &FakeOutbox::default(),
+            1,
+            1,
+            20,
+        )
+        .await;
+
+        assert!(matches!(result, Err(AppError::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn deliveries_reject_out_of_range_per_page() {
+        let result = execute(
+            &FakeWebhookRepo::default(),
+            &FakeOutbox::default(),
+            1,
+            1,
+            101,
+        )
+        .await;
+
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+}
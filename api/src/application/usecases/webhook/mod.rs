@@ -0,0 +1,11 @@
+pub mod create;
+pub mod delete;
+pub mod dispatch_pending;
+pub mod enqueue_event;
+pub mod get;
+pub mod list;
+pub mod list_deliveries;
+pub mod update;
+
+#[cfg(test)]
+pub(crate) mod test_support;
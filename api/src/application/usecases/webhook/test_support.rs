@@ -0,0 +1,250 @@
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::application::errors::AppError;
+use crate::application::ports::webhook_outbox::{PendingDelivery, WebhookOutbox};
+use crate::application::ports::webhook_repository::{NewWebhook, WebhookPatch, WebhookRepository};
+use crate::application::ports::webhook_sender::{OutgoingWebhook, SendError, WebhookSender};
+use crate::domain::entities::webhook::{DeliveryStatus, Webhook, WebhookDelivery};
+
+// Webhookのユースケース用のインメモリ実装
+
+#[derive(Default)]
+pub struct FakeWebhookRepo {
+    pub rows: Mutex<Vec<Webhook>>,
+}
+
+impl FakeWebhookRepo {
+    pub fn rows(&self) -> std::sync::MutexGuard<'_, Vec<Webhook>> {
+        self.rows.lock().expect("failed to lock rows")
+    }
+}
+
+#[async_trait]
+impl WebhookRepository for FakeWebhookRepo {
+    async fn create(&self, webhook: NewWebhook) -> Result<Webhook, AppError> {
+        let mut rows = self.rows();
+        let now = Utc::now();
+        let webhook = Webhook {
+            id: rows.len() as i64 + 1,
+            url: webhook.url,
+            secret: webhook.secret,
+            events: webhook.events,
+            created_at: now,
+            updated_at: now,
+        };
+        rows.push(webhook.clone());
+        Ok(webhook)
+    }
+
+    async fn list(&self) -> Result<Vec<Webhook>, AppError> {
+        Ok(self.rows().clone())
+    }
+
+    async fn get_by_id(&self, id: u32) -> Result<Option<Webhook>, AppError> {
+        Ok(self.rows().iter().find(|w| w.id == id as i64).cloned())
+    }
+
+    async fn update(&self, id: u32, patch: WebhookPatch) -> Result<Option<Webhook>, AppError> {
+        let mut rows = self.rows();
+        let Some(webhook) = rows.iter_mut().find(|w| w.id == id as i64) else {
+            return Ok(None);
+        };
+        if let Some(url) = patch.url {
+            webhook.url = url;
+        }
+        if let Some(secret) = patch.secret {
+            webhook.secret = secret;
+        }
+        if let Some(events) = patch.events {
+            webhook.events = events;
+        }
+        Ok(Some(webhook.clone()))
+    }
+
+    async fn delete(&self, id: u32) -> Result<bool, AppError> {
+        let mut rows = self.rows();
+        let before = rows.len();
+        rows.retain(|w| w.id != id as i64);
+        Ok(rows.len() < before)
+    }
+}
+
+#[derive(Default)]
+pub struct FakeOutbox {
+    pub webhooks: Vec<Webhook>,
+    pub rows: Mutex<Vec<WebhookDelivery>>,
+    /// enqueue に渡された (イベント名, ペイロード)
+    pub enqueued: Mutex<Vec<(String, String)>>,
+}
+
+impl FakeOutbox {
+    pub fn with_webhooks(webhooks: Vec<Webhook>) -> Self {
+        Self {
+            webhooks,
+            ..Default::default()
+        }
+    }
+
+    pub fn rows(&self) -> std::sync::MutexGuard<'_, Vec<WebhookDelivery>> {
+        self.rows.lock().expect("failed to lock rows")
+    }
+
+    fn update_row(&self, id: i64, f: impl FnOnce(&mut WebhookDelivery)) {
+        if let Some(row) = self.rows().iter_mut().find(|d| d.id == id) {
+            f(row);
+        }
+    }
+}
+
+#[async_trait]
+impl WebhookOutbox for FakeOutbox {
+    async fn enqueue(
+        &self,
+        event: &str,
+        payload: &str,
+        now: DateTime<Utc>,
+    ) -> Result<usize, AppError> {
+        self.enqueued
+            .lock()
+            .expect("failed to lock enqueued")
+            .push((event.to_string(), payload.to_string()));
+        let mut rows = self.rows();
+        let mut count = 0;
+        for webhook in self.webhooks.iter().filter(|w| w.subscribes_to(event)) {
+            let id = rows.len() as i64 + 1;
+            rows.push(WebhookDelivery {
+                id,
+                webhook_id: webhook.id,
+                event: event.to_string(),
+                payload: payload.to_string(),
+                status: DeliveryStatus::Pending,
+                attempts: 0,
+                response_status: None,
+                last_error: None,
+                created_at: now,
+                next_attempt_at: Some(now),
+                delivered_at: None,
+            });
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    async fn due(&self, now: DateTime<Utc>, limit: u32) -> Result<Vec<PendingDelivery>, AppError> {
+        Ok(self
+            .rows()
+            .iter()
+            .filter(|d| {
+                d.status == DeliveryStatus::Pending && d.next_attempt_at.is_some_and(|at| at <= now)
+            })
+            .take(limit as usize)
+            .map(|d| {
+                let webhook = self
+                    .webhooks
+                    .iter()
+                    .find(|w| w.id == d.webhook_id)
+                    .expect("delivery without webhook");
+                PendingDelivery {
+                    delivery: d.clone(),
+                    url: webhook.url.clone(),
+                    secret: webhook.secret.clone(),
+                }
+            })
+            .collect())
+    }
+
+    async fn mark_delivered(
+        &self,
+        id: i64,
+        response_status: u16,
+        delivered_at: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        self.update_row(id, |d| {
+            d.status = DeliveryStatus::Delivered;
+            d.attempts += 1;
+            d.response_status = Some(response_status);
+            d.last_error = None;
+            d.next_attempt_at = None;
+            d.delivered_at = Some(delivered_at);
+        });
+        Ok(())
+    }
+
+    async fn mark_failed(
+        &self,
+        id: i64,
+        response_status: Option<u16>,
+        error: String,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> Result<(), AppError> {
+        self.update_row(id, |d| {
+            d.attempts += 1;
+            d.response_status = response_status;
+            d.last_error = Some(error);
+            d.next_attempt_at = retry_at;
+            if retry_at.is_none() {
+                d.status = DeliveryStatus::Failed;
+            }
+        });
+        Ok(())
+    }
+
+    async fn list_by_webhook(
+        &self,
+        webhook_id: u32,
+        limit: u32,
+        offset: u32,
+    ) -> Result<(Vec<WebhookDelivery>, u64), AppError> {
+        let rows: Vec<WebhookDelivery> = self
+            .rows()
+            .iter()
+            .rev()
+            .filter(|d| d.webhook_id == webhook_id as i64)
+            .cloned()
+            .collect();
+        let total = rows.len() as u64;
+        Ok((
+            rows.into_iter()
+                .skip(offset as usize)
+                .take(limit as usize)
+                .collect(),
+            total,
+        ))
+    }
+}
+
+#[derive(Default)]
+pub struct RecordingSender {
+    pub sent: Mutex<Vec<OutgoingWebhook>>,
+    /// 設定されていれば、このステータスで失敗したことにする
+    pub fail_with: Option<u16>,
+}
+
+impl RecordingSender {
+    pub fn failing(status: u16) -> Self {
+        Self {
+            fail_with: Some(status),
+            ..Default::default()
+        }
+    }
+}
+
+#[async_trait]
+impl WebhookSender for RecordingSender {
+    async fn send(&self, webhook: &OutgoingWebhook) -> Result<u16, SendError> {
+        self.sent
+            .lock()
+            .expect("failed to lock sent")
+            .push(webhook.clone());
+        match self.fail_with {
+            Some(status) => Err(SendError {
+                status: Some(status),
+                message: format!("responded with {}", status),
+            }),
+            None => Ok(200),
+        }
+    }
+}
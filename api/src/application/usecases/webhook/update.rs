@@ -0,0 +1,63 @@
+use crate::application::errors::AppError;
+use crate::application::ports::webhook_repository::{WebhookPatch, WebhookRepository};
+use crate::application::usecases::webhook::create::{normalize_events, validate_url};
+use crate::domain::entities::webhook::Webhook;
+
+#[tracing::instrument(name = "usecase.webhook.update", skip_all)]
+pub async fn execute(
+    webhooks: &dyn WebhookRepository,
+    id: u32,
+    patch: WebhookPatch,
+) -> Result<Webhook, AppError> {
+    let patch = WebhookPatch {
+        url: patch.url.map(validate_url).transpose()?,
+        secret: patch.secret,
+        events: patch.events.map(normalize_events).transpose()?,
+    };
+    webhooks.update(id, patch).await?.ok_or(AppError::NotFound)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::execute;
+    use crate::application::errors::AppError;
+    use crate::application::ports::webhook_repository::{
+        NewWebhook, WebhookPatch, WebhookRepository,
+    };
+    use crate::application::usecases::webhook::test_support::FakeWebhookRepo;
+
+    #[tokio::test]
+    async fn update_changes_only_given_fields() {
+        let webhooks = FakeWebhookRepo::default();
+        webhooks
+            .create(NewWebhook {
+                url: "http://example.com/a".to_string(),
+                secret: "first-secret".to_string(),
+                events: vec![],
+            })
+            .await
+            .unwrap();
+
+        let updated = execute(
+            &webhooks,
+            1,
+            WebhookPatch {
+                events: Some(vec!["todo.deleted".to_string()]),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(updated.url, "http://example.com/a");
+        assert_eq!(updated.secret, "first-secret");
+        assert_eq!(updated.events, vec!["todo.deleted"]);
+    }
+
+    #[tokio::test]
+    async fn update_missing_webhook_is_not_found() {
+        let result = execute(&FakeWebhookRepo::default(), 1, WebhookPatch::default()).await;
+
+        assert!(matches!(result, Err(AppError::NotFound)));
+    }
+}
@@ -0,0 +1,1306 @@
+//! サーバの設定。既定値 → TOMLファイル → 環境変数 → コマンドライン引数 の順に重ね、
+//! 後から読んだものを優先する。起動時に [`Config::validate`] でまとめて検証する
+use std::fmt;
+use std::net::{IpAddr, SocketAddr};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use clap::{Parser, ValueEnum};
+use serde::Deserialize;
+
+use crate::application::usecases::attachment::upload::AttachmentLimits;
+use crate::presentation::i18n::Lang;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub server: ServerConfig,
+    pub database: DatabaseConfig,
+    pub cors: CorsConfig,
+    pub log: LogConfig,
+    pub otel: OtelConfig,
+    pub features: FeatureConfig,
+    pub reminders: ReminderConfig,
+    pub webhooks: WebhookConfig,
+    pub attachments: AttachmentsConfig,
+    pub limits: LimitsConfig,
+    pub idempotency: IdempotencyConfig,
+    pub tls: TlsConfig,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    pub host: String,
+    pub port: u16,
+    /// 設定したときだけgRPCサーバをこのアドレスで起動する（例: `0.0.0.0:50051`）
+    pub grpc_addr: Option<String>,
+    /// `Accept-Language` で言語が決まらないときの応答言語
+    pub default_language: Lang,
+    /// 終了シグナルを受けてから、処理中のリクエストと接続の完了を待つ秒数
+    pub shutdown_timeout_secs: u64,
+    /// 古いクライアント向けに、TODOの作成を `200`（`Location` なし）、並び替えを
+    /// ボディなしの `200` で返す
+    pub legacy_status_codes: bool,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            host: "0.0.0.0".to_string(),
+            port: 3000,
+            grpc_addr: None,
+            default_language: Lang::default(),
+            shutdown_timeout_secs: 30,
+            legacy_status_codes: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DatabaseConfig {
+    pub url: String,
+    pub max_connections: u32,
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self {
+            url: "sqlite:todos.db".to_string(),
+            max_connections: 5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CorsConfig {
+    /// 許可するオリジン（例: `https://todo.example.com`）。`*` はすべて許可、空（既定）なら同一オリジンのみ
+    pub allowed_origins: Vec<String>,
+}
+
+impl CorsConfig {
+    pub fn allows_any_origin(&self) -> bool {
+        self.allowed_origins.iter().any(|origin| origin == "*")
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LogConfig {
+    pub format: LogFormat,
+    /// `tracing_subscriber::EnvFilter` の書式（例: `info,rust_todo_app=debug`）
+    pub level: String,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self {
+            format: LogFormat::Text,
+            level: "rust_todo_app=debug,tower_http=debug".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// 人が読む1行形式
+    Text,
+    /// 1行1つのJSON（ログ基盤に取り込む用）
+    Json,
+}
+
+/// OTLPでのトレースの送信。`otel` フィーチャ付きでビルドしたときだけ有効にできる
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OtelConfig {
+    pub enabled: bool,
+    /// OTLP/gRPCの送信先（例: `http://otel-collector:4317`）
+    pub endpoint: String,
+    /// リソース属性 `service.name`
+    pub service_name: String,
+}
+
+impl Default for OtelConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoint: "http://localhost:4317".to_string(),
+            service_name: "rust_todo_app".to_string(),
+        }
+    }
+}
+
+/// 機能ごとの有効・無効。既定はすべて有効
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FeatureConfig {
+    /// `/graphql` と `/graphql/ws`
+    pub graphql: bool,
+    /// `/openapi.json` と `/docs`
+    pub api_docs: bool,
+    /// プレフィックスなしの廃止予定のパス（`/todos` など）
+    pub legacy_paths: bool,
+    /// リマインダーの配信タスク
+    pub reminder_delivery: bool,
+    /// Webhookの送信タスク
+    pub webhook_delivery: bool,
+    /// `/metrics`（Prometheus）
+    pub metrics: bool,
+}
+
+impl Default for FeatureConfig {
+    fn default() -> Self {
+        Self {
+            graphql: true,
+            api_docs: true,
+            legacy_paths: true,
+            reminder_delivery: true,
+            webhook_delivery: true,
+            metrics: true,
+        }
+    }
+}
+
+/// コマンドラインで切り替える機能の名前
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Feature {
+    Graphql,
+    ApiDocs,
+    LegacyPaths,
+    ReminderDelivery,
+    WebhookDelivery,
+    Metrics,
+}
+
+impl FeatureConfig {
+    fn set(&mut self, feature: Feature, enabled: bool) {
+        let flag = match feature {
+            Feature::Graphql => &mut self.graphql,
+            Feature::ApiDocs => &mut self.api_docs,
+            Feature::LegacyPaths => &mut self.legacy_paths,
+            Feature::ReminderDelivery => &mut self.reminder_delivery,
+            Feature::WebhookDelivery => &mut self.webhook_delivery,
+            Feature::Metrics => &mut self.metrics,
+        };
+        *flag = enabled;
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ReminderConfig {
+    pub poll_interval_secs: u64,
+    /// 設定するとWebhookへPOSTし、未設定ならログに出す
+    pub webhook_url: Option<String>,
+}
+
+impl Default for ReminderConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval_secs: 30,
+            webhook_url: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WebhookConfig {
+    pub poll_interval_secs: u64,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval_secs: 5,
+        }
+    }
+}
+
+/// 添付ファイルの保存先と制限
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AttachmentsConfig {
+    pub dir: PathBuf,
+    pub max_bytes: usize,
+    /// `image/*` のような指定も可
+    pub allowed_types: Vec<String>,
+}
+
+impl Default for AttachmentsConfig {
+    fn default() -> Self {
+        let limits = AttachmentLimits::default();
+        Self {
+            dir: PathBuf::from("attachments"),
+            max_bytes: limits.max_size_bytes,
+            allowed_types: limits.allowed_content_types,
+        }
+    }
+}
+
+impl AttachmentsConfig {
+    pub fn limits(&self) -> AttachmentLimits {
+        AttachmentLimits {
+            max_size_bytes: self.max_bytes,
+            allowed_content_types: self.allowed_types.clone(),
+        }
+    }
+}
+
+/// リクエストの制限。既定値をルートのグループごとに上書きできる（例: `[limits.webhooks]`）
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LimitsConfig {
+    /// クライアント（`Authorization` の値、なければ接続元のIPアドレス）ごとのレート制限を掛けるか
+    pub rate_limit_enabled: bool,
+    /// `X-Forwarded-For` / `Forwarded` を信じるプロキシ（フロントのサーバやリバースプロキシ）のIPアドレス。
+    /// ここから来たリクエストだけ、ヘッダに書かれた元のクライアントのIPアドレスで数える
+    pub trusted_proxies: Vec<IpAddr>,
+    /// 1分あたりに補充するリクエスト数
+    pub requests_per_minute: u32,
+    /// 続けて受け付けられるリクエスト数（トークンバケットの容量）
+    pub burst: u32,
+    /// リクエストボディの上限。添付のアップロードは `attachments.max_bytes` に従う
+    pub max_body_bytes: usize,
+    /// 並び替えなどで一度に送れるIDの数の上限
+    pub max_list_len: usize,
+    pub todos: RouteLimitOverrides,
+    pub comments: RouteLimitOverrides,
+    pub reminders: RouteLimitOverrides,
+    pub attachments: RouteLimitOverrides,
+    pub webhooks: RouteLimitOverrides,
+    /// `/events`（SSE）と `/ws`
+    pub realtime: RouteLimitOverrides,
+    pub graphql: RouteLimitOverrides,
+}
+
+impl Default for LimitsConfig {
+    fn default() -> Self {
+        Self {
+            rate_limit_enabled: true,
+            trusted_proxies: Vec::new(),
+            requests_per_minute: 600,
+            burst: 100,
+            max_body_bytes: 1024 * 1024,
+            max_list_len: 1000,
+            todos: RouteLimitOverrides::default(),
+            comments: RouteLimitOverrides::default(),
+            reminders: RouteLimitOverrides::default(),
+            attachments: RouteLimitOverrides::default(),
+            webhooks: RouteLimitOverrides::default(),
+            realtime: RouteLimitOverrides::default(),
+            graphql: RouteLimitOverrides::default(),
+        }
+    }
+}
+
+/// グループごとの上書き。書かなかった項目は `[limits]` の値になる
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RouteLimitOverrides {
+    pub requests_per_minute: Option<u32>,
+    pub burst: Option<u32>,
+    pub max_body_bytes: Option<usize>,
+    pub max_list_len: Option<usize>,
+}
+
+/// 制限を分けて掛けるルートのまとまり
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RouteGroup {
+    Todos,
+    Comments,
+    Reminders,
+    Attachments,
+    Webhooks,
+    Realtime,
+    Graphql,
+}
+
+impl RouteGroup {
+    pub const ALL: [RouteGroup; 7] = [
+        RouteGroup::Todos,
+        RouteGroup::Comments,
+        RouteGroup::Reminders,
+        RouteGroup::Attachments,
+        RouteGroup::Webhooks,
+        RouteGroup::Realtime,
+        RouteGroup::Graphql,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RouteGroup::Todos => "todos",
+            RouteGroup::Comments => "comments",
+            RouteGroup::Reminders => "reminders",
+            RouteGroup::Attachments => "attachments",
+            RouteGroup::Webhooks => "webhooks",
+            RouteGroup::Realtime => "realtime",
+            RouteGroup::Graphql => "graphql",
+        }
+    }
+}
+
+/// 上書きを反映した、あるグループの制限
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RouteLimits {
+    pub requests_per_minute: u32,
+    pub burst: u32,
+    pub max_body_bytes: usize,
+    pub max_list_len: usize,
+}
+
+impl LimitsConfig {
+    pub fn route(&self, group: RouteGroup) -> RouteLimits {
+        let overrides = self.overrides(group);
+        RouteLimits {
+            requests_per_minute: overrides
+                .requests_per_minute
+                .unwrap_or(self.requests_per_minute),
+            burst: overrides.burst.unwrap_or(self.burst),
+            max_body_bytes: overrides.max_body_bytes.unwrap_or(self.max_body_bytes),
+            max_list_len: overrides.max_list_len.unwrap_or(self.max_list_len),
+        }
+    }
+
+    fn overrides(&self, group: RouteGroup) -> &RouteLimitOverrides {
+        match group {
+            RouteGroup::Todos => &self.todos,
+            RouteGroup::Comments => &self.comments,
+            RouteGroup::Reminders => &self.reminders,
+            RouteGroup::Attachments => &self.attachments,
+            RouteGroup::Webhooks => &self.webhooks,
+            RouteGroup::Realtime => &self.realtime,
+            RouteGroup::Graphql => &self.graphql,
+        }
+    }
+}
+
+/// `Idempotency-Key` 付きのPOSTの扱い
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct IdempotencyConfig {
+    /// 応答を保存しておく秒数
+    pub ttl_secs: u64,
+    /// キー付きのPOSTの処理にかけてよい秒数。過ぎたら打ち切って `500` を返し、キーを解放する
+    pub request_timeout_secs: u64,
+    /// 予約したまま応答が保存されないキーを、処理が止まったものとみなすまでの秒数。
+    /// 処理中のリクエストと二重に処理しないよう、`request_timeout_secs` より長くする
+    pub in_progress_timeout_secs: u64,
+}
+
+impl Default for IdempotencyConfig {
+    fn default() -> Self {
+        Self {
+            ttl_secs: 24 * 60 * 60,
+            request_timeout_secs: 60,
+            in_progress_timeout_secs: 120,
+        }
+    }
+}
+
+/// リバースプロキシを置かずにHTTPSで待ち受けるときの設定。
+/// 有効にすると `server.port` はHTTPSになる
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TlsConfig {
+    pub enabled: bool,
+    /// PEM形式の証明書チェーン
+    pub cert_path: PathBuf,
+    /// PEM形式の秘密鍵（PKCS#8 / PKCS#1 / SEC1）
+    pub key_path: PathBuf,
+    /// 証明書と鍵のファイルが更新されていないか確かめる間隔。更新されていれば読み直す
+    pub reload_interval_secs: u64,
+    /// TLSのハンドシェイクを待つ秒数。終わらない接続はこれを過ぎたら切る
+    pub handshake_timeout_secs: u64,
+    /// 設定したときだけ、このポートのHTTPへのリクエストをHTTPSへリダイレクトする
+    pub redirect_http_port: Option<u16>,
+    /// `Strict-Transport-Security` の `max-age`。0なら付けない
+    pub hsts_max_age_secs: u64,
+    pub hsts_include_subdomains: bool,
+}
+
+impl Default for TlsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            cert_path: PathBuf::from("cert.pem"),
+            key_path: PathBuf::from("key.pem"),
+            reload_interval_secs: 60,
+            handshake_timeout_secs: 10,
+            redirect_http_port: None,
+            hsts_max_age_secs: 365 * 24 * 60 * 60,
+            hsts_include_subdomains: false,
+        }
+    }
+}
+
+/// サーバのコマンドライン引数。指定したものが設定ファイルと環境変数より優先される
+#[derive(Debug, Default, Parser)]
+#[command(name = "rust_todo_app", about = "Todo API server")]
+pub struct CliArgs {
+    /// 設定ファイル（TOML）
+    #[arg(long, env = "CONFIG_FILE")]
+    pub config: Option<PathBuf>,
+    #[arg(long)]
+    pub host: Option<String>,
+    #[arg(long)]
+    pub port: Option<u16>,
+    /// gRPCサーバのアドレス（例: `0.0.0.0:50051`）
+    #[arg(long)]
+    pub grpc_addr: Option<String>,
+    /// 終了時に処理中のリクエストを待つ秒数
+    #[arg(long)]
+    pub shutdown_timeout_secs: Option<u64>,
+    #[arg(long)]
+    pub database_url: Option<String>,
+    #[arg(long)]
+    pub database_max_connections: Option<u32>,
+    /// 許可するオリジン。繰り返し指定でき、設定ファイルなどの値を置き換える
+    #[arg(long = "cors-allowed-origin", value_name = "ORIGIN")]
+    pub cors_allowed_origins: Vec<String>,
+    #[arg(long, value_enum)]
+    pub log_format: Option<LogFormat>,
+    /// 例: `info,rust_todo_app=debug`
+    #[arg(long)]
+    pub log_level: Option<String>,
+    /// 機能を有効にする（繰り返し指定可）
+    #[arg(long, value_enum, value_name = "FEATURE")]
+    pub enable: Vec<Feature>,
+    /// 機能を無効にする（繰り返し指定可）
+    #[arg(long, value_enum, value_name = "FEATURE")]
+    pub disable: Vec<Feature>,
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    /// 設定ファイルを読めない
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    /// TOMLとして読めない、または知らない項目がある
+    Parse { path: PathBuf, message: String },
+    /// 環境変数の値が読めない
+    Env { name: &'static str, message: String },
+    /// 検証に通らなかった項目（すべてまとめて報告する）
+    Invalid(Vec<String>),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Read { path, source } => {
+                write!(
+                    f,
+                    "failed to read config file {}: {}",
+                    path.display(),
+                    source
+                )
+            }
+            ConfigError::Parse { path, message } => {
+                write!(f, "invalid config file {}: {}", path.display(), message)
+            }
+            ConfigError::Env { name, message } => {
+                write!(f, "invalid environment variable {}: {}", name, message)
+            }
+            ConfigError::Invalid(problems) => {
+                write!(f, "invalid configuration:")?;
+                for problem in problems {
+                    write!(f, "\n  - {}", problem)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl Config {
+    /// 設定ファイル・環境変数・コマンドライン引数を重ねて読み、検証する
+    pub fn load(args: &CliArgs) -> Result<Self, ConfigError> {
+        Self::load_from(args, |name| std::env::var(name).ok())
+    }
+
+    /// 環境変数の読み方を差し替えられる [`Config::load`]（テスト用）
+    pub fn load_from(
+        args: &CliArgs,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self, ConfigError> {
+        let mut config = match &args.config {
+            Some(path) => {
+                let text = std::fs::read_to_string(path).map_err(|source| ConfigError::Read {
+                    path: path.clone(),
+                    source,
+                })?;
+                Self::from_toml(&text).map_err(|message| ConfigError::Parse {
+                    path: path.clone(),
+                    message,
+                })?
+            }
+            None => Self::default(),
+        };
+        config.apply_env(env)?;
+        config.apply_args(args);
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// TOMLを読む。書かれていない項目は既定値になる
+    pub fn from_toml(text: &str) -> Result<Self, String> {
+        toml::from_str(text).map_err(|e| e.to_string())
+    }
+
+    fn apply_env(&mut self, env: impl Fn(&str) -> Option<String>) -> Result<(), ConfigError> {
+        let env = |name: &'static str| env(name).map(|value| (name, value));
+
+        if let Some((_, value)) = env("HTTP_HOST") {
+            self.server.host = value;
+        }
+        if let Some(var) = env("HTTP_PORT") {
+            self.server.port = parse_env(var, "a port number")?;
+        }
+        if let Some((_, value)) = env("GRPC_ADDR") {
+            self.server.grpc_addr = Some(value).filter(|addr| !addr.is_empty());
+        }
+        if let Some(var) = env("DEFAULT_LANGUAGE") {
+            self.server.default_language = parse_env(var, "ja or en")?;
+        }
+        if let Some(var) = env("SHUTDOWN_TIMEOUT_SECS") {
+            self.server.shutdown_timeout_secs = parse_env(var, "a number of seconds")?;
+        }
+        if let Some(var) = env("LEGACY_STATUS_CODES") {
+            self.server.legacy_status_codes = parse_bool(var)?;
+        }
+        if let Some((_, value)) = env("DATABASE_URL") {
+            self.database.url = value;
+        }
+        if let Some(var) = env("DATABASE_MAX_CONNECTIONS") {
+            self.database.max_connections = parse_env(var, "a number")?;
+        }
+        if let Some((_, value)) = env("CORS_ALLOWED_ORIGINS") {
+            self.cors.allowed_origins = split_list(&value);
+        }
+        if let Some((name, value)) = env("LOG_FORMAT") {
+            self.log.format = LogFormat::from_str(&value, true).map_err(|_| ConfigError::Env {
+                name,
+                message: format!("expected text or json, got {:?}", value),
+            })?;
+        }
+        if let Some((_, value)) = env("RUST_LOG") {
+            self.log.level = value;
+        }
+        if let Some(var) = env("OTEL_ENABLED") {
+            self.otel.enabled = parse_bool(var)?;
+        }
+        if let Some((_, value)) = env("OTEL_EXPORTER_OTLP_ENDPOINT") {
+            self.otel.endpoint = value;
+        }
+        if let Some((_, value)) = env("OTEL_SERVICE_NAME") {
+            self.otel.service_name = value;
+        }
+        let features = [
+            ("FEATURE_GRAPHQL", Feature::Graphql),
+            ("FEATURE_API_DOCS", Feature::ApiDocs),
+            ("FEATURE_LEGACY_PATHS", Feature::LegacyPaths),
+            ("FEATURE_REMINDER_DELIVERY", Feature::ReminderDelivery),
+            ("FEATURE_WEBHOOK_DELIVERY", Feature::WebhookDelivery),
+            ("FEATURE_METRICS", Feature::Metrics),
+        ];
+        for (name, feature) in features {
+            if let Some(var) = env(name) {
+                self.features.set(feature, parse_bool(var)?);
+            }
+        }
+        if let Some(var) = env("REMINDER_POLL_INTERVAL_SECS") {
+            self.reminders.poll_interval_secs = parse_env(var, "a number of seconds")?;
+        }
+        if let Some((_, value)) = env("REMINDER_WEBHOOK_URL") {
+            self.reminders.webhook_url = Some(value).filter(|url| !url.is_empty());
+        }
+        if let Some(var) = env("WEBHOOK_POLL_INTERVAL_SECS") {
+            self.webhooks.poll_interval_secs = parse_env(var, "a number of seconds")?;
+        }
+        if let Some((_, value)) = env("ATTACHMENTS_DIR") {
+            self.attachments.dir = value.into();
+        }
+        if let Some(var) = env("ATTACHMENTS_MAX_BYTES") {
+            self.attachments.max_bytes = parse_env(var, "a number of bytes")?;
+        }
+        if let Some((_, value)) = env("ATTACHMENTS_ALLOWED_TYPES") {
+            self.attachments.allowed_types = split_list(&value)
+                .into_iter()
+                .map(|t| t.to_ascii_lowercase())
+                .collect();
+        }
+        if let Some(var) = env("RATE_LIMIT_ENABLED") {
+            self.limits.rate_limit_enabled = parse_bool(var)?;
+        }
+        if let Some((name, value)) = env("TRUSTED_PROXIES") {
+            self.limits.trusted_proxies = split_list(&value)
+                .into_iter()
+                .map(|ip| {
+                    ip.parse().map_err(|_| ConfigError::Env {
+                        name,
+                        message: format!("expected IP addresses, got {:?}", ip),
+                    })
+                })
+                .collect::<Result<_, _>>()?;
+        }
+        if let Some(var) = env("RATE_LIMIT_PER_MINUTE") {
+            self.limits.requests_per_minute = parse_env(var, "a number of requests")?;
+        }
+        if let Some(var) = env("RATE_LIMIT_BURST") {
+            self.limits.burst = parse_env(var, "a number of requests")?;
+        }
+        if let Some(var) = env("MAX_BODY_BYTES") {
+            self.limits.max_body_bytes = parse_env(var, "a number of bytes")?;
+        }
+        if let Some(var) = env("MAX_LIST_LEN") {
+            self.limits.max_list_len = parse_env(var, "a number")?;
+        }
+        if let Some(var) = env("IDEMPOTENCY_TTL_SECS") {
+            self.idempotency.ttl_secs = parse_env(var, "a number of seconds")?;
+        }
+        if let Some(var) = env("IDEMPOTENCY_REQUEST_TIMEOUT_SECS") {
+            self.idempotency.request_timeout_secs = parse_env(var, "a number of seconds")?;
+        }
+        if let Some(var) = env("IDEMPOTENCY_IN_PROGRESS_TIMEOUT_SECS") {
+            self.idempotency.in_progress_timeout_secs = parse_env(var, "a number of seconds")?;
+        }
+        if let Some(var) = env("TLS_ENABLED") {
+            self.tls.enabled = parse_bool(var)?;
+        }
+        if let Some((_, value)) = env("TLS_CERT_PATH") {
+            self.tls.cert_path = PathBuf::from(value);
+        }
+        if let Some((_, value)) = env("TLS_KEY_PATH") {
+            self.tls.key_path = PathBuf::from(value);
+        }
+        if let Some(var) = env("TLS_RELOAD_INTERVAL_SECS") {
+            self.tls.reload_interval_secs = parse_env(var, "a number of seconds")?;
+        }
+        if let Some(var) = env("TLS_HANDSHAKE_TIMEOUT_SECS") {
+            self.tls.handshake_timeout_secs = parse_env(var, "a number of seconds")?;
+        }
+        if let Some(var) = env("TLS_REDIRECT_HTTP_PORT") {
+            self.tls.redirect_http_port = match var.1.is_empty() {
+                true => None,
+                false => Some(parse_env(var, "a port number")?),
+            };
+        }
+        if let Some(var) = env("HSTS_MAX_AGE_SECS") {
+            self.tls.hsts_max_age_secs = parse_env(var, "a number of seconds")?;
+        }
+        if let Some(var) = env("HSTS_INCLUDE_SUBDOMAINS") {
+            self.tls.hsts_include_subdomains = parse_bool(var)?;
+        }
+        Ok(())
+    }
+
+    fn apply_args(&mut self, args: &CliArgs) {
+        if let Some(host) = &args.host {
+            self.server.host = host.clone();
+        }
+        if let Some(port) = args.port {
+            self.server.port = port;
+        }
+        if let Some(addr) = &args.grpc_addr {
+            self.server.grpc_addr = Some(addr.clone());
+        }
+        if let Some(secs) = args.shutdown_timeout_secs {
+            self.server.shutdown_timeout_secs = secs;
+        }
+        if let Some(url) = &args.database_url {
+            self.database.url = url.clone();
+        }
+        if let Some(max) = args.database_max_connections {
+            self.database.max_connections = max;
+        }
+        if !args.cors_allowed_origins.is_empty() {
+            self.cors.allowed_origins = args.cors_allowed_origins.clone();
+        }
+        if let Some(format) = args.log_format {
+            self.log.format = format;
+        }
+        if let Some(level) = &args.log_level {
+            self.log.level = level.clone();
+        }
+        for &feature in &args.enable {
+            self.features.set(feature, true);
+        }
+        for &feature in &args.disable {
+            self.features.set(feature, false);
+        }
+    }
+
+    /// 矛盾や範囲外の値をすべて集めて返す
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut problems = Vec::new();
+
+        if self.server.host.trim().is_empty() {
+            problems.push("server.host must not be empty".to_string());
+        }
+        if let Some(addr) = &self.server.grpc_addr {
+            match addr.parse::<SocketAddr>() {
+                Err(_) => problems.push(format!(
+                    "server.grpc_addr must be an address like 0.0.0.0:50051, got {:?}",
+                    addr
+                )),
+                Ok(addr) if addr.port() == self.server.port && addr.port() != 0 => {
+                    problems.push(format!(
+                        "server.grpc_addr must use a different port than server.port ({})",
+                        self.server.port
+                    ))
+                }
+                Ok(_) => {}
+            }
+        }
+        if self.server.shutdown_timeout_secs == 0 {
+            problems.push("server.shutdown_timeout_secs must be at least 1".to_string());
+        }
+        if !self.database.url.starts_with("sqlite:") {
+            problems.push(format!(
+                "database.url must be a sqlite: URL, got {:?}",
+                self.database.url
+            ));
+        }
+        if self.database.max_connections == 0 {
+            problems.push("database.max_connections must be at least 1".to_string());
+        }
+        for origin in &self.cors.allowed_origins {
+            if origin == "*" {
+                if self.cors.allowed_origins.len() > 1 {
+                    problems.push(
+                        "cors.allowed_origins cannot mix \"*\" with specific origins".to_string(),
+                    );
+                }
+            } else if !is_origin(origin) {
+                problems.push(format!(
+                    "cors.allowed_origins entries must look like https://example.com (no path), got {:?}",
+                    origin
+                ));
+            }
+        }
+        if let Err(e) = tracing_subscriber::EnvFilter::try_new(&self.log.level) {
+            problems.push(format!("log.level is not a valid filter: {}", e));
+        }
+        if self.otel.enabled {
+            if !cfg!(feature = "otel") {
+                problems.push(
+                    "otel.enabled requires a build with the otel feature (cargo build --features otel)"
+                        .to_string(),
+                );
+            }
+            if !(self.otel.endpoint.starts_with("http://")
+                || self.otel.endpoint.starts_with("https://"))
+            {
+                problems.push(format!(
+                    "otel.endpoint must be an http(s) URL, got {:?}",
+                    self.otel.endpoint
+                ));
+            }
+            if self.otel.service_name.trim().is_empty() {
+                problems.push("otel.service_name must not be empty".to_string());
+            }
+        }
+        if self.reminders.poll_interval_secs == 0 {
+            problems.push("reminders.poll_interval_secs must be at least 1".to_string());
+        }
+        if let Some(url) = &self.reminders.webhook_url {
+            if !(url.starts_with("http://") || url.starts_with("https://")) {
+                problems.push(format!(
+                    "reminders.webhook_url must be an http(s) URL, got {:?}",
+                    url
+                ));
+            }
+        }
+        if self.webhooks.poll_interval_secs == 0 {
+            problems.push("webhooks.poll_interval_secs must be at least 1".to_string());
+        }
+        if self.attachments.max_bytes == 0 {
+            problems.push("attachments.max_bytes must be at least 1".to_string());
+        }
+        for content_type in &self.attachments.allowed_types {
+            if !content_type.contains('/') {
+                problems.push(format!(
+                    "attachments.allowed_types entries must look like image/png or image/*, got {:?}",
+                    content_type
+                ));
+            }
+        }
+        // 既定値と、グループごとに上書きした値を同じように確かめる
+        let rate_limited = self.limits.rate_limit_enabled;
+        let defaults = RouteLimitOverrides {
+            requests_per_minute: Some(self.limits.requests_per_minute),
+            burst: Some(self.limits.burst),
+            max_body_bytes: Some(self.limits.max_body_bytes),
+            max_list_len: Some(self.limits.max_list_len),
+        };
+        let sections = std::iter::once(("limits".to_string(), &defaults)).chain(
+            RouteGroup::ALL.map(|group| {
+                (
+                    format!("limits.{}", group.as_str()),
+                    self.limits.overrides(group),
+                )
+            }),
+        );
+        for (section, limits) in sections {
+            let checks = [
+                (
+                    "requests_per_minute",
+                    rate_limited,
+                    limits.requests_per_minute.map(|n| n as usize),
+                ),
+                ("burst", rate_limited, limits.burst.map(|n| n as usize)),
+                ("max_body_bytes", true, limits.max_body_bytes),
+                ("max_list_len", true, limits.max_list_len),
+            ];
+            for (key, applies, value) in checks {
+                if applies && value == Some(0) {
+                    problems.push(format!("{}.{} must be at least 1", section, key));
+                }
+            }
+        }
+        if self.idempotency.ttl_secs == 0 {
+            problems.push("idempotency.ttl_secs must be at least 1".to_string());
+        }
+        if self.idempotency.request_timeout_secs == 0 {
+            problems.push("idempotency.request_timeout_secs must be at least 1".to_string());
+        }
+        if self.idempotency.in_progress_timeout_secs <= self.idempotency.request_timeout_secs {
+            problems.push(
+                "idempotency.in_progress_timeout_secs must be longer than idempotency.request_timeout_secs"
+                    .to_string(),
+            );
+        }
+        if self.tls.enabled {
+            for (key, path) in [
+                ("tls.cert_path", &self.tls.cert_path),
+                ("tls.key_path", &self.tls.key_path),
+            ] {
+                if path.as_os_str().is_empty() {
+                    problems.push(format!("{} must not be empty", key));
+                }
+            }
+            if self.tls.reload_interval_secs == 0 {
+                problems.push("tls.reload_interval_secs must be at least 1".to_string());
+            }
+            if self.tls.handshake_timeout_secs == 0 {
+                problems.push("tls.handshake_timeout_secs must be at least 1".to_string());
+            }
+            if self.tls.redirect_http_port == Some(self.server.port) && self.server.port != 0 {
+                problems.push(format!(
+                    "tls.redirect_http_port must be different from server.port ({})",
+                    self.server.port
+                ));
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError::Invalid(problems))
+        }
+    }
+
+    /// HTTPサーバの待ち受けアドレス（`host:port`）
+    pub fn http_addr(&self) -> String {
+        format!("{}:{}", self.server.host, self.server.port)
+    }
+}
+
+fn parse_env<T: FromStr>(
+    (name, value): (&'static str, String),
+    expected: &str,
+) -> Result<T, ConfigError> {
+    value.trim().parse().map_err(|_| ConfigError::Env {
+        name,
+        message: format!("expected {}, got {:?}", expected, value),
+    })
+}
+
+fn parse_bool((name, value): (&'static str, String)) -> Result<bool, ConfigError> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Ok(true),
+        "0" | "false" | "no" | "off" => Ok(false),
+        _ => Err(ConfigError::Env {
+            name,
+            message: format!("expected true or false, got {:?}", value),
+        }),
+    }
+}
+
+// カンマ区切りの一覧。空の要素は捨てる
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+// `Origin` ヘッダと比べられる形（スキーム + ホスト + ポート、末尾のスラッシュやパスなし）
+fn is_origin(origin: &str) -> bool {
+    let Some(rest) = origin
+        .strip_prefix("http://")
+        .or_else(|| origin.strip_prefix("https://"))
+    else {
+        return false;
+    };
+    !rest.is_empty() && !rest.contains('/') && axum::http::HeaderValue::from_str(origin).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::io::Write;
+
+    use super::*;
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    fn config_file(text: &str) -> tempfile::NamedTempFile {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(text.as_bytes()).unwrap();
+        file
+    }
+
+    #[test]
+    fn test_defaults_are_valid() {
+        let config = Config::load_from(&CliArgs::default(), env(&[])).unwrap();
+        assert_eq!(config.http_addr(), "0.0.0.0:3000");
+        assert_eq!(config.database.url, "sqlite:todos.db");
+        assert!(config.cors.allowed_origins.is_empty());
+        assert!(config.features.graphql);
+    }
+
+    #[test]
+    fn test_example_file_is_valid() {
+        let config = Config::from_toml(include_str!("../config.example.toml")).unwrap();
+        config.validate().unwrap();
+        assert_eq!(
+            config.attachments.allowed_types,
+            AttachmentsConfig::default().allowed_types
+        );
+        assert_eq!(config.limits.route(RouteGroup::Webhooks).burst, 10);
+    }
+
+    #[test]
+    fn test_cli_overrides_env_which_overrides_file() {
+        let file = config_file(
+            r#"
+            [server]
+            port = 8080
+            host = "127.0.0.1"
+            default_language = "en"
+
+            [database]
+            url = "sqlite:from-file.db"
+            max_connections = 2
+
+            [features]
+            graphql = false
+            "#,
+        );
+        let args = CliArgs {
+            config: Some(file.path().to_path_buf()),
+            port: Some(9090),
+            enable: vec![Feature::Graphql],
+            disable: vec![Feature::ApiDocs],
+            ..CliArgs::default()
+        };
+        let config = Config::load_from(
+            &args,
+            env(&[
+                ("HTTP_PORT", "8081"),
+                ("DATABASE_URL", "sqlite:from-env.db"),
+                (
+                    "CORS_ALLOWED_ORIGINS",
+                    "https://a.example, https://b.example",
+                ),
+            ]),
+        )
+        .unwrap();
+
+        assert_eq!(config.http_addr(), "127.0.0.1:9090");
+        assert_eq!(config.server.default_language, Lang::En);
+        assert_eq!(config.database.url, "sqlite:from-env.db");
+        assert_eq!(config.database.max_connections, 2);
+        assert_eq!(
+            config.cors.allowed_origins,
+            vec!["https://a.example", "https://b.example"]
+        );
+        assert!(config.features.graphql);
+        assert!(!config.features.api_docs);
+    }
+
+    #[test]
+    fn test_validation_reports_every_problem() {
+        let file = config_file(
+            r#"
+            [database]
+            url = "postgres://localhost/todos"
+            max_connections = 0
+
+            [cors]
+            allowed_origins = ["https://ok.example", "https://bad.example/"]
+            "#,
+        );
+        let args = CliArgs {
+            config: Some(file.path().to_path_buf()),
+            grpc_addr: Some("0.0.0.0:3000".to_string()),
+            ..CliArgs::default()
+        };
+        let Err(ConfigError::Invalid(problems)) = Config::load_from(&args, env(&[])) else {
+            panic!("expected validation errors");
+        };
+        assert_eq!(problems.len(), 4, "{:?}", problems);
+        assert!(problems[0].starts_with("server.grpc_addr must use a different port"));
+        assert!(problems[1].starts_with("database.url"));
+        assert!(problems[2].starts_with("database.max_connections"));
+        assert!(problems[3].contains("https://bad.example/"));
+    }
+
+    #[test]
+    fn test_unknown_keys_and_bad_env_are_rejected() {
+        let file = config_file("[server]\nprot = 3000\n");
+        let args = CliArgs {
+            config: Some(file.path().to_path_buf()),
+            ..CliArgs::default()
+        };
+        let error = Config::load_from(&args, env(&[])).unwrap_err();
+        assert!(matches!(error, ConfigError::Parse { .. }));
+        assert!(error.to_string().contains("prot"), "{}", error);
+
+        let error =
+            Config::load_from(&CliArgs::default(), env(&[("HTTP_PORT", "http")])).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid environment variable HTTP_PORT: expected a port number, got \"http\""
+        );
+        let error = Config::load_from(&CliArgs::default(), env(&[("FEATURE_GRAPHQL", "maybe")]))
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            ConfigError::Env {
+                name: "FEATURE_GRAPHQL",
+                ..
+            }
+        ));
+        let error = Config::load_from(
+            &CliArgs::default(),
+            env(&[("TRUSTED_PROXIES", "10.0.0.1, proxy.internal")]),
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid environment variable TRUSTED_PROXIES: expected IP addresses, got \"proxy.internal\""
+        );
+    }
+
+    #[test]
+    fn test_legacy_env_vars_are_still_read() {
+        let config = Config::load_from(
+            &CliArgs::default(),
+            env(&[
+                ("GRPC_ADDR", "0.0.0.0:50051"),
+                ("DEFAULT_LANGUAGE", "en"),
+                ("LEGACY_STATUS_CODES", "true"),
+                ("REMINDER_WEBHOOK_URL", ""),
+                ("ATTACHMENTS_ALLOWED_TYPES", "Image/*, text/plain,"),
+                ("RUST_LOG", "info"),
+            ]),
+        )
+        .unwrap();
+        assert_eq!(config.server.grpc_addr.as_deref(), Some("0.0.0.0:50051"));
+        assert_eq!(config.server.default_language, Lang::En);
+        assert!(config.server.legacy_status_codes);
+        assert_eq!(config.reminders.webhook_url, None);
+        assert_eq!(
+            config.attachments.allowed_types,
+            vec!["image/*", "text/plain"]
+        );
+        assert_eq!(config.log.level, "info");
+    }
+
+    #[test]
+    fn test_otel_is_read_from_the_standard_env_vars() {
+        let result = Config::load_from(
+            &CliArgs::default(),
+            env(&[
+                ("OTEL_ENABLED", "true"),
+                ("OTEL_EXPORTER_OTLP_ENDPOINT", "http://collector:4317"),
+                ("OTEL_SERVICE_NAME", "todo-api"),
+            ]),
+        );
+        if cfg!(feature = "otel") {
+            let config = result.unwrap();
+            assert!(config.otel.enabled);
+            assert_eq!(config.otel.endpoint, "http://collector:4317");
+            assert_eq!(config.otel.service_name, "todo-api");
+        } else {
+            let Err(ConfigError::Invalid(problems)) = result else {
+                panic!("expected validation errors");
+            };
+            assert_eq!(problems.len(), 1, "{:?}", problems);
+            assert!(problems[0].starts_with("otel.enabled requires"));
+        }
+    }
+
+    #[test]
+    fn test_route_groups_override_the_default_limits() {
+        let file = config_file(
+            r#"
+            [limits]
+            requests_per_minute = 120
+
+            [limits.graphql]
+            burst = 5
+            max_body_bytes = 4096
+            "#,
+        );
+        let args = CliArgs {
+            config: Some(file.path().to_path_buf()),
+            ..CliArgs::default()
+        };
+        let config = Config::load_from(
+            &args,
+            env(&[("RATE_LIMIT_BURST", "20"), ("MAX_LIST_LEN", "50")]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.limits.route(RouteGroup::Todos),
+            RouteLimits {
+                requests_per_minute: 120,
+                burst: 20,
+                max_body_bytes: 1024 * 1024,
+                max_list_len: 50,
+            }
+        );
+        assert_eq!(
+            config.limits.route(RouteGroup::Graphql),
+            RouteLimits {
+                requests_per_minute: 120,
+                burst: 5,
+                max_body_bytes: 4096,
+                max_list_len: 50,
+            }
+        );
+    }
+
+    #[test]
+    fn test_zero_limits_are_rejected() {
+        let file = config_file(
+            r#"
+            [limits]
+            max_list_len = 0
+
+            [limits.webhooks]
+            requests_per_minute = 0
+            "#,
+        );
+        let args = CliArgs {
+            config: Some(file.path().to_path_buf()),
+            ..CliArgs::default()
+        };
+        let Err(ConfigError::Invalid(problems)) = Config::load_from(&args, env(&[])) else {
+            panic!("expected validation errors");
+        };
+        assert_eq!(
+            problems,
+            vec![
+                "limits.max_list_len must be at least 1",
+                "limits.webhooks.requests_per_minute must be at least 1",
+            ]
+        );
+
+        // レート制限を切っていれば回数の設定は見ない
+        let config = Config::load_from(
+            &args,
+            env(&[("RATE_LIMIT_ENABLED", "false"), ("MAX_LIST_LEN", "10")]),
+        )
+        .unwrap();
+        assert!(!config.limits.rate_limit_enabled);
+    }
+
+    #[test]
+    fn test_in_progress_timeout_must_outlast_the_request_timeout() {
+        let args = CliArgs::default();
+        let config = Config::load_from(
+            &args,
+            env(&[
+                ("IDEMPOTENCY_REQUEST_TIMEOUT_SECS", "10"),
+                ("IDEMPOTENCY_IN_PROGRESS_TIMEOUT_SECS", "30"),
+            ]),
+        )
+        .unwrap();
+        assert_eq!(config.idempotency.request_timeout_secs, 10);
+        assert_eq!(config.idempotency.in_progress_timeout_secs, 30);
+
+        let Err(ConfigError::Invalid(problems)) = Config::load_from(
+            &args,
+            env(&[("IDEMPOTENCY_IN_PROGRESS_TIMEOUT_SECS", "60")]),
+        ) else {
+            panic!("expected validation errors");
+        };
+        assert_eq!(
+            problems,
+            vec![
+                "idempotency.in_progress_timeout_secs must be longer than idempotency.request_timeout_secs"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tls_is_read_from_env_and_validated() {
+        let config = Config::load_from(
+            &CliArgs::default(),
+            env(&[
+                ("TLS_ENABLED", "true"),
+                ("TLS_CERT_PATH", "/etc/todo/cert.pem"),
+                ("TLS_KEY_PATH", "/etc/todo/key.pem"),
+                ("TLS_REDIRECT_HTTP_PORT", "8080"),
+                ("HSTS_MAX_AGE_SECS", "0"),
+            ]),
+        )
+        .unwrap();
+        assert!(config.tls.enabled);
+        assert_eq!(config.tls.cert_path, PathBuf::from("/etc/todo/cert.pem"));
+        assert_eq!(config.tls.key_path, PathBuf::from("/etc/todo/key.pem"));
+        assert_eq!(config.tls.redirect_http_port, Some(8080));
+        assert_eq!(config.tls.hsts_max_age_secs, 0);
+
+        let Err(ConfigError::Invalid(problems)) = Config::load_from(
+            &CliArgs::default(),
+            env(&[
+                ("TLS_ENABLED", "true"),
+                ("TLS_KEY_PATH", ""),
+                ("TLS_RELOAD_INTERVAL_SECS", "0"),
+                ("TLS_HANDSHAKE_TIMEOUT_SECS", "0"),
+                ("TLS_REDIRECT_HTTP_PORT", "3000"),
+            ]),
+        ) else {
+            panic!("expected validation errors");
+        };
+        assert_eq!(
+            problems,
+            vec![
+                "tls.key_path must not be empty",
+                "tls.reload_interval_secs must be at least 1",
+                "tls.handshake_timeout_secs must be at least 1",
+                "tls.redirect_http_port must be different from server.port (3000)",
+            ]
+        );
+    }
+}
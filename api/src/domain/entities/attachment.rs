@@ -0,0 +1,13 @@
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone)]
+pub struct Attachment {
+    pub id: i64,
+    pub todo_id: i64,
+    pub filename: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    /// 本体のSHA-256（16進数）。BlobStorage上のキーを兼ねる
+    pub sha256: String,
+    pub created_at: DateTime<Utc>,
+}
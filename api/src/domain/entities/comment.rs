@@ -0,0 +1,40 @@
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone)]
+pub struct Comment {
+    pub id: i64,
+    pub todo_id: i64,
+    pub author: String,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+    pub edited_at: Option<DateTime<Utc>>,
+}
+
+impl Comment {
+    pub fn is_edited(&self) -> bool {
+        self.edited_at.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::Comment;
+
+    #[test]
+    fn comment_is_not_edited_until_edited_at_is_set() {
+        let mut comment = Comment {
+            id: 1,
+            todo_id: 2,
+            author: "alice".to_string(),
+            body: "**looks good**".to_string(),
+            created_at: Utc::now(),
+            edited_at: None,
+        };
+        assert!(!comment.is_edited());
+
+        comment.edited_at = Some(Utc::now());
+        assert!(comment.is_edited());
+    }
+}
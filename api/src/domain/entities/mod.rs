@@ -1 +1,6 @@
+pub mod attachment;
+pub mod comment;
+pub mod presence;
+pub mod reminder;
 pub mod todo;
+pub mod webhook;
@@ -0,0 +1,30 @@
+use chrono::{DateTime, Utc};
+
+/// 接続中のユーザーと、いま見ているTODO
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Viewer {
+    pub connection_id: u64,
+    pub user: String,
+    pub todo_id: Option<i64>,
+}
+
+/// TODOの編集ロック。期限までに更新されなければ自動で外れる
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditLock {
+    pub todo_id: i64,
+    pub connection_id: u64,
+    pub user: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl EditLock {
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at <= now
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PresenceSnapshot {
+    pub viewers: Vec<Viewer>,
+    pub locks: Vec<EditLock>,
+}
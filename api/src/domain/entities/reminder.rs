@@ -0,0 +1,55 @@
+use chrono::{DateTime, Duration, Utc};
+
+/// いつ通知するか。期限からの相対指定は、TODOに期限がないと発火しない
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReminderSchedule {
+    At(DateTime<Utc>),
+    BeforeDue(Duration),
+}
+
+impl ReminderSchedule {
+    pub fn fire_at(&self, due_at: Option<DateTime<Utc>>) -> Option<DateTime<Utc>> {
+        match self {
+            Self::At(at) => Some(*at),
+            Self::BeforeDue(offset) => due_at.map(|due| due - *offset),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Reminder {
+    pub id: i64,
+    pub todo_id: i64,
+    pub schedule: ReminderSchedule,
+    pub fire_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+    pub attempts: u32,
+    pub last_error: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone, Utc};
+
+    use super::ReminderSchedule;
+
+    #[test]
+    fn absolute_schedule_ignores_due_date() {
+        let at = Utc.with_ymd_and_hms(2030, 1, 1, 9, 0, 0).unwrap();
+
+        assert_eq!(ReminderSchedule::At(at).fire_at(None), Some(at));
+    }
+
+    #[test]
+    fn offset_schedule_is_relative_to_due_date() {
+        let due = Utc.with_ymd_and_hms(2030, 1, 1, 9, 0, 0).unwrap();
+        let schedule = ReminderSchedule::BeforeDue(Duration::minutes(30));
+
+        assert_eq!(
+            schedule.fire_at(Some(due)),
+            Some(Utc.with_ymd_and_hms(2030, 1, 1, 8, 30, 0).unwrap())
+        );
+        assert_eq!(schedule.fire_at(None), None);
+    }
+}
@@ -1,9 +1,12 @@
+use chrono::{DateTime, Utc};
+
 #[derive(Debug, Clone)]
 pub struct Todo {
     pub id: i64,
     pub title: String,
     pub completed: bool,
     pub position: i64,
+    pub due_at: Option<DateTime<Utc>>,
 }
 
 #[cfg(test)]
@@ -17,6 +20,7 @@ mod tests {
             title: "write_tests".to_string(),
             completed: false,
             position: 10,
+            due_at: None,
         };
 
         assert_eq!(todo.id, 1);
@@ -32,6 +36,7 @@ mod tests {
             title: "clone me".to_string(),
             completed: true,
             position: 20,
+            due_at: None,
         };
 
         let cloned = todo.clone();
@@ -0,0 +1,105 @@
+use chrono::{DateTime, Utc};
+
+/// Webhookの購読。`events` が空なら全イベントを受け取る
+#[derive(Debug, Clone)]
+pub struct Webhook {
+    pub id: i64,
+    pub url: String,
+    pub secret: String,
+    pub events: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Webhook {
+    pub fn subscribes_to(&self, event: &str) -> bool {
+        self.events.is_empty() || self.events.iter().any(|e| e == event)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryStatus {
+    /// 未送信または再送待ち
+    Pending,
+    Delivered,
+    /// 再送の上限に達して諦めたもの
+    Failed,
+}
+
+impl DeliveryStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Delivered => "delivered",
+            Self::Failed => "failed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(Self::Pending),
+            "delivered" => Some(Self::Delivered),
+            "failed" => Some(Self::Failed),
+            _ => None,
+        }
+    }
+}
+
+/// 1件のイベントを1つの購読先へ送る配信記録（アウトボックスの行）
+#[derive(Debug, Clone)]
+pub struct WebhookDelivery {
+    pub id: i64,
+    pub webhook_id: i64,
+    pub event: String,
+    pub payload: String,
+    pub status: DeliveryStatus,
+    pub attempts: u32,
+    pub response_status: Option<u16>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::{DeliveryStatus, Webhook};
+
+    fn webhook(events: &[&str]) -> Webhook {
+        Webhook {
+            id: 1,
+            url: "http://example.com/hook".to_string(),
+            secret: "secret".to_string(),
+            events: events.iter().map(|e| e.to_string()).collect(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn empty_filter_subscribes_to_everything() {
+        assert!(webhook(&[]).subscribes_to("todo.deleted"));
+    }
+
+    #[test]
+    fn filter_limits_events() {
+        let hook = webhook(&["todo.created"]);
+
+        assert!(hook.subscribes_to("todo.created"));
+        assert!(!hook.subscribes_to("todo.updated"));
+    }
+
+    #[test]
+    fn status_round_trips_through_str() {
+        for status in [
+            DeliveryStatus::Pending,
+            DeliveryStatus::Delivered,
+            DeliveryStatus::Failed,
+        ] {
+            assert_eq!(DeliveryStatus::parse(status.as_str()), Some(status));
+        }
+        assert_eq!(DeliveryStatus::parse("unknown"), None);
+    }
+}
@@ -0,0 +1,46 @@
+use crate::domain::entities::todo::Todo;
+
+/// TODOに起きた変更。外部への通知（Webhookなど）の元になる
+#[derive(Debug, Clone)]
+pub enum TodoEvent {
+    Created(Todo),
+    Updated(Todo),
+    Deleted { id: i64 },
+    Reordered { ids: Vec<i64> },
+}
+
+impl TodoEvent {
+    /// 購読時に指定できるイベント名の一覧
+    pub const NAMES: [&'static str; 4] = [
+        "todo.created",
+        "todo.updated",
+        "todo.deleted",
+        "todo.reordered",
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Created(_) => "todo.created",
+            Self::Updated(_) => "todo.updated",
+            Self::Deleted { .. } => "todo.deleted",
+            Self::Reordered { .. } => "todo.reordered",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TodoEvent;
+
+    #[test]
+    fn every_event_name_is_listed() {
+        let events = [
+            TodoEvent::Deleted { id: 1 },
+            TodoEvent::Reordered { ids: vec![1] },
+        ];
+
+        for event in events {
+            assert!(TodoEvent::NAMES.contains(&event.name()));
+        }
+    }
+}
@@ -1 +1,2 @@
 pub mod entities;
+pub mod events;
@@ -1,214 +1,297 @@
-use crate::presentation::dto::todo_requests::{
+pub mod attachments;
+pub mod comments;
+pub mod docs;
+pub mod events;
+pub mod graphql;
+pub mod health;
+pub mod metrics;
+pub mod reminders;
+pub mod webhooks;
+pub mod ws;
+
+use crate::config::RouteGroup;
+use crate::presentation::dto::v1::todo_requests::{
     CreateTodoRequest, ReorderRequest, UpdateTodoRequest,
 };
-use crate::presentation::dto::todo_responses::TodoResponse;
+use crate::presentation::dto::v1::todo_responses::TodoResponse;
+use crate::state::AppState;
 use std::sync::Arc;
 
 use crate::application::errors::AppError;
 use crate::application::ports::todo_repository::TodoRepository;
+use crate::application::usecases::todo::edit_lock::Editor;
 use crate::application::usecases::todo::{
     create as create_todo, delete as delete_todo_usecase, get as get_todo, list as list_todos,
     reorder as reorder_todos_usecase, update as update_todo_usecase,
 };
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{OriginalUri, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
     Json,
 };
 use tracing::{error, info, warn};
 use validator::Validate;
 
+use crate::presentation::extract::{JsonBody, PathParams};
+
+/// ヘルスチェック用の固定文字列を返す
+#[utoipa::path(
+    get,
+    path = "/",
+    tag = "meta",
+    responses(
+        (status = 200, description = "疎通確認", body = String, content_type = "text/plain")
+    )
+)]
 pub async fn handler() -> &'static str {
     "Hello, World!"
 }
 
-pub async fn get_todos(State(repo): State<Arc<dyn TodoRepository>>) -> Json<Vec<TodoResponse>> {
-    info!("GET /todos: fetching all todos");
-    let todos = list_todos::execute(repo.as_ref()).await.unwrap();
-    info!("GET /todos: returned {} todo(s)", todos.len());
+/// TODOの一覧を取得する
+#[utoipa::path(
+    get,
+    path = "/todos",
+    tag = "todos",
+    responses(
+        (status = 200, description = "並び順でのTODO一覧", body = [TodoResponse]),
+        (status = 500, description = "想定外のエラー", body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+pub async fn get_todos(
+    State(repo): State<Arc<dyn TodoRepository>>,
+) -> Result<Json<Vec<TodoResponse>>, AppError> {
+    info!("fetching all todos");
+    let todos = list_todos::execute(repo.as_ref()).await.map_err(|e| {
+        error!(error = ?e, "repository error");
+        e
+    })?;
+    info!(count = todos.len(), "returned todos");
     let responses: Vec<TodoResponse> = todos.into_iter().map(Into::into).collect();
-    Json(responses)
+    Ok(Json(responses))
 }
 
+/// TODOを1件取得する
+#[utoipa::path(
+    get,
+    path = "/todos/{id}",
+    tag = "todos",
+    params(
+        ("id" = u32, Path, description = "TODOのID")
+    ),
+    responses(
+        (status = 200, description = "TODO", body = TodoResponse),
+        (status = 404, description = "TODOが見つからない", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "想定外のエラー", body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
 pub async fn get_todo_by_id(
     State(repo): State<Arc<dyn TodoRepository>>,
-    Path(id): Path<u32>,
-) -> Result<Json<TodoResponse>, StatusCode> {
-    info!("GET /todos/{}: fetching todo by id", id);
+    PathParams(id): PathParams<u32>,
+) -> Result<Json<TodoResponse>, AppError> {
+    info!(todo_id = id, "fetching todo");
     match get_todo::execute(repo.as_ref(), id).await {
         Ok(Some(todo)) => {
-            info!("GET /todos/{}: todo found", id);
+            info!(todo_id = id, "todo found");
             Ok(Json(todo.into()))
         }
         Ok(None) => {
-            warn!("GET /todos/{}: todo not found", id);
-            Err(StatusCode::NOT_FOUND)
+            warn!(todo_id = id, "todo not found");
+            Err(AppError::NotFound)
         }
         Err(e) => {
-            error!("GET /todos/{}: repository error: {:?}", id, e);
-            Err(app_error_status(&e))
+            error!(todo_id = id, error = ?e, "repository error");
+            Err(e)
         }
     }
 }
 
+/// TODOを作成する
+#[utoipa::path(
+    post,
+    path = "/todos",
+    tag = "todos",
+    params(
+        ("Idempotency-Key" = Option<String>, Header, description = "再送しても一度だけ処理するためのキー（255文字以内）")
+    ),
+    request_body = CreateTodoRequest,
+    responses(
+        (status = 201, description = "作成したTODO。`server.legacy_status_codes` が有効なら200で `Location` なし", body = TodoResponse,
+            headers(("Location" = String, description = "作成したTODOのパス"))),
+        (status = 400, description = "入力エラー", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 409, description = "同じIdempotency-Keyのリクエストを処理中", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Idempotency-Keyが別の内容のリクエストで使われている", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "想定外のエラー", body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
 pub async fn create_todo(
-    State(repo): State<Arc<dyn TodoRepository>>,
-    Json(payload): Json<CreateTodoRequest>,
-) -> Result<Json<TodoResponse>, (StatusCode, Json<serde_json::Value>)> {
-    info!("POST /todos: creating todo with title: {}", payload.title);
+    State(state): State<AppState>,
+    OriginalUri(uri): OriginalUri,
+    JsonBody(payload): JsonBody<CreateTodoRequest>,
+) -> Result<Response, AppError> {
+    info!(title = %payload.title, "creating todo");
     if let Err(errors) = payload.validate() {
-        let error_messages: Vec<String> = errors
-            .field_errors()
-            .iter()
-            .flat_map(|(_, errors)| {
-                errors.iter().map(|e| {
-                    e.message
-                        .as_ref()
-                        .map(|m| m.to_string())
-                        .unwrap_or_else(|| "Invalid value".to_string())
-                })
-            })
-            .collect();
-        warn!("POST /todos: validation failed: {:?}", error_messages);
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({
-            "error": "Validation failed",
-            "details": error_messages
-            })),
-        ));
+        warn!(errors = ?errors, "validation failed");
+        return Err(errors.into());
     }
-    match create_todo::execute(repo.as_ref(), payload.title).await {
+    match create_todo::execute(state.todos.as_ref(), state.events.as_ref(), payload.title).await {
         Ok(todo) => {
-            info!("POST /todos: todo created successfully, id={}", todo.id);
-            Ok(Json(todo.into()))
+            info!(todo_id = todo.id, "todo created");
+            let id = todo.id;
+            let body = Json(TodoResponse::from(todo));
+            if state.legacy_status_codes {
+                return Ok(body.into_response());
+            }
+            // 呼ばれたパス（`/api/v1/todos` か旧パスの `/todos`）の下を指す
+            let location = format!("{}/{}", uri.path().trim_end_matches('/'), id);
+            Ok((StatusCode::CREATED, [(header::LOCATION, location)], body).into_response())
         }
         Err(e) => {
-            error!("POST /todos: failed to create todo: {:?}", e);
-            Err(app_error_response(&e))
+            error!(error = ?e, "failed to create todo");
+            Err(e)
         }
     }
 }
 
+/// TODOを部分更新する
+#[utoipa::path(
+    put,
+    path = "/todos/{id}",
+    tag = "todos",
+    params(
+        ("id" = u32, Path, description = "TODOのID")
+    ),
+    request_body = UpdateTodoRequest,
+    responses(
+        (status = 200, description = "更新後のTODO", body = TodoResponse),
+        (status = 400, description = "入力エラー", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "TODOが見つからない", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 409, description = "他の接続が編集ロックを持っている", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "想定外のエラー", body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
 pub async fn update_todo(
-    State(repo): State<Arc<dyn TodoRepository>>,
-    Path(id): Path<u32>,
-    Json(payload): Json<UpdateTodoRequest>,
-) -> Result<Json<TodoResponse>, (StatusCode, Json<serde_json::Value>)> {
-    info!("PUT /todos/{}: updating todo", id);
+    State(state): State<AppState>,
+    PathParams(id): PathParams<u32>,
+    JsonBody(payload): JsonBody<UpdateTodoRequest>,
+) -> Result<Json<TodoResponse>, AppError> {
+    info!(todo_id = id, "updating todo");
     if let Err(errors) = payload.validate() {
-        let error_messages: Vec<String> = errors
-            .field_errors()
-            .iter()
-            .flat_map(|(_, errors)| {
-                errors.iter().map(|e| {
-                    e.message
-                        .as_ref()
-                        .map(|m| m.to_string())
-                        .unwrap_or_else(|| "Invalid value".to_string())
-                })
-            })
-            .collect();
-        warn!("PUT /todos/{}: validation failed: {:?}", id, error_messages);
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({
-            "error": "Validation failed",
-            "details": error_messages
-            })),
-        ));
+        warn!(todo_id = id, errors = ?errors, "validation failed");
+        return Err(errors.into());
     }
-    match update_todo_usecase::execute(repo.as_ref(), id, payload.title, payload.completed).await {
+    match update_todo_usecase::execute(
+        state.todos.as_ref(),
+        state.events.as_ref(),
+        Editor::anonymous(state.presence.as_ref()),
+        id,
+        payload.title,
+        payload.completed,
+        payload.due_at,
+    )
+    .await
+    {
         Ok(Some(todo)) => {
-            info!("PUT /todos/{}: todo updated successfully", id);
+            info!(todo_id = id, "todo updated");
             Ok(Json(todo.into()))
         }
         Ok(None) => {
-            warn!("PUT /todos/{}: todo not found", id);
-            Err((
-                StatusCode::NOT_FOUND,
-                Json(serde_json::json!({
-                    "error": "Todo not found"
-                })),
-            ))
+            warn!(todo_id = id, "todo not found");
+            Err(AppError::NotFound)
         }
         Err(e) => {
-            error!("PUT /todos/{}: repository error: {:?}", id, e);
-            Err(app_error_response(&e))
+            error!(todo_id = id, error = ?e, "repository error");
+            Err(e)
         }
     }
 }
 
+/// TODOを削除する（コメント・添付・リマインダーも消える）
+#[utoipa::path(
+    delete,
+    path = "/todos/{id}",
+    tag = "todos",
+    params(
+        ("id" = u32, Path, description = "TODOのID")
+    ),
+    responses(
+        (status = 204, description = "削除した"),
+        (status = 404, description = "TODOが見つからない", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 409, description = "他の接続が編集ロックを持っている", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "想定外のエラー", body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
 pub async fn delete_todo(
-    State(repo): State<Arc<dyn TodoRepository>>,
-    Path(id): Path<u32>,
-) -> Result<StatusCode, StatusCode> {
-    info!("DELETE /todos/{}: deleting todo", id);
-    match delete_todo_usecase::execute(repo.as_ref(), id).await {
+    State(state): State<AppState>,
+    PathParams(id): PathParams<u32>,
+) -> Result<StatusCode, AppError> {
+    info!(todo_id = id, "deleting todo");
+    match delete_todo_usecase::execute(
+        state.todos.as_ref(),
+        state.attachments.as_ref(),
+        state.blobs.as_ref(),
+        state.events.as_ref(),
+        Editor::anonymous(state.presence.as_ref()),
+        id,
+    )
+    .await
+    {
         Ok(true) => {
-            info!("DELETE /todos/{}: todo deleted successfully", id);
+            info!(todo_id = id, "todo deleted");
             Ok(StatusCode::NO_CONTENT)
         }
         Ok(false) => {
-            warn!("DELETE /todos/{}: todo not found", id);
-            Err(StatusCode::NOT_FOUND)
+            warn!(todo_id = id, "todo not found");
+            Err(AppError::NotFound)
         }
         Err(e) => {
-            error!("DELETE /todos/{}: repository error: {:?}", id, e);
-            Err(app_error_status(&e))
+            error!(todo_id = id, error = ?e, "repository error");
+            Err(e)
         }
     }
 }
 
+/// TODOを並び替える
+#[utoipa::path(
+    put,
+    path = "/todos/reorder",
+    tag = "todos",
+    request_body = ReorderRequest,
+    responses(
+        (status = 204, description = "並び替えた。`server.legacy_status_codes` が有効なら200"),
+        (status = 400, description = "IDの数が上限（limits.max_list_len）を超えた", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "想定外のエラー", body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
 pub async fn reorder_todos(
-    State(repo): State<Arc<dyn TodoRepository>>,
-    Json(payload): Json<ReorderRequest>,
-) -> Result<StatusCode, StatusCode> {
-    info!("PUT /todos/reorder: reordering todos");
-    match reorder_todos_usecase::execute(repo.as_ref(), payload.ids).await {
+    State(state): State<AppState>,
+    JsonBody(payload): JsonBody<ReorderRequest>,
+) -> Result<StatusCode, AppError> {
+    info!("reordering todos");
+    let max_ids = state.limits.route(RouteGroup::Todos).max_list_len;
+    match reorder_todos_usecase::execute(
+        state.todos.as_ref(),
+        state.events.as_ref(),
+        payload.ids,
+        max_ids,
+    )
+    .await
+    {
         Ok(_) => {
-            info!("PUT /todos/reorder: todos reordered successfully");
-            Ok(StatusCode::OK)
+            info!("todos reordered");
+            if state.legacy_status_codes {
+                Ok(StatusCode::OK)
+            } else {
+                Ok(StatusCode::NO_CONTENT)
+            }
         }
         Err(e) => {
-            error!("PUT /todos/reorder: repository error: {:?}", e);
-            Err(app_error_status(&e))
+            error!(error = ?e, "repository error");
+            Err(e)
         }
     }
 }
 
-fn app_error_status(error: &AppError) -> StatusCode {
-    match error {
-        AppError::Validation(_) => StatusCode::BAD_REQUEST,
-        AppError::NotFound => StatusCode::NOT_FOUND,
-        AppError::Unexpected(_) => StatusCode::INTERNAL_SERVER_ERROR,
-    }
-}
-
-fn app_error_response(error: &AppError) -> (StatusCode, Json<serde_json::Value>) {
-    match error {
-        AppError::Validation(message) => (
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({
-                "error": "Validation failed",
-                "details": [message],
-            })),
-        ),
-        AppError::NotFound => (
-            StatusCode::NOT_FOUND,
-            Json(serde_json::json!({
-                "error": "Todo not found",
-            })),
-        ),
-        AppError::Unexpected(_) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({
-                "error": "Unexpected error",
-            })),
-        ),
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use std::sync::{Arc, Mutex};
@@ -218,12 +301,25 @@ mod tests {
     use axum::http::{Request, StatusCode};
     use axum::routing::{delete, post};
     use axum::Router;
+    use chrono::{DateTime, Utc};
     use tower::ServiceExt;
 
     use super::{create_todo, delete_todo};
     use crate::application::errors::AppError;
     use crate::application::ports::todo_repository::TodoRepository;
+    use crate::application::usecases::attachment::test_support::{
+        FakeAttachmentRepo, FakeBlobStorage,
+    };
+    use crate::application::usecases::todo::test_support::RecordingPublisher;
+    use crate::application::usecases::webhook::test_support::{FakeOutbox, FakeWebhookRepo};
     use crate::domain::entities::todo::Todo;
+    use crate::infrastructure::clock::SystemClock;
+    use crate::infrastructure::events::broadcaster::EventBroadcaster;
+    use crate::infrastructure::persistence::sqlite_comment_repo::CommentStore;
+    use crate::infrastructure::persistence::sqlite_idempotency_store::IdempotencyKeyStore;
+    use crate::infrastructure::persistence::sqlite_reminder_repo::ReminderStore;
+    use crate::infrastructure::realtime::presence_registry::PresenceRegistry;
+    use crate::state::AppState;
 
     struct FakeRepo {
         created_title: Mutex<Option<String>>,
@@ -255,13 +351,16 @@ mod tests {
             _id: u32,
             _title: Option<String>,
             _completed: Option<bool>,
+            _due_at: Option<Option<DateTime<Utc>>>,
         ) -> Result<Option<Todo>, AppError> {
             unimplemented!("not needed for this test");
         }
 
-        async fn delete(&self, id: u32) -> Result<bool, AppError> {
+        async fn delete(&self, id: u32) -> Result<Option<Vec<String>>, AppError> {
             *self.deleted_id.lock().expect("failed to lock deleted_id") = Some(id);
-            self.delete_result.clone()
+            self.delete_result
+                .clone()
+                .map(|deleted| deleted.then(Vec::new))
         }
 
         async fn reorder(&self, _todo_ids: Vec<i64>) -> Result<(), AppError> {
@@ -270,10 +369,33 @@ mod tests {
     }
 
     fn app(repo: Arc<dyn TodoRepository>) -> Router {
+        // このテストではコメントやリマインダーに触れないので、接続しないプールで足りる
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .connect_lazy("sqlite::memory:")
+            .expect("failed to create lazy pool");
+        let state = AppState {
+            todos: repo,
+            comments: Arc::new(CommentStore::new(pool.clone())),
+            attachments: Arc::new(FakeAttachmentRepo::default()),
+            blobs: Arc::new(FakeBlobStorage::default()),
+            attachment_limits: Arc::new(Default::default()),
+            reminders: Arc::new(ReminderStore::new(pool.clone())),
+            webhooks: Arc::new(FakeWebhookRepo::default()),
+            webhook_outbox: Arc::new(FakeOutbox::default()),
+            events: Arc::new(RecordingPublisher::default()),
+            event_stream: Arc::new(EventBroadcaster::default()),
+            presence: Arc::new(PresenceRegistry::new(
+                Arc::new(SystemClock),
+                chrono::Duration::seconds(30),
+            )),
+            limits: Arc::new(Default::default()),
+            idempotency: Arc::new(IdempotencyKeyStore::new(pool)),
+            legacy_status_codes: false,
+        };
         Router::new()
             .route("/todos", post(create_todo))
             .route("/todos/:id", delete(delete_todo))
-            .with_state(repo)
+            .with_state(state)
     }
 
     #[tokio::test]
@@ -286,6 +408,7 @@ mod tests {
                 title: "saved".to_string(),
                 completed: false,
                 position: 1,
+                due_at: None,
             }),
             delete_result: Ok(false),
         });
@@ -302,7 +425,8 @@ mod tests {
             .await
             .expect("request failed");
 
-        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(response.headers()["location"], "/todos/1");
 
         let body = to_bytes(response.into_body(), usize::MAX)
             .await
@@ -329,6 +453,7 @@ mod tests {
                 title: "saved".to_string(),
                 completed: false,
                 position: 1,
+                due_at: None,
             }),
             delete_result: Ok(false),
         });
@@ -352,7 +477,8 @@ mod tests {
             .expect("failed to read body");
         let json: serde_json::Value = serde_json::from_slice(&body).expect("failed to parse json");
 
-        assert_eq!(json["error"], "Validation failed");
+        assert_eq!(json["code"], "invalid_fields");
+        assert_eq!(json["errors"][0]["field"], "title");
         let created_title = repo
             .created_title
             .lock()
@@ -371,6 +497,7 @@ mod tests {
                 title: "saved".to_string(),
                 completed: false,
                 position: 1,
+                due_at: None,
             }),
             delete_result: Ok(true),
         });
@@ -0,0 +1,339 @@
+use crate::application::errors::{AppError, ErrorMessage};
+use crate::application::usecases::attachment::{
+    delete as delete_attachment_usecase, download as download_attachment_usecase,
+    get as get_attachment_usecase, list as list_attachments_usecase,
+    upload as upload_attachment_usecase,
+};
+use crate::presentation::dto::v1::attachment_responses::AttachmentResponse;
+use crate::state::AppState;
+use axum::{
+    extract::{multipart::Field, Multipart, State},
+    http::{header, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use tracing::{error, info, warn};
+
+use crate::presentation::extract::PathParams;
+
+const FILE_FIELD: &str = "file";
+
+/// ファイルを添付する
+#[utoipa::path(
+    post,
+    path = "/todos/{id}/attachments",
+    tag = "attachments",
+    params(
+        ("id" = u32, Path, description = "TODOのID"),
+        ("Idempotency-Key" = Option<String>, Header, description = "再送しても一度だけ処理するためのキー（255文字以内）")
+    ),
+    request_body(content = UploadAttachmentForm, content_type = "multipart/form-data"),
+    responses(
+        (status = 201, description = "保存した添付", body = AttachmentResponse),
+        (status = 400, description = "入力エラー", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "TODOが見つからない", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 413, description = "サイズ上限を超えた", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 415, description = "許可されていないMIMEタイプ", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 409, description = "同じIdempotency-Keyのリクエストを処理中", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Idempotency-Keyが別の内容のリクエストで使われている", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "想定外のエラー", body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+pub async fn upload_attachment(
+    State(state): State<AppState>,
+    PathParams(todo_id): PathParams<u32>,
+    mut multipart: Multipart,
+) -> Result<(StatusCode, Json<AttachmentResponse>), AppError> {
+    info!(todo_id, "uploading attachment");
+    let max_size = state.attachment_limits.max_size_bytes;
+
+    let mut upload = None;
+    while let Some(field) = multipart.next_field().await.map_err(|e| {
+        warn!(todo_id, error = %e, "bad multipart");
+        AppError::validation(ErrorMessage::new("invalid_multipart").with_reason(e.body_text()))
+    })? {
+        if field.name() != Some(FILE_FIELD) {
+            continue;
+        }
+        upload = Some(read_upload(field, max_size).await.map_err(|e| {
+            warn!(todo_id, error = ?e, "attachment rejected");
+            e
+        })?);
+        break;
+    }
+    let upload = upload.ok_or_else(|| {
+        AppError::validation(
+            ErrorMessage::new("attachment_field_required").with("field", FILE_FIELD),
+        )
+    })?;
+
+    match upload_attachment_usecase::execute(
+        state.todos.as_ref(),
+        state.attachments.as_ref(),
+        state.blobs.as_ref(),
+        &state.attachment_limits,
+        todo_id,
+        upload,
+    )
+    .await
+    {
+        Ok(attachment) => {
+            info!(
+                todo_id,
+                attachment_id = attachment.id,
+                size_bytes = attachment.size_bytes,
+                "attachment stored"
+            );
+            Ok((StatusCode::CREATED, Json(attachment.into())))
+        }
+        Err(e) => {
+            warn!(todo_id, error = ?e, "failed to store attachment");
+            Err(e)
+        }
+    }
+}
+
+// 上限を超えた時点で読み込みを打ち切る
+async fn read_upload(
+    mut field: Field<'_>,
+    max_size: usize,
+) -> Result<upload_attachment_usecase::Upload, AppError> {
+    let filename = field.file_name().unwrap_or_default().to_string();
+    let content_type = field
+        .content_type()
+        .unwrap_or("application/octet-stream")
+        .to_string();
+
+    let mut data = Vec::new();
+    while let Some(chunk) = field.chunk().await.map_err(|e| {
+        AppError::validation(ErrorMessage::new("invalid_multipart").with_reason(e.body_text()))
+    })? {
+        if data.len() + chunk.len() > max_size {
+            return Err(AppError::PayloadTooLarge(
+                ErrorMessage::new("attachment_too_large").with("max", max_size),
+            ));
+        }
+        data.extend_from_slice(&chunk);
+    }
+
+    Ok(upload_attachment_usecase::Upload {
+        filename,
+        content_type,
+        data,
+    })
+}
+
+/// TODOの添付一覧を取得する
+#[utoipa::path(
+    get,
+    path = "/todos/{id}/attachments",
+    tag = "attachments",
+    params(
+        ("id" = u32, Path, description = "TODOのID")
+    ),
+    responses(
+        (status = 200, description = "添付一覧", body = [AttachmentResponse]),
+        (status = 404, description = "TODOが見つからない", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "想定外のエラー", body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+pub async fn list_attachments(
+    State(state): State<AppState>,
+    PathParams(todo_id): PathParams<u32>,
+) -> Result<Json<Vec<AttachmentResponse>>, AppError> {
+    info!(todo_id, "listing attachments");
+    match list_attachments_usecase::execute(
+        state.todos.as_ref(),
+        state.attachments.as_ref(),
+        todo_id,
+    )
+    .await
+    {
+        Ok(attachments) => Ok(Json(attachments.into_iter().map(Into::into).collect())),
+        Err(e) => {
+            warn!(todo_id, error = ?e, "failed to list attachments");
+            Err(e)
+        }
+    }
+}
+
+/// 添付のメタデータを取得する
+#[utoipa::path(
+    get,
+    path = "/todos/{id}/attachments/{attachment_id}",
+    tag = "attachments",
+    params(
+        ("id" = u32, Path, description = "TODOのID"),
+        ("attachment_id" = u32, Path, description = "添付のID")
+    ),
+    responses(
+        (status = 200, description = "添付のメタデータ", body = AttachmentResponse),
+        (status = 404, description = "添付が見つからない", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "想定外のエラー", body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+pub async fn get_attachment(
+    State(state): State<AppState>,
+    PathParams((todo_id, id)): PathParams<(u32, u32)>,
+) -> Result<Json<AttachmentResponse>, AppError> {
+    info!(todo_id, attachment_id = id, "fetching attachment metadata");
+    match get_attachment_usecase::execute(state.attachments.as_ref(), todo_id, id).await {
+        Ok(Some(attachment)) => Ok(Json(attachment.into())),
+        Ok(None) => {
+            warn!(todo_id, attachment_id = id, "attachment not found");
+            Err(AppError::NotFound)
+        }
+        Err(e) => {
+            error!(todo_id, attachment_id = id, error = ?e, "repository error");
+            Err(e)
+        }
+    }
+}
+
+/// 添付のファイル本体をダウンロードする
+#[utoipa::path(
+    get,
+    path = "/todos/{id}/attachments/{attachment_id}/content",
+    tag = "attachments",
+    params(
+        ("id" = u32, Path, description = "TODOのID"),
+        ("attachment_id" = u32, Path, description = "添付のID")
+    ),
+    responses(
+        (status = 200, description = "ファイル本体", body = Vec<u8>, content_type = "application/octet-stream"),
+        (status = 404, description = "添付が見つからない", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "想定外のエラー", body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+pub async fn download_attachment(
+    State(state): State<AppState>,
+    PathParams((todo_id, id)): PathParams<(u32, u32)>,
+) -> Result<Response, AppError> {
+    info!(todo_id, attachment_id = id, "downloading attachment");
+    match download_attachment_usecase::execute(
+        state.attachments.as_ref(),
+        state.blobs.as_ref(),
+        todo_id,
+        id,
+    )
+    .await
+    {
+        Ok(Some((attachment, data))) => {
+            let content_type = HeaderValue::from_str(&attachment.content_type)
+                .unwrap_or(HeaderValue::from_static("application/octet-stream"));
+            let disposition = HeaderValue::from_str(&content_disposition(&attachment.filename))
+                .unwrap_or(HeaderValue::from_static("attachment"));
+            let etag = HeaderValue::from_str(&format!("\"{}\"", attachment.sha256))
+                .expect("sha256 is a valid header value");
+            Ok((
+                [
+                    (header::CONTENT_TYPE, content_type),
+                    (header::CONTENT_DISPOSITION, disposition),
+                    (header::ETAG, etag),
+                    (
+                        header::X_CONTENT_TYPE_OPTIONS,
+                        HeaderValue::from_static("nosniff"),
+                    ),
+                ],
+                data,
+            )
+                .into_response())
+        }
+        Ok(None) => {
+            warn!(todo_id, attachment_id = id, "attachment not found");
+            Err(AppError::NotFound)
+        }
+        Err(e) => {
+            error!(todo_id, attachment_id = id, error = ?e, "failed to download attachment");
+            Err(e)
+        }
+    }
+}
+
+/// 添付を削除する
+#[utoipa::path(
+    delete,
+    path = "/todos/{id}/attachments/{attachment_id}",
+    tag = "attachments",
+    params(
+        ("id" = u32, Path, description = "TODOのID"),
+        ("attachment_id" = u32, Path, description = "添付のID")
+    ),
+    responses(
+        (status = 204, description = "削除した"),
+        (status = 404, description = "添付が見つからない", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "想定外のエラー", body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+pub async fn delete_attachment(
+    State(state): State<AppState>,
+    PathParams((todo_id, id)): PathParams<(u32, u32)>,
+) -> Result<StatusCode, AppError> {
+    info!(todo_id, attachment_id = id, "deleting attachment");
+    match delete_attachment_usecase::execute(
+        state.attachments.as_ref(),
+        state.blobs.as_ref(),
+        todo_id,
+        id,
+    )
+    .await
+    {
+        Ok(true) => {
+            info!(todo_id, attachment_id = id, "attachment deleted");
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Ok(false) => {
+            warn!(todo_id, attachment_id = id, "attachment not found");
+            Err(AppError::NotFound)
+        }
+        Err(e) => {
+            error!(todo_id, attachment_id = id, error = ?e, "failed to delete attachment");
+            Err(e)
+        }
+    }
+}
+
+// ASCII以外のファイル名は RFC 6266 の filename* で渡す
+fn content_disposition(filename: &str) -> String {
+    let fallback: String = filename
+        .chars()
+        .map(|c| {
+            if c.is_ascii() && !c.is_ascii_control() && c != '"' && c != '\\' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let encoded: String = filename
+        .bytes()
+        .map(|b| {
+            if b.is_ascii_alphanumeric() || b"!#$&+-.^_`|~".contains(&b) {
+                (b as char).to_string()
+            } else {
+                format!("%{:02X}", b)
+            }
+        })
+        .collect();
+    format!(
+        "attachment; filename=\"{}\"; filename*=UTF-8''{}",
+        fallback, encoded
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::content_disposition;
+
+    #[test]
+    fn content_disposition_encodes_non_ascii_names() {
+        assert_eq!(
+            content_disposition("メモ.txt"),
+            "attachment; filename=\"__.txt\"; filename*=UTF-8''%E3%83%A1%E3%83%A2.txt"
+        );
+        assert_eq!(
+            content_disposition("a b.pdf"),
+            "attachment; filename=\"a b.pdf\"; filename*=UTF-8''a%20b.pdf"
+        );
+    }
+}
@@ -0,0 +1,228 @@
+use crate::application::usecases::comment::{
+    create as create_comment_usecase, delete as delete_comment_usecase, get as get_comment_usecase,
+    list as list_comments_usecase, update as update_comment_usecase,
+};
+use crate::presentation::dto::v1::comment_requests::{
+    CommentListQuery, CreateCommentRequest, UpdateCommentRequest,
+};
+use crate::presentation::dto::v1::comment_responses::{CommentPageResponse, CommentResponse};
+use crate::state::AppState;
+use axum::{extract::State, http::StatusCode, Json};
+use tracing::{error, info, warn};
+use validator::Validate;
+
+use crate::application::errors::AppError;
+use crate::presentation::extract::{JsonBody, PathParams, QueryParams};
+
+/// TODOのコメントを古い順にページ単位で取得する
+#[utoipa::path(
+    get,
+    path = "/todos/{id}/comments",
+    tag = "comments",
+    params(
+        ("id" = u32, Path, description = "TODOのID"),
+        CommentListQuery
+    ),
+    responses(
+        (status = 200, description = "コメントの1ページ分", body = CommentPageResponse),
+        (status = 400, description = "入力エラー", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "TODOが見つからない", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "想定外のエラー", body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+pub async fn list_comments(
+    State(state): State<AppState>,
+    PathParams(todo_id): PathParams<u32>,
+    QueryParams(query): QueryParams<CommentListQuery>,
+) -> Result<Json<CommentPageResponse>, AppError> {
+    info!(todo_id, "listing comments");
+    let page = query.page.unwrap_or(1);
+    let per_page = query
+        .per_page
+        .unwrap_or(list_comments_usecase::DEFAULT_PER_PAGE);
+    match list_comments_usecase::execute(
+        state.todos.as_ref(),
+        state.comments.as_ref(),
+        todo_id,
+        page,
+        per_page,
+    )
+    .await
+    {
+        Ok(page) => {
+            info!(
+                todo_id,
+                count = page.comments.len(),
+                total = page.total,
+                "returned comments"
+            );
+            Ok(Json(page.into()))
+        }
+        Err(e) => {
+            warn!(todo_id, error = ?e, "failed to list comments");
+            Err(e)
+        }
+    }
+}
+
+/// コメントを投稿する
+#[utoipa::path(
+    post,
+    path = "/todos/{id}/comments",
+    tag = "comments",
+    params(
+        ("id" = u32, Path, description = "TODOのID"),
+        ("Idempotency-Key" = Option<String>, Header, description = "再送しても一度だけ処理するためのキー（255文字以内）")
+    ),
+    request_body = CreateCommentRequest,
+    responses(
+        (status = 201, description = "作成したコメント", body = CommentResponse),
+        (status = 400, description = "入力エラー", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "TODOが見つからない", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 409, description = "同じIdempotency-Keyのリクエストを処理中", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Idempotency-Keyが別の内容のリクエストで使われている", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "想定外のエラー", body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+pub async fn create_comment(
+    State(state): State<AppState>,
+    PathParams(todo_id): PathParams<u32>,
+    JsonBody(payload): JsonBody<CreateCommentRequest>,
+) -> Result<(StatusCode, Json<CommentResponse>), AppError> {
+    info!(todo_id, "creating comment");
+    if let Err(errors) = payload.validate() {
+        warn!(todo_id, errors = ?errors, "validation failed");
+        return Err(errors.into());
+    }
+    match create_comment_usecase::execute(
+        state.todos.as_ref(),
+        state.comments.as_ref(),
+        todo_id,
+        payload.author,
+        payload.body,
+    )
+    .await
+    {
+        Ok(comment) => {
+            info!(todo_id, comment_id = comment.id, "comment created");
+            Ok((StatusCode::CREATED, Json(comment.into())))
+        }
+        Err(e) => {
+            warn!(todo_id, error = ?e, "failed to create comment");
+            Err(e)
+        }
+    }
+}
+
+/// コメントを1件取得する
+#[utoipa::path(
+    get,
+    path = "/todos/{id}/comments/{comment_id}",
+    tag = "comments",
+    params(
+        ("id" = u32, Path, description = "TODOのID"),
+        ("comment_id" = u32, Path, description = "コメントのID")
+    ),
+    responses(
+        (status = 200, description = "コメント", body = CommentResponse),
+        (status = 404, description = "コメントが見つからない", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "想定外のエラー", body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+pub async fn get_comment(
+    State(state): State<AppState>,
+    PathParams((todo_id, id)): PathParams<(u32, u32)>,
+) -> Result<Json<CommentResponse>, AppError> {
+    info!(todo_id, comment_id = id, "fetching comment");
+    match get_comment_usecase::execute(state.comments.as_ref(), todo_id, id).await {
+        Ok(Some(comment)) => Ok(Json(comment.into())),
+        Ok(None) => {
+            warn!(todo_id, comment_id = id, "comment not found");
+            Err(AppError::NotFound)
+        }
+        Err(e) => {
+            error!(todo_id, comment_id = id, error = ?e, "repository error");
+            Err(e)
+        }
+    }
+}
+
+/// コメントを編集する
+#[utoipa::path(
+    put,
+    path = "/todos/{id}/comments/{comment_id}",
+    tag = "comments",
+    params(
+        ("id" = u32, Path, description = "TODOのID"),
+        ("comment_id" = u32, Path, description = "コメントのID")
+    ),
+    request_body = UpdateCommentRequest,
+    responses(
+        (status = 200, description = "編集後のコメント", body = CommentResponse),
+        (status = 400, description = "入力エラー", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "コメントが見つからない", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "想定外のエラー", body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+pub async fn update_comment(
+    State(state): State<AppState>,
+    PathParams((todo_id, id)): PathParams<(u32, u32)>,
+    JsonBody(payload): JsonBody<UpdateCommentRequest>,
+) -> Result<Json<CommentResponse>, AppError> {
+    info!(todo_id, comment_id = id, "editing comment");
+    if let Err(errors) = payload.validate() {
+        warn!(todo_id, comment_id = id, errors = ?errors, "validation failed");
+        return Err(errors.into());
+    }
+    match update_comment_usecase::execute(state.comments.as_ref(), todo_id, id, payload.body).await
+    {
+        Ok(Some(comment)) => {
+            info!(todo_id, comment_id = id, "comment edited");
+            Ok(Json(comment.into()))
+        }
+        Ok(None) => {
+            warn!(todo_id, comment_id = id, "comment not found");
+            Err(AppError::NotFound)
+        }
+        Err(e) => {
+            error!(todo_id, comment_id = id, error = ?e, "repository error");
+            Err(e)
+        }
+    }
+}
+
+/// コメントを削除する
+#[utoipa::path(
+    delete,
+    path = "/todos/{id}/comments/{comment_id}",
+    tag = "comments",
+    params(
+        ("id" = u32, Path, description = "TODOのID"),
+        ("comment_id" = u32, Path, description = "コメントのID")
+    ),
+    responses(
+        (status = 204, description = "削除した"),
+        (status = 404, description = "コメントが見つからない", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "想定外のエラー", body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+pub async fn delete_comment(
+    State(state): State<AppState>,
+    PathParams((todo_id, id)): PathParams<(u32, u32)>,
+) -> Result<StatusCode, AppError> {
+    info!(todo_id, comment_id = id, "deleting comment");
+    match delete_comment_usecase::execute(state.comments.as_ref(), todo_id, id).await {
+        Ok(true) => {
+            info!(todo_id, comment_id = id, "comment deleted");
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Ok(false) => {
+            warn!(todo_id, comment_id = id, "comment not found");
+            Err(AppError::NotFound)
+        }
+        Err(e) => {
+            error!(todo_id, comment_id = id, error = ?e, "repository error");
+            Err(e)
+        }
+    }
+}
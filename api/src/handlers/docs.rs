@@ -0,0 +1,30 @@
+use crate::openapi::api_doc;
+use axum::{response::Html, Json};
+
+/// Swagger UIはCDNから読み込み、`/openapi.json` を表示する
+const SWAGGER_UI_HTML: &str = r##"<!DOCTYPE html>
+<html lang="ja">
+<head>
+  <meta charset="utf-8" />
+  <title>Rust Todo App API</title>
+  <link rel="stylesheet" href="https://unpkg.com/swagger-ui-dist@5/swagger-ui.css" />
+</head>
+<body>
+  <div id="swagger-ui"></div>
+  <script src="https://unpkg.com/swagger-ui-dist@5/swagger-ui-bundle.js" crossorigin></script>
+  <script>
+    window.onload = () => {
+      window.ui = SwaggerUIBundle({ url: "/openapi.json", dom_id: "#swagger-ui" });
+    };
+  </script>
+</body>
+</html>
+"##;
+
+pub async fn openapi_json() -> Json<utoipa::openapi::OpenApi> {
+    Json(api_doc())
+}
+
+pub async fn swagger_ui() -> Html<&'static str> {
+    Html(SWAGGER_UI_HTML)
+}
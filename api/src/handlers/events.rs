@@ -0,0 +1,83 @@
+use std::convert::Infallible;
+use std::time::Duration;
+
+use crate::infrastructure::events::broadcaster::StreamEvent;
+use crate::presentation::dto::v1::event_responses::TodoEventData;
+use crate::state::AppState;
+use axum::{
+    extract::State,
+    http::HeaderMap,
+    response::sse::{Event, KeepAlive, Sse},
+};
+use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
+use tokio_stream::{Stream, StreamExt};
+use tracing::{info, warn};
+
+/// 接続が切れていないことを知らせるコメント行の送信間隔
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(15);
+
+/// TODOの変更をServer-Sent Eventsで流す。
+/// `Last-Event-ID` 付きで再接続すると、それ以降のイベントを再送してから続きを流す
+#[utoipa::path(
+    get,
+    path = "/events",
+    tag = "events",
+    params(
+        ("Last-Event-ID" = Option<String>, Header, description = "最後に受け取ったイベントのID（`<起動時刻>-<通し番号>`）")
+    ),
+    responses(
+        (status = 200, description = "`todo.created` などのイベントと `resync` を流す", body = String, content_type = "text/event-stream")
+    )
+)]
+pub async fn stream_events(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|value| value.to_str().ok());
+    info!(last_event_id = ?last_event_id, "event stream client connected");
+
+    let subscription = state.event_stream.subscribe(last_event_id);
+    if subscription.missed {
+        warn!(
+            last_event_id = ?last_event_id,
+            "events are no longer buffered, asking client to resync"
+        );
+    }
+
+    let backlog: Vec<Event> = subscription
+        .missed
+        .then(resync_event)
+        .into_iter()
+        .chain(subscription.replay.into_iter().map(todo_event))
+        .collect();
+    let live = BroadcastStream::new(subscription.receiver).map(|received| match received {
+        Ok(event) => todo_event(event),
+        Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+            warn!(skipped, "event stream client lagged behind");
+            resync_event()
+        }
+    });
+
+    let stream = tokio_stream::iter(backlog).chain(live).map(Ok);
+    Sse::new(stream).keep_alive(
+        KeepAlive::new()
+            .interval(HEARTBEAT_INTERVAL)
+            .text("heartbeat"),
+    )
+}
+
+fn todo_event(stream_event: StreamEvent) -> Event {
+    let name = stream_event.event.name();
+    Event::default()
+        .id(stream_event.id.to_string())
+        .event(name)
+        .json_data(TodoEventData::from(stream_event.event))
+        .expect("todo event is always serializable")
+}
+
+// 取りこぼしがあったので、クライアントに一覧を取り直してもらう
+fn resync_event() -> Event {
+    Event::default().event("resync").data("{}")
+}
@@ -0,0 +1,106 @@
+use std::str::FromStr;
+
+use crate::application::errors::{AppError, ErrorMessage};
+use crate::presentation::extract::JsonBody;
+use crate::presentation::graphql::{request_data, TodoSchema};
+use crate::presentation::request_context;
+use crate::state::AppState;
+use async_graphql::http::{
+    GraphiQLSource, WebSocketProtocols as Protocols, WsMessage, ALL_WEBSOCKET_PROTOCOLS,
+};
+use axum::{
+    extract::{
+        ws::{CloseFrame, Message, WebSocket, WebSocketUpgrade},
+        State,
+    },
+    http::{header, HeaderMap},
+    response::{Html, Response},
+    Extension, Json,
+};
+use futures_util::{future, SinkExt, StreamExt};
+use tracing::{info, warn};
+
+/// GraphQLのクエリ・ミューテーションを実行する。
+/// エラーはGraphQLの作法どおり200の `errors` で返す（リクエストが読めない場合だけproblem+json）
+pub async fn graphql(
+    State(state): State<AppState>,
+    Extension(schema): Extension<TodoSchema>,
+    JsonBody(mut request): JsonBody<async_graphql::Request>,
+) -> Json<async_graphql::Response> {
+    info!(operation = ?request.operation_name, "executing graphql operation");
+    request.data = request_data(&state, request_context::current_lang());
+    Json(schema.execute(request).await)
+}
+
+/// ブラウザで試すためのGraphiQL。本体はCDNから読み込む
+pub async fn graphiql() -> Html<String> {
+    Html(
+        GraphiQLSource::build()
+            .endpoint("/graphql")
+            .subscription_endpoint("/graphql/ws")
+            .finish(),
+    )
+}
+
+/// サブスクリプション用のWebSocket。`graphql-transport-ws` と旧来の `graphql-ws` に対応する
+pub async fn graphql_ws(
+    ws: WebSocketUpgrade,
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Extension(schema): Extension<TodoSchema>,
+) -> Result<Response, AppError> {
+    let protocol = headers
+        .get(header::SEC_WEBSOCKET_PROTOCOL)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| {
+            value
+                .split(',')
+                .find_map(|protocol| Protocols::from_str(protocol.trim()).ok())
+        });
+    let Some(protocol) = protocol else {
+        warn!("missing or unsupported Sec-WebSocket-Protocol");
+        return Err(AppError::validation(
+            ErrorMessage::new("graphql_ws_protocol_required")
+                .with("expected", ALL_WEBSOCKET_PROTOCOLS.join(", ")),
+        ));
+    };
+    info!(protocol = ?protocol, "upgrading graphql connection");
+    let data = request_data(&state, request_context::current_lang());
+    Ok(ws
+        .protocols(ALL_WEBSOCKET_PROTOCOLS)
+        .on_upgrade(move |socket| run_session(socket, schema, protocol, data)))
+}
+
+async fn run_session(
+    socket: WebSocket,
+    schema: TodoSchema,
+    protocol: Protocols,
+    data: async_graphql::Data,
+) {
+    let (mut sink, stream) = socket.split();
+    let input = stream
+        .take_while(|received| future::ready(received.is_ok()))
+        .filter_map(|received| {
+            future::ready(match received {
+                Ok(Message::Text(text)) => Some(text.into_bytes()),
+                Ok(Message::Binary(bytes)) => Some(bytes),
+                _ => None,
+            })
+        });
+    let mut output =
+        async_graphql::http::WebSocket::new(schema, input, protocol).connection_data(data);
+
+    while let Some(message) = output.next().await {
+        let message = match message {
+            WsMessage::Text(text) => Message::Text(text),
+            WsMessage::Close(code, reason) => Message::Close(Some(CloseFrame {
+                code,
+                reason: reason.into(),
+            })),
+        };
+        if sink.send(message).await.is_err() {
+            break;
+        }
+    }
+    info!("graphql connection closed");
+}
@@ -0,0 +1,87 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use tracing::warn;
+
+use crate::infrastructure::health::HealthChecker;
+use crate::presentation::dto::health_responses::{
+    CheckResponse, HealthQuery, LivenessResponse, ReadinessResponse,
+};
+use crate::presentation::extract::QueryParams;
+
+/// プロセスが応答できるかだけを返す。依存先は見ないので、失敗したら再起動の合図になる
+#[utoipa::path(
+    get,
+    path = "/healthz",
+    tag = "meta",
+    params(HealthQuery),
+    responses(
+        (status = 200, description = "稼働中。`?verbose` ならJSON", body = LivenessResponse)
+    )
+)]
+pub async fn healthz(
+    State(checker): State<Arc<HealthChecker>>,
+    QueryParams(query): QueryParams<HealthQuery>,
+) -> Response {
+    if !query.is_verbose() {
+        return "ok".into_response();
+    }
+    Json(LivenessResponse {
+        status: "ok".to_string(),
+        uptime_secs: checker.uptime().as_secs(),
+    })
+    .into_response()
+}
+
+/// リクエストを受けられる状態かを返す。失敗したらトラフィックを流さない合図になる
+#[utoipa::path(
+    get,
+    path = "/readyz",
+    tag = "meta",
+    params(HealthQuery),
+    responses(
+        (status = 200, description = "すべての確認が通った。`?verbose` ならJSON", body = ReadinessResponse),
+        (status = 503, description = "失敗した確認がある。`?verbose` ならJSON", body = ReadinessResponse)
+    )
+)]
+pub async fn readyz(
+    State(checker): State<Arc<HealthChecker>>,
+    QueryParams(query): QueryParams<HealthQuery>,
+) -> Response {
+    let checks = checker.check().await;
+    let failed: Vec<String> = checks
+        .iter()
+        .filter(|check| !check.healthy)
+        .map(|check| check.name.clone())
+        .collect();
+    let status = if failed.is_empty() {
+        StatusCode::OK
+    } else {
+        warn!(failed = %failed.join(", "), "not ready");
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    if !query.is_verbose() {
+        let body = if failed.is_empty() {
+            "ok".to_string()
+        } else {
+            format!("unavailable: {}", failed.join(", "))
+        };
+        return (status, body).into_response();
+    }
+    let body = ReadinessResponse {
+        status: if failed.is_empty() {
+            "ok"
+        } else {
+            "unavailable"
+        }
+        .to_string(),
+        checks: checks.into_iter().map(CheckResponse::from).collect(),
+    };
+    (status, Json(body)).into_response()
+}
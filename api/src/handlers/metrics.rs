@@ -0,0 +1,21 @@
+use std::sync::Arc;
+
+use axum::{extract::State, http::header, response::IntoResponse};
+
+use crate::infrastructure::metrics::Metrics;
+
+/// Prometheusのテキスト形式でメトリクスを返す
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    tag = "meta",
+    responses(
+        (status = 200, description = "Prometheusのテキスト形式のメトリクス", body = String, content_type = "text/plain; version=0.0.4")
+    )
+)]
+pub async fn metrics(State(metrics): State<Arc<Metrics>>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, prometheus::TEXT_FORMAT)],
+        metrics.render().await,
+    )
+}
@@ -0,0 +1,134 @@
+use crate::application::errors::{AppError, ErrorMessage};
+use crate::application::usecases::reminder::{
+    create as create_reminder_usecase, delete as delete_reminder_usecase,
+    list as list_reminders_usecase,
+};
+use crate::domain::entities::reminder::ReminderSchedule;
+use crate::presentation::dto::v1::reminder_requests::CreateReminderRequest;
+use crate::presentation::dto::v1::reminder_responses::ReminderResponse;
+use crate::state::AppState;
+use axum::{extract::State, http::StatusCode, Json};
+use tracing::{error, info, warn};
+use validator::Validate;
+
+use crate::presentation::extract::{JsonBody, PathParams};
+
+/// TODOのリマインダーを取得する
+#[utoipa::path(
+    get,
+    path = "/todos/{id}/reminders",
+    tag = "reminders",
+    params(
+        ("id" = u32, Path, description = "TODOのID")
+    ),
+    responses(
+        (status = 200, description = "リマインダー一覧", body = [ReminderResponse]),
+        (status = 404, description = "TODOが見つからない", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "想定外のエラー", body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+pub async fn list_reminders(
+    State(state): State<AppState>,
+    PathParams(todo_id): PathParams<u32>,
+) -> Result<Json<Vec<ReminderResponse>>, AppError> {
+    info!(todo_id, "listing reminders");
+    match list_reminders_usecase::execute(state.todos.as_ref(), state.reminders.as_ref(), todo_id)
+        .await
+    {
+        Ok(reminders) => Ok(Json(reminders.into_iter().map(Into::into).collect())),
+        Err(e) => {
+            warn!(todo_id, error = ?e, "failed to list reminders");
+            Err(e)
+        }
+    }
+}
+
+/// リマインダーを作成する
+#[utoipa::path(
+    post,
+    path = "/todos/{id}/reminders",
+    tag = "reminders",
+    params(
+        ("id" = u32, Path, description = "TODOのID"),
+        ("Idempotency-Key" = Option<String>, Header, description = "再送しても一度だけ処理するためのキー（255文字以内）")
+    ),
+    request_body = CreateReminderRequest,
+    responses(
+        (status = 201, description = "作成したリマインダー", body = ReminderResponse),
+        (status = 400, description = "入力エラー", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "TODOが見つからない", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 409, description = "同じIdempotency-Keyのリクエストを処理中", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Idempotency-Keyが別の内容のリクエストで使われている", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "想定外のエラー", body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+pub async fn create_reminder(
+    State(state): State<AppState>,
+    PathParams(todo_id): PathParams<u32>,
+    JsonBody(payload): JsonBody<CreateReminderRequest>,
+) -> Result<(StatusCode, Json<ReminderResponse>), AppError> {
+    info!(todo_id, "creating reminder");
+    if let Err(errors) = payload.validate() {
+        warn!(todo_id, errors = ?errors, "validation failed");
+        return Err(errors.into());
+    }
+    let schedule = match (payload.remind_at, payload.offset()) {
+        (Some(at), _) => ReminderSchedule::At(at),
+        (None, Some(offset)) => ReminderSchedule::BeforeDue(offset),
+        (None, None) => {
+            return Err(AppError::validation(ErrorMessage::new(
+                "reminder_schedule_required",
+            )))
+        }
+    };
+    match create_reminder_usecase::execute(
+        state.todos.as_ref(),
+        state.reminders.as_ref(),
+        todo_id,
+        schedule,
+    )
+    .await
+    {
+        Ok(reminder) => {
+            info!(todo_id, reminder_id = reminder.id, "reminder created");
+            Ok((StatusCode::CREATED, Json(reminder.into())))
+        }
+        Err(e) => {
+            warn!(todo_id, error = ?e, "failed to create reminder");
+            Err(e)
+        }
+    }
+}
+
+/// リマインダーを削除する
+#[utoipa::path(
+    delete,
+    path = "/todos/{id}/reminders/{reminder_id}",
+    tag = "reminders",
+    params(
+        ("id" = u32, Path, description = "TODOのID"),
+        ("reminder_id" = u32, Path, description = "リマインダーのID")
+    ),
+    responses(
+        (status = 204, description = "削除した"),
+        (status = 404, description = "リマインダーが見つからない", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "想定外のエラー", body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+pub async fn delete_reminder(
+    State(state): State<AppState>,
+    PathParams((todo_id, id)): PathParams<(u32, u32)>,
+) -> Result<StatusCode, AppError> {
+    info!(todo_id, reminder_id = id, "deleting reminder");
+    match delete_reminder_usecase::execute(state.reminders.as_ref(), todo_id, id).await {
+        Ok(true) => Ok(StatusCode::NO_CONTENT),
+        Ok(false) => {
+            warn!(todo_id, reminder_id = id, "reminder not found");
+            Err(AppError::NotFound)
+        }
+        Err(e) => {
+            error!(todo_id, reminder_id = id, error = ?e, "repository error");
+            Err(e)
+        }
+    }
+}
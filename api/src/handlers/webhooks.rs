@@ -0,0 +1,246 @@
+use crate::application::errors::AppError;
+use crate::application::ports::webhook_repository::WebhookPatch;
+use crate::application::usecases::webhook::{
+    create as create_webhook_usecase, delete as delete_webhook_usecase, get as get_webhook_usecase,
+    list as list_webhooks_usecase, list_deliveries as list_deliveries_usecase,
+    update as update_webhook_usecase,
+};
+use crate::presentation::dto::v1::webhook_requests::{
+    CreateWebhookRequest, DeliveryListQuery, UpdateWebhookRequest,
+};
+use crate::presentation::dto::v1::webhook_responses::{
+    WebhookDeliveryPageResponse, WebhookResponse,
+};
+use crate::state::AppState;
+use axum::{extract::State, http::StatusCode, Json};
+use tracing::{error, info, warn};
+use validator::Validate;
+
+use crate::presentation::extract::{JsonBody, PathParams, QueryParams};
+
+/// 登録済みのWebhookを取得する
+#[utoipa::path(
+    get,
+    path = "/webhooks",
+    tag = "webhooks",
+    responses(
+        (status = 200, description = "Webhook一覧", body = [WebhookResponse]),
+        (status = 500, description = "想定外のエラー", body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+pub async fn list_webhooks(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<WebhookResponse>>, AppError> {
+    info!("listing webhooks");
+    match list_webhooks_usecase::execute(state.webhooks.as_ref()).await {
+        Ok(webhooks) => Ok(Json(webhooks.into_iter().map(Into::into).collect())),
+        Err(e) => {
+            error!(error = ?e, "repository error");
+            Err(e)
+        }
+    }
+}
+
+/// Webhookを登録する
+#[utoipa::path(
+    post,
+    path = "/webhooks",
+    tag = "webhooks",
+    params(
+        ("Idempotency-Key" = Option<String>, Header, description = "再送しても一度だけ処理するためのキー（255文字以内）")
+    ),
+    request_body = CreateWebhookRequest,
+    responses(
+        (status = 201, description = "登録したWebhook", body = WebhookResponse),
+        (status = 400, description = "入力エラー", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 409, description = "同じIdempotency-Keyのリクエストを処理中", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Idempotency-Keyが別の内容のリクエストで使われている", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "想定外のエラー", body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+pub async fn create_webhook(
+    State(state): State<AppState>,
+    JsonBody(payload): JsonBody<CreateWebhookRequest>,
+) -> Result<(StatusCode, Json<WebhookResponse>), AppError> {
+    info!(url = %payload.url, "creating webhook");
+    if let Err(errors) = payload.validate() {
+        warn!(errors = ?errors, "validation failed");
+        return Err(errors.into());
+    }
+    match create_webhook_usecase::execute(
+        state.webhooks.as_ref(),
+        payload.url,
+        payload.secret,
+        payload.events,
+    )
+    .await
+    {
+        Ok(webhook) => {
+            info!(webhook_id = webhook.id, "webhook created");
+            Ok((StatusCode::CREATED, Json(webhook.into())))
+        }
+        Err(e) => {
+            warn!(error = ?e, "failed to create webhook");
+            Err(e)
+        }
+    }
+}
+
+/// Webhookを1件取得する
+#[utoipa::path(
+    get,
+    path = "/webhooks/{id}",
+    tag = "webhooks",
+    params(
+        ("id" = u32, Path, description = "WebhookのID")
+    ),
+    responses(
+        (status = 200, description = "Webhook", body = WebhookResponse),
+        (status = 404, description = "Webhookが見つからない", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "想定外のエラー", body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+pub async fn get_webhook(
+    State(state): State<AppState>,
+    PathParams(id): PathParams<u32>,
+) -> Result<Json<WebhookResponse>, AppError> {
+    info!(webhook_id = id, "fetching webhook");
+    match get_webhook_usecase::execute(state.webhooks.as_ref(), id).await {
+        Ok(webhook) => Ok(Json(webhook.into())),
+        Err(e) => {
+            warn!(webhook_id = id, error = ?e, "failed to fetch webhook");
+            Err(e)
+        }
+    }
+}
+
+/// Webhookを更新する
+#[utoipa::path(
+    put,
+    path = "/webhooks/{id}",
+    tag = "webhooks",
+    params(
+        ("id" = u32, Path, description = "WebhookのID")
+    ),
+    request_body = UpdateWebhookRequest,
+    responses(
+        (status = 200, description = "更新後のWebhook", body = WebhookResponse),
+        (status = 400, description = "入力エラー", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Webhookが見つからない", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "想定外のエラー", body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+pub async fn update_webhook(
+    State(state): State<AppState>,
+    PathParams(id): PathParams<u32>,
+    JsonBody(payload): JsonBody<UpdateWebhookRequest>,
+) -> Result<Json<WebhookResponse>, AppError> {
+    info!(webhook_id = id, "updating webhook");
+    if let Err(errors) = payload.validate() {
+        warn!(webhook_id = id, errors = ?errors, "validation failed");
+        return Err(errors.into());
+    }
+    let patch = WebhookPatch {
+        url: payload.url,
+        secret: payload.secret,
+        events: payload.events,
+    };
+    match update_webhook_usecase::execute(state.webhooks.as_ref(), id, patch).await {
+        Ok(webhook) => {
+            info!(webhook_id = id, "webhook updated");
+            Ok(Json(webhook.into()))
+        }
+        Err(e) => {
+            warn!(webhook_id = id, error = ?e, "failed to update webhook");
+            Err(e)
+        }
+    }
+}
+
+/// Webhookを削除する
+#[utoipa::path(
+    delete,
+    path = "/webhooks/{id}",
+    tag = "webhooks",
+    params(
+        ("id" = u32, Path, description = "WebhookのID")
+    ),
+    responses(
+        (status = 204, description = "削除した"),
+        (status = 404, description = "Webhookが見つからない", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "想定外のエラー", body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+pub async fn delete_webhook(
+    State(state): State<AppState>,
+    PathParams(id): PathParams<u32>,
+) -> Result<StatusCode, AppError> {
+    info!(webhook_id = id, "deleting webhook");
+    match delete_webhook_usecase::execute(state.webhooks.as_ref(), id).await {
+        Ok(true) => {
+            info!(webhook_id = id, "webhook deleted");
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Ok(false) => {
+            warn!(webhook_id = id, "webhook not found");
+            Err(AppError::NotFound)
+        }
+        Err(e) => {
+            error!(webhook_id = id, error = ?e, "repository error");
+            Err(e)
+        }
+    }
+}
+
+/// Webhookの配信履歴を新しい順に取得する
+#[utoipa::path(
+    get,
+    path = "/webhooks/{id}/deliveries",
+    tag = "webhooks",
+    params(
+        ("id" = u32, Path, description = "WebhookのID"),
+        DeliveryListQuery
+    ),
+    responses(
+        (status = 200, description = "配信履歴の1ページ分", body = WebhookDeliveryPageResponse),
+        (status = 400, description = "入力エラー", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Webhookが見つからない", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "想定外のエラー", body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+pub async fn list_webhook_deliveries(
+    State(state): State<AppState>,
+    PathParams(id): PathParams<u32>,
+    QueryParams(query): QueryParams<DeliveryListQuery>,
+) -> Result<Json<WebhookDeliveryPageResponse>, AppError> {
+    info!(webhook_id = id, "listing deliveries");
+    let page = query.page.unwrap_or(1);
+    let per_page = query
+        .per_page
+        .unwrap_or(list_deliveries_usecase::DEFAULT_PER_PAGE);
+    match list_deliveries_usecase::execute(
+        state.webhooks.as_ref(),
+        state.webhook_outbox.as_ref(),
+        id,
+        page,
+        per_page,
+    )
+    .await
+    {
+        Ok(page) => {
+            info!(
+                webhook_id = id,
+                count = page.deliveries.len(),
+                total = page.total,
+                "returned deliveries"
+            );
+            Ok(Json(page.into()))
+        }
+        Err(e) => {
+            warn!(webhook_id = id, error = ?e, "failed to list deliveries");
+            Err(e)
+        }
+    }
+}
+
+// NotFound はTODOではなくWebhookが見つからない意味で返す
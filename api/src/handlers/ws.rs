@@ -0,0 +1,352 @@
+use std::time::Duration;
+
+use crate::application::errors::{AppError, ErrorMessage};
+use crate::application::usecases::todo::edit_lock::{self, Editor};
+use crate::application::usecases::todo::{
+    create as create_todo_usecase, delete as delete_todo_usecase, get as get_todo_usecase,
+    reorder as reorder_todos_usecase, update as update_todo_usecase,
+};
+use crate::config::RouteGroup;
+use crate::infrastructure::events::broadcaster::StreamEvent;
+use crate::presentation::dto::v1::event_responses::TodoEventData;
+use crate::presentation::dto::v1::todo_requests::{CreateTodoRequest, UpdateTodoRequest};
+use crate::presentation::dto::v1::todo_responses::TodoResponse;
+use crate::presentation::dto::v1::ws_messages::{
+    ClientEnvelope, ClientMessage, ErrorCode, LockMessage, ServerMessage, WsConnectQuery,
+};
+use crate::state::AppState;
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        State,
+    },
+    response::Response,
+};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tracing::{error, info, warn};
+use validator::Validate;
+
+use crate::presentation::extract::QueryParams;
+use crate::presentation::i18n::{self, Lang};
+use crate::presentation::request_context;
+
+type MessageResult = Result<serde_json::Value, (ErrorCode, String)>;
+
+/// 期限切れの編集ロックを片付ける間隔
+const LOCK_SWEEP_INTERVAL: Duration = Duration::from_secs(5);
+const MAX_USER_CHARS: usize = 100;
+
+/// 共同編集用のWebSocketへ接続する
+#[utoipa::path(
+    get,
+    path = "/ws",
+    tag = "events",
+    params(
+        ("user" = String, Query, description = "表示名（1〜100文字）")
+    ),
+    responses(
+        (status = 101, description = "WebSocketへ切り替えた"),
+        (status = 400, description = "userが不正", body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+pub async fn ws_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<AppState>,
+    QueryParams(query): QueryParams<WsConnectQuery>,
+) -> Result<Response, AppError> {
+    let user = query
+        .user
+        .map(|user| user.trim().to_string())
+        .filter(|user| !user.is_empty() && user.chars().count() <= MAX_USER_CHARS);
+    let Some(user) = user else {
+        warn!("missing or invalid user");
+        return Err(AppError::validation(
+            ErrorMessage::new("ws_user_required").with("max", MAX_USER_CHARS),
+        ));
+    };
+    info!(user = %user, "upgrading connection");
+    // 接続中のエラーメッセージは接続時の Accept-Language に合わせる
+    let lang = request_context::current_lang();
+    Ok(ws.on_upgrade(move |socket| run_session(socket, state, user, lang)))
+}
+
+struct Session {
+    state: AppState,
+    connection_id: u64,
+    lang: Lang,
+    /// `subscribe` するまでは None
+    events: Option<broadcast::Receiver<StreamEvent>>,
+}
+
+async fn run_session(mut socket: WebSocket, state: AppState, user: String, lang: Lang) {
+    let presence = state.presence.clone();
+    let connection_id = presence.join(user.clone());
+    let mut presence_changes = presence.subscribe_changes();
+    let mut sweep = tokio::time::interval(LOCK_SWEEP_INTERVAL);
+    let mut session = Session {
+        state,
+        connection_id,
+        lang,
+        events: None,
+    };
+    info!(connection_id, user = %user, "ws connected");
+
+    let welcome = ServerMessage::Welcome {
+        connection_id,
+        user: user.clone(),
+    };
+    if send(&mut socket, &welcome).await {
+        loop {
+            let outgoing = tokio::select! {
+                incoming = socket.recv() => match incoming {
+                    Some(Ok(Message::Text(text))) => session.handle_text(&text).await,
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    // Ping への Pong はaxumが返す。バイナリは扱わない
+                    Some(Ok(_)) => continue,
+                },
+                received = next_event(&mut session.events) => match received {
+                    Ok(event) => vec![event_message(event)],
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!(connection_id, skipped, "ws client lagged behind");
+                        vec![ServerMessage::Resync]
+                    }
+                    Err(RecvError::Closed) => break,
+                },
+                changed = presence_changes.recv() => match changed {
+                    Ok(()) | Err(RecvError::Lagged(_)) if session.events.is_some() => {
+                        vec![presence.snapshot().into()]
+                    }
+                    Err(RecvError::Closed) => break,
+                    _ => continue,
+                },
+                _ = sweep.tick() => {
+                    presence.sweep();
+                    continue;
+                }
+            };
+            let mut delivered = true;
+            for message in &outgoing {
+                delivered = send(&mut socket, message).await;
+                if !delivered {
+                    break;
+                }
+            }
+            if !delivered {
+                break;
+            }
+        }
+    }
+
+    presence.leave(connection_id);
+    info!(connection_id, user = %user, "ws disconnected");
+}
+
+impl Session {
+    async fn handle_text(&mut self, text: &str) -> Vec<ServerMessage> {
+        let envelope: ClientEnvelope = match serde_json::from_str(text) {
+            Ok(envelope) => envelope,
+            Err(e) => {
+                warn!(connection_id = self.connection_id, error = %e, "invalid ws message");
+                return vec![ServerMessage::Error {
+                    request_id: None,
+                    code: ErrorCode::InvalidMessage,
+                    message: e.to_string(),
+                }];
+            }
+        };
+        let request_id = envelope.request_id;
+
+        let mut replies = Vec::new();
+        let result = match envelope.message {
+            ClientMessage::Ping => return vec![ServerMessage::Pong { request_id }],
+            ClientMessage::Subscribe => {
+                self.events = Some(self.state.event_stream.subscribe(None).receiver);
+                replies.push(self.state.presence.snapshot().into());
+                Ok(serde_json::Value::Null)
+            }
+            ClientMessage::Unsubscribe => {
+                self.events = None;
+                Ok(serde_json::Value::Null)
+            }
+            ClientMessage::View { todo_id } => {
+                self.state.presence.view(self.connection_id, todo_id);
+                Ok(serde_json::Value::Null)
+            }
+            ClientMessage::Lock { todo_id } => self.lock(todo_id).await,
+            ClientMessage::Unlock { todo_id } => {
+                let released = self.state.presence.unlock(self.connection_id, todo_id);
+                Ok(serde_json::json!({ "released": released }))
+            }
+            ClientMessage::CreateTodo { todo } => self.create_todo(todo).await,
+            ClientMessage::UpdateTodo { id, changes } => self.update_todo(id, changes).await,
+            ClientMessage::DeleteTodo { id } => self.delete_todo(id).await,
+            ClientMessage::ReorderTodos { ids } => reorder_todos_usecase::execute(
+                self.state.todos.as_ref(),
+                self.state.events.as_ref(),
+                ids,
+                self.state.limits.route(RouteGroup::Realtime).max_list_len,
+            )
+            .await
+            .map(|()| serde_json::Value::Null)
+            .map_err(|e| app_error(self.lang, e)),
+        };
+
+        let reply = match result {
+            Ok(result) => ServerMessage::Ack { request_id, result },
+            Err((code, message)) => {
+                warn!(
+                    connection_id = self.connection_id,
+                    request_id = ?request_id,
+                    message = %message,
+                    "ws request failed"
+                );
+                ServerMessage::Error {
+                    request_id,
+                    code,
+                    message,
+                }
+            }
+        };
+        // ack を先に返し、その後に在席状況などを続ける
+        replies.insert(0, reply);
+        replies
+    }
+
+    async fn lock(&self, todo_id: i64) -> MessageResult {
+        self.ensure_todo_exists(todo_id).await?;
+        match self.state.presence.lock(self.connection_id, todo_id) {
+            Ok(lock) => Ok(to_json(LockMessage::from(lock))),
+            Err(held) => Err(app_error(self.lang, edit_lock::locked(&held))),
+        }
+    }
+
+    async fn create_todo(&self, request: CreateTodoRequest) -> MessageResult {
+        if let Err(errors) = request.validate() {
+            return Err(app_error(self.lang, errors.into()));
+        }
+        let todo = create_todo_usecase::execute(
+            self.state.todos.as_ref(),
+            self.state.events.as_ref(),
+            request.title,
+        )
+        .await
+        .map_err(|e| app_error(self.lang, e))?;
+        info!(
+            connection_id = self.connection_id,
+            todo_id = todo.id,
+            "todo created"
+        );
+        Ok(to_json(TodoResponse::from(todo)))
+    }
+
+    async fn update_todo(&self, id: u32, changes: UpdateTodoRequest) -> MessageResult {
+        if let Err(errors) = changes.validate() {
+            return Err(app_error(self.lang, errors.into()));
+        }
+        match update_todo_usecase::execute(
+            self.state.todos.as_ref(),
+            self.state.events.as_ref(),
+            Editor::connection(self.state.presence.as_ref(), self.connection_id),
+            id,
+            changes.title,
+            changes.completed,
+            changes.due_at,
+        )
+        .await
+        .map_err(|e| app_error(self.lang, e))?
+        {
+            Some(todo) => Ok(to_json(TodoResponse::from(todo))),
+            None => Err(todo_not_found(self.lang, id as i64)),
+        }
+    }
+
+    async fn delete_todo(&self, id: u32) -> MessageResult {
+        let deleted = delete_todo_usecase::execute(
+            self.state.todos.as_ref(),
+            self.state.attachments.as_ref(),
+            self.state.blobs.as_ref(),
+            self.state.events.as_ref(),
+            Editor::connection(self.state.presence.as_ref(), self.connection_id),
+            id,
+        )
+        .await
+        .map_err(|e| app_error(self.lang, e))?;
+        if !deleted {
+            return Err(todo_not_found(self.lang, id as i64));
+        }
+        self.state.presence.unlock(self.connection_id, id as i64);
+        Ok(serde_json::Value::Null)
+    }
+
+    async fn ensure_todo_exists(&self, todo_id: i64) -> Result<(), (ErrorCode, String)> {
+        let id = u32::try_from(todo_id).map_err(|_| todo_not_found(self.lang, todo_id))?;
+        match get_todo_usecase::execute(self.state.todos.as_ref(), id)
+            .await
+            .map_err(|e| app_error(self.lang, e))?
+        {
+            Some(_) => Ok(()),
+            None => Err(todo_not_found(self.lang, todo_id)),
+        }
+    }
+}
+
+async fn next_event(
+    events: &mut Option<broadcast::Receiver<StreamEvent>>,
+) -> Result<StreamEvent, RecvError> {
+    match events {
+        Some(receiver) => receiver.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
+fn event_message(stream_event: StreamEvent) -> ServerMessage {
+    ServerMessage::Event {
+        id: stream_event.id.to_string(),
+        event: stream_event.event.name().to_string(),
+        data: TodoEventData::from(stream_event.event),
+    }
+}
+
+/// 送信に失敗したら（切断済みなら）false
+async fn send(socket: &mut WebSocket, message: &ServerMessage) -> bool {
+    let text = match serde_json::to_string(message) {
+        Ok(text) => text,
+        Err(e) => {
+            error!(error = %e, "failed to serialize ws message");
+            return true;
+        }
+    };
+    socket.send(Message::Text(text)).await.is_ok()
+}
+
+fn to_json(value: impl serde::Serialize) -> serde_json::Value {
+    serde_json::to_value(value).unwrap_or(serde_json::Value::Null)
+}
+
+fn app_error(lang: Lang, error: AppError) -> (ErrorCode, String) {
+    let code = match &error {
+        AppError::Validation(_) | AppError::InvalidFields(_) => ErrorCode::ValidationFailed,
+        AppError::NotFound => ErrorCode::NotFound,
+        AppError::Conflict(_) => ErrorCode::Locked,
+        e => {
+            error!(error = ?e, "repository error");
+            ErrorCode::Internal
+        }
+    };
+    let message = match &error {
+        AppError::InvalidFields(fields) => fields
+            .iter()
+            .map(|field| i18n::render_field(lang, field))
+            .collect::<Vec<_>>()
+            .join(", "),
+        _ => error.localized_message(lang),
+    };
+    (code, message)
+}
+
+fn todo_not_found(lang: Lang, id: i64) -> (ErrorCode, String) {
+    (
+        ErrorCode::NotFound,
+        i18n::render(lang, &ErrorMessage::new("ws_todo_not_found").with("id", id)),
+    )
+}
@@ -0,0 +1,274 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use tokio::sync::broadcast;
+
+use crate::application::ports::event_publisher::EventPublisher;
+use crate::domain::events::TodoEvent;
+
+pub const DEFAULT_REPLAY_CAPACITY: usize = 1024;
+
+/// イベントのID。`<起動時刻（ミリ秒）>-<通し番号>` の形で送る。
+/// 通し番号は起動ごとに1から振り直すので、起動時刻の違うIDは前の起動のものとして扱う
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventId {
+    pub epoch: u64,
+    pub seq: u64,
+}
+
+impl EventId {
+    /// `Last-Event-ID` などで受け取った値を読む。形が違えば `None`
+    pub fn parse(value: &str) -> Option<Self> {
+        let (epoch, seq) = value.trim().split_once('-')?;
+        Some(Self {
+            epoch: epoch.parse().ok()?,
+            seq: seq.parse().ok()?,
+        })
+    }
+}
+
+impl fmt::Display for EventId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.epoch, self.seq)
+    }
+}
+
+/// IDを振ったイベント。通し番号はプロセス内で単調増加する
+#[derive(Debug, Clone)]
+pub struct StreamEvent {
+    pub id: EventId,
+    pub event: TodoEvent,
+}
+
+/// 購読開始時点で取りこぼしを埋めるためのイベントと、以降のイベントの受信口
+pub struct EventSubscription {
+    pub replay: Vec<StreamEvent>,
+    /// 再送バッファに残っていない分があり、クライアントは全件を取り直す必要がある
+    pub missed: bool,
+    pub receiver: broadcast::Receiver<StreamEvent>,
+}
+
+struct Inner {
+    next_id: u64,
+    buffer: VecDeque<StreamEvent>,
+    /// `close` 後は `None`。送信側がなくなると受信側のストリームは終わる
+    sender: Option<broadcast::Sender<StreamEvent>>,
+}
+
+/// TODOの変更をSSEなどの接続中のクライアントへ配る。
+/// 直近のイベントを一定数だけ保持し、再接続時に `Last-Event-ID` 以降を再送できるようにする
+pub struct EventBroadcaster {
+    inner: Mutex<Inner>,
+    replay_capacity: usize,
+    epoch: u64,
+}
+
+impl EventBroadcaster {
+    pub fn new(replay_capacity: usize) -> Self {
+        let replay_capacity = replay_capacity.max(1);
+        let (sender, _) = broadcast::channel(replay_capacity);
+        Self {
+            inner: Mutex::new(Inner {
+                next_id: 1,
+                buffer: VecDeque::with_capacity(replay_capacity),
+                sender: Some(sender),
+            }),
+            replay_capacity,
+            epoch: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_millis() as u64),
+        }
+    }
+
+    /// `last_event_id`（クライアントが最後に受け取ったID）より後のイベントを再送分として返し、
+    /// 以降のイベントを購読する。読めないIDや前の起動のIDは取りこぼしとして扱う
+    pub fn subscribe(&self, last_event_id: Option<&str>) -> EventSubscription {
+        // 発行と同じロックの中で購読するので、再送分とライブ分の間に抜けや重複が出ない
+        let inner = self.inner.lock().expect("failed to lock event buffer");
+        let receiver = match &inner.sender {
+            Some(sender) => sender.subscribe(),
+            // 閉じたあとの購読は、すぐに終わる受信口を返す
+            None => broadcast::channel(1).1,
+        };
+        let Some(last_event_id) = last_event_id else {
+            return EventSubscription {
+                replay: Vec::new(),
+                missed: false,
+                receiver,
+            };
+        };
+
+        let latest_seq = inner.next_id - 1;
+        let oldest_seq = inner.buffer.front().map_or(inner.next_id, |e| e.id.seq);
+        // 未来の番号は来ないはずの値。古すぎる番号はバッファから溢れている
+        let last_seq = match EventId::parse(last_event_id) {
+            Some(id)
+                if id.epoch == self.epoch && id.seq <= latest_seq && id.seq + 1 >= oldest_seq =>
+            {
+                id.seq
+            }
+            _ => {
+                return EventSubscription {
+                    replay: Vec::new(),
+                    missed: true,
+                    receiver,
+                }
+            }
+        };
+
+        EventSubscription {
+            replay: inner
+                .buffer
+                .iter()
+                .filter(|e| e.id.seq > last_seq)
+                .cloned()
+                .collect(),
+            missed: false,
+            receiver,
+        }
+    }
+
+    /// 配信を終える。購読中のSSE・WebSocket・gRPCなどのストリームは残りを受け取ったあと終わる。
+    /// サーバの終了時に、長く続く接続が終わるのを待たなくて済むようにする
+    pub fn close(&self) {
+        let mut inner = self.inner.lock().expect("failed to lock event buffer");
+        inner.sender = None;
+    }
+}
+
+impl Default for EventBroadcaster {
+    fn default() -> Self {
+        Self::new(DEFAULT_REPLAY_CAPACITY)
+    }
+}
+
+#[async_trait]
+impl EventPublisher for EventBroadcaster {
+    async fn publish(&self, event: TodoEvent) {
+        let mut inner = self.inner.lock().expect("failed to lock event buffer");
+        let stream_event = StreamEvent {
+            id: EventId {
+                epoch: self.epoch,
+                seq: inner.next_id,
+            },
+            event,
+        };
+        inner.next_id += 1;
+        if inner.buffer.len() == self.replay_capacity {
+            inner.buffer.pop_front();
+        }
+        inner.buffer.push_back(stream_event.clone());
+        // 購読者がいない場合や閉じたあとは送らなくてよい
+        if let Some(sender) = &inner.sender {
+            let _ = sender.send(stream_event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EventBroadcaster, EventId};
+    use crate::application::ports::event_publisher::EventPublisher;
+    use crate::domain::events::TodoEvent;
+
+    async fn publish_deletes(broadcaster: &EventBroadcaster, count: i64) {
+        for id in 1..=count {
+            broadcaster.publish(TodoEvent::Deleted { id }).await;
+        }
+    }
+
+    fn id(broadcaster: &EventBroadcaster, seq: u64) -> String {
+        EventId {
+            epoch: broadcaster.epoch,
+            seq,
+        }
+        .to_string()
+    }
+
+    #[test]
+    fn event_ids_round_trip() {
+        let id = EventId { epoch: 17, seq: 4 };
+        assert_eq!(id.to_string(), "17-4");
+        assert_eq!(EventId::parse("17-4"), Some(id));
+        assert_eq!(EventId::parse("4"), None);
+        assert_eq!(EventId::parse("17-x"), None);
+    }
+
+    #[tokio::test]
+    async fn live_subscribers_receive_increasing_ids() {
+        let broadcaster = EventBroadcaster::new(8);
+        let mut subscription = broadcaster.subscribe(None);
+
+        publish_deletes(&broadcaster, 2).await;
+
+        assert_eq!(subscription.receiver.recv().await.unwrap().id.seq, 1);
+        assert_eq!(subscription.receiver.recv().await.unwrap().id.seq, 2);
+        assert!(subscription.replay.is_empty());
+    }
+
+    #[tokio::test]
+    async fn resume_replays_events_after_last_id() {
+        let broadcaster = EventBroadcaster::new(8);
+        publish_deletes(&broadcaster, 3).await;
+
+        let subscription = broadcaster.subscribe(Some(&id(&broadcaster, 1)));
+
+        let seqs: Vec<u64> = subscription.replay.iter().map(|e| e.id.seq).collect();
+        assert_eq!(seqs, vec![2, 3]);
+        assert!(!subscription.missed);
+    }
+
+    #[tokio::test]
+    async fn resume_beyond_buffer_requires_resync() {
+        let broadcaster = EventBroadcaster::new(2);
+        publish_deletes(&broadcaster, 5).await;
+
+        // 3までは溢れているので、2からの再開は取りこぼしになる
+        assert!(broadcaster.subscribe(Some(&id(&broadcaster, 2))).missed);
+        assert_eq!(
+            broadcaster
+                .subscribe(Some(&id(&broadcaster, 3)))
+                .replay
+                .len(),
+            2
+        );
+        // まだ振っていない番号も取り直しになる
+        assert!(broadcaster.subscribe(Some(&id(&broadcaster, 99))).missed);
+    }
+
+    #[tokio::test]
+    async fn ids_from_another_boot_require_resync() {
+        let broadcaster = EventBroadcaster::new(8);
+        publish_deletes(&broadcaster, 3).await;
+
+        // 前の起動の番号は、今の起動の番号と重なっていても再送しない
+        let previous_boot = EventId {
+            epoch: broadcaster.epoch - 1,
+            seq: 1,
+        };
+        assert!(
+            broadcaster
+                .subscribe(Some(&previous_boot.to_string()))
+                .missed
+        );
+        // 形の違うIDも取り直しになる
+        assert!(broadcaster.subscribe(Some("1")).missed);
+    }
+
+    #[tokio::test]
+    async fn close_ends_subscriptions() {
+        let broadcaster = EventBroadcaster::new(8);
+        let mut subscription = broadcaster.subscribe(None);
+        publish_deletes(&broadcaster, 1).await;
+
+        broadcaster.close();
+
+        // 閉じる前のイベントは受け取れる
+        assert_eq!(subscription.receiver.recv().await.unwrap().id.seq, 1);
+        assert!(subscription.receiver.recv().await.is_err());
+        assert!(broadcaster.subscribe(None).receiver.recv().await.is_err());
+    }
+}
@@ -0,0 +1,26 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::application::ports::event_publisher::EventPublisher;
+use crate::domain::events::TodoEvent;
+
+/// 同じイベントを複数の配信先へ順に流す EventPublisher
+pub struct FanoutPublisher {
+    publishers: Vec<Arc<dyn EventPublisher>>,
+}
+
+impl FanoutPublisher {
+    pub fn new(publishers: Vec<Arc<dyn EventPublisher>>) -> Self {
+        Self { publishers }
+    }
+}
+
+#[async_trait]
+impl EventPublisher for FanoutPublisher {
+    async fn publish(&self, event: TodoEvent) {
+        for publisher in &self.publishers {
+            publisher.publish(event.clone()).await;
+        }
+    }
+}
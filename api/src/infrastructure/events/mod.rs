@@ -0,0 +1,2 @@
+pub mod broadcaster;
+pub mod fanout_publisher;
@@ -0,0 +1,106 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use sqlx::sqlite::SqlitePool;
+
+use crate::infrastructure::persistence::schema::missing_schema_objects;
+use crate::infrastructure::workers::monitor::{WorkerMonitor, WorkerState};
+
+/// データベースの確認にかける時間の上限。オーケストレータのプローブ（既定1秒）より短くしておく
+pub const DATABASE_CHECK_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// 1つの確認項目の結果
+#[derive(Debug, Clone)]
+pub struct CheckResult {
+    /// `database` / `schema` / `worker:<タスク名>`
+    pub name: String,
+    pub healthy: bool,
+    pub latency: Duration,
+    /// 失敗の理由や補足
+    pub detail: Option<String>,
+}
+
+/// `/readyz` の確認項目をまとめて実行する
+pub struct HealthChecker {
+    pool: SqlitePool,
+    workers: Arc<WorkerMonitor>,
+    started_at: Instant,
+    database_timeout: Duration,
+}
+
+impl HealthChecker {
+    pub fn new(pool: SqlitePool, workers: Arc<WorkerMonitor>) -> Self {
+        Self {
+            pool,
+            workers,
+            started_at: Instant::now(),
+            database_timeout: DATABASE_CHECK_TIMEOUT,
+        }
+    }
+
+    pub fn uptime(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+
+    /// データベースへの疎通、テーブルの作成、バックグラウンドタスクの稼働を確認する
+    pub async fn check(&self) -> Vec<CheckResult> {
+        let mut results = vec![self.check_database().await, self.check_schema().await];
+        results.extend(self.check_workers());
+        results
+    }
+
+    async fn check_database(&self) -> CheckResult {
+        let started = Instant::now();
+        let result = tokio::time::timeout(
+            self.database_timeout,
+            sqlx::query_scalar::<_, i64>("SELECT 1").fetch_one(&self.pool),
+        )
+        .await;
+        let detail = match result {
+            Ok(Ok(_)) => None,
+            Ok(Err(e)) => Some(e.to_string()),
+            Err(_) => Some(format!("no response within {:?}", self.database_timeout)),
+        };
+        CheckResult {
+            name: "database".to_string(),
+            healthy: detail.is_none(),
+            latency: started.elapsed(),
+            detail,
+        }
+    }
+
+    async fn check_schema(&self) -> CheckResult {
+        let started = Instant::now();
+        let result =
+            tokio::time::timeout(self.database_timeout, missing_schema_objects(&self.pool)).await;
+        let detail = match result {
+            Ok(Ok(missing)) if missing.is_empty() => None,
+            Ok(Ok(missing)) => Some(format!("missing {}", missing.join(", "))),
+            Ok(Err(e)) => Some(e.to_string()),
+            Err(_) => Some(format!("no response within {:?}", self.database_timeout)),
+        };
+        CheckResult {
+            name: "schema".to_string(),
+            healthy: detail.is_none(),
+            latency: started.elapsed(),
+            detail,
+        }
+    }
+
+    fn check_workers(&self) -> impl Iterator<Item = CheckResult> {
+        self.workers.statuses().into_iter().map(|status| {
+            let since = status.since_last_beat.as_secs();
+            let (healthy, detail) = match status.state {
+                WorkerState::Running => (true, format!("last run {}s ago", since)),
+                WorkerState::Stalled => (false, format!("no run for {}s", since)),
+                WorkerState::Stopped => (false, "stopped".to_string()),
+            };
+            CheckResult {
+                name: format!("worker:{}", status.name),
+                healthy,
+                latency: Duration::ZERO,
+                detail: Some(detail),
+            }
+        })
+    }
+}
@@ -0,0 +1,84 @@
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::application::errors::AppError;
+use crate::application::ports::todo_repository::{TodoFilter, TodoPage, TodoRepository, TodoSort};
+use crate::domain::entities::todo::Todo;
+use crate::infrastructure::metrics::Metrics;
+
+/// 呼び出しごとの所要時間とエラーを記録する TodoRepository。中身の実装はそのまま呼ぶ
+pub struct MeteredTodoRepository {
+    inner: Arc<dyn TodoRepository>,
+    metrics: Arc<Metrics>,
+}
+
+impl MeteredTodoRepository {
+    pub fn new(inner: Arc<dyn TodoRepository>, metrics: Arc<Metrics>) -> Self {
+        Self { inner, metrics }
+    }
+
+    async fn observe<T>(
+        &self,
+        method: &str,
+        call: impl Future<Output = Result<T, AppError>>,
+    ) -> Result<T, AppError> {
+        let started = Instant::now();
+        let result = call.await;
+        self.metrics
+            .observe_repository(method, started.elapsed(), result.is_err());
+        result
+    }
+}
+
+#[async_trait]
+impl TodoRepository for MeteredTodoRepository {
+    async fn create(&self, title: String) -> Result<Todo, AppError> {
+        self.observe("create", self.inner.create(title)).await
+    }
+
+    async fn get_all(&self) -> Result<Vec<Todo>, AppError> {
+        self.observe("get_all", self.inner.get_all()).await
+    }
+
+    async fn get_by_id(&self, id: u32) -> Result<Option<Todo>, AppError> {
+        self.observe("get_by_id", self.inner.get_by_id(id)).await
+    }
+
+    async fn get_by_ids(&self, ids: &[u32]) -> Result<Vec<Todo>, AppError> {
+        self.observe("get_by_ids", self.inner.get_by_ids(ids)).await
+    }
+
+    async fn query(
+        &self,
+        filter: &TodoFilter,
+        sort: TodoSort,
+        limit: u32,
+        offset: u32,
+    ) -> Result<TodoPage, AppError> {
+        self.observe("query", self.inner.query(filter, sort, limit, offset))
+            .await
+    }
+
+    async fn update(
+        &self,
+        id: u32,
+        title: Option<String>,
+        completed: Option<bool>,
+        due_at: Option<Option<DateTime<Utc>>>,
+    ) -> Result<Option<Todo>, AppError> {
+        self.observe("update", self.inner.update(id, title, completed, due_at))
+            .await
+    }
+
+    async fn delete(&self, id: u32) -> Result<Option<Vec<String>>, AppError> {
+        self.observe("delete", self.inner.delete(id)).await
+    }
+
+    async fn reorder(&self, todo_ids: Vec<i64>) -> Result<(), AppError> {
+        self.observe("reorder", self.inner.reorder(todo_ids)).await
+    }
+}
@@ -0,0 +1,171 @@
+pub mod metered_todo_repo;
+
+use std::time::Duration;
+
+use prometheus::{
+    exponential_buckets, Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, Opts,
+    Registry, TextEncoder,
+};
+use sqlx::sqlite::SqlitePool;
+
+use crate::infrastructure::health::DATABASE_CHECK_TIMEOUT;
+
+/// `TodoRepository` のメソッド名。エラーが0件でも系列が出るよう、起動時に0で作っておく
+const TODO_REPOSITORY_METHODS: [&str; 8] = [
+    "create",
+    "get_all",
+    "get_by_id",
+    "get_by_ids",
+    "query",
+    "update",
+    "delete",
+    "reorder",
+];
+
+/// `/metrics` で公開するメトリクス。アプリごとにレジストリを持つ（テストで混ざらないように）
+pub struct Metrics {
+    registry: Registry,
+    http_requests: IntCounterVec,
+    http_request_duration: HistogramVec,
+    repository_duration: HistogramVec,
+    repository_errors: IntCounterVec,
+    pool_connections: IntGauge,
+    pool_idle_connections: IntGauge,
+    pool_max_connections: IntGauge,
+    todos: IntGauge,
+    todos_completed: IntGauge,
+    pool: SqlitePool,
+}
+
+impl Metrics {
+    pub fn new(pool: SqlitePool) -> Self {
+        let registry = Registry::new();
+
+        let http_requests = IntCounterVec::new(
+            Opts::new("http_requests_total", "HTTP requests by route template"),
+            &["method", "route", "status"],
+        )
+        .expect("valid metric");
+        let http_request_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "http_request_duration_seconds",
+                "Time until the response headers were ready",
+            ),
+            &["method", "route", "status"],
+        )
+        .expect("valid metric");
+        // SQLiteへの1回の問い合わせは速いので、0.5msから細かく刻む
+        let repository_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "todo_repository_duration_seconds",
+                "Latency of TodoRepository calls",
+            )
+            .buckets(exponential_buckets(0.0005, 2.0, 12).expect("valid buckets")),
+            &["method"],
+        )
+        .expect("valid metric");
+        let repository_errors = IntCounterVec::new(
+            Opts::new(
+                "todo_repository_errors_total",
+                "TodoRepository calls that returned an error",
+            ),
+            &["method"],
+        )
+        .expect("valid metric");
+        for method in TODO_REPOSITORY_METHODS {
+            repository_errors.with_label_values(&[method]);
+        }
+
+        let gauge = |name: &str, help: &str| IntGauge::new(name, help).expect("valid metric");
+        let pool_connections = gauge("sqlite_pool_connections", "Open database connections");
+        let pool_idle_connections =
+            gauge("sqlite_pool_idle_connections", "Idle database connections");
+        let pool_max_connections = gauge(
+            "sqlite_pool_max_connections",
+            "Maximum database connections",
+        );
+        let todos = gauge("todos", "Todos currently stored");
+        let todos_completed = gauge("todos_completed", "Todos marked as completed");
+
+        let collectors: [Box<dyn prometheus::core::Collector>; 9] = [
+            Box::new(http_requests.clone()),
+            Box::new(http_request_duration.clone()),
+            Box::new(repository_duration.clone()),
+            Box::new(repository_errors.clone()),
+            Box::new(pool_connections.clone()),
+            Box::new(pool_idle_connections.clone()),
+            Box::new(pool_max_connections.clone()),
+            Box::new(todos.clone()),
+            Box::new(todos_completed.clone()),
+        ];
+        for collector in collectors {
+            registry
+                .register(collector)
+                .expect("metric names are unique");
+        }
+
+        Self {
+            registry,
+            http_requests,
+            http_request_duration,
+            repository_duration,
+            repository_errors,
+            pool_connections,
+            pool_idle_connections,
+            pool_max_connections,
+            todos,
+            todos_completed,
+            pool,
+        }
+    }
+
+    /// `route` はパスのテンプレート（`/api/v1/todos/:id`）。IDごとに系列が増えないようにする
+    pub fn observe_http(&self, method: &str, route: &str, status: u16, elapsed: Duration) {
+        let status = status.to_string();
+        let labels = [method, route, status.as_str()];
+        self.http_requests.with_label_values(&labels).inc();
+        self.http_request_duration
+            .with_label_values(&labels)
+            .observe(elapsed.as_secs_f64());
+    }
+
+    pub fn observe_repository(&self, method: &str, elapsed: Duration, failed: bool) {
+        self.repository_duration
+            .with_label_values(&[method])
+            .observe(elapsed.as_secs_f64());
+        if failed {
+            self.repository_errors.with_label_values(&[method]).inc();
+        }
+    }
+
+    /// 接続プールとTODOの件数を読み直してから、Prometheusのテキスト形式で書き出す
+    pub async fn render(&self) -> String {
+        self.pool_connections.set(self.pool.size() as i64);
+        self.pool_idle_connections.set(self.pool.num_idle() as i64);
+        self.pool_max_connections
+            .set(self.pool.options().get_max_connections() as i64);
+        self.refresh_todo_counts().await;
+
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .expect("failed to encode metrics");
+        String::from_utf8(buffer).expect("metrics are valid UTF-8")
+    }
+
+    // 読めなかったときは前回の値のままにする
+    async fn refresh_todo_counts(&self) {
+        let query = sqlx::query_as::<_, (i64, i64)>(
+            "SELECT COUNT(*), COALESCE(SUM(completed), 0) FROM todos",
+        )
+        .fetch_one(&self.pool);
+        match tokio::time::timeout(DATABASE_CHECK_TIMEOUT, query).await {
+            Ok(Ok((total, completed))) => {
+                self.todos.set(total);
+                self.todos_completed.set(completed);
+            }
+            Ok(Err(e)) => tracing::warn!("metrics: failed to count todos: {}", e),
+            Err(_) => tracing::warn!("metrics: counting todos timed out"),
+        }
+    }
+}
@@ -1 +1,13 @@
+pub mod clock;
+pub mod events;
+pub mod health;
+pub mod metrics;
+pub mod notifiers;
 pub mod persistence;
+pub mod rate_limit;
+pub mod realtime;
+pub mod storage;
+pub mod telemetry;
+pub mod tls;
+pub mod webhooks;
+pub mod workers;
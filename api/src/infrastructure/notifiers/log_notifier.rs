@@ -0,0 +1,22 @@
+use async_trait::async_trait;
+
+use crate::application::errors::AppError;
+use crate::application::ports::notifier::{Notifier, ReminderNotification};
+
+/// 通知内容をログに出すだけの Notifier（通知先が未設定の場合のデフォルト）
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LogNotifier;
+
+#[async_trait]
+impl Notifier for LogNotifier {
+    async fn notify(&self, notification: &ReminderNotification) -> Result<(), AppError> {
+        tracing::info!(
+            reminder_id = notification.reminder_id,
+            todo_id = notification.todo_id,
+            due_at = ?notification.due_at,
+            "reminder: {}",
+            notification.todo_title
+        );
+        Ok(())
+    }
+}
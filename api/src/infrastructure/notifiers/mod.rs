@@ -0,0 +1,2 @@
+pub mod log_notifier;
+pub mod webhook_notifier;
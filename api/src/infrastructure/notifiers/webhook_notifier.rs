@@ -0,0 +1,49 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use crate::application::errors::AppError;
+use crate::application::ports::notifier::{Notifier, ReminderNotification};
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 通知内容をJSONで指定URLへPOSTする Notifier。2xx以外は失敗として再送対象にする
+#[derive(Clone)]
+pub struct WebhookNotifier {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl WebhookNotifier {
+    pub fn new(url: impl Into<String>) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(DEFAULT_TIMEOUT)
+            .build()
+            .expect("Failed to build HTTP client");
+        Self {
+            client,
+            url: url.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    async fn notify(&self, notification: &ReminderNotification) -> Result<(), AppError> {
+        let response = self
+            .client
+            .post(&self.url)
+            .json(notification)
+            .send()
+            .await
+            .map_err(|e| AppError::unexpected(format!("webhook request failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::unexpected(format!(
+                "webhook responded with {}",
+                response.status()
+            )));
+        }
+        Ok(())
+    }
+}
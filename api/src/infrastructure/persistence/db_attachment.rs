@@ -0,0 +1,29 @@
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+use crate::domain::entities::attachment::Attachment;
+
+#[derive(Debug, Clone, FromRow)]
+pub struct DbAttachment {
+    pub id: i64,
+    pub todo_id: i64,
+    pub filename: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub sha256: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<DbAttachment> for Attachment {
+    fn from(row: DbAttachment) -> Self {
+        Self {
+            id: row.id,
+            todo_id: row.todo_id,
+            filename: row.filename,
+            content_type: row.content_type,
+            size_bytes: row.size_bytes,
+            sha256: row.sha256,
+            created_at: row.created_at,
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+use crate::domain::entities::comment::Comment;
+
+#[derive(Debug, Clone, FromRow)]
+pub struct DbComment {
+    pub id: i64,
+    pub todo_id: i64,
+    pub author: String,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+    pub edited_at: Option<DateTime<Utc>>,
+}
+
+impl From<DbComment> for Comment {
+    fn from(row: DbComment) -> Self {
+        Self {
+            id: row.id,
+            todo_id: row.todo_id,
+            author: row.author,
+            body: row.body,
+            created_at: row.created_at,
+            edited_at: row.edited_at,
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use sqlx::FromRow;
+
+use crate::application::ports::idempotency_store::{Reservation, StoredResponse};
+
+#[derive(Debug, Clone, FromRow)]
+pub struct DbIdempotencyKey {
+    pub fingerprint: String,
+    /// 処理中は NULL
+    pub status: Option<i64>,
+    /// `[[名前, 値], ...]` のJSON
+    pub headers: Option<String>,
+    pub body: Option<Vec<u8>>,
+}
+
+impl From<DbIdempotencyKey> for Reservation {
+    fn from(row: DbIdempotencyKey) -> Self {
+        let response = row.status.map(|status| StoredResponse {
+            status: status as u16,
+            headers: row
+                .headers
+                .and_then(|headers| serde_json::from_str(&headers).ok())
+                .unwrap_or_default(),
+            body: row.body.unwrap_or_default(),
+        });
+        Reservation::Existing {
+            fingerprint: row.fingerprint,
+            response,
+        }
+    }
+}
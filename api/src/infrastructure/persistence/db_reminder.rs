@@ -0,0 +1,47 @@
+use chrono::{DateTime, Duration, Utc};
+use sqlx::FromRow;
+
+use crate::domain::entities::reminder::{Reminder, ReminderSchedule};
+
+#[derive(Debug, Clone, FromRow)]
+pub struct DbReminder {
+    pub id: i64,
+    pub todo_id: i64,
+    pub remind_at: Option<DateTime<Utc>>,
+    pub offset_seconds: Option<i64>,
+    pub fire_at: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+    pub attempts: i64,
+    pub last_error: Option<String>,
+}
+
+impl From<DbReminder> for Reminder {
+    fn from(row: DbReminder) -> Self {
+        // remind_at と offset_seconds はどちらか一方だけが入っている
+        let schedule = match (row.remind_at, row.offset_seconds) {
+            (Some(at), _) => ReminderSchedule::At(at),
+            (None, offset) => ReminderSchedule::BeforeDue(Duration::seconds(offset.unwrap_or(0))),
+        };
+        Self {
+            id: row.id,
+            todo_id: row.todo_id,
+            schedule,
+            fire_at: row
+                .fire_at
+                .and_then(|secs| DateTime::from_timestamp(secs, 0)),
+            created_at: row.created_at,
+            delivered_at: row.delivered_at,
+            attempts: row.attempts as u32,
+            last_error: row.last_error,
+        }
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct DbDueReminder {
+    #[sqlx(flatten)]
+    pub reminder: DbReminder,
+    pub todo_title: String,
+    pub todo_due_at: Option<DateTime<Utc>>,
+}
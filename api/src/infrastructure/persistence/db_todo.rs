@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use sqlx::FromRow;
 
 use crate::domain::entities::todo::Todo;
@@ -8,6 +9,7 @@ pub struct DbTodo {
     pub title: String,
     pub completed: bool,
     pub position: i64,
+    pub due_at: Option<DateTime<Utc>>,
 }
 
 impl From<DbTodo> for Todo {
@@ -17,6 +19,7 @@ impl From<DbTodo> for Todo {
             title: row.title,
             completed: row.completed,
             position: row.position,
+            due_at: row.due_at,
         }
     }
 }
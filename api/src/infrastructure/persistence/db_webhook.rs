@@ -0,0 +1,83 @@
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+use crate::domain::entities::webhook::{DeliveryStatus, Webhook, WebhookDelivery};
+
+#[derive(Debug, Clone, FromRow)]
+pub struct DbWebhook {
+    pub id: i64,
+    pub url: String,
+    pub secret: String,
+    /// カンマ区切り。空文字は全イベント
+    pub events: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<DbWebhook> for Webhook {
+    fn from(row: DbWebhook) -> Self {
+        Self {
+            id: row.id,
+            url: row.url,
+            secret: row.secret,
+            events: split_events(&row.events),
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+pub fn join_events(events: &[String]) -> String {
+    events.join(",")
+}
+
+fn split_events(events: &str) -> Vec<String> {
+    events
+        .split(',')
+        .filter(|e| !e.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct DbWebhookDelivery {
+    pub id: i64,
+    pub webhook_id: i64,
+    pub event: String,
+    pub payload: String,
+    pub status: String,
+    pub attempts: i64,
+    pub response_status: Option<i64>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub next_attempt_at: Option<i64>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+impl From<DbWebhookDelivery> for WebhookDelivery {
+    fn from(row: DbWebhookDelivery) -> Self {
+        Self {
+            id: row.id,
+            webhook_id: row.webhook_id,
+            event: row.event,
+            payload: row.payload,
+            status: DeliveryStatus::parse(&row.status).unwrap_or(DeliveryStatus::Failed),
+            attempts: row.attempts as u32,
+            response_status: row.response_status.map(|s| s as u16),
+            last_error: row.last_error,
+            created_at: row.created_at,
+            next_attempt_at: row
+                .next_attempt_at
+                .and_then(|secs| DateTime::from_timestamp(secs, 0)),
+            delivered_at: row.delivered_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct DbPendingDelivery {
+    #[sqlx(flatten)]
+    pub delivery: DbWebhookDelivery,
+    pub url: String,
+    pub secret: String,
+}
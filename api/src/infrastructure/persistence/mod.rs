@@ -1,2 +1,14 @@
+pub mod db_attachment;
+pub mod db_comment;
+pub mod db_idempotency;
+pub mod db_reminder;
 pub mod db_todo;
+pub mod db_webhook;
+pub mod schema;
+pub mod sqlite_attachment_repo;
+pub mod sqlite_comment_repo;
+pub mod sqlite_idempotency_store;
+pub mod sqlite_reminder_repo;
 pub mod sqlite_todo_repo;
+pub mod sqlite_webhook_outbox;
+pub mod sqlite_webhook_repo;
@@ -0,0 +1,235 @@
+use sqlx::sqlite::SqlitePool;
+use sqlx::Row;
+
+// アプリケーションが使うテーブルを作成する（既に存在する場合は何もしない）
+pub async fn create_tables(pool: &SqlitePool) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS todos (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            title TEXT NOT NULL,
+            completed BOOLEAN NOT NULL DEFAULT 0,
+            position INTEGER NOT NULL DEFAULT 0
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // 期限は後から追加した列なので、既存のDBにも足しておく
+    add_column_if_missing(pool, "todos", "due_at", "TEXT").await?;
+
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS comments (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            todo_id INTEGER NOT NULL,
+            author TEXT NOT NULL,
+            body TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            edited_at TEXT
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_comments_todo_id ON comments (todo_id, created_at, id)",
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS attachments (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            todo_id INTEGER NOT NULL,
+            filename TEXT NOT NULL,
+            content_type TEXT NOT NULL,
+            size_bytes INTEGER NOT NULL,
+            sha256 TEXT NOT NULL,
+            created_at TEXT NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_attachments_todo_id ON attachments (todo_id)")
+        .execute(pool)
+        .await?;
+
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_attachments_sha256 ON attachments (sha256)")
+        .execute(pool)
+        .await?;
+
+    // fire_at / next_attempt_at はUNIX秒。相対指定の fire_at は期限から計算した値を保持する
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS reminders (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            todo_id INTEGER NOT NULL,
+            remind_at TEXT,
+            offset_seconds INTEGER,
+            fire_at INTEGER,
+            created_at TEXT NOT NULL,
+            delivered_at TEXT,
+            attempts INTEGER NOT NULL DEFAULT 0,
+            last_error TEXT,
+            next_attempt_at INTEGER
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_reminders_pending ON reminders (delivered_at, fire_at)",
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_reminders_todo_id ON reminders (todo_id)")
+        .execute(pool)
+        .await?;
+
+    // events はカンマ区切りのイベント名。空文字なら全イベントを購読する
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS webhooks (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            url TEXT NOT NULL,
+            secret TEXT NOT NULL,
+            events TEXT NOT NULL DEFAULT '',
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Webhook配信のアウトボックス。next_attempt_at はUNIX秒
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS webhook_deliveries (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            webhook_id INTEGER NOT NULL,
+            event TEXT NOT NULL,
+            payload TEXT NOT NULL,
+            status TEXT NOT NULL,
+            attempts INTEGER NOT NULL DEFAULT 0,
+            response_status INTEGER,
+            last_error TEXT,
+            created_at TEXT NOT NULL,
+            next_attempt_at INTEGER,
+            delivered_at TEXT
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_pending \
+         ON webhook_deliveries (status, next_attempt_at)",
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_webhook_id \
+         ON webhook_deliveries (webhook_id, id)",
+    )
+    .execute(pool)
+    .await?;
+
+    // Idempotency-Key ごとの応答。status が NULL の間は処理中。created_at / expires_at はUNIX秒。
+    // token は予約ごとに振り、応答の保存と取り消しを予約したリクエスト自身に限る
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS idempotency_keys (
+            key TEXT PRIMARY KEY,
+            token TEXT,
+            fingerprint TEXT NOT NULL,
+            status INTEGER,
+            headers TEXT,
+            body BLOB,
+            created_at INTEGER NOT NULL,
+            expires_at INTEGER NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    add_column_if_missing(pool, "idempotency_keys", "token", "TEXT").await?;
+
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_idempotency_keys_expires_at \
+         ON idempotency_keys (expires_at)",
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// `create_tables` で作るテーブルと、後から足した列
+const REQUIRED_TABLES: [&str; 7] = [
+    "todos",
+    "comments",
+    "attachments",
+    "reminders",
+    "webhooks",
+    "webhook_deliveries",
+    "idempotency_keys",
+];
+const REQUIRED_COLUMNS: [(&str, &str); 2] = [("todos", "due_at"), ("idempotency_keys", "token")];
+
+// `create_tables` が済んでいるかを確かめ、足りないテーブルと列の名前を返す（空なら最新）
+pub async fn missing_schema_objects(pool: &SqlitePool) -> Result<Vec<String>, sqlx::Error> {
+    let tables: Vec<String> =
+        sqlx::query_scalar("SELECT name FROM sqlite_master WHERE type = 'table'")
+            .fetch_all(pool)
+            .await?;
+
+    let mut missing: Vec<String> = REQUIRED_TABLES
+        .iter()
+        .filter(|table| !tables.iter().any(|name| name == *table))
+        .map(|table| table.to_string())
+        .collect();
+    for (table, column) in REQUIRED_COLUMNS {
+        if tables.iter().any(|name| name == table) && !has_column(pool, table, column).await? {
+            missing.push(format!("{}.{}", table, column));
+        }
+    }
+    Ok(missing)
+}
+
+async fn has_column(pool: &SqlitePool, table: &str, column: &str) -> Result<bool, sqlx::Error> {
+    let columns = sqlx::query(&format!("PRAGMA table_info({})", table))
+        .fetch_all(pool)
+        .await?;
+    Ok(columns
+        .iter()
+        .any(|row| row.get::<String, _>("name") == column))
+}
+
+async fn add_column_if_missing(
+    pool: &SqlitePool,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> Result<(), sqlx::Error> {
+    if !has_column(pool, table, column).await? {
+        sqlx::query(&format!(
+            "ALTER TABLE {} ADD COLUMN {} {}",
+            table, column, definition
+        ))
+        .execute(pool)
+        .await?;
+    }
+    Ok(())
+}
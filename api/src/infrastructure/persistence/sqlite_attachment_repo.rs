@@ -0,0 +1,210 @@
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::application::errors::AppError;
+use crate::application::ports::attachment_repository::{AttachmentRepository, NewAttachment};
+use crate::application::ports::blob_storage::BlobStorage;
+use crate::domain::entities::attachment::Attachment;
+use crate::infrastructure::persistence::db_attachment::DbAttachment;
+use sqlx::sqlite::{SqliteConnection, SqlitePool};
+
+const RETURNING_COLUMNS: &str =
+    "id, todo_id, filename, content_type, size_bytes, sha256, created_at";
+const SELECT_COLUMNS: &str =
+    "SELECT id, todo_id, filename, content_type, size_bytes, sha256, created_at FROM attachments";
+
+#[derive(Clone)]
+pub struct AttachmentStore {
+    pool: SqlitePool,
+}
+
+impl AttachmentStore {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    async fn create_inner(
+        &self,
+        new: NewAttachment,
+        data: &[u8],
+        blobs: &dyn BlobStorage,
+    ) -> Result<Attachment, AppError> {
+        let created_at = Utc::now();
+        let mut tx = self.pool.begin().await.map_err(map_sqlx_error)?;
+
+        // 先に書き込んで書き込みのロックを取る。本体の保存が終わるまで同じ内容の削除を待たせる
+        let result = sqlx::query(
+            "INSERT INTO attachments (todo_id, filename, content_type, size_bytes, sha256, created_at) \
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(new.todo_id as i64)
+        .bind(&new.filename)
+        .bind(&new.content_type)
+        .bind(new.size_bytes)
+        .bind(&new.sha256)
+        .bind(created_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        let key = blobs.put(data).await?;
+        if key != new.sha256 {
+            return Err(AppError::unexpected(format!(
+                "blob key mismatch: expected {}, got {}",
+                new.sha256, key
+            )));
+        }
+
+        tx.commit().await.map_err(map_sqlx_error)?;
+
+        Ok(Attachment {
+            id: result.last_insert_rowid(),
+            todo_id: new.todo_id as i64,
+            filename: new.filename,
+            content_type: new.content_type,
+            size_bytes: new.size_bytes,
+            sha256: new.sha256,
+            created_at,
+        })
+    }
+
+    async fn list_by_todo_inner(&self, todo_id: u32) -> Result<Vec<Attachment>, AppError> {
+        let rows = sqlx::query_as::<_, DbAttachment>(&format!(
+            "{} WHERE todo_id = ? ORDER BY created_at ASC, id ASC",
+            SELECT_COLUMNS
+        ))
+        .bind(todo_id as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn get_by_id_inner(&self, todo_id: u32, id: u32) -> Result<Option<Attachment>, AppError> {
+        let row = sqlx::query_as::<_, DbAttachment>(&format!(
+            "{} WHERE todo_id = ? AND id = ?",
+            SELECT_COLUMNS
+        ))
+        .bind(todo_id as i64)
+        .bind(id as i64)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn delete_inner(
+        &self,
+        todo_id: u32,
+        id: u32,
+        blobs: &dyn BlobStorage,
+    ) -> Result<Option<Attachment>, AppError> {
+        let mut tx = self.pool.begin().await.map_err(map_sqlx_error)?;
+
+        let row = sqlx::query_as::<_, DbAttachment>(&format!(
+            "DELETE FROM attachments WHERE todo_id = ? AND id = ? RETURNING {}",
+            RETURNING_COLUMNS
+        ))
+        .bind(todo_id as i64)
+        .bind(id as i64)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(map_sqlx_error)?;
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let attachment: Attachment = row.into();
+
+        delete_unreferenced_blobs(&mut tx, blobs, [attachment.sha256.as_str()]).await?;
+        tx.commit().await.map_err(map_sqlx_error)?;
+
+        Ok(Some(attachment))
+    }
+
+    async fn delete_unreferenced_blobs_inner(
+        &self,
+        keys: &[String],
+        blobs: &dyn BlobStorage,
+    ) -> Result<(), AppError> {
+        if keys.is_empty() {
+            return Ok(());
+        }
+        let mut tx = self.pool.begin().await.map_err(map_sqlx_error)?;
+
+        // 何も消さない書き込みで先に書き込みのロックを取り、数えてから本体を消すまでの間に
+        // 同じ内容のアップロードが割り込まないようにする
+        sqlx::query("DELETE FROM attachments WHERE 0")
+            .execute(&mut *tx)
+            .await
+            .map_err(map_sqlx_error)?;
+        delete_unreferenced_blobs(&mut tx, blobs, keys.iter().map(String::as_str)).await?;
+        tx.commit().await.map_err(map_sqlx_error)?;
+
+        Ok(())
+    }
+}
+
+/// 削除した添付が参照していた本体のうち、もう参照されていないものを消す。
+/// 削除と同じトランザクション（書き込みのロックを持ったまま）で行うので、
+/// 同じ内容のアップロードと入れ違いになって、参照されている本体を消すことはない。
+/// コミットに失敗すると本体だけが消えるが、SQLiteでは書き込みのロックを持っていればまず起きない
+async fn delete_unreferenced_blobs<'a>(
+    conn: &mut SqliteConnection,
+    blobs: &dyn BlobStorage,
+    keys: impl IntoIterator<Item = &'a str>,
+) -> Result<(), AppError> {
+    for key in keys {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM attachments WHERE sha256 = ?")
+            .bind(key)
+            .fetch_one(&mut *conn)
+            .await
+            .map_err(map_sqlx_error)?;
+        if count == 0 {
+            blobs.delete(key).await?;
+        }
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl AttachmentRepository for AttachmentStore {
+    async fn create(
+        &self,
+        attachment: NewAttachment,
+        data: &[u8],
+        blobs: &dyn BlobStorage,
+    ) -> Result<Attachment, AppError> {
+        self.create_inner(attachment, data, blobs).await
+    }
+
+    async fn list_by_todo(&self, todo_id: u32) -> Result<Vec<Attachment>, AppError> {
+        self.list_by_todo_inner(todo_id).await
+    }
+
+    async fn get_by_id(&self, todo_id: u32, id: u32) -> Result<Option<Attachment>, AppError> {
+        self.get_by_id_inner(todo_id, id).await
+    }
+
+    async fn delete(
+        &self,
+        todo_id: u32,
+        id: u32,
+        blobs: &dyn BlobStorage,
+    ) -> Result<Option<Attachment>, AppError> {
+        self.delete_inner(todo_id, id, blobs).await
+    }
+
+    async fn delete_unreferenced_blobs(
+        &self,
+        keys: &[String],
+        blobs: &dyn BlobStorage,
+    ) -> Result<(), AppError> {
+        self.delete_unreferenced_blobs_inner(keys, blobs).await
+    }
+}
+
+fn map_sqlx_error(error: sqlx::Error) -> AppError {
+    AppError::unexpected(error.to_string())
+}
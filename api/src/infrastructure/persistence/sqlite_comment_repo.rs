@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::application::errors::AppError;
+use crate::application::ports::comment_repository::CommentRepository;
+use crate::domain::entities::comment::Comment;
+use crate::infrastructure::persistence::db_comment::DbComment;
+use sqlx::sqlite::SqlitePool;
+use sqlx::QueryBuilder;
+
+#[derive(Clone)]
+pub struct CommentStore {
+    pool: SqlitePool,
+}
+
+impl CommentStore {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    async fn create_inner(
+        &self,
+        todo_id: u32,
+        author: String,
+        body: String,
+    ) -> Result<Comment, AppError> {
+        let created_at = Utc::now();
+
+        let result = sqlx::query(
+            "INSERT INTO comments (todo_id, author, body, created_at) VALUES (?, ?, ?, ?)",
+        )
+        .bind(todo_id as i64)
+        .bind(&author)
+        .bind(&body)
+        .bind(created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(Comment {
+            id: result.last_insert_rowid(),
+            todo_id: todo_id as i64,
+            author,
+            body,
+            created_at,
+            edited_at: None,
+        })
+    }
+
+    async fn list_by_todo_inner(
+        &self,
+        todo_id: u32,
+        limit: u32,
+        offset: u32,
+    ) -> Result<(Vec<Comment>, u64), AppError> {
+        let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM comments WHERE todo_id = ?")
+            .bind(todo_id as i64)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        let rows = sqlx::query_as::<_, DbComment>(
+            "SELECT id, todo_id, author, body, created_at, edited_at FROM comments \
+             WHERE todo_id = ? ORDER BY created_at ASC, id ASC LIMIT ? OFFSET ?",
+        )
+        .bind(todo_id as i64)
+        .bind(limit as i64)
+        .bind(offset as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok((rows.into_iter().map(Into::into).collect(), total as u64))
+    }
+
+    async fn count_by_todos_inner(&self, todo_ids: &[u32]) -> Result<HashMap<u32, u64>, AppError> {
+        if todo_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let mut query =
+            QueryBuilder::new("SELECT todo_id, COUNT(*) FROM comments WHERE todo_id IN (");
+        let mut separated = query.separated(", ");
+        for todo_id in todo_ids {
+            separated.push_bind(*todo_id as i64);
+        }
+        separated.push_unseparated(") GROUP BY todo_id");
+
+        let rows: Vec<(i64, i64)> = query
+            .build_query_as()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(todo_id, count)| (todo_id as u32, count as u64))
+            .collect())
+    }
+
+    async fn get_by_id_inner(&self, todo_id: u32, id: u32) -> Result<Option<Comment>, AppError> {
+        let row = sqlx::query_as::<_, DbComment>(
+            "SELECT id, todo_id, author, body, created_at, edited_at FROM comments \
+             WHERE todo_id = ? AND id = ?",
+        )
+        .bind(todo_id as i64)
+        .bind(id as i64)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn update_inner(
+        &self,
+        todo_id: u32,
+        id: u32,
+        body: String,
+    ) -> Result<Option<Comment>, AppError> {
+        let mut comment = match self.get_by_id_inner(todo_id, id).await? {
+            Some(c) => c,
+            None => return Ok(None),
+        };
+
+        let edited_at = Utc::now();
+        sqlx::query("UPDATE comments SET body = ?, edited_at = ? WHERE id = ?")
+            .bind(&body)
+            .bind(edited_at)
+            .bind(id as i64)
+            .execute(&self.pool)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        comment.body = body;
+        comment.edited_at = Some(edited_at);
+        Ok(Some(comment))
+    }
+
+    async fn delete_inner(&self, todo_id: u32, id: u32) -> Result<bool, AppError> {
+        let result = sqlx::query("DELETE FROM comments WHERE todo_id = ? AND id = ?")
+            .bind(todo_id as i64)
+            .bind(id as i64)
+            .execute(&self.pool)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[async_trait]
+impl CommentRepository for CommentStore {
+    async fn create(
+        &self,
+        todo_id: u32,
+        author: String,
+        body: String,
+    ) -> Result<Comment, AppError> {
+        self.create_inner(todo_id, author, body).await
+    }
+
+    async fn list_by_todo(
+        &self,
+        todo_id: u32,
+        limit: u32,
+        offset: u32,
+    ) -> Result<(Vec<Comment>, u64), AppError> {
+        self.list_by_todo_inner(todo_id, limit, offset).await
+    }
+
+    async fn count_by_todos(&self, todo_ids: &[u32]) -> Result<HashMap<u32, u64>, AppError> {
+        self.count_by_todos_inner(todo_ids).await
+    }
+
+    async fn get_by_id(&self, todo_id: u32, id: u32) -> Result<Option<Comment>, AppError> {
+        self.get_by_id_inner(todo_id, id).await
+    }
+
+    async fn update(
+        &self,
+        todo_id: u32,
+        id: u32,
+        body: String,
+    ) -> Result<Option<Comment>, AppError> {
+        self.update_inner(todo_id, id, body).await
+    }
+
+    async fn delete(&self, todo_id: u32, id: u32) -> Result<bool, AppError> {
+        self.delete_inner(todo_id, id).await
+    }
+}
+
+fn map_sqlx_error(error: sqlx::Error) -> AppError {
+    AppError::unexpected(error.to_string())
+}
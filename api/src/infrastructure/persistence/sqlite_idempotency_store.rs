@@ -0,0 +1,131 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::sqlite::SqlitePool;
+
+use crate::application::errors::AppError;
+use crate::application::ports::idempotency_store::{IdempotencyStore, Reservation, StoredResponse};
+use crate::infrastructure::persistence::db_idempotency::DbIdempotencyKey;
+
+#[derive(Clone)]
+pub struct IdempotencyKeyStore {
+    pool: SqlitePool,
+}
+
+impl IdempotencyKeyStore {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    async fn reserve_inner(
+        &self,
+        key: &str,
+        fingerprint: &str,
+        now: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+        stale_before: DateTime<Utc>,
+    ) -> Result<Reservation, AppError> {
+        // 期限切れの行はついでにまとめて消す
+        sqlx::query(
+            "DELETE FROM idempotency_keys WHERE expires_at <= ? \
+             OR (key = ? AND status IS NULL AND created_at <= ?)",
+        )
+        .bind(now.timestamp())
+        .bind(key)
+        .bind(stale_before.timestamp())
+        .execute(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        // 同時に来た再送のうち、挿入できた1つだけが処理に進む
+        let token = uuid::Uuid::new_v4().to_string();
+        let inserted = sqlx::query(
+            "INSERT INTO idempotency_keys (key, token, fingerprint, created_at, expires_at) \
+             VALUES (?, ?, ?, ?, ?) ON CONFLICT (key) DO NOTHING",
+        )
+        .bind(key)
+        .bind(&token)
+        .bind(fingerprint)
+        .bind(now.timestamp())
+        .bind(expires_at.timestamp())
+        .execute(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+        if inserted.rows_affected() == 1 {
+            return Ok(Reservation::Reserved { token });
+        }
+
+        let row = sqlx::query_as::<_, DbIdempotencyKey>(
+            "SELECT fingerprint, status, headers, body FROM idempotency_keys WHERE key = ?",
+        )
+        .bind(key)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+        Ok(row.into())
+    }
+
+    async fn complete_inner(
+        &self,
+        key: &str,
+        token: &str,
+        response: &StoredResponse,
+    ) -> Result<(), AppError> {
+        let headers = serde_json::to_string(&response.headers)
+            .map_err(|e| AppError::unexpected(e.to_string()))?;
+        sqlx::query(
+            "UPDATE idempotency_keys SET status = ?, headers = ?, body = ? \
+             WHERE key = ? AND token = ? AND status IS NULL",
+        )
+        .bind(i64::from(response.status))
+        .bind(headers)
+        .bind(&response.body)
+        .bind(key)
+        .bind(token)
+        .execute(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+        Ok(())
+    }
+
+    async fn release_inner(&self, key: &str, token: &str) -> Result<(), AppError> {
+        sqlx::query("DELETE FROM idempotency_keys WHERE key = ? AND token = ? AND status IS NULL")
+            .bind(key)
+            .bind(token)
+            .execute(&self.pool)
+            .await
+            .map_err(map_sqlx_error)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl IdempotencyStore for IdempotencyKeyStore {
+    async fn reserve(
+        &self,
+        key: &str,
+        fingerprint: &str,
+        now: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+        stale_before: DateTime<Utc>,
+    ) -> Result<Reservation, AppError> {
+        self.reserve_inner(key, fingerprint, now, expires_at, stale_before)
+            .await
+    }
+
+    async fn complete(
+        &self,
+        key: &str,
+        token: &str,
+        response: &StoredResponse,
+    ) -> Result<(), AppError> {
+        self.complete_inner(key, token, response).await
+    }
+
+    async fn release(&self, key: &str, token: &str) -> Result<(), AppError> {
+        self.release_inner(key, token).await
+    }
+}
+
+fn map_sqlx_error(error: sqlx::Error) -> AppError {
+    AppError::unexpected(error.to_string())
+}
@@ -0,0 +1,192 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::application::errors::AppError;
+use crate::application::ports::reminder_repository::{DueReminder, ReminderRepository};
+use crate::domain::entities::reminder::{Reminder, ReminderSchedule};
+use crate::infrastructure::persistence::db_reminder::{DbDueReminder, DbReminder};
+use sqlx::sqlite::SqlitePool;
+
+const REMINDER_COLUMNS: &str = "r.id, r.todo_id, r.remind_at, r.offset_seconds, r.fire_at, \
+     r.created_at, r.delivered_at, r.attempts, r.last_error";
+
+#[derive(Clone)]
+pub struct ReminderStore {
+    pool: SqlitePool,
+}
+
+impl ReminderStore {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    async fn create_inner(
+        &self,
+        todo_id: u32,
+        schedule: ReminderSchedule,
+        fire_at: Option<DateTime<Utc>>,
+    ) -> Result<Reminder, AppError> {
+        let created_at = Utc::now();
+        let (remind_at, offset_seconds) = match schedule {
+            ReminderSchedule::At(at) => (Some(at), None),
+            ReminderSchedule::BeforeDue(offset) => (None, Some(offset.num_seconds())),
+        };
+
+        let result = sqlx::query(
+            "INSERT INTO reminders (todo_id, remind_at, offset_seconds, fire_at, created_at) \
+             VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(todo_id as i64)
+        .bind(remind_at)
+        .bind(offset_seconds)
+        .bind(fire_at.map(|at| at.timestamp()))
+        .bind(created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(Reminder {
+            id: result.last_insert_rowid(),
+            todo_id: todo_id as i64,
+            schedule,
+            fire_at,
+            created_at,
+            delivered_at: None,
+            attempts: 0,
+            last_error: None,
+        })
+    }
+
+    async fn list_by_todo_inner(&self, todo_id: u32) -> Result<Vec<Reminder>, AppError> {
+        let rows = sqlx::query_as::<_, DbReminder>(&format!(
+            "SELECT {} FROM reminders r WHERE r.todo_id = ? ORDER BY r.fire_at IS NULL, r.fire_at, r.id",
+            REMINDER_COLUMNS
+        ))
+        .bind(todo_id as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn delete_inner(&self, todo_id: u32, id: u32) -> Result<bool, AppError> {
+        let result = sqlx::query("DELETE FROM reminders WHERE todo_id = ? AND id = ?")
+            .bind(todo_id as i64)
+            .bind(id as i64)
+            .execute(&self.pool)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn due_inner(
+        &self,
+        now: DateTime<Utc>,
+        limit: u32,
+    ) -> Result<Vec<DueReminder>, AppError> {
+        let rows = sqlx::query_as::<_, DbDueReminder>(&format!(
+            "SELECT {}, t.title AS todo_title, t.due_at AS todo_due_at \
+             FROM reminders r JOIN todos t ON t.id = r.todo_id \
+             WHERE r.delivered_at IS NULL AND r.fire_at IS NOT NULL AND r.fire_at <= ? \
+             AND (r.next_attempt_at IS NULL OR r.next_attempt_at <= ?) \
+             ORDER BY r.fire_at, r.id LIMIT ?",
+            REMINDER_COLUMNS
+        ))
+        .bind(now.timestamp())
+        .bind(now.timestamp())
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| DueReminder {
+                reminder: row.reminder.into(),
+                todo_title: row.todo_title,
+                due_at: row.todo_due_at,
+            })
+            .collect())
+    }
+
+    async fn mark_delivered_inner(
+        &self,
+        id: i64,
+        delivered_at: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            "UPDATE reminders SET delivered_at = ?, attempts = attempts + 1, last_error = NULL, \
+             next_attempt_at = NULL WHERE id = ?",
+        )
+        .bind(delivered_at)
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(())
+    }
+
+    async fn mark_failed_inner(
+        &self,
+        id: i64,
+        error: String,
+        retry_at: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            "UPDATE reminders SET attempts = attempts + 1, last_error = ?, next_attempt_at = ? \
+             WHERE id = ?",
+        )
+        .bind(error)
+        .bind(retry_at.timestamp())
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ReminderRepository for ReminderStore {
+    async fn create(
+        &self,
+        todo_id: u32,
+        schedule: ReminderSchedule,
+        fire_at: Option<DateTime<Utc>>,
+    ) -> Result<Reminder, AppError> {
+        self.create_inner(todo_id, schedule, fire_at).await
+    }
+
+    async fn list_by_todo(&self, todo_id: u32) -> Result<Vec<Reminder>, AppError> {
+        self.list_by_todo_inner(todo_id).await
+    }
+
+    async fn delete(&self, todo_id: u32, id: u32) -> Result<bool, AppError> {
+        self.delete_inner(todo_id, id).await
+    }
+
+    async fn due(&self, now: DateTime<Utc>, limit: u32) -> Result<Vec<DueReminder>, AppError> {
+        self.due_inner(now, limit).await
+    }
+
+    async fn mark_delivered(&self, id: i64, delivered_at: DateTime<Utc>) -> Result<(), AppError> {
+        self.mark_delivered_inner(id, delivered_at).await
+    }
+
+    async fn mark_failed(
+        &self,
+        id: i64,
+        error: String,
+        retry_at: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        self.mark_failed_inner(id, error, retry_at).await
+    }
+}
+
+fn map_sqlx_error(error: sqlx::Error) -> AppError {
+    AppError::unexpected(error.to_string())
+}
@@ -1,78 +1,207 @@
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 
 use crate::application::errors::AppError;
-use crate::application::ports::todo_repository::TodoRepository;
+use crate::application::ports::clock::Clock;
+use crate::application::ports::todo_repository::{
+    SortDirection, TodoFilter, TodoPage, TodoRepository, TodoSort, TodoSortField,
+};
+use crate::application::usecases::webhook::enqueue_event;
 use crate::domain::entities::todo::Todo;
+use crate::domain::events::TodoEvent;
 use crate::infrastructure::persistence::db_todo::DbTodo;
-use sqlx::sqlite::SqlitePool;
+use crate::infrastructure::persistence::sqlite_webhook_outbox::insert_deliveries;
+use sqlx::sqlite::{Sqlite, SqliteConnection, SqlitePool};
+use sqlx::QueryBuilder;
+use tracing::Instrument;
 
 #[derive(Clone)]
 pub struct TodoStore {
     pool: SqlitePool,
+    /// 設定されていれば、変更と同じトランザクションでWebhookの配信も積む
+    webhook_clock: Option<Arc<dyn Clock>>,
 }
 
 impl TodoStore {
     pub fn new(pool: SqlitePool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            webhook_clock: None,
+        }
+    }
+
+    /// 変更を確定するトランザクションの中で、Webhookのアウトボックスにも配信を積む。
+    /// 変更だけが残って配信が漏れることも、配信だけが積まれることもない
+    pub fn with_webhook_outbox(pool: SqlitePool, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            pool,
+            webhook_clock: Some(clock),
+        }
+    }
+
+    async fn enqueue_webhooks(
+        &self,
+        conn: &mut SqliteConnection,
+        event: &TodoEvent,
+    ) -> Result<(), AppError> {
+        let Some(clock) = &self.webhook_clock else {
+            return Ok(());
+        };
+        let now = clock.now();
+        insert_deliveries(conn, event.name(), &enqueue_event::payload(event, now), now)
+            .await
+            .map_err(map_sqlx_error)?;
+        Ok(())
     }
 
     async fn create_inner(&self, title: String) -> Result<Todo, AppError> {
+        let mut tx = self.pool.begin().await.map_err(map_sqlx_error)?;
+
         // 最大positionを取得
-        let max_position: Option<i64> = sqlx::query_scalar("SELECT MAX(position) FROM todos")
-            .fetch_one(&self.pool)
+        let sql = "SELECT MAX(position) FROM todos";
+        let max_position: Option<i64> = sqlx::query_scalar(sql)
+            .fetch_one(&mut *tx)
+            .instrument(query_span(sql))
             .await
             .map_err(map_sqlx_error)?;
 
         let new_position = max_position.unwrap_or(0) + 1;
 
         // SQLiteではRETURNING句が使えないので、INSERT後に取得
-        let result = sqlx::query("INSERT INTO todos (title, completed, position) VALUES (?, ?, ?)")
+        let sql = "INSERT INTO todos (title, completed, position) VALUES (?, ?, ?)";
+        let result = sqlx::query(sql)
             .bind(&title)
             .bind(false)
             .bind(new_position)
-            .execute(&self.pool)
+            .execute(&mut *tx)
+            .instrument(query_span(sql))
             .await
             .map_err(map_sqlx_error)?;
 
         // 最後に挿入されたIDを取得
         let id = result.last_insert_rowid();
 
-        Ok(Todo {
+        let todo = Todo {
             id,
             title,
             completed: false,
             position: new_position,
-        })
+            due_at: None,
+        };
+        self.enqueue_webhooks(&mut tx, &TodoEvent::Created(todo.clone()))
+            .await?;
+
+        tx.commit().await.map_err(map_sqlx_error)?;
+
+        Ok(todo)
     }
 
     async fn get_all_inner(&self) -> Result<Vec<Todo>, AppError> {
-        let rows = sqlx::query_as::<_, DbTodo>(
-            "SELECT id, title, completed, position FROM todos ORDER BY position ASC",
-        )
-        .fetch_all(&self.pool)
-        .await
-        .map_err(map_sqlx_error)?;
+        let sql = "SELECT id, title, completed, position, due_at FROM todos ORDER BY position ASC";
+        let rows = sqlx::query_as::<_, DbTodo>(sql)
+            .fetch_all(&self.pool)
+            .instrument(query_span(sql))
+            .await
+            .map_err(map_sqlx_error)?;
 
         Ok(rows.into_iter().map(Into::into).collect())
     }
 
+    async fn query_inner(
+        &self,
+        filter: &TodoFilter,
+        sort: TodoSort,
+        limit: u32,
+        offset: u32,
+    ) -> Result<TodoPage, AppError> {
+        let mut count = QueryBuilder::new("SELECT COUNT(*) FROM todos");
+        push_filter(&mut count, filter);
+        let span = query_span(count.sql());
+        let total: i64 = count
+            .build_query_scalar()
+            .fetch_one(&self.pool)
+            .instrument(span)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        let mut query =
+            QueryBuilder::new("SELECT id, title, completed, position, due_at FROM todos");
+        push_filter(&mut query, filter);
+        let direction = match sort.direction {
+            SortDirection::Asc => "ASC",
+            SortDirection::Desc => "DESC",
+        };
+        // 同じ値のものはIDの昇順にする。期限なしは向きによらず末尾
+        let order = match sort.field {
+            TodoSortField::Position => format!("position {}", direction),
+            TodoSortField::Title => format!("title {}", direction),
+            TodoSortField::Id => format!("id {}", direction),
+            TodoSortField::DueAt => format!("due_at IS NULL, julianday(due_at) {}", direction),
+        };
+        query.push(format!(" ORDER BY {}, id ASC LIMIT ", order));
+        query.push_bind(i64::from(limit));
+        query.push(" OFFSET ");
+        query.push_bind(i64::from(offset));
+
+        let span = query_span(query.sql());
+        let rows = query
+            .build_query_as::<DbTodo>()
+            .fetch_all(&self.pool)
+            .instrument(span)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        Ok(TodoPage {
+            todos: rows.into_iter().map(Into::into).collect(),
+            total: total as u64,
+        })
+    }
+
     async fn get_by_id_inner(&self, id: u32) -> Result<Option<Todo>, AppError> {
-        let row = sqlx::query_as::<_, DbTodo>(
-            "SELECT id, title, completed, position FROM todos WHERE id = ?",
-        )
-        .bind(id as i64)
-        .fetch_optional(&self.pool)
-        .await
-        .map_err(map_sqlx_error)?;
+        let sql = "SELECT id, title, completed, position, due_at FROM todos WHERE id = ?";
+        let row = sqlx::query_as::<_, DbTodo>(sql)
+            .bind(id as i64)
+            .fetch_optional(&self.pool)
+            .instrument(query_span(sql))
+            .await
+            .map_err(map_sqlx_error)?;
 
         Ok(row.map(Into::into))
     }
 
+    async fn get_by_ids_inner(&self, ids: &[u32]) -> Result<Vec<Todo>, AppError> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut query = QueryBuilder::new(
+            "SELECT id, title, completed, position, due_at FROM todos WHERE id IN (",
+        );
+        let mut separated = query.separated(", ");
+        for id in ids {
+            separated.push_bind(*id as i64);
+        }
+        separated.push_unseparated(")");
+
+        let span = query_span(query.sql());
+        let rows = query
+            .build_query_as::<DbTodo>()
+            .fetch_all(&self.pool)
+            .instrument(span)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
     async fn update_inner(
         &self,
         id: u32,
         title: Option<String>,
         completed: Option<bool>,
+        due_at: Option<Option<DateTime<Utc>>>,
     ) -> Result<Option<Todo>, AppError> {
         let mut todo = match self.get_by_id_inner(id).await? {
             Some(t) => t,
@@ -85,37 +214,115 @@ impl TodoStore {
         if let Some(new_completed) = completed {
             todo.completed = new_completed;
         }
+        let due_changed = due_at.is_some_and(|new_due| new_due != todo.due_at);
+        if let Some(new_due) = due_at {
+            todo.due_at = new_due;
+        }
+
+        let mut tx = self.pool.begin().await.map_err(map_sqlx_error)?;
 
-        sqlx::query("UPDATE todos SET title = ?, completed = ? WHERE id = ?")
+        let sql = "UPDATE todos SET title = ?, completed = ?, due_at = ? WHERE id = ?";
+        sqlx::query(sql)
             .bind(&todo.title)
             .bind(todo.completed)
+            .bind(todo.due_at)
             .bind(id as i64)
-            .execute(&self.pool)
+            .execute(&mut *tx)
+            .instrument(query_span(sql))
             .await
             .map_err(map_sqlx_error)?;
 
+        // 期限からの相対指定のリマインダーは、期限が変わったら発火時刻を計算し直す
+        if due_changed {
+            let sql = "UPDATE reminders SET fire_at = ? - offset_seconds, next_attempt_at = NULL \
+                       WHERE todo_id = ? AND offset_seconds IS NOT NULL AND delivered_at IS NULL";
+            sqlx::query(sql)
+                .bind(todo.due_at.map(|d| d.timestamp()))
+                .bind(id as i64)
+                .execute(&mut *tx)
+                .instrument(query_span(sql))
+                .await
+                .map_err(map_sqlx_error)?;
+        }
+
+        self.enqueue_webhooks(&mut tx, &TodoEvent::Updated(todo.clone()))
+            .await?;
+
+        tx.commit().await.map_err(map_sqlx_error)?;
+
         Ok(Some(todo))
     }
 
-    async fn delete_inner(&self, id: u32) -> Result<bool, AppError> {
-        let result = sqlx::query("DELETE FROM todos WHERE id = ?")
+    async fn delete_inner(&self, id: u32) -> Result<Option<Vec<String>>, AppError> {
+        let mut tx = self.pool.begin().await.map_err(map_sqlx_error)?;
+
+        // TODOに紐づくコメント・リマインダー・添付のレコードも一緒に削除する。
+        // 添付の本体はロールバックで戻せないので、ここでは消さずにキーだけ返す
+        let sql = "DELETE FROM comments WHERE todo_id = ?";
+        sqlx::query(sql)
+            .bind(id as i64)
+            .execute(&mut *tx)
+            .instrument(query_span(sql))
+            .await
+            .map_err(map_sqlx_error)?;
+
+        let sql = "DELETE FROM reminders WHERE todo_id = ?";
+        sqlx::query(sql)
             .bind(id as i64)
-            .execute(&self.pool)
+            .execute(&mut *tx)
+            .instrument(query_span(sql))
             .await
             .map_err(map_sqlx_error)?;
 
-        Ok(result.rows_affected() > 0)
+        let sql = "DELETE FROM attachments WHERE todo_id = ? RETURNING sha256";
+        let keys: BTreeSet<String> = sqlx::query_scalar(sql)
+            .bind(id as i64)
+            .fetch_all(&mut *tx)
+            .instrument(query_span(sql))
+            .await
+            .map_err(map_sqlx_error)?
+            .into_iter()
+            .collect();
+
+        let sql = "DELETE FROM todos WHERE id = ?";
+        let result = sqlx::query(sql)
+            .bind(id as i64)
+            .execute(&mut *tx)
+            .instrument(query_span(sql))
+            .await
+            .map_err(map_sqlx_error)?;
+
+        let deleted = result.rows_affected() > 0;
+        if deleted {
+            self.enqueue_webhooks(&mut tx, &TodoEvent::Deleted { id: id as i64 })
+                .await?;
+        }
+
+        tx.commit().await.map_err(map_sqlx_error)?;
+
+        Ok(deleted.then(|| keys.into_iter().collect()))
     }
 
     async fn reorder_inner(&self, todo_ids: Vec<i64>) -> Result<(), AppError> {
+        // 途中で止まっても並び順が半端に残らないよう、まとめて反映する
+        let mut tx = self.pool.begin().await.map_err(map_sqlx_error)?;
+
+        let sql = "UPDATE todos SET position = ? WHERE id = ?";
         for (index, id) in todo_ids.iter().enumerate() {
-            sqlx::query("UPDATE todos SET position = ? WHERE id = ?")
+            sqlx::query(sql)
                 .bind(index as i64)
                 .bind(id)
-                .execute(&self.pool)
+                .execute(&mut *tx)
+                .instrument(query_span(sql))
                 .await
                 .map_err(map_sqlx_error)?;
         }
+
+        self.enqueue_webhooks(&mut tx, &TodoEvent::Reordered { ids: todo_ids })
+            .await?;
+
+        tx.commit().await.map_err(map_sqlx_error)?;
+
         Ok(())
     }
 }
@@ -134,16 +341,31 @@ impl TodoRepository for TodoStore {
         self.get_by_id_inner(id).await
     }
 
+    async fn query(
+        &self,
+        filter: &TodoFilter,
+        sort: TodoSort,
+        limit: u32,
+        offset: u32,
+    ) -> Result<TodoPage, AppError> {
+        self.query_inner(filter, sort, limit, offset).await
+    }
+
+    async fn get_by_ids(&self, ids: &[u32]) -> Result<Vec<Todo>, AppError> {
+        self.get_by_ids_inner(ids).await
+    }
+
     async fn update(
         &self,
         id: u32,
         title: Option<String>,
         completed: Option<bool>,
+        due_at: Option<Option<DateTime<Utc>>>,
     ) -> Result<Option<Todo>, AppError> {
-        self.update_inner(id, title, completed).await
+        self.update_inner(id, title, completed, due_at).await
     }
 
-    async fn delete(&self, id: u32) -> Result<bool, AppError> {
+    async fn delete(&self, id: u32) -> Result<Option<Vec<String>>, AppError> {
         self.delete_inner(id).await
     }
 
@@ -152,6 +374,52 @@ impl TodoRepository for TodoStore {
     }
 }
 
+// `WHERE` 句を足す。期限で絞り込むときは期限のないTODOを含めない。
+// `lower` は英字しか変換しないので、それ以外の大文字小文字は区別する
+fn push_filter(query: &mut QueryBuilder<'_, Sqlite>, filter: &TodoFilter) {
+    let mut keyword = " WHERE ";
+    let mut next = |query: &mut QueryBuilder<'_, Sqlite>| {
+        query.push(keyword);
+        keyword = " AND ";
+    };
+    if let Some(completed) = filter.completed {
+        next(query);
+        query.push("completed = ").push_bind(completed);
+    }
+    if let Some(needle) = &filter.title_contains {
+        next(query);
+        query
+            .push("instr(lower(title), lower(")
+            .push_bind(needle.clone())
+            .push(")) > 0");
+    }
+    if let Some(before) = filter.due_before {
+        next(query);
+        query
+            .push("julianday(due_at) < julianday(")
+            .push_bind(before)
+            .push(")");
+    }
+    if let Some(after) = filter.due_after {
+        next(query);
+        query
+            .push("julianday(due_at) > julianday(")
+            .push_bind(after)
+            .push(")");
+    }
+}
+
+// 1つのSQLの実行を表すspan。OTLPで送るときはクライアント側のDB呼び出しとして扱われる
+fn query_span(sql: &str) -> tracing::Span {
+    tracing::info_span!(
+        "db.query",
+        otel.name = sql.split_whitespace().next().unwrap_or("SQL"),
+        otel.kind = "client",
+        db.system = "sqlite",
+        db.statement = sql,
+    )
+}
+
 fn map_sqlx_error(error: sqlx::Error) -> AppError {
     AppError::unexpected(error.to_string())
 }
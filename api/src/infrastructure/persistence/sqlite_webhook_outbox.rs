@@ -0,0 +1,219 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::application::errors::AppError;
+use crate::application::ports::webhook_outbox::{PendingDelivery, WebhookOutbox};
+use crate::domain::entities::webhook::{DeliveryStatus, WebhookDelivery};
+use crate::infrastructure::persistence::db_webhook::{DbPendingDelivery, DbWebhookDelivery};
+use sqlx::sqlite::{SqliteConnection, SqlitePool};
+
+const DELIVERY_COLUMNS: &str = "d.id, d.webhook_id, d.event, d.payload, d.status, d.attempts, \
+     d.response_status, d.last_error, d.created_at, d.next_attempt_at, d.delivered_at";
+
+#[derive(Clone)]
+pub struct WebhookOutboxStore {
+    pool: SqlitePool,
+}
+
+impl WebhookOutboxStore {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    async fn enqueue_inner(
+        &self,
+        event: &str,
+        payload: &str,
+        now: DateTime<Utc>,
+    ) -> Result<usize, AppError> {
+        let mut conn = self.pool.acquire().await.map_err(map_sqlx_error)?;
+        insert_deliveries(&mut conn, event, payload, now)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn due_inner(
+        &self,
+        now: DateTime<Utc>,
+        limit: u32,
+    ) -> Result<Vec<PendingDelivery>, AppError> {
+        let rows = sqlx::query_as::<_, DbPendingDelivery>(&format!(
+            "SELECT {}, w.url AS url, w.secret AS secret \
+             FROM webhook_deliveries d JOIN webhooks w ON w.id = d.webhook_id \
+             WHERE d.status = ? AND d.next_attempt_at <= ? \
+             ORDER BY d.next_attempt_at, d.id LIMIT ?",
+            DELIVERY_COLUMNS
+        ))
+        .bind(DeliveryStatus::Pending.as_str())
+        .bind(now.timestamp())
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| PendingDelivery {
+                delivery: row.delivery.into(),
+                url: row.url,
+                secret: row.secret,
+            })
+            .collect())
+    }
+
+    async fn mark_delivered_inner(
+        &self,
+        id: i64,
+        response_status: u16,
+        delivered_at: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            "UPDATE webhook_deliveries SET status = ?, attempts = attempts + 1, \
+             response_status = ?, last_error = NULL, next_attempt_at = NULL, delivered_at = ? \
+             WHERE id = ?",
+        )
+        .bind(DeliveryStatus::Delivered.as_str())
+        .bind(response_status as i64)
+        .bind(delivered_at)
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(())
+    }
+
+    async fn mark_failed_inner(
+        &self,
+        id: i64,
+        response_status: Option<u16>,
+        error: String,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> Result<(), AppError> {
+        let status = if retry_at.is_some() {
+            DeliveryStatus::Pending
+        } else {
+            DeliveryStatus::Failed
+        };
+
+        sqlx::query(
+            "UPDATE webhook_deliveries SET status = ?, attempts = attempts + 1, \
+             response_status = ?, last_error = ?, next_attempt_at = ? WHERE id = ?",
+        )
+        .bind(status.as_str())
+        .bind(response_status.map(i64::from))
+        .bind(error)
+        .bind(retry_at.map(|at| at.timestamp()))
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(())
+    }
+
+    async fn list_by_webhook_inner(
+        &self,
+        webhook_id: u32,
+        limit: u32,
+        offset: u32,
+    ) -> Result<(Vec<WebhookDelivery>, u64), AppError> {
+        let total: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM webhook_deliveries WHERE webhook_id = ?")
+                .bind(webhook_id as i64)
+                .fetch_one(&self.pool)
+                .await
+                .map_err(map_sqlx_error)?;
+
+        let rows = sqlx::query_as::<_, DbWebhookDelivery>(&format!(
+            "SELECT {} FROM webhook_deliveries d WHERE d.webhook_id = ? \
+             ORDER BY d.id DESC LIMIT ? OFFSET ?",
+            DELIVERY_COLUMNS
+        ))
+        .bind(webhook_id as i64)
+        .bind(limit as i64)
+        .bind(offset as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok((rows.into_iter().map(Into::into).collect(), total as u64))
+    }
+}
+
+/// 購読しているWebhookごとに配信を1行ずつ積む。
+/// TODOの変更と同じトランザクションで積めるよう、接続を受け取る
+pub(crate) async fn insert_deliveries(
+    conn: &mut SqliteConnection,
+    event: &str,
+    payload: &str,
+    now: DateTime<Utc>,
+) -> Result<usize, sqlx::Error> {
+    // events はカンマ区切りで、空なら全イベント
+    let result = sqlx::query(
+        "INSERT INTO webhook_deliveries \
+         (webhook_id, event, payload, status, attempts, created_at, next_attempt_at) \
+         SELECT id, ?, ?, ?, 0, ?, ? FROM webhooks \
+         WHERE events = '' OR instr(',' || events || ',', ',' || ? || ',') > 0",
+    )
+    .bind(event)
+    .bind(payload)
+    .bind(DeliveryStatus::Pending.as_str())
+    .bind(now)
+    .bind(now.timestamp())
+    .bind(event)
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected() as usize)
+}
+
+#[async_trait]
+impl WebhookOutbox for WebhookOutboxStore {
+    async fn enqueue(
+        &self,
+        event: &str,
+        payload: &str,
+        now: DateTime<Utc>,
+    ) -> Result<usize, AppError> {
+        self.enqueue_inner(event, payload, now).await
+    }
+
+    async fn due(&self, now: DateTime<Utc>, limit: u32) -> Result<Vec<PendingDelivery>, AppError> {
+        self.due_inner(now, limit).await
+    }
+
+    async fn mark_delivered(
+        &self,
+        id: i64,
+        response_status: u16,
+        delivered_at: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        self.mark_delivered_inner(id, response_status, delivered_at)
+            .await
+    }
+
+    async fn mark_failed(
+        &self,
+        id: i64,
+        response_status: Option<u16>,
+        error: String,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> Result<(), AppError> {
+        self.mark_failed_inner(id, response_status, error, retry_at)
+            .await
+    }
+
+    async fn list_by_webhook(
+        &self,
+        webhook_id: u32,
+        limit: u32,
+        offset: u32,
+    ) -> Result<(Vec<WebhookDelivery>, u64), AppError> {
+        self.list_by_webhook_inner(webhook_id, limit, offset).await
+    }
+}
+
+fn map_sqlx_error(error: sqlx::Error) -> AppError {
+    AppError::unexpected(error.to_string())
+}
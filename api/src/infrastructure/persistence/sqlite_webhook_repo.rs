@@ -0,0 +1,155 @@
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::application::errors::AppError;
+use crate::application::ports::webhook_repository::{NewWebhook, WebhookPatch, WebhookRepository};
+use crate::domain::entities::webhook::Webhook;
+use crate::infrastructure::persistence::db_webhook::{join_events, DbWebhook};
+use sqlx::sqlite::SqlitePool;
+
+const WEBHOOK_COLUMNS: &str = "id, url, secret, events, created_at, updated_at";
+
+#[derive(Clone)]
+pub struct WebhookStore {
+    pool: SqlitePool,
+}
+
+impl WebhookStore {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    async fn create_inner(&self, webhook: NewWebhook) -> Result<Webhook, AppError> {
+        let now = Utc::now();
+
+        let result = sqlx::query(
+            "INSERT INTO webhooks (url, secret, events, created_at, updated_at) \
+             VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(&webhook.url)
+        .bind(&webhook.secret)
+        .bind(join_events(&webhook.events))
+        .bind(now)
+        .bind(now)
+        .execute(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(Webhook {
+            id: result.last_insert_rowid(),
+            url: webhook.url,
+            secret: webhook.secret,
+            events: webhook.events,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    async fn list_inner(&self) -> Result<Vec<Webhook>, AppError> {
+        let rows = sqlx::query_as::<_, DbWebhook>(&format!(
+            "SELECT {} FROM webhooks ORDER BY id",
+            WEBHOOK_COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn get_by_id_inner(&self, id: u32) -> Result<Option<Webhook>, AppError> {
+        let row = sqlx::query_as::<_, DbWebhook>(&format!(
+            "SELECT {} FROM webhooks WHERE id = ?",
+            WEBHOOK_COLUMNS
+        ))
+        .bind(id as i64)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn update_inner(
+        &self,
+        id: u32,
+        patch: WebhookPatch,
+    ) -> Result<Option<Webhook>, AppError> {
+        let mut webhook = match self.get_by_id_inner(id).await? {
+            Some(w) => w,
+            None => return Ok(None),
+        };
+
+        if let Some(url) = patch.url {
+            webhook.url = url;
+        }
+        if let Some(secret) = patch.secret {
+            webhook.secret = secret;
+        }
+        if let Some(events) = patch.events {
+            webhook.events = events;
+        }
+        webhook.updated_at = Utc::now();
+
+        sqlx::query(
+            "UPDATE webhooks SET url = ?, secret = ?, events = ?, updated_at = ? WHERE id = ?",
+        )
+        .bind(&webhook.url)
+        .bind(&webhook.secret)
+        .bind(join_events(&webhook.events))
+        .bind(webhook.updated_at)
+        .bind(id as i64)
+        .execute(&self.pool)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(Some(webhook))
+    }
+
+    async fn delete_inner(&self, id: u32) -> Result<bool, AppError> {
+        let mut tx = self.pool.begin().await.map_err(map_sqlx_error)?;
+
+        sqlx::query("DELETE FROM webhook_deliveries WHERE webhook_id = ?")
+            .bind(id as i64)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        let result = sqlx::query("DELETE FROM webhooks WHERE id = ?")
+            .bind(id as i64)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        tx.commit().await.map_err(map_sqlx_error)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[async_trait]
+impl WebhookRepository for WebhookStore {
+    async fn create(&self, webhook: NewWebhook) -> Result<Webhook, AppError> {
+        self.create_inner(webhook).await
+    }
+
+    async fn list(&self) -> Result<Vec<Webhook>, AppError> {
+        self.list_inner().await
+    }
+
+    async fn get_by_id(&self, id: u32) -> Result<Option<Webhook>, AppError> {
+        self.get_by_id_inner(id).await
+    }
+
+    async fn update(&self, id: u32, patch: WebhookPatch) -> Result<Option<Webhook>, AppError> {
+        self.update_inner(id, patch).await
+    }
+
+    async fn delete(&self, id: u32) -> Result<bool, AppError> {
+        self.delete_inner(id).await
+    }
+}
+
+fn map_sqlx_error(error: sqlx::Error) -> AppError {
+    AppError::unexpected(error.to_string())
+}
@@ -0,0 +1,140 @@
+//! クライアントごとのトークンバケット。バケットはメモリに持つので、プロセスごとに数える
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// 持っておくバケットの上限。達したら満タンに戻ったものを捨て、それでも足りなければ
+/// 最後に使われたのが古いものから `EVICT_DIVISOR` 分の1をまとめて捨てる
+const MAX_BUCKETS: usize = 10_000;
+const EVICT_DIVISOR: usize = 10;
+
+pub struct RateLimiter {
+    capacity: f64,
+    /// 1秒あたりに補充するトークン数
+    refill_per_sec: f64,
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl RateLimiter {
+    /// 容量 `burst` のバケットに、1分あたり `requests_per_minute` 個ずつ補充する
+    pub fn new(requests_per_minute: u32, burst: u32) -> Self {
+        Self {
+            capacity: f64::from(burst),
+            refill_per_sec: f64::from(requests_per_minute) / 60.0,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// `key` のバケットからトークンを1つ取る。空なら、次の1つがたまるまでの時間を返す
+    pub fn acquire(&self, key: &str) -> Result<(), Duration> {
+        self.acquire_at(key, Instant::now())
+    }
+
+    fn acquire_at(&self, key: &str, now: Instant) -> Result<(), Duration> {
+        let mut buckets = self.buckets.lock().expect("rate limiter lock poisoned");
+        if !buckets.contains_key(key) && buckets.len() >= MAX_BUCKETS {
+            self.evict(&mut buckets, now);
+        }
+        let bucket = buckets.entry(key.to_string()).or_insert(Bucket {
+            tokens: self.capacity,
+            updated_at: now,
+        });
+        bucket.tokens = self.refilled(bucket, now);
+        bucket.updated_at = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - bucket.tokens) / self.refill_per_sec,
+            ))
+        }
+    }
+
+    fn evict(&self, buckets: &mut HashMap<String, Bucket>, now: Instant) {
+        buckets.retain(|_, bucket| self.refilled(bucket, now) < self.capacity);
+        if buckets.len() < MAX_BUCKETS {
+            return;
+        }
+        let mut updated: Vec<Instant> = buckets.values().map(|bucket| bucket.updated_at).collect();
+        let (_, cutoff, _) = updated.select_nth_unstable(MAX_BUCKETS / EVICT_DIVISOR);
+        let cutoff = *cutoff;
+        buckets.retain(|_, bucket| bucket.updated_at > cutoff);
+    }
+
+    fn refilled(&self, bucket: &Bucket, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(bucket.updated_at);
+        (bucket.tokens + elapsed.as_secs_f64() * self.refill_per_sec).min(self.capacity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{RateLimiter, MAX_BUCKETS};
+
+    #[test]
+    fn burst_is_allowed_then_refilled_over_time() {
+        let limiter = RateLimiter::new(60, 2);
+        let start = Instant::now();
+
+        assert_eq!(limiter.acquire_at("a", start), Ok(()));
+        assert_eq!(limiter.acquire_at("a", start), Ok(()));
+        assert_eq!(limiter.acquire_at("a", start), Err(Duration::from_secs(1)));
+        // 別のクライアントは別のバケット
+        assert_eq!(limiter.acquire_at("b", start), Ok(()));
+
+        let later = start + Duration::from_millis(500);
+        assert_eq!(
+            limiter.acquire_at("a", later),
+            Err(Duration::from_millis(500))
+        );
+        assert_eq!(
+            limiter.acquire_at("a", start + Duration::from_secs(1)),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn full_buckets_are_pruned() {
+        let limiter = RateLimiter::new(60, 1);
+        let start = Instant::now();
+        for i in 0..MAX_BUCKETS {
+            limiter.acquire_at(&i.to_string(), start).unwrap();
+        }
+
+        // 1秒後にはどれも満タンに戻っているので、新しいバケットだけが残る
+        limiter
+            .acquire_at("new", start + Duration::from_secs(1))
+            .unwrap();
+        assert_eq!(limiter.buckets.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn least_recently_used_buckets_are_evicted_when_full() {
+        // 補充をごく遅くして、どのバケットも満タンに戻らないようにする
+        let limiter = RateLimiter::new(1, 2);
+        let start = Instant::now();
+        for i in 0..MAX_BUCKETS {
+            let at = start + Duration::from_millis(i as u64);
+            limiter.acquire_at(&i.to_string(), at).unwrap();
+        }
+        let later = start + Duration::from_millis(MAX_BUCKETS as u64);
+        // 最初のクライアントは使い切ってから使い直したので、最近使われたほうに入る
+        limiter.acquire_at("0", later).unwrap();
+
+        limiter.acquire_at("new", later).unwrap();
+        let buckets = limiter.buckets.lock().unwrap();
+        assert!(buckets.len() < MAX_BUCKETS);
+        assert!(buckets.contains_key("0"));
+        assert!(buckets.contains_key("new"));
+        assert!(!buckets.contains_key("1"));
+        assert!(buckets.contains_key(&(MAX_BUCKETS - 1).to_string()));
+    }
+}
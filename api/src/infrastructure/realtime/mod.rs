@@ -0,0 +1 @@
+pub mod presence_registry;
@@ -0,0 +1,276 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+
+use chrono::Duration;
+use tokio::sync::broadcast;
+
+use crate::application::ports::clock::Clock;
+use crate::application::ports::edit_locks::EditLocks;
+use crate::domain::entities::presence::{EditLock, PresenceSnapshot, Viewer};
+
+/// ロックを更新しないまま放置した場合に自動で外れるまでの時間
+pub const DEFAULT_LOCK_TTL_SECS: i64 = 30;
+
+struct Connection {
+    user: String,
+    viewing: Option<i64>,
+}
+
+#[derive(Default)]
+struct Inner {
+    next_connection_id: u64,
+    connections: BTreeMap<u64, Connection>,
+    locks: HashMap<i64, EditLock>,
+}
+
+/// WebSocketの接続ごとの閲覧状況と編集ロックを保持する（プロセス内のみ）。
+/// 状態が変わるたびに `subscribe_changes` の受信側へ通知する
+pub struct PresenceRegistry {
+    inner: Mutex<Inner>,
+    clock: Arc<dyn Clock>,
+    lock_ttl: Duration,
+    changes: broadcast::Sender<()>,
+}
+
+impl PresenceRegistry {
+    pub fn new(clock: Arc<dyn Clock>, lock_ttl: Duration) -> Self {
+        let (changes, _) = broadcast::channel(16);
+        Self {
+            inner: Mutex::new(Inner::default()),
+            clock,
+            lock_ttl,
+            changes,
+        }
+    }
+
+    pub fn subscribe_changes(&self) -> broadcast::Receiver<()> {
+        self.changes.subscribe()
+    }
+
+    pub fn join(&self, user: String) -> u64 {
+        let id = {
+            let mut inner = self.lock_inner();
+            inner.next_connection_id += 1;
+            let id = inner.next_connection_id;
+            inner.connections.insert(
+                id,
+                Connection {
+                    user,
+                    viewing: None,
+                },
+            );
+            id
+        };
+        self.notify();
+        id
+    }
+
+    /// 切断時に呼ぶ。その接続が持っていたロックも外す
+    pub fn leave(&self, connection_id: u64) {
+        {
+            let mut inner = self.lock_inner();
+            inner.connections.remove(&connection_id);
+            inner
+                .locks
+                .retain(|_, lock| lock.connection_id != connection_id);
+        }
+        self.notify();
+    }
+
+    pub fn view(&self, connection_id: u64, todo_id: Option<i64>) {
+        {
+            let mut inner = self.lock_inner();
+            if let Some(connection) = inner.connections.get_mut(&connection_id) {
+                connection.viewing = todo_id;
+            }
+        }
+        self.notify();
+    }
+
+    /// 編集ロックを取得または延長する。他の接続が有効なロックを持っていればそれを返す
+    pub fn lock(&self, connection_id: u64, todo_id: i64) -> Result<EditLock, EditLock> {
+        let now = self.clock.now();
+        let lock = {
+            let mut inner = self.lock_inner();
+            if let Some(held) = inner.locks.get(&todo_id) {
+                if held.connection_id != connection_id && !held.is_expired(now) {
+                    return Err(held.clone());
+                }
+            }
+            let user = inner
+                .connections
+                .get(&connection_id)
+                .map(|c| c.user.clone())
+                .unwrap_or_default();
+            let lock = EditLock {
+                todo_id,
+                connection_id,
+                user,
+                expires_at: now + self.lock_ttl,
+            };
+            inner.locks.insert(todo_id, lock.clone());
+            lock
+        };
+        self.notify();
+        Ok(lock)
+    }
+
+    /// 自分が持っているロックだけを外せる
+    pub fn unlock(&self, connection_id: u64, todo_id: i64) -> bool {
+        let released = {
+            let mut inner = self.lock_inner();
+            match inner.locks.get(&todo_id) {
+                Some(lock) if lock.connection_id == connection_id => {
+                    inner.locks.remove(&todo_id);
+                    true
+                }
+                _ => false,
+            }
+        };
+        if released {
+            self.notify();
+        }
+        released
+    }
+
+    /// 期限切れのロックを片付ける。何か外れたら変更を通知する
+    pub fn sweep(&self) {
+        let now = self.clock.now();
+        let removed = {
+            let mut inner = self.lock_inner();
+            let before = inner.locks.len();
+            inner.locks.retain(|_, lock| !lock.is_expired(now));
+            before - inner.locks.len()
+        };
+        if removed > 0 {
+            self.notify();
+        }
+    }
+
+    pub fn snapshot(&self) -> PresenceSnapshot {
+        let now = self.clock.now();
+        let inner = self.lock_inner();
+        let viewers = inner
+            .connections
+            .iter()
+            .map(|(id, connection)| Viewer {
+                connection_id: *id,
+                user: connection.user.clone(),
+                todo_id: connection.viewing,
+            })
+            .collect();
+        let mut locks: Vec<EditLock> = inner
+            .locks
+            .values()
+            .filter(|lock| !lock.is_expired(now))
+            .cloned()
+            .collect();
+        locks.sort_by_key(|lock| lock.todo_id);
+        PresenceSnapshot { viewers, locks }
+    }
+
+    fn lock_inner(&self) -> std::sync::MutexGuard<'_, Inner> {
+        self.inner.lock().expect("failed to lock presence registry")
+    }
+
+    fn notify(&self) {
+        // 受信側がいない場合のエラーは無視してよい
+        let _ = self.changes.send(());
+    }
+}
+
+impl EditLocks for PresenceRegistry {
+    fn held_by_other(&self, todo_id: i64, editor: Option<u64>) -> Option<EditLock> {
+        let now = self.clock.now();
+        let inner = self.lock_inner();
+        inner
+            .locks
+            .get(&todo_id)
+            .filter(|lock| Some(lock.connection_id) != editor && !lock.is_expired(now))
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use chrono::{Duration, TimeZone, Utc};
+
+    use super::PresenceRegistry;
+    use crate::application::ports::clock::Clock;
+    use crate::application::ports::edit_locks::EditLocks;
+    use crate::infrastructure::clock::ManualClock;
+
+    fn registry() -> (PresenceRegistry, Arc<ManualClock>) {
+        let clock = Arc::new(ManualClock::new(
+            Utc.with_ymd_and_hms(2030, 1, 1, 9, 0, 0).unwrap(),
+        ));
+        (
+            PresenceRegistry::new(clock.clone(), Duration::seconds(30)),
+            clock,
+        )
+    }
+
+    #[test]
+    fn lock_is_exclusive_until_it_expires() {
+        let (registry, clock) = registry();
+        let alice = registry.join("alice".to_string());
+        let bob = registry.join("bob".to_string());
+
+        registry.lock(alice, 1).unwrap();
+        let held = registry.lock(bob, 1).unwrap_err();
+        assert_eq!(held.user, "alice");
+        assert!(registry.held_by_other(1, Some(bob)).is_some());
+        assert!(registry.held_by_other(1, None).is_some());
+        assert!(registry.held_by_other(1, Some(alice)).is_none());
+
+        clock.advance(Duration::seconds(30));
+        assert!(registry.snapshot().locks.is_empty());
+        assert_eq!(registry.lock(bob, 1).unwrap().user, "bob");
+    }
+
+    #[test]
+    fn relocking_extends_expiry() {
+        let (registry, clock) = registry();
+        let alice = registry.join("alice".to_string());
+        registry.lock(alice, 1).unwrap();
+
+        clock.advance(Duration::seconds(20));
+        let renewed = registry.lock(alice, 1).unwrap();
+
+        assert_eq!(renewed.expires_at, clock.now() + Duration::seconds(30));
+    }
+
+    #[test]
+    fn leaving_releases_locks_and_presence() {
+        let (registry, _clock) = registry();
+        let alice = registry.join("alice".to_string());
+        let bob = registry.join("bob".to_string());
+        registry.view(alice, Some(3));
+        registry.lock(alice, 3).unwrap();
+
+        assert!(!registry.unlock(bob, 3));
+        registry.leave(alice);
+
+        let snapshot = registry.snapshot();
+        assert_eq!(snapshot.viewers.len(), 1);
+        assert_eq!(snapshot.viewers[0].user, "bob");
+        assert!(snapshot.locks.is_empty());
+    }
+
+    #[tokio::test]
+    async fn sweep_notifies_when_a_lock_expires() {
+        let (registry, clock) = registry();
+        let alice = registry.join("alice".to_string());
+        registry.lock(alice, 1).unwrap();
+        let mut changes = registry.subscribe_changes();
+
+        registry.sweep();
+        assert!(changes.try_recv().is_err());
+
+        clock.advance(Duration::minutes(1));
+        registry.sweep();
+        assert!(changes.try_recv().is_ok());
+    }
+}
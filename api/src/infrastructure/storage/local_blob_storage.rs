@@ -0,0 +1,144 @@
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+
+use crate::application::errors::AppError;
+use crate::application::ports::blob_storage::BlobStorage;
+
+/// 指定ディレクトリ配下に `ab/cd/<sha256>` の形で保存する BlobStorage
+#[derive(Clone)]
+pub struct LocalBlobStorage {
+    root: PathBuf,
+}
+
+impl LocalBlobStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn path_for(&self, key: &str) -> Result<PathBuf, AppError> {
+        // キーはSHA-256の16進数のみ受け付け、パストラバーサルを防ぐ
+        if key.len() != 64 || !key.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(AppError::unexpected(format!("invalid blob key: {}", key)));
+        }
+        Ok(self.root.join(&key[0..2]).join(&key[2..4]).join(key))
+    }
+}
+
+#[async_trait]
+impl BlobStorage for LocalBlobStorage {
+    fn key_for(&self, data: &[u8]) -> String {
+        hex::encode(Sha256::digest(data))
+    }
+
+    async fn put(&self, data: &[u8]) -> Result<String, AppError> {
+        let key = self.key_for(data);
+        let path = self.path_for(&key)?;
+        if tokio::fs::try_exists(&path).await.map_err(map_io_error)? {
+            return Ok(key);
+        }
+
+        let dir = path.parent().expect("blob path always has a parent");
+        tokio::fs::create_dir_all(dir).await.map_err(map_io_error)?;
+
+        // 書きかけのファイルが読まれないよう、一時ファイルに書いてからリネームする。
+        // 同じ内容を同時に保存しても一時ファイルが重ならないよう、名前は毎回変える
+        let tmp = dir.join(format!("{}.{}.tmp", key, uuid::Uuid::new_v4()));
+        let written = match tokio::fs::write(&tmp, data).await {
+            Ok(()) => tokio::fs::rename(&tmp, &path).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = written {
+            let _ = tokio::fs::remove_file(&tmp).await;
+            return Err(map_io_error(e));
+        }
+
+        Ok(key)
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, AppError> {
+        match tokio::fs::read(self.path_for(key)?).await {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(map_io_error(e)),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), AppError> {
+        match tokio::fs::remove_file(self.path_for(key)?).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(map_io_error(e)),
+        }
+    }
+}
+
+fn map_io_error(error: std::io::Error) -> AppError {
+    AppError::unexpected(error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LocalBlobStorage;
+    use crate::application::ports::blob_storage::BlobStorage;
+
+    #[tokio::test]
+    async fn put_get_delete_round_trip() {
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let storage = LocalBlobStorage::new(dir.path());
+
+        let key = storage.put(b"hello").await.unwrap();
+        assert_eq!(
+            key,
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+        assert!(dir.path().join("2c").join("f2").join(&key).is_file());
+        assert_eq!(storage.get(&key).await.unwrap(), Some(b"hello".to_vec()));
+
+        // 同じ内容は同じキーになる
+        assert_eq!(storage.put(b"hello").await.unwrap(), key);
+
+        storage.delete(&key).await.unwrap();
+        assert_eq!(storage.get(&key).await.unwrap(), None);
+        storage.delete(&key).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn concurrent_puts_of_same_content_do_not_collide() {
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let storage = LocalBlobStorage::new(dir.path());
+        let data = vec![7u8; 256 * 1024];
+
+        let puts = (0..8).map(|_| storage.put(&data));
+        let keys = futures_util::future::join_all(puts).await;
+
+        let key = storage.key_for(&data);
+        for result in keys {
+            assert_eq!(result.unwrap(), key);
+        }
+        assert_eq!(storage.get(&key).await.unwrap(), Some(data));
+
+        // 一時ファイルは残らない
+        let shard = dir.path().join(&key[0..2]).join(&key[2..4]);
+        let leftovers: Vec<_> = std::fs::read_dir(shard)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .filter(|name| name.to_string_lossy().ends_with(".tmp"))
+            .collect();
+        assert!(leftovers.is_empty(), "{:?}", leftovers);
+    }
+
+    #[tokio::test]
+    async fn rejects_keys_that_are_not_hashes() {
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let storage = LocalBlobStorage::new(dir.path());
+
+        assert!(storage.get("../../etc/passwd").await.is_err());
+    }
+}
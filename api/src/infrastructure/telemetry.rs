@@ -0,0 +1,99 @@
+//! OTLPでのトレースの送信（`otel` フィーチャ）。`tracing` のspanをそのままOpenTelemetryのspanとして送る
+use axum::http::HeaderMap;
+
+#[cfg(feature = "otel")]
+pub use otel::{init, Telemetry};
+
+/// 受け取ったリクエストの `traceparent`（W3C Trace Context）を `span` の親にする。
+/// `otel` フィーチャなしでビルドしたときは何もしない
+pub fn set_remote_parent(span: &tracing::Span, headers: &HeaderMap) {
+    #[cfg(feature = "otel")]
+    otel::set_remote_parent(span, headers);
+    #[cfg(not(feature = "otel"))]
+    let _ = (span, headers);
+}
+
+#[cfg(feature = "otel")]
+mod otel {
+    use axum::http::HeaderMap;
+    use opentelemetry::propagation::Extractor;
+    use opentelemetry::trace::{TraceError, TracerProvider as _};
+    use opentelemetry::KeyValue;
+    use opentelemetry_otlp::WithExportConfig;
+    use opentelemetry_sdk::propagation::TraceContextPropagator;
+    use opentelemetry_sdk::trace::{Tracer, TracerProvider};
+    use opentelemetry_sdk::{runtime, Resource};
+    use tracing_opentelemetry::{OpenTelemetryLayer, OpenTelemetrySpanExt};
+    use tracing_subscriber::registry::LookupSpan;
+
+    use crate::config::OtelConfig;
+
+    /// 送信の設定を持つ。終了時に [`Telemetry::shutdown`] で送り残しを送る
+    pub struct Telemetry {
+        provider: TracerProvider,
+    }
+
+    /// OTLP/gRPCの送信先を用意し、`traceparent` の読み取り方を登録する。
+    /// 送信はバックグラウンドでまとめて行うので、tokioのランタイムの中で呼ぶ
+    pub fn init(config: &OtelConfig) -> Result<Telemetry, TraceError> {
+        let exporter = opentelemetry_otlp::SpanExporter::builder()
+            .with_tonic()
+            .with_endpoint(&config.endpoint)
+            .build()?;
+        let provider = TracerProvider::builder()
+            .with_batch_exporter(exporter, runtime::Tokio)
+            .with_resource(Resource::new([
+                KeyValue::new("service.name", config.service_name.clone()),
+                KeyValue::new("service.version", env!("CARGO_PKG_VERSION")),
+            ]))
+            .build();
+        opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+        Ok(Telemetry { provider })
+    }
+
+    impl Telemetry {
+        /// `tracing_subscriber` に重ねるレイヤー
+        pub fn layer<S>(&self) -> OpenTelemetryLayer<S, Tracer>
+        where
+            S: tracing::Subscriber + for<'span> LookupSpan<'span>,
+        {
+            let tracer = self.provider.tracer(env!("CARGO_PKG_NAME"));
+            tracing_opentelemetry::layer().with_tracer(tracer)
+        }
+
+        /// たまっているspanをすぐに送る
+        pub fn flush(&self) {
+            for result in self.provider.force_flush() {
+                if let Err(e) = result {
+                    tracing::warn!("failed to export spans: {}", e);
+                }
+            }
+        }
+
+        /// 送り残しを送ってから止める
+        pub fn shutdown(self) {
+            if let Err(e) = self.provider.shutdown() {
+                tracing::warn!("failed to shut down the span exporter: {}", e);
+            }
+        }
+    }
+
+    pub(super) fn set_remote_parent(span: &tracing::Span, headers: &HeaderMap) {
+        let parent = opentelemetry::global::get_text_map_propagator(|propagator| {
+            propagator.extract(&HeaderExtractor(headers))
+        });
+        span.set_parent(parent);
+    }
+
+    struct HeaderExtractor<'a>(&'a HeaderMap);
+
+    impl Extractor for HeaderExtractor<'_> {
+        fn get(&self, key: &str) -> Option<&str> {
+            self.0.get(key).and_then(|value| value.to_str().ok())
+        }
+
+        fn keys(&self) -> Vec<&str> {
+            self.0.keys().map(|name| name.as_str()).collect()
+        }
+    }
+}
@@ -0,0 +1,241 @@
+//! HTTPSの証明書。ファイルの更新を見張り、読み直した証明書を次の接続から使う
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+
+use tokio::task::JoinHandle;
+use tokio_rustls::rustls::{
+    self,
+    crypto::{ring, CryptoProvider},
+    pki_types::{pem::PemObject, CertificateDer, PrivateKeyDer},
+    server::{ClientHello, ResolvesServerCert},
+    sign::CertifiedKey,
+    ServerConfig,
+};
+use tokio_rustls::TlsAcceptor;
+use tokio_util::sync::CancellationToken;
+
+#[derive(Debug)]
+pub enum TlsError {
+    /// PEMとして読めない（ファイルがない場合も含む）
+    Pem {
+        path: PathBuf,
+        source: rustls::pki_types::pem::Error,
+    },
+    /// 証明書と鍵の組み合わせが使えない
+    Invalid { message: String },
+}
+
+impl fmt::Display for TlsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TlsError::Pem { path, source } => {
+                write!(f, "failed to read {}: {}", path.display(), source)
+            }
+            TlsError::Invalid { message } => write!(f, "invalid certificate or key: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for TlsError {}
+
+/// 証明書と鍵のファイル。更新を検知したら読み直す
+pub struct ReloadingCertificate {
+    cert_path: PathBuf,
+    key_path: PathBuf,
+    provider: Arc<CryptoProvider>,
+    current: RwLock<Loaded>,
+}
+
+struct Loaded {
+    key: Arc<CertifiedKey>,
+    versions: [Option<FileVersion>; 2],
+}
+
+/// 更新日時とサイズが両方同じなら、読み直さない
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileVersion {
+    modified: SystemTime,
+    len: u64,
+}
+
+impl ReloadingCertificate {
+    pub fn load(cert_path: &Path, key_path: &Path) -> Result<Arc<Self>, TlsError> {
+        let provider = Arc::new(ring::default_provider());
+        let versions = [file_version(cert_path), file_version(key_path)];
+        let key = load_certified_key(cert_path, key_path, &provider)?;
+        Ok(Arc::new(Self {
+            cert_path: cert_path.to_path_buf(),
+            key_path: key_path.to_path_buf(),
+            provider,
+            current: RwLock::new(Loaded {
+                key: Arc::new(key),
+                versions,
+            }),
+        }))
+    }
+
+    /// どちらかのファイルが変わっていれば読み直し、読み直したら `true` を返す。
+    /// 読めなかったときは今の証明書を使い続け、次の確認でもう一度読む。
+    /// ファイルを同期的に読むので、非同期の処理からは `spawn_blocking` で呼ぶ
+    pub fn reload_if_changed(&self) -> Result<bool, TlsError> {
+        let versions = [file_version(&self.cert_path), file_version(&self.key_path)];
+        if self.lock_read().versions == versions {
+            return Ok(false);
+        }
+        let key = load_certified_key(&self.cert_path, &self.key_path, &self.provider)?;
+        let mut current = self.current.write().expect("certificate lock poisoned");
+        current.key = Arc::new(key);
+        current.versions = versions;
+        Ok(true)
+    }
+
+    /// この証明書で待ち受ける `TlsAcceptor`。HTTP/2とHTTP/1.1をALPNで選ぶ
+    pub fn acceptor(self: &Arc<Self>) -> Result<TlsAcceptor, TlsError> {
+        let mut config = ServerConfig::builder_with_provider(self.provider.clone())
+            .with_safe_default_protocol_versions()
+            .map_err(invalid)?
+            .with_no_client_auth()
+            .with_cert_resolver(self.clone());
+        config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+        Ok(TlsAcceptor::from(Arc::new(config)))
+    }
+
+    fn lock_read(&self) -> std::sync::RwLockReadGuard<'_, Loaded> {
+        self.current.read().expect("certificate lock poisoned")
+    }
+}
+
+impl ResolvesServerCert for ReloadingCertificate {
+    fn resolve(&self, _client_hello: ClientHello<'_>) -> Option<Arc<CertifiedKey>> {
+        Some(self.lock_read().key.clone())
+    }
+}
+
+impl fmt::Debug for ReloadingCertificate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReloadingCertificate")
+            .field("cert_path", &self.cert_path)
+            .field("key_path", &self.key_path)
+            .finish_non_exhaustive()
+    }
+}
+
+/// `interval` ごとに証明書のファイルを確かめ、更新されていれば読み直す
+pub fn spawn_certificate_reloader(
+    certificate: Arc<ReloadingCertificate>,
+    interval: Duration,
+    shutdown: CancellationToken,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        // 最初のtickはすぐに来るので読み捨てる（起動時に読んだばかり）
+        ticker.tick().await;
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = ticker.tick() => {}
+            }
+            // ファイルの読み込みでランタイムのワーカーを止めないよう、ブロッキング用のスレッドで読む
+            let reloading = certificate.clone();
+            match tokio::task::spawn_blocking(move || reloading.reload_if_changed()).await {
+                Ok(Ok(true)) => tracing::info!(
+                    "reloaded the TLS certificate from {}",
+                    certificate.cert_path.display()
+                ),
+                Ok(Ok(false)) => {}
+                Ok(Err(e)) => tracing::warn!(
+                    "failed to reload the TLS certificate, keeping the current one: {}",
+                    e
+                ),
+                Err(e) => tracing::error!("TLS certificate reload task failed: {}", e),
+            }
+        }
+    })
+}
+
+fn load_certified_key(
+    cert_path: &Path,
+    key_path: &Path,
+    provider: &CryptoProvider,
+) -> Result<CertifiedKey, TlsError> {
+    let pem_error = |path: &Path| {
+        let path = path.to_path_buf();
+        move |source| TlsError::Pem { path, source }
+    };
+    let certs = CertificateDer::pem_file_iter(cert_path)
+        .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+        .map_err(pem_error(cert_path))?;
+    if certs.is_empty() {
+        return Err(pem_error(cert_path)(
+            rustls::pki_types::pem::Error::NoItemsFound,
+        ));
+    }
+    let key = PrivateKeyDer::from_pem_file(key_path).map_err(pem_error(key_path))?;
+    CertifiedKey::from_der(certs, key, provider).map_err(invalid)
+}
+
+fn file_version(path: &Path) -> Option<FileVersion> {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some(FileVersion {
+        modified: metadata.modified().ok()?,
+        len: metadata.len(),
+    })
+}
+
+fn invalid(error: rustls::Error) -> TlsError {
+    TlsError::Invalid {
+        message: error.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::ReloadingCertificate;
+
+    fn write_self_signed(dir: &Path, name: &str) {
+        let cert = rcgen::generate_simple_self_signed(vec![name.to_string()]).unwrap();
+        std::fs::write(dir.join("cert.pem"), cert.cert.pem()).unwrap();
+        std::fs::write(dir.join("key.pem"), cert.key_pair.serialize_pem()).unwrap();
+    }
+
+    #[test]
+    fn reloads_only_when_the_files_change() {
+        let dir = tempfile::tempdir().unwrap();
+        write_self_signed(dir.path(), "a.example");
+        let certificate =
+            ReloadingCertificate::load(&dir.path().join("cert.pem"), &dir.path().join("key.pem"))
+                .unwrap();
+        let before = certificate.lock_read().key.clone();
+
+        assert!(!certificate.reload_if_changed().unwrap());
+
+        write_self_signed(dir.path(), "b.example-with-a-longer-name");
+        assert!(certificate.reload_if_changed().unwrap());
+        assert_ne!(certificate.lock_read().key.cert, before.cert);
+    }
+
+    #[test]
+    fn keeps_the_current_certificate_when_the_new_one_is_broken() {
+        let dir = tempfile::tempdir().unwrap();
+        write_self_signed(dir.path(), "a.example");
+        let certificate =
+            ReloadingCertificate::load(&dir.path().join("cert.pem"), &dir.path().join("key.pem"))
+                .unwrap();
+        let before = certificate.lock_read().key.clone();
+
+        std::fs::write(dir.path().join("key.pem"), "not a key").unwrap();
+        assert!(certificate.reload_if_changed().is_err());
+        assert_eq!(certificate.lock_read().key.cert, before.cert);
+
+        // 鍵が証明書と合わなくても使わない
+        let other = rcgen::KeyPair::generate().unwrap();
+        std::fs::write(dir.path().join("key.pem"), other.serialize_pem()).unwrap();
+        assert!(certificate.reload_if_changed().is_err());
+        assert_eq!(certificate.lock_read().key.cert, before.cert);
+    }
+}
@@ -0,0 +1,92 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::application::ports::webhook_sender::{OutgoingWebhook, SendError, WebhookSender};
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub const EVENT_HEADER: &str = "x-webhook-event";
+pub const DELIVERY_HEADER: &str = "x-webhook-delivery";
+/// `sha256=<HMAC-SHA256(secret, body) の16進>`
+pub const SIGNATURE_HEADER: &str = "x-webhook-signature";
+
+/// ペイロードをHMAC-SHA256で署名してPOSTする。2xx以外は失敗として再送対象にする
+#[derive(Clone)]
+pub struct HttpWebhookSender {
+    client: reqwest::Client,
+}
+
+impl HttpWebhookSender {
+    pub fn new() -> Self {
+        // リダイレクト先へは署名付きのペイロードを送らない。3xxは失敗として再送に回す
+        let client = reqwest::Client::builder()
+            .timeout(DEFAULT_TIMEOUT)
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .expect("Failed to build HTTP client");
+        Self { client }
+    }
+}
+
+impl Default for HttpWebhookSender {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 受信側が検証に使う署名ヘッダの値を計算する
+pub fn sign_payload(secret: &str, payload: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(payload.as_bytes());
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+#[async_trait]
+impl WebhookSender for HttpWebhookSender {
+    async fn send(&self, webhook: &OutgoingWebhook) -> Result<u16, SendError> {
+        let response = self
+            .client
+            .post(&webhook.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(EVENT_HEADER, &webhook.event)
+            .header(DELIVERY_HEADER, webhook.delivery_id.to_string())
+            .header(
+                SIGNATURE_HEADER,
+                sign_payload(&webhook.secret, &webhook.payload),
+            )
+            .body(webhook.payload.clone())
+            .send()
+            .await
+            .map_err(|e| SendError {
+                status: None,
+                message: format!("webhook request failed: {}", e),
+            })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(SendError {
+                status: Some(status.as_u16()),
+                message: format!("webhook responded with {}", status),
+            });
+        }
+        Ok(status.as_u16())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sign_payload;
+
+    #[test]
+    fn signature_matches_known_hmac_sha256() {
+        // RFC 4231 のテストケース2
+        assert_eq!(
+            sign_payload("Jefe", "what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
@@ -0,0 +1,3 @@
+pub mod monitor;
+pub mod reminder_scheduler;
+pub mod webhook_dispatcher;
@@ -0,0 +1,162 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// この回数ぶんの間隔を過ぎても1回も回らなければ止まっているとみなす
+const STALL_AFTER_INTERVALS: u32 = 3;
+/// 1回の処理が長引くこともあるので、止まっているとみなすまで最低でもこれだけ待つ
+const MIN_STALL_THRESHOLD: Duration = Duration::from_secs(60);
+
+/// バックグラウンドタスクの稼働状況。`/readyz` で報告する
+#[derive(Default)]
+pub struct WorkerMonitor {
+    workers: Mutex<BTreeMap<&'static str, WorkerEntry>>,
+}
+
+struct WorkerEntry {
+    poll_interval: Duration,
+    last_beat: Instant,
+    stopped: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkerState {
+    Running,
+    /// 最後に回ってから時間が経ちすぎている
+    Stalled,
+    /// タスクが終わった（終了処理中か、パニックした）
+    Stopped,
+}
+
+#[derive(Debug, Clone)]
+pub struct WorkerStatus {
+    pub name: &'static str,
+    pub state: WorkerState,
+    /// 最後に回ってからの経過時間
+    pub since_last_beat: Duration,
+}
+
+impl WorkerMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// タスクを登録し、タスクが持つ `Heartbeat` を返す。登録した時点で1回回ったものとして扱う
+    pub fn register(self: &Arc<Self>, name: &'static str, poll_interval: Duration) -> Heartbeat {
+        self.lock().insert(
+            name,
+            WorkerEntry {
+                poll_interval,
+                last_beat: Instant::now(),
+                stopped: false,
+            },
+        );
+        Heartbeat {
+            name,
+            monitor: self.clone(),
+        }
+    }
+
+    /// 登録済みのタスクの状態を名前順に返す
+    pub fn statuses(&self) -> Vec<WorkerStatus> {
+        self.statuses_at(Instant::now())
+    }
+
+    fn statuses_at(&self, now: Instant) -> Vec<WorkerStatus> {
+        self.lock()
+            .iter()
+            .map(|(&name, entry)| {
+                let since_last_beat = now.saturating_duration_since(entry.last_beat);
+                let threshold =
+                    (entry.poll_interval * STALL_AFTER_INTERVALS).max(MIN_STALL_THRESHOLD);
+                let state = if entry.stopped {
+                    WorkerState::Stopped
+                } else if since_last_beat > threshold {
+                    WorkerState::Stalled
+                } else {
+                    WorkerState::Running
+                };
+                WorkerStatus {
+                    name,
+                    state,
+                    since_last_beat,
+                }
+            })
+            .collect()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<&'static str, WorkerEntry>> {
+        self.workers.lock().expect("failed to lock worker monitor")
+    }
+}
+
+/// タスクが1回処理するたびに `beat` を呼ぶ。タスクが終わって（パニックも含む）手放されると停止として記録される
+pub struct Heartbeat {
+    name: &'static str,
+    monitor: Arc<WorkerMonitor>,
+}
+
+impl Heartbeat {
+    pub fn beat(&self) {
+        if let Some(entry) = self.monitor.lock().get_mut(self.name) {
+            entry.last_beat = Instant::now();
+        }
+    }
+}
+
+impl Drop for Heartbeat {
+    fn drop(&mut self) {
+        // パニック中にロックが壊れていても、ここで二重にパニックしないようにする
+        if let Ok(mut workers) = self.monitor.workers.lock() {
+            if let Some(entry) = workers.get_mut(self.name) {
+                entry.stopped = true;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn worker_is_stalled_after_missing_several_intervals() {
+        let monitor = Arc::new(WorkerMonitor::new());
+        let _heartbeat = monitor.register("fast", Duration::from_secs(5));
+        let _slow = monitor.register("slow", Duration::from_secs(60));
+        let now = Instant::now();
+
+        let states = |after: Duration| -> Vec<WorkerState> {
+            monitor
+                .statuses_at(now + after)
+                .into_iter()
+                .map(|status| status.state)
+                .collect()
+        };
+        // 間隔が短くても最低60秒は待つ
+        assert_eq!(
+            states(Duration::from_secs(59)),
+            [WorkerState::Running, WorkerState::Running]
+        );
+        assert_eq!(
+            states(Duration::from_secs(61)),
+            [WorkerState::Stalled, WorkerState::Running]
+        );
+        assert_eq!(
+            states(Duration::from_secs(181)),
+            [WorkerState::Stalled, WorkerState::Stalled]
+        );
+    }
+
+    #[test]
+    fn dropping_the_heartbeat_marks_the_worker_stopped() {
+        let monitor = Arc::new(WorkerMonitor::new());
+        let heartbeat = monitor.register("worker", Duration::from_secs(5));
+        heartbeat.beat();
+        assert_eq!(monitor.statuses()[0].state, WorkerState::Running);
+
+        drop(heartbeat);
+
+        assert_eq!(monitor.statuses()[0].state, WorkerState::Stopped);
+    }
+}
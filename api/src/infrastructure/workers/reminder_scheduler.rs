@@ -0,0 +1,72 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+use crate::application::ports::clock::Clock;
+use crate::application::ports::notifier::Notifier;
+use crate::application::ports::reminder_repository::ReminderRepository;
+use crate::application::usecases::reminder::dispatch_due;
+use crate::infrastructure::workers::monitor::Heartbeat;
+
+const BATCH_SIZE: u32 = 100;
+
+/// 一定間隔で配信期限を過ぎたリマインダーを探して通知するバックグラウンドタスク
+pub struct ReminderScheduler {
+    pub reminders: Arc<dyn ReminderRepository>,
+    pub notifier: Arc<dyn Notifier>,
+    pub clock: Arc<dyn Clock>,
+    pub poll_interval: Duration,
+}
+
+impl ReminderScheduler {
+    /// 1回分だけ処理する。バッチが埋まっている間は続けて処理する
+    pub async fn tick(&self) {
+        loop {
+            match dispatch_due::execute(
+                self.reminders.as_ref(),
+                self.notifier.as_ref(),
+                self.clock.as_ref(),
+                BATCH_SIZE,
+            )
+            .await
+            {
+                Ok(summary) => {
+                    if summary.delivered + summary.failed > 0 {
+                        tracing::info!(
+                            delivered = summary.delivered,
+                            failed = summary.failed,
+                            "reminders dispatched"
+                        );
+                    }
+                    if summary.delivered + summary.failed < BATCH_SIZE as usize {
+                        return;
+                    }
+                }
+                Err(e) => {
+                    tracing::error!("reminder dispatch failed: {:?}", e);
+                    return;
+                }
+            }
+        }
+    }
+
+    /// `shutdown` が発火したら止まる。処理中の回は最後まで終えてから止まる。
+    /// 1回処理するたびに `heartbeat` で稼働を知らせる
+    pub fn spawn(self, heartbeat: Heartbeat, shutdown: CancellationToken) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.poll_interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                tokio::select! {
+                    _ = shutdown.cancelled() => break,
+                    _ = interval.tick() => {
+                        self.tick().await;
+                        heartbeat.beat();
+                    }
+                }
+            }
+        })
+    }
+}
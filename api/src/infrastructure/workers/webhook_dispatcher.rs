@@ -0,0 +1,73 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+use crate::application::ports::clock::Clock;
+use crate::application::ports::webhook_outbox::WebhookOutbox;
+use crate::application::ports::webhook_sender::WebhookSender;
+use crate::application::usecases::webhook::dispatch_pending;
+use crate::infrastructure::workers::monitor::Heartbeat;
+
+const BATCH_SIZE: u32 = 100;
+
+/// 一定間隔でアウトボックスから送信待ちのWebhookを取り出して送るバックグラウンドタスク
+pub struct WebhookDispatcher {
+    pub outbox: Arc<dyn WebhookOutbox>,
+    pub sender: Arc<dyn WebhookSender>,
+    pub clock: Arc<dyn Clock>,
+    pub poll_interval: Duration,
+}
+
+impl WebhookDispatcher {
+    /// 1回分だけ処理する。バッチが埋まっている間は続けて処理する
+    pub async fn tick(&self) {
+        loop {
+            match dispatch_pending::execute(
+                self.outbox.as_ref(),
+                self.sender.as_ref(),
+                self.clock.as_ref(),
+                BATCH_SIZE,
+            )
+            .await
+            {
+                Ok(summary) => {
+                    if summary.total() > 0 {
+                        tracing::info!(
+                            delivered = summary.delivered,
+                            retrying = summary.retrying,
+                            failed = summary.failed,
+                            "webhooks dispatched"
+                        );
+                    }
+                    if summary.total() < BATCH_SIZE as usize {
+                        return;
+                    }
+                }
+                Err(e) => {
+                    tracing::error!("webhook dispatch failed: {:?}", e);
+                    return;
+                }
+            }
+        }
+    }
+
+    /// `shutdown` が発火したら止まる。処理中の回は最後まで終えてから止まる。
+    /// 1回処理するたびに `heartbeat` で稼働を知らせる
+    pub fn spawn(self, heartbeat: Heartbeat, shutdown: CancellationToken) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.poll_interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                tokio::select! {
+                    _ = shutdown.cancelled() => break,
+                    _ = interval.tick() => {
+                        self.tick().await;
+                        heartbeat.beat();
+                    }
+                }
+            }
+        })
+    }
+}
@@ -1,17 +1,69 @@
 pub mod application;
+pub mod config;
 pub mod domain;
 pub mod handlers;
 pub mod infrastructure;
+pub mod openapi;
 pub mod presentation;
+pub mod state;
 
+use crate::application::ports::attachment_repository::AttachmentRepository;
+use crate::application::ports::blob_storage::BlobStorage;
+use crate::application::ports::comment_repository::CommentRepository;
+use crate::application::ports::event_publisher::EventPublisher;
+use crate::application::ports::idempotency_store::IdempotencyStore;
+use crate::application::ports::notifier::Notifier;
+use crate::application::ports::reminder_repository::ReminderRepository;
 use crate::application::ports::todo_repository::TodoRepository;
+use crate::application::ports::webhook_outbox::WebhookOutbox;
+use crate::application::ports::webhook_repository::WebhookRepository;
+use crate::config::{Config, CorsConfig, DatabaseConfig, RouteGroup};
+use crate::infrastructure::clock::SystemClock;
+use crate::infrastructure::events::broadcaster::EventBroadcaster;
+use crate::infrastructure::health::HealthChecker;
+use crate::infrastructure::metrics::metered_todo_repo::MeteredTodoRepository;
+use crate::infrastructure::metrics::Metrics;
+use crate::infrastructure::persistence::schema::create_tables;
+use crate::infrastructure::persistence::sqlite_attachment_repo::AttachmentStore;
+use crate::infrastructure::persistence::sqlite_comment_repo::CommentStore;
+use crate::infrastructure::persistence::sqlite_idempotency_store::IdempotencyKeyStore;
+use crate::infrastructure::persistence::sqlite_reminder_repo::ReminderStore;
 use crate::infrastructure::persistence::sqlite_todo_repo::TodoStore;
+use crate::infrastructure::persistence::sqlite_webhook_outbox::WebhookOutboxStore;
+use crate::infrastructure::persistence::sqlite_webhook_repo::WebhookStore;
+use crate::infrastructure::realtime::presence_registry::{PresenceRegistry, DEFAULT_LOCK_TTL_SECS};
+use crate::infrastructure::storage::local_blob_storage::LocalBlobStorage;
+use crate::infrastructure::telemetry;
+use crate::infrastructure::webhooks::http_webhook_sender::HttpWebhookSender;
+use crate::infrastructure::workers::monitor::WorkerMonitor;
+use crate::infrastructure::workers::reminder_scheduler::ReminderScheduler;
+use crate::infrastructure::workers::webhook_dispatcher::WebhookDispatcher;
+use crate::presentation::grpc::pb::todo_service_server::TodoServiceServer;
+use crate::presentation::grpc::TodoGrpcService;
+use crate::presentation::idempotency::Idempotency;
+use crate::presentation::rate_limit::RouteLimitLayers;
+use crate::state::AppState;
+use axum::extract::{DefaultBodyLimit, MatchedPath};
+use axum::http::Method;
+use axum::routing::{MethodFilter, MethodRouter};
 use axum::Router;
-use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use hyper_util::server::graceful::GracefulShutdown;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use std::collections::HashMap;
+use std::future::IntoFuture;
+use std::net::SocketAddr;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+use tokio_rustls::TlsAcceptor;
+use tokio_stream::wrappers::TcpListenerStream;
+use tokio_util::sync::CancellationToken;
 use tower_http::classify::ServerErrorsFailureClass;
-use tower_http::cors::{Any, CorsLayer};
+use tower_http::cors::{AllowOrigin, CorsLayer};
 use tower_http::trace::TraceLayer;
 
 // テスト用のアプリケーションを作成する関数
@@ -23,67 +75,394 @@ pub async fn create_test_app() -> Router {
         .unwrap();
 
     // テーブルを作成
-    sqlx::query(
-        r#"
-        CREATE TABLE todos (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            title TEXT NOT NULL,
-            completed BOOLEAN NOT NULL DEFAULT 0,
-            position INTEGER NOT NULL DEFAULT 0
-        )
-        "#,
-    )
-    .execute(&pool)
-    .await
-    .unwrap();
+    create_tables(&pool).await.unwrap();
 
-    let store: Arc<dyn TodoRepository> = Arc::new(TodoStore::new(pool));
+    // 添付ファイルはテストごとに別の一時ディレクトリへ保存する
+    static NEXT_TEST_DIR: AtomicUsize = AtomicUsize::new(0);
+    let mut config = Config::default();
+    config.attachments.dir = std::env::temp_dir().join(format!(
+        "rust_todo_app-test-{}-{}",
+        std::process::id(),
+        NEXT_TEST_DIR.fetch_add(1, Ordering::Relaxed)
+    ));
 
     // ルーターを作成（main.rsから関数をインポート）
-    create_router(store)
+    create_app(pool, &config)
 }
 
-// 本番用のアプリケーションを作成する関数
-pub async fn create_app(database_url: &str) -> Router {
-    let connect_options = SqliteConnectOptions::from_str(database_url)
-        .expect("Invalid database URL")
-        .create_if_missing(true);
+// 本番用のデータベースに接続し、テーブルを用意する関数。
+// URLの誤りや開けないファイルは呼び出し側で設定の誤りとして報告する
+pub async fn connect_database(config: &DatabaseConfig) -> Result<SqlitePool, sqlx::Error> {
+    let connect_options = SqliteConnectOptions::from_str(&config.url)?.create_if_missing(true);
 
     let pool = SqlitePoolOptions::new()
-        .max_connections(5)
+        .max_connections(config.max_connections)
         .connect_with(connect_options)
+        .await?;
+
+    create_tables(&pool).await?;
+
+    Ok(pool)
+}
+
+// 本番用のアプリケーションを作成する関数
+// CORS・機能の有効/無効・添付の制限・既定の応答言語は config に従う
+pub fn create_app(pool: SqlitePool, config: &Config) -> Router {
+    // バックグラウンドタスクは動かさないので、稼働状況も空のまま
+    let health = Arc::new(HealthChecker::new(
+        pool.clone(),
+        Arc::new(WorkerMonitor::new()),
+    ));
+    let metrics = Arc::new(Metrics::new(pool.clone()));
+    let state = app_state(pool, config, &metrics);
+    create_router(state, health, metrics, config)
+}
+
+/// `create_app_with_grpc` が作るサーバ一式
+pub struct AppServices {
+    pub http: Router,
+    pub grpc: TodoServiceServer<TodoGrpcService>,
+    /// SSEなどへ変更を配るチャネル。終了時に閉じると、続いている購読のストリームが終わる
+    pub event_stream: Arc<EventBroadcaster>,
+    /// `/readyz` が報告するバックグラウンドタスクの稼働状況。タスクの起動時に渡す
+    pub workers: Arc<WorkerMonitor>,
+}
+
+// HTTPとgRPCの両方を作成する関数。リポジトリとイベントの配信先は両者で共有する
+pub fn create_app_with_grpc(pool: SqlitePool, config: &Config) -> AppServices {
+    let workers = Arc::new(WorkerMonitor::new());
+    let health = Arc::new(HealthChecker::new(pool.clone(), workers.clone()));
+    let metrics = Arc::new(Metrics::new(pool.clone()));
+    let state = app_state(pool, config, &metrics);
+    let event_stream = state.event_stream.clone();
+    let grpc = TodoGrpcService::new(state.clone(), config.server.default_language).into_server();
+    AppServices {
+        http: create_router(state, health, metrics, config),
+        grpc,
+        event_stream,
+        workers,
+    }
+}
+
+// HTTPサーバを動かす関数。shutdown が発火したら新しい接続を受け付けるのをやめ、
+// 処理中のリクエストが終わるのを drain_timeout まで待つ。過ぎたら待つのをやめて戻る
+// （残った接続は各自のタスクで動いているので、プロセスの終了とともに切れる）
+pub async fn serve_http(
+    listener: TcpListener,
+    app: Router,
+    shutdown: CancellationToken,
+    drain_timeout: Duration,
+) -> std::io::Result<()> {
+    // レート制限でIPアドレスごとに数えられるよう、接続元のアドレスをリクエストに付ける
+    let server = axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(shutdown.clone().cancelled_owned())
+    .into_future();
+    let deadline = async {
+        shutdown.cancelled().await;
+        tokio::time::sleep(drain_timeout).await;
+    };
+    tokio::select! {
+        result = server => result,
+        _ = deadline => {
+            tracing::warn!(
+                "connections still open after {:?}, giving up on draining",
+                drain_timeout
+            );
+            Ok(())
+        }
+    }
+}
+
+// HTTPSで待ち受ける関数。TLSのハンドシェイクは接続ごとのタスクで行い、証明書は
+// `acceptor` の持つ ReloadingCertificate から接続のたびに引くので、差し替えは次の接続から効く。
+// shutdown 後の扱いは serve_http と同じ
+pub async fn serve_https(
+    listener: TcpListener,
+    app: Router,
+    acceptor: TlsAcceptor,
+    shutdown: CancellationToken,
+    drain_timeout: Duration,
+    handshake_timeout: Duration,
+) -> std::io::Result<()> {
+    let graceful = GracefulShutdown::new();
+    loop {
+        let (stream, remote_addr) = tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok(accepted) => accepted,
+                Err(e) => {
+                    // 開けるファイルの上限などの一時的なエラーで止まらないよう、少し待って続ける
+                    tracing::warn!("failed to accept a connection: {}", e);
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                    continue;
+                }
+            },
+            _ = shutdown.cancelled() => break,
+        };
+        let acceptor = acceptor.clone();
+        let app = app.clone();
+        let watcher = graceful.watcher();
+        tokio::spawn(async move {
+            // 何も送らずに接続だけを抱え込むクライアントで、タスクとソケットが溜まらないようにする
+            let stream =
+                match tokio::time::timeout(handshake_timeout, acceptor.accept(stream)).await {
+                    Ok(Ok(stream)) => stream,
+                    Ok(Err(e)) => {
+                        tracing::debug!("TLS handshake with {} failed: {}", remote_addr, e);
+                        return;
+                    }
+                    Err(_) => {
+                        tracing::debug!("TLS handshake with {} timed out", remote_addr);
+                        return;
+                    }
+                };
+            // レート制限でIPアドレスごとに数えられるよう、接続元のアドレスをリクエストに付ける
+            let service = hyper::service::service_fn(move |mut request: hyper::Request<_>| {
+                request
+                    .extensions_mut()
+                    .insert(axum::extract::ConnectInfo(remote_addr));
+                tower::ServiceExt::oneshot(app.clone(), request.map(axum::body::Body::new))
+            });
+            let builder = hyper_util::server::conn::auto::Builder::new(TokioExecutor::new());
+            let connection = builder.serve_connection_with_upgrades(TokioIo::new(stream), service);
+            if let Err(e) = watcher.watch(connection.into_owned()).await {
+                tracing::debug!(
+                    "connection from {} closed with an error: {}",
+                    remote_addr,
+                    e
+                );
+            }
+        });
+    }
+    drop(listener);
+    if tokio::time::timeout(drain_timeout, graceful.shutdown())
         .await
-        .expect("Failed to connect to database");
-
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS todos (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            title TEXT NOT NULL,
-            completed BOOLEAN NOT NULL DEFAULT 0,
-            position INTEGER NOT NULL DEFAULT 0
-        )
-        "#,
+        .is_err()
+    {
+        tracing::warn!(
+            "connections still open after {:?}, giving up on draining",
+            drain_timeout
+        );
+    }
+    Ok(())
+}
+
+// gRPCサーバを起動する関数。HTTPとは別のポートで待ち受け、shutdown が発火したら処理中の呼び出しを終えて止まる
+pub fn spawn_grpc_server(
+    listener: TcpListener,
+    service: TodoServiceServer<TodoGrpcService>,
+    shutdown: CancellationToken,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        if let Err(e) = tonic::transport::Server::builder()
+            .add_service(service)
+            .serve_with_incoming_shutdown(
+                TcpListenerStream::new(listener),
+                shutdown.cancelled_owned(),
+            )
+            .await
+        {
+            tracing::error!("gRPC server error: {:?}", e);
+        }
+    })
+}
+
+// リマインダーの配信タスクを起動する関数
+pub fn spawn_reminder_scheduler(
+    pool: SqlitePool,
+    notifier: Arc<dyn Notifier>,
+    poll_interval: Duration,
+    workers: &Arc<WorkerMonitor>,
+    shutdown: CancellationToken,
+) -> JoinHandle<()> {
+    ReminderScheduler {
+        reminders: Arc::new(ReminderStore::new(pool)),
+        notifier,
+        clock: Arc::new(SystemClock),
+        poll_interval,
+    }
+    .spawn(
+        workers.register("reminder_scheduler", poll_interval),
+        shutdown,
     )
-    .execute(&pool)
-    .await
-    .expect("Failed to create table");
+}
 
-    let store: Arc<dyn TodoRepository> = Arc::new(TodoStore::new(pool));
-    create_router(store)
+// Webhookのアウトボックスを送信するタスクを起動する関数
+pub fn spawn_webhook_dispatcher(
+    pool: SqlitePool,
+    poll_interval: Duration,
+    workers: &Arc<WorkerMonitor>,
+    shutdown: CancellationToken,
+) -> JoinHandle<()> {
+    WebhookDispatcher {
+        outbox: Arc::new(WebhookOutboxStore::new(pool)),
+        sender: Arc::new(HttpWebhookSender::new()),
+        clock: Arc::new(SystemClock),
+        poll_interval,
+    }
+    .spawn(
+        workers.register("webhook_dispatcher", poll_interval),
+        shutdown,
+    )
 }
 
+fn app_state(pool: SqlitePool, config: &Config, metrics: &Arc<Metrics>) -> AppState {
+    let todos: Arc<dyn TodoRepository> = Arc::new(MeteredTodoRepository::new(
+        Arc::new(TodoStore::with_webhook_outbox(
+            pool.clone(),
+            Arc::new(SystemClock),
+        )),
+        metrics.clone(),
+    ));
+    let comments: Arc<dyn CommentRepository> = Arc::new(CommentStore::new(pool.clone()));
+    let attachment_repo: Arc<dyn AttachmentRepository> =
+        Arc::new(AttachmentStore::new(pool.clone()));
+    let reminders: Arc<dyn ReminderRepository> = Arc::new(ReminderStore::new(pool.clone()));
+    let webhooks: Arc<dyn WebhookRepository> = Arc::new(WebhookStore::new(pool.clone()));
+    let webhook_outbox: Arc<dyn WebhookOutbox> = Arc::new(WebhookOutboxStore::new(pool.clone()));
+    let idempotency: Arc<dyn IdempotencyStore> = Arc::new(IdempotencyKeyStore::new(pool));
+    // Webhookの配信はTodoStoreが変更と同じトランザクションで積むので、ここではSSEなどへ流すだけ
+    let event_stream = Arc::new(EventBroadcaster::default());
+    let events: Arc<dyn EventPublisher> = event_stream.clone();
+    let blobs: Arc<dyn BlobStorage> =
+        Arc::new(LocalBlobStorage::new(config.attachments.dir.clone()));
+    AppState {
+        todos,
+        comments,
+        attachments: attachment_repo,
+        blobs,
+        attachment_limits: Arc::new(config.attachments.limits()),
+        reminders,
+        webhooks,
+        webhook_outbox,
+        events,
+        event_stream,
+        presence: Arc::new(PresenceRegistry::new(
+            Arc::new(SystemClock),
+            chrono::Duration::seconds(DEFAULT_LOCK_TTL_SECS),
+        )),
+        limits: Arc::new(config.limits.clone()),
+        idempotency,
+        legacy_status_codes: config.server.legacy_status_codes,
+    }
+}
+
+/// 現行バージョンのAPIのパスのプレフィックス
+pub use todo_api_types::API_V1_PREFIX;
+
+/// `/api/v1` のルート（グループ, メソッド, パス）。ルーターはこの表から組み立てるので、
+/// ルートを足すときはここに書き、`v1_handler` にハンドラを足す。OpenAPIのテストもこの表を見る
+pub const V1_ROUTES: [(RouteGroup, Method, &str); 27] = [
+    (RouteGroup::Todos, Method::GET, "/todos"),
+    (RouteGroup::Todos, Method::POST, "/todos"),
+    (RouteGroup::Todos, Method::PUT, "/todos/reorder"),
+    (RouteGroup::Todos, Method::GET, "/todos/:id"),
+    (RouteGroup::Todos, Method::PUT, "/todos/:id"),
+    (RouteGroup::Todos, Method::DELETE, "/todos/:id"),
+    (RouteGroup::Comments, Method::GET, "/todos/:id/comments"),
+    (RouteGroup::Comments, Method::POST, "/todos/:id/comments"),
+    (
+        RouteGroup::Comments,
+        Method::GET,
+        "/todos/:id/comments/:comment_id",
+    ),
+    (
+        RouteGroup::Comments,
+        Method::PUT,
+        "/todos/:id/comments/:comment_id",
+    ),
+    (
+        RouteGroup::Comments,
+        Method::DELETE,
+        "/todos/:id/comments/:comment_id",
+    ),
+    (RouteGroup::Reminders, Method::GET, "/todos/:id/reminders"),
+    (RouteGroup::Reminders, Method::POST, "/todos/:id/reminders"),
+    (
+        RouteGroup::Reminders,
+        Method::DELETE,
+        "/todos/:id/reminders/:reminder_id",
+    ),
+    (
+        RouteGroup::Attachments,
+        Method::GET,
+        "/todos/:id/attachments",
+    ),
+    (
+        RouteGroup::Attachments,
+        Method::POST,
+        "/todos/:id/attachments",
+    ),
+    (
+        RouteGroup::Attachments,
+        Method::GET,
+        "/todos/:id/attachments/:attachment_id",
+    ),
+    (
+        RouteGroup::Attachments,
+        Method::DELETE,
+        "/todos/:id/attachments/:attachment_id",
+    ),
+    (
+        RouteGroup::Attachments,
+        Method::GET,
+        "/todos/:id/attachments/:attachment_id/content",
+    ),
+    (RouteGroup::Realtime, Method::GET, "/events"),
+    (RouteGroup::Realtime, Method::GET, "/ws"),
+    (RouteGroup::Webhooks, Method::GET, "/webhooks"),
+    (RouteGroup::Webhooks, Method::POST, "/webhooks"),
+    (RouteGroup::Webhooks, Method::GET, "/webhooks/:id"),
+    (RouteGroup::Webhooks, Method::PUT, "/webhooks/:id"),
+    (RouteGroup::Webhooks, Method::DELETE, "/webhooks/:id"),
+    (
+        RouteGroup::Webhooks,
+        Method::GET,
+        "/webhooks/:id/deliveries",
+    ),
+];
+
 // ルーターを作成する共通関数
-fn create_router(store: Arc<dyn TodoRepository>) -> Router {
-    use crate::handlers::*;
-    use axum::{
-        routing::{delete, get, post, put},
-        Router,
+fn create_router(
+    state: AppState,
+    health: Arc<HealthChecker>,
+    metrics: Arc<Metrics>,
+    config: &Config,
+) -> Router {
+    use crate::application::errors::AppError;
+    use crate::handlers::docs::*;
+    use crate::handlers::graphql::*;
+    use crate::handlers::handler;
+    use crate::handlers::health::{healthz, readyz};
+    use crate::handlers::metrics::metrics as metrics_handler;
+    use crate::presentation::allow::{advertise_options, method_not_allowed_problem};
+    use crate::presentation::deprecation::{deprecated_alias, DEPRECATION_HEADER, SUNSET_HEADER};
+    use crate::presentation::graphql::build_schema;
+    use crate::presentation::http_metrics::record_http_metrics;
+    use crate::presentation::idempotency::{IDEMPOTENCY_KEY_HEADER, IDEMPOTENT_REPLAYED_HEADER};
+    use crate::presentation::request_context::{
+        current_request_id, track_request, REQUEST_ID_HEADER,
+    };
+    use axum::{middleware, routing::get, Extension, Router};
+
+    // multipartのヘッダ分を見込んで、添付の上限より少し大きめに受け付ける
+    let upload_body_limit = state.attachment_limits.max_size_bytes + 64 * 1024;
+    let limits = RouteLimitLayers::new(state.limits.clone());
+    let idempotency = Idempotency {
+        store: state.idempotency.clone(),
+        clock: Arc::new(SystemClock),
+        ttl: chrono::Duration::seconds(config.idempotency.ttl_secs as i64),
+        request_timeout: Duration::from_secs(config.idempotency.request_timeout_secs),
+        in_progress_timeout: chrono::Duration::seconds(
+            config.idempotency.in_progress_timeout_secs as i64,
+        ),
     };
 
-    // CORS設定
+    // CORS設定（許可するオリジンは config.cors）
     let cors = CorsLayer::new()
-        .allow_origin(Any) // 開発用: すべてのオリジンを許可。本番では .allow_origin(["http://localhost:3000"].map(|o| o.parse().unwrap())) などに変更
+        .allow_origin(allowed_origins(&config.cors))
         .allow_methods([
             axum::http::Method::GET,
             axum::http::Method::POST,
@@ -91,16 +470,41 @@ fn create_router(store: Arc<dyn TodoRepository>) -> Router {
             axum::http::Method::DELETE,
             axum::http::Method::OPTIONS,
         ])
-        .allow_headers([axum::http::header::CONTENT_TYPE]);
+        .allow_headers([
+            axum::http::header::CONTENT_TYPE,
+            axum::http::HeaderName::from_static("last-event-id"),
+            REQUEST_ID_HEADER,
+            IDEMPOTENCY_KEY_HEADER,
+        ])
+        .expose_headers([
+            REQUEST_ID_HEADER,
+            DEPRECATION_HEADER,
+            SUNSET_HEADER,
+            axum::http::header::LINK,
+            IDEMPOTENT_REPLAYED_HEADER,
+        ]);
 
     // ログ設定（HTTPリクエスト/レスポンスを自動ログ）
     let trace_layer = TraceLayer::new_for_http()
         .make_span_with(|request: &axum::http::Request<_>| {
-            tracing::info_span!(
+            // OTLPで送るときのspan名はルートのテンプレート単位にして、IDごとに分かれないようにする
+            let route = request
+                .extensions()
+                .get::<MatchedPath>()
+                .map_or(request.uri().path(), |path| path.as_str());
+            // track_request の内側で呼ばれるので、採番済み（または引き継いだ）IDを参照できる
+            let request_id = current_request_id().unwrap_or_default();
+            let span = tracing::info_span!(
                 "http_request",
                 method = %request.method(),
                 uri = %request.uri(),
-            )
+                request_id = %request_id,
+                otel.name = %format_args!("{} {}", request.method(), route),
+                otel.kind = "server",
+                otel.status_code = tracing::field::Empty,
+            );
+            telemetry::set_remote_parent(&span, request.headers());
+            span
         })
         .on_request(|_request: &axum::http::Request<_>, _span: &tracing::Span| {
             tracing::debug!("request started");
@@ -119,7 +523,8 @@ fn create_router(store: Arc<dyn TodoRepository>) -> Router {
         .on_failure(
             |_failure_class: ServerErrorsFailureClass,
              latency: std::time::Duration,
-             _span: &tracing::Span| {
+             span: &tracing::Span| {
+                span.record("otel.status_code", "ERROR");
                 tracing::error!(
                     failure = ?_failure_class,
                     latency = ?latency,
@@ -128,15 +533,157 @@ fn create_router(store: Arc<dyn TodoRepository>) -> Router {
             },
         );
 
-    Router::new()
-        .route("/", get(handler))
-        .route("/todos", get(get_todos))
-        .route("/todos", post(create_todo))
-        .route("/todos/reorder", put(reorder_todos))
-        .route("/todos/:id", get(get_todo_by_id))
-        .route("/todos/:id", put(update_todo))
-        .route("/todos/:id", delete(delete_todo))
-        .with_state(store)
+    let mut router = Router::new().route("/", get(handler)).nest(
+        API_V1_PREFIX,
+        v1_routes(upload_body_limit, &limits, &idempotency),
+    );
+    if config.features.api_docs {
+        router = router
+            .route("/openapi.json", get(openapi_json))
+            .route("/docs", get(swagger_ui));
+    }
+    if config.features.graphql {
+        // GraphQLはスキーマ自体で互換性を保つので、バージョンのプレフィックスは付けない
+        router = router.merge(
+            limits.apply(
+                RouteGroup::Graphql,
+                Router::new()
+                    .route("/graphql", get(graphiql).post(graphql))
+                    .route("/graphql/ws", get(graphql_ws)),
+            ),
+        );
+    }
+    if config.features.legacy_paths {
+        // プレフィックスなしのパスは移行期間中の別名として残す
+        router = router.merge(
+            v1_routes(upload_body_limit, &limits, &idempotency)
+                .layer(middleware::from_fn(deprecated_alias)),
+        );
+    }
+
+    // プローブとスクレイプは頻繁に来るので、アクセスログ・CORS・計測の層の外に置く
+    let mut probes = Router::new()
+        .route("/healthz", get(healthz))
+        .route("/readyz", get(readyz))
+        .with_state(health);
+    if config.features.metrics {
+        probes = probes.merge(
+            Router::new()
+                .route("/metrics", get(metrics_handler))
+                .with_state(metrics.clone()),
+        );
+    }
+
+    // どのルートにも当たらないリクエストも計測とアクセスログの対象にするため、層より前に置く
+    let router = router
+        .fallback(|| async { AppError::NotFound })
+        .layer(middleware::from_fn(method_not_allowed_problem))
+        .with_state(state)
+        .layer(middleware::from_fn_with_state(metrics, record_http_metrics))
+        .layer(Extension(build_schema()))
         .layer(cors)
         .layer(trace_layer)
+        .layer(middleware::from_fn_with_state(
+            config.server.default_language,
+            track_request,
+        ))
+        .merge(probes);
+
+    // axumが付けた `Allow` を見るため、ルーター全体を包む
+    Router::new()
+        .fallback_service(router)
+        .layer(middleware::from_fn(advertise_options))
+}
+
+// `*` ならすべて、それ以外は列挙したオリジンだけ許可する（値は Config::validate で検証済み）
+fn allowed_origins(cors: &CorsConfig) -> AllowOrigin {
+    if cors.allows_any_origin() {
+        AllowOrigin::any()
+    } else {
+        AllowOrigin::list(
+            cors.allowed_origins
+                .iter()
+                .map(|origin| origin.parse().expect("origins are validated in Config")),
+        )
+    }
+}
+
+// /api/v1 のルート。形は presentation::dto::v1 のDTOで決まる。
+// `V1_ROUTES` の表の順に登録し、レート制限とボディの上限はグループ（config の `[limits.<グループ>]`）ごとに掛ける
+fn v1_routes(
+    upload_body_limit: usize,
+    limits: &RouteLimitLayers,
+    idempotency: &Idempotency,
+) -> Router<AppState> {
+    let mut groups: HashMap<RouteGroup, Router<AppState>> = HashMap::new();
+    for (group, method, path) in &V1_ROUTES {
+        let handler = v1_handler(method, path, upload_body_limit, idempotency);
+        let routes = groups
+            .remove(group)
+            .unwrap_or_default()
+            .route(path, handler);
+        groups.insert(*group, routes);
+    }
+    groups
+        .into_iter()
+        .fold(Router::new(), |router, (group, routes)| {
+            router.merge(limits.apply(group, routes))
+        })
+}
+
+// `V1_ROUTES` の1行に対応するハンドラ。
+// POSTは `Idempotency-Key` が付いていれば、再送されても一度だけ処理する
+fn v1_handler(
+    method: &Method,
+    path: &str,
+    upload_body_limit: usize,
+    idempotency: &Idempotency,
+) -> MethodRouter<AppState> {
+    use crate::handlers::attachments::*;
+    use crate::handlers::comments::*;
+    use crate::handlers::events::*;
+    use crate::handlers::reminders::*;
+    use crate::handlers::webhooks::*;
+    use crate::handlers::ws::*;
+    use crate::handlers::*;
+    use crate::presentation::idempotency::idempotent;
+    use axum::routing::on;
+
+    let filter = MethodFilter::try_from(method.clone()).expect("routes use standard methods");
+    let idempotent = || axum::middleware::from_fn_with_state(idempotency.clone(), idempotent);
+    match (method.as_str(), path) {
+        ("GET", "/todos") => on(filter, get_todos),
+        ("POST", "/todos") => on(filter, create_todo).layer(idempotent()),
+        ("PUT", "/todos/reorder") => on(filter, reorder_todos),
+        ("GET", "/todos/:id") => on(filter, get_todo_by_id),
+        ("PUT", "/todos/:id") => on(filter, update_todo),
+        ("DELETE", "/todos/:id") => on(filter, delete_todo),
+        ("GET", "/todos/:id/comments") => on(filter, list_comments),
+        ("POST", "/todos/:id/comments") => on(filter, create_comment).layer(idempotent()),
+        ("GET", "/todos/:id/comments/:comment_id") => on(filter, get_comment),
+        ("PUT", "/todos/:id/comments/:comment_id") => on(filter, update_comment),
+        ("DELETE", "/todos/:id/comments/:comment_id") => on(filter, delete_comment),
+        ("GET", "/todos/:id/reminders") => on(filter, list_reminders),
+        ("POST", "/todos/:id/reminders") => on(filter, create_reminder).layer(idempotent()),
+        ("DELETE", "/todos/:id/reminders/:reminder_id") => on(filter, delete_reminder),
+        ("GET", "/todos/:id/attachments") => on(filter, list_attachments),
+        // 冪等キーの指紋を取るときも添付の上限まで読めるよう、上限の内側に置く
+        ("POST", "/todos/:id/attachments") => on(filter, upload_attachment).layer(
+            tower::ServiceBuilder::new()
+                .layer(DefaultBodyLimit::max(upload_body_limit))
+                .layer(idempotent()),
+        ),
+        ("GET", "/todos/:id/attachments/:attachment_id") => on(filter, get_attachment),
+        ("DELETE", "/todos/:id/attachments/:attachment_id") => on(filter, delete_attachment),
+        ("GET", "/todos/:id/attachments/:attachment_id/content") => on(filter, download_attachment),
+        ("GET", "/events") => on(filter, stream_events),
+        ("GET", "/ws") => on(filter, ws_handler),
+        ("GET", "/webhooks") => on(filter, list_webhooks),
+        ("POST", "/webhooks") => on(filter, create_webhook).layer(idempotent()),
+        ("GET", "/webhooks/:id") => on(filter, get_webhook),
+        ("PUT", "/webhooks/:id") => on(filter, update_webhook),
+        ("DELETE", "/webhooks/:id") => on(filter, delete_webhook),
+        ("GET", "/webhooks/:id/deliveries") => on(filter, list_webhook_deliveries),
+        _ => unreachable!("no handler for {} {}", method, path),
+    }
 }
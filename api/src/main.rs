@@ -1,25 +1,261 @@
-use rust_todo_app::create_app;
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::Duration;
+
+use clap::Parser;
+use futures_util::future::join_all;
+use rust_todo_app::application::ports::notifier::Notifier;
+use rust_todo_app::config::{CliArgs, Config, LogFormat, ReminderConfig};
+use rust_todo_app::infrastructure::notifiers::log_notifier::LogNotifier;
+use rust_todo_app::infrastructure::notifiers::webhook_notifier::WebhookNotifier;
+#[cfg(feature = "otel")]
+use rust_todo_app::infrastructure::telemetry::{self, Telemetry};
+use rust_todo_app::infrastructure::tls::{spawn_certificate_reloader, ReloadingCertificate};
+use rust_todo_app::presentation::https::{https_redirect_app, with_hsts};
+use rust_todo_app::{
+    connect_database, create_app_with_grpc, serve_http, serve_https, spawn_grpc_server,
+    spawn_reminder_scheduler, spawn_webhook_dispatcher,
+};
+use tokio::time::{timeout_at, Instant};
+use tokio_util::sync::CancellationToken;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer};
 
 #[tokio::main]
-async fn main() {
-    // ログの初期化
-    tracing_subscriber::fmt()
-        .with_target(false)
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "rust_todo_app=debug,tower_http=debug".into()),
-        )
-        .init();
+async fn main() -> ExitCode {
+    // 設定の誤りはログの初期化より前に、すべてまとめて標準エラーへ出す
+    let config = match Config::load(&CliArgs::parse()) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::from(2);
+        }
+    };
+
+    #[cfg(feature = "otel")]
+    let telemetry = match config.otel.enabled {
+        true => match telemetry::init(&config.otel) {
+            Ok(telemetry) => Some(telemetry),
+            Err(e) => {
+                eprintln!("failed to set up the OTLP exporter: {}", e);
+                return ExitCode::FAILURE;
+            }
+        },
+        false => None,
+    };
+    #[cfg(feature = "otel")]
+    init_tracing(&config, telemetry.as_ref());
+    #[cfg(not(feature = "otel"))]
+    init_tracing(&config);
+    if config.cors.allows_any_origin() {
+        tracing::warn!("CORS allows any origin; set cors.allowed_origins in production");
+    }
+
+    // TLSを有効にしたときは、起動前に証明書を読めるか確かめる
+    let tls = match config.tls.enabled {
+        true => match ReloadingCertificate::load(&config.tls.cert_path, &config.tls.key_path)
+            .and_then(|certificate| Ok((certificate.acceptor()?, certificate)))
+        {
+            Ok(tls) => Some(tls),
+            Err(e) => {
+                tracing::error!("failed to load the TLS certificate: {}", e);
+                return ExitCode::FAILURE;
+            }
+        },
+        false => None,
+    };
+
+    // 設定の誤りと同じく、開けなければ終了コード2で終わる
+    let pool = match connect_database(&config.database).await {
+        Ok(pool) => pool,
+        Err(e) => {
+            tracing::error!("failed to open the database {}: {}", config.database.url, e);
+            return ExitCode::from(2);
+        }
+    };
+    let shutdown = CancellationToken::new();
+    let services = create_app_with_grpc(pool.clone(), &config);
+    let mut tasks = Vec::new();
 
-    let database_url =
-        std::env::var("DATABASE_URL").unwrap_or_else(|_| "sqlite:todos.db".to_string());
-    let app = create_app(&database_url).await;
+    // リマインダーの配信タスクを起動
+    if config.features.reminder_delivery {
+        tasks.push(spawn_reminder_scheduler(
+            pool.clone(),
+            reminder_notifier(&config.reminders),
+            Duration::from_secs(config.reminders.poll_interval_secs),
+            &services.workers,
+            shutdown.clone(),
+        ));
+    }
 
-    let listener = tokio::net::TcpListener::bind("0.0.0.0:3000")
+    // Webhookの送信タスクを起動
+    if config.features.webhook_delivery {
+        tasks.push(spawn_webhook_dispatcher(
+            pool.clone(),
+            Duration::from_secs(config.webhooks.poll_interval_secs),
+            &services.workers,
+            shutdown.clone(),
+        ));
+    }
+
+    // grpc_addr を設定したときだけgRPCサーバを別ポートで起動
+    if let Some(grpc_addr) = &config.server.grpc_addr {
+        let grpc_listener = tokio::net::TcpListener::bind(grpc_addr)
+            .await
+            .expect("Failed to bind gRPC address");
+        tracing::info!("gRPC server running on {}", grpc_addr);
+        tasks.push(spawn_grpc_server(
+            grpc_listener,
+            services.grpc,
+            shutdown.clone(),
+        ));
+    }
+
+    let listener = tokio::net::TcpListener::bind(config.http_addr())
         .await
         .expect("Failed to bind to address");
 
-    tracing::info!("Server running on http://127.0.0.1:3000");
+    let drain_timeout = Duration::from_secs(config.server.shutdown_timeout_secs);
+    let scheme = if tls.is_some() { "https" } else { "http" };
+    tracing::info!("Server running on {}://{}", scheme, config.http_addr());
+
+    // redirect_http_port を設定したときだけ、HTTPで来たリクエストをHTTPSへ案内する
+    if let (Some(_), Some(redirect_port)) = (&tls, config.tls.redirect_http_port) {
+        let redirect_addr = format!("{}:{}", config.server.host, redirect_port);
+        let redirect_listener = tokio::net::TcpListener::bind(&redirect_addr)
+            .await
+            .expect("Failed to bind the HTTP redirect address");
+        tracing::info!("Redirecting http://{} to HTTPS", redirect_addr);
+        let shutdown = shutdown.clone();
+        let app = https_redirect_app(config.server.port);
+        tasks.push(tokio::spawn(async move {
+            if let Err(e) = serve_http(redirect_listener, app, shutdown, drain_timeout).await {
+                tracing::error!("HTTP redirect server error: {:?}", e);
+            }
+        }));
+    }
+
+    // シグナルを受けたら、SSEなどの続く接続を閉じてから全体に終了を伝える
+    let signal = tokio::spawn({
+        let shutdown = shutdown.clone();
+        let event_stream = services.event_stream.clone();
+        async move {
+            let name = shutdown_signal().await;
+            tracing::info!("{} received, draining connections", name);
+            event_stream.close();
+            shutdown.cancel();
+            Instant::now()
+        }
+    });
+
+    let mut exit_code = ExitCode::SUCCESS;
+    let served = match tls {
+        Some((acceptor, certificate)) => {
+            tasks.push(spawn_certificate_reloader(
+                certificate,
+                Duration::from_secs(config.tls.reload_interval_secs),
+                shutdown.clone(),
+            ));
+            let app = with_hsts(services.http, &config.tls);
+            let handshake_timeout = Duration::from_secs(config.tls.handshake_timeout_secs);
+            serve_https(
+                listener,
+                app,
+                acceptor,
+                shutdown.clone(),
+                drain_timeout,
+                handshake_timeout,
+            )
+            .await
+        }
+        None => serve_http(listener, services.http, shutdown.clone(), drain_timeout).await,
+    };
+    if let Err(e) = served {
+        tracing::error!("Server error: {:?}", e);
+        exit_code = ExitCode::FAILURE;
+    }
+
+    // HTTPが先に止まった場合も、ほかのタスクを止める
+    services.event_stream.close();
+    shutdown.cancel();
+    // 待つ時間はシグナルを受けた時点から数える
+    let signaled_at = if signal.is_finished() {
+        signal.await.unwrap_or_else(|_| Instant::now())
+    } else {
+        Instant::now()
+    };
+    let deadline = signaled_at + drain_timeout;
+    if timeout_at(deadline, join_all(tasks)).await.is_err() {
+        tracing::warn!("background tasks did not stop within {:?}", drain_timeout);
+    }
+
+    // 使用中の接続が返らなくても、期限を過ぎたら待たずに終わる
+    if timeout_at(deadline, pool.close()).await.is_err() {
+        tracing::warn!(
+            "database connections did not close within {:?}",
+            drain_timeout
+        );
+    }
+    tracing::info!("Shutdown complete");
+    // 送り残しのspanを送る。送信の完了を同期で待つので、ランタイムのスレッドをふさがないようにする
+    #[cfg(feature = "otel")]
+    if let Some(telemetry) = telemetry {
+        let _ = tokio::task::spawn_blocking(move || telemetry.shutdown()).await;
+    }
+    exit_code
+}
+
+// SIGINT（Ctrl+C）かSIGTERMを待ち、受けたシグナルの名前を返す
+async fn shutdown_signal() -> &'static str {
+    let interrupt = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("Failed to listen for Ctrl+C");
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Failed to listen for SIGTERM")
+            .recv()
+            .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = interrupt => "SIGINT",
+        _ = terminate => "SIGTERM",
+    }
+}
+
+// ログの初期化。level は Config::validate で検証済み。
+// OTLPへはログの出力レベルと関係なく、このクレートのinfo以上のspanを送る
+fn init_tracing(config: &Config, #[cfg(feature = "otel")] telemetry: Option<&Telemetry>) {
+    let fmt = tracing_subscriber::fmt::layer().with_target(false);
+    let fmt = match config.log.format {
+        LogFormat::Text => fmt.boxed(),
+        LogFormat::Json => fmt.json().boxed(),
+    };
+    let registry =
+        tracing_subscriber::registry().with(fmt.with_filter(EnvFilter::new(&config.log.level)));
+    #[cfg(feature = "otel")]
+    let registry = registry.with(telemetry.map(|telemetry| {
+        telemetry.layer().with_filter(
+            tracing_subscriber::filter::Targets::new()
+                .with_target("rust_todo_app", tracing::Level::INFO),
+        )
+    }));
+    registry.init();
+}
 
-    axum::serve(listener, app).await.expect("Server error");
+// webhook_url があればWebhookへ、なければログへ通知する
+fn reminder_notifier(config: &ReminderConfig) -> Arc<dyn Notifier> {
+    match &config.webhook_url {
+        Some(url) => {
+            tracing::info!("Reminders will be posted to {}", url);
+            Arc::new(WebhookNotifier::new(url.clone()))
+        }
+        None => Arc::new(LogNotifier),
+    }
 }
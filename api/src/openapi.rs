@@ -0,0 +1,110 @@
+use utoipa::OpenApi;
+
+use crate::handlers;
+use crate::presentation::dto::v1::{
+    attachment_requests, attachment_responses, comment_requests, comment_responses,
+    reminder_requests, reminder_responses, todo_requests, todo_responses, webhook_requests,
+    webhook_responses,
+};
+use crate::presentation::dto::{error_responses, health_responses};
+use crate::API_V1_PREFIX;
+
+/// APIのOpenAPIドキュメント。
+/// ハンドラに付けた `#[utoipa::path]` とDTOの `ToSchema` から組み立てる。
+/// バージョンごとのパスはプレフィックス（`/api/v1`）を付けて載せる
+pub fn api_doc() -> utoipa::openapi::OpenApi {
+    let mut doc = RootApi::openapi();
+    doc.merge(with_prefix(V1Api::openapi(), API_V1_PREFIX));
+    doc
+}
+
+fn with_prefix(mut doc: utoipa::openapi::OpenApi, prefix: &str) -> utoipa::openapi::OpenApi {
+    doc.paths.paths = std::mem::take(&mut doc.paths.paths)
+        .into_iter()
+        .map(|(path, item)| (format!("{}{}", prefix, path), item))
+        .collect();
+    doc
+}
+
+/// バージョンによらないエンドポイント
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Rust Todo App API", description = "TODOとそのコメント・添付・リマインダー、Webhookを扱うAPI"),
+    paths(
+        handlers::handler,
+        handlers::health::healthz,
+        handlers::health::readyz,
+        handlers::metrics::metrics
+    ),
+    components(schemas(
+        error_responses::ProblemDetails,
+        error_responses::FieldErrorResponse,
+        health_responses::LivenessResponse,
+        health_responses::ReadinessResponse,
+        health_responses::CheckResponse,
+    )),
+    tags((name = "meta", description = "疎通確認・ヘルスチェック"))
+)]
+struct RootApi;
+
+/// `/api/v1` 配下のエンドポイント（パスはプレフィックスなしで書く）
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        handlers::get_todos,
+        handlers::create_todo,
+        handlers::reorder_todos,
+        handlers::get_todo_by_id,
+        handlers::update_todo,
+        handlers::delete_todo,
+        handlers::comments::list_comments,
+        handlers::comments::create_comment,
+        handlers::comments::get_comment,
+        handlers::comments::update_comment,
+        handlers::comments::delete_comment,
+        handlers::reminders::list_reminders,
+        handlers::reminders::create_reminder,
+        handlers::reminders::delete_reminder,
+        handlers::attachments::list_attachments,
+        handlers::attachments::upload_attachment,
+        handlers::attachments::get_attachment,
+        handlers::attachments::delete_attachment,
+        handlers::attachments::download_attachment,
+        handlers::events::stream_events,
+        handlers::ws::ws_handler,
+        handlers::webhooks::list_webhooks,
+        handlers::webhooks::create_webhook,
+        handlers::webhooks::get_webhook,
+        handlers::webhooks::update_webhook,
+        handlers::webhooks::delete_webhook,
+        handlers::webhooks::list_webhook_deliveries,
+    ),
+    components(schemas(
+        todo_requests::CreateTodoRequest,
+        todo_requests::UpdateTodoRequest,
+        todo_requests::ReorderRequest,
+        todo_responses::TodoResponse,
+        comment_requests::CreateCommentRequest,
+        comment_requests::UpdateCommentRequest,
+        comment_responses::CommentResponse,
+        comment_responses::CommentPageResponse,
+        reminder_requests::CreateReminderRequest,
+        reminder_responses::ReminderResponse,
+        attachment_requests::UploadAttachmentForm,
+        attachment_responses::AttachmentResponse,
+        webhook_requests::CreateWebhookRequest,
+        webhook_requests::UpdateWebhookRequest,
+        webhook_responses::WebhookResponse,
+        webhook_responses::WebhookDeliveryResponse,
+        webhook_responses::WebhookDeliveryPageResponse,
+    )),
+    tags(
+        (name = "todos", description = "TODO"),
+        (name = "comments", description = "TODOへのコメント"),
+        (name = "reminders", description = "期限前のリマインダー"),
+        (name = "attachments", description = "TODOへの添付ファイル"),
+        (name = "events", description = "変更のリアルタイム配信（SSE / WebSocket）"),
+        (name = "webhooks", description = "変更を外部へ通知するWebhook"),
+    )
+)]
+struct V1Api;
@@ -0,0 +1,67 @@
+use axum::{
+    body::Body,
+    extract::Request,
+    http::{header, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::application::errors::AppError;
+
+/// ルーター全体の外側に置き、`Allow` に `OPTIONS` を加える。
+/// CORSのプリフライトでない `OPTIONS` には、ボディなしの `204` と `Allow` だけを返す。
+///
+/// `Allow` はaxumがルートごとの層より外で付けるので、ルートに掛けた層からは見えない
+pub async fn advertise_options(request: Request, next: Next) -> Response {
+    let plain_options = request.method() == Method::OPTIONS
+        && !request
+            .headers()
+            .contains_key(header::ACCESS_CONTROL_REQUEST_METHOD);
+    let mut response = next.run(request).await;
+    // `Allow` がないのはパスに当たらなかったとき
+    let Some(allow) = response
+        .headers()
+        .get(header::ALLOW)
+        .and_then(|allow| allow.to_str().ok())
+    else {
+        return response;
+    };
+
+    let mut methods: Vec<&str> = allow
+        .split(',')
+        .map(str::trim)
+        .filter(|method| !method.is_empty())
+        .collect();
+    if !methods.contains(&Method::OPTIONS.as_str()) {
+        methods.push(Method::OPTIONS.as_str());
+    }
+    let allow = HeaderValue::from_str(&methods.join(",")).expect("method names are valid");
+    response.headers_mut().insert(header::ALLOW, allow);
+
+    if plain_options {
+        *response.status_mut() = StatusCode::NO_CONTENT;
+        *response.body_mut() = Body::empty();
+        response.headers_mut().remove(header::CONTENT_LENGTH);
+        response.headers_mut().remove(header::CONTENT_TYPE);
+    }
+    response
+}
+
+/// axumが返すボディなしの `405` をproblem+jsonにする。`Allow` はこの層より外でaxumが付けるので残る。
+/// 言語とリクエストIDを使うため、`track_request` より内側に置く
+pub async fn method_not_allowed_problem(request: Request, next: Next) -> Response {
+    let response = next.run(request).await;
+    if response.status() != StatusCode::METHOD_NOT_ALLOWED
+        || response.headers().contains_key(header::CONTENT_TYPE)
+    {
+        return response;
+    }
+    let (parts, _) = response.into_parts();
+    let mut problem = AppError::MethodNotAllowed.into_response();
+    for (name, value) in &parts.headers {
+        if name != header::CONTENT_LENGTH {
+            problem.headers_mut().append(name, value.clone());
+        }
+    }
+    problem
+}
@@ -0,0 +1,33 @@
+use axum::{
+    extract::Request,
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+
+use crate::API_V1_PREFIX;
+
+pub const DEPRECATION_HEADER: HeaderName = HeaderName::from_static("deprecation");
+pub const SUNSET_HEADER: HeaderName = HeaderName::from_static("sunset");
+
+/// プレフィックスなしのパスを廃止予定にした日時（RFC 9745 の `@<UNIX時刻>` 形式）。2026-10-19
+const DEPRECATED_SINCE: &str = "@1792368000";
+/// プレフィックスなしのパスを提供しなくなる日時（RFC 8594）
+const SUNSET_AT: &str = "Fri, 30 Apr 2027 00:00:00 GMT";
+
+/// 旧パス（`/todos` など）への応答に廃止予定であることと移行先を付ける
+pub async fn deprecated_alias(request: Request, next: Next) -> Response {
+    let successor = format!("{}{}", API_V1_PREFIX, request.uri().path());
+    let mut response = next.run(request).await;
+    let headers = response.headers_mut();
+    headers.insert(
+        DEPRECATION_HEADER,
+        HeaderValue::from_static(DEPRECATED_SINCE),
+    );
+    headers.insert(SUNSET_HEADER, HeaderValue::from_static(SUNSET_AT));
+    if let Ok(link) = HeaderValue::from_str(&format!("<{}>; rel=\"successor-version\"", successor))
+    {
+        headers.insert(axum::http::header::LINK, link);
+    }
+    response
+}
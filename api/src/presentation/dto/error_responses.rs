@@ -0,0 +1,3 @@
+pub use todo_api_types::error_responses::{
+    FieldErrorResponse, ProblemDetails, PROBLEM_CONTENT_TYPE,
+};
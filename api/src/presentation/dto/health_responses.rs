@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::infrastructure::health::CheckResult;
+
+/// `?verbose` を付けると、確認項目ごとの結果をJSONで返す
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct HealthQuery {
+    /// 値なし（`?verbose`）でも有効。`false` / `0` なら無効
+    #[param(value_type = Option<String>)]
+    pub verbose: Option<String>,
+}
+
+impl HealthQuery {
+    pub fn is_verbose(&self) -> bool {
+        matches!(self.verbose.as_deref(), Some(value) if value != "false" && value != "0")
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LivenessResponse {
+    /// 常に `ok`
+    pub status: String,
+    pub uptime_secs: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ReadinessResponse {
+    /// すべての項目が通れば `ok`、1つでも失敗すれば `unavailable`
+    pub status: String,
+    pub checks: Vec<CheckResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CheckResponse {
+    /// `database` / `schema` / `worker:<タスク名>`
+    pub name: String,
+    /// `ok` / `fail`
+    pub status: String,
+    pub latency_ms: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl From<CheckResult> for CheckResponse {
+    fn from(check: CheckResult) -> Self {
+        Self {
+            name: check.name,
+            status: if check.healthy { "ok" } else { "fail" }.to_string(),
+            latency_ms: check.latency.as_secs_f64() * 1000.0,
+            detail: check.detail,
+        }
+    }
+}
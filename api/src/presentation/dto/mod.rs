@@ -1,2 +1,9 @@
-pub mod todo_requests;
-pub mod todo_responses;
+//! リクエスト/レスポンスの形。
+//! 形はAPIのバージョンごとにモジュールを分ける（`/api/v1` は `v1`）。
+//! 互換性のない変更は新しいバージョンのモジュールに置き、ユースケースは共有する。
+//! エラー（problem+json）とヘルスチェックの形はバージョンによらない。
+//! クライアントと共有する形は `todo-api-types` クレートに置いてここから再公開し、
+//! ドメインの型からの変換（`From`）だけをこちらに書く
+pub mod error_responses;
+pub mod health_responses;
+pub mod v1;
@@ -0,0 +1,11 @@
+use utoipa::ToSchema;
+
+/// `multipart/form-data` で送るアップロードの中身（ドキュメント用）。
+/// 実際の読み込みはハンドラ側でフィールドを順に読んで行う
+#[derive(ToSchema)]
+#[allow(dead_code)]
+pub struct UploadAttachmentForm {
+    /// ファイル本体
+    #[schema(value_type = String, format = Binary)]
+    pub file: Vec<u8>,
+}
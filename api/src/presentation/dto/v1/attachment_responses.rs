@@ -0,0 +1,16 @@
+use crate::domain::entities::attachment::Attachment;
+pub use todo_api_types::v1::attachment_responses::AttachmentResponse;
+
+impl From<Attachment> for AttachmentResponse {
+    fn from(attachment: Attachment) -> Self {
+        Self {
+            id: attachment.id,
+            todo_id: attachment.todo_id,
+            filename: attachment.filename,
+            content_type: attachment.content_type,
+            size_bytes: attachment.size_bytes,
+            sha256: attachment.sha256,
+            created_at: attachment.created_at,
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use crate::application::usecases::comment::list::CommentPage;
+use crate::domain::entities::comment::Comment;
+pub use todo_api_types::v1::comment_responses::{CommentPageResponse, CommentResponse};
+
+impl From<Comment> for CommentResponse {
+    fn from(comment: Comment) -> Self {
+        Self {
+            id: comment.id,
+            todo_id: comment.todo_id,
+            author: comment.author,
+            body: comment.body,
+            created_at: comment.created_at,
+            edited_at: comment.edited_at,
+        }
+    }
+}
+
+impl From<CommentPage> for CommentPageResponse {
+    fn from(page: CommentPage) -> Self {
+        Self {
+            comments: page.comments.into_iter().map(Into::into).collect(),
+            page: page.page,
+            per_page: page.per_page,
+            total: page.total,
+        }
+    }
+}
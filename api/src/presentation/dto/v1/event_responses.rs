@@ -0,0 +1,22 @@
+use crate::domain::events::TodoEvent;
+use crate::presentation::dto::v1::todo_responses::TodoResponse;
+use serde::Serialize;
+
+/// イベントの `data` 部分。作成・更新はTODO全体、削除はID、並び替えは新しい順序を返す
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum TodoEventData {
+    Todo(TodoResponse),
+    Deleted { id: i64 },
+    Reordered { ids: Vec<i64> },
+}
+
+impl From<TodoEvent> for TodoEventData {
+    fn from(event: TodoEvent) -> Self {
+        match event {
+            TodoEvent::Created(todo) | TodoEvent::Updated(todo) => Self::Todo(todo.into()),
+            TodoEvent::Deleted { id } => Self::Deleted { id },
+            TodoEvent::Reordered { ids } => Self::Reordered { ids },
+        }
+    }
+}
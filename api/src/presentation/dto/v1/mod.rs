@@ -0,0 +1,12 @@
+pub mod attachment_requests;
+pub mod attachment_responses;
+pub mod comment_requests;
+pub mod comment_responses;
+pub mod event_responses;
+pub mod reminder_requests;
+pub mod reminder_responses;
+pub mod todo_requests;
+pub mod todo_responses;
+pub mod webhook_requests;
+pub mod webhook_responses;
+pub mod ws_messages;
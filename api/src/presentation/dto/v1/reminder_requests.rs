@@ -0,0 +1 @@
+pub use todo_api_types::v1::reminder_requests::CreateReminderRequest;
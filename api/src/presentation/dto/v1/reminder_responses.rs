@@ -0,0 +1,22 @@
+use crate::domain::entities::reminder::{Reminder, ReminderSchedule};
+pub use todo_api_types::v1::reminder_responses::ReminderResponse;
+
+impl From<Reminder> for ReminderResponse {
+    fn from(reminder: Reminder) -> Self {
+        let (remind_at, offset_minutes) = match reminder.schedule {
+            ReminderSchedule::At(at) => (Some(at), None),
+            ReminderSchedule::BeforeDue(offset) => (None, Some(offset.num_minutes())),
+        };
+        Self {
+            id: reminder.id,
+            todo_id: reminder.todo_id,
+            remind_at,
+            offset_minutes,
+            fire_at: reminder.fire_at,
+            created_at: reminder.created_at,
+            delivered_at: reminder.delivered_at,
+            attempts: reminder.attempts,
+            last_error: reminder.last_error,
+        }
+    }
+}
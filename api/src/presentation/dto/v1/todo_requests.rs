@@ -0,0 +1 @@
+pub use todo_api_types::v1::todo_requests::{CreateTodoRequest, ReorderRequest, UpdateTodoRequest};
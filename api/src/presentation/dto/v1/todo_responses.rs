@@ -1,13 +1,5 @@
 use crate::domain::entities::todo::Todo;
-use serde::{Deserialize, Serialize};
-
-#[derive(Serialize, Deserialize)]
-pub struct TodoResponse {
-    pub id: i64,
-    pub title: String,
-    pub completed: bool,
-    pub position: i64,
-}
+pub use todo_api_types::v1::todo_responses::TodoResponse;
 
 impl From<Todo> for TodoResponse {
     fn from(todo: Todo) -> Self {
@@ -16,6 +8,7 @@ impl From<Todo> for TodoResponse {
             title: todo.title,
             completed: todo.completed,
             position: todo.position,
+            due_at: todo.due_at,
         }
     }
 }
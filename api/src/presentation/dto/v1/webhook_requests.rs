@@ -0,0 +1,3 @@
+pub use todo_api_types::v1::webhook_requests::{
+    CreateWebhookRequest, DeliveryListQuery, UpdateWebhookRequest,
+};
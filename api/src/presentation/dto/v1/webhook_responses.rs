@@ -0,0 +1,47 @@
+use crate::application::usecases::webhook::list_deliveries::DeliveryPage;
+use crate::domain::entities::webhook::{Webhook, WebhookDelivery};
+pub use todo_api_types::v1::webhook_responses::{
+    WebhookDeliveryPageResponse, WebhookDeliveryResponse, WebhookResponse,
+};
+
+impl From<Webhook> for WebhookResponse {
+    fn from(webhook: Webhook) -> Self {
+        Self {
+            id: webhook.id,
+            url: webhook.url,
+            events: webhook.events,
+            created_at: webhook.created_at,
+            updated_at: webhook.updated_at,
+        }
+    }
+}
+
+impl From<WebhookDelivery> for WebhookDeliveryResponse {
+    fn from(delivery: WebhookDelivery) -> Self {
+        Self {
+            id: delivery.id,
+            webhook_id: delivery.webhook_id,
+            event: delivery.event,
+            payload: serde_json::from_str(&delivery.payload)
+                .unwrap_or(serde_json::Value::String(delivery.payload)),
+            status: delivery.status.as_str().to_string(),
+            attempts: delivery.attempts,
+            response_status: delivery.response_status,
+            last_error: delivery.last_error,
+            created_at: delivery.created_at,
+            next_attempt_at: delivery.next_attempt_at,
+            delivered_at: delivery.delivered_at,
+        }
+    }
+}
+
+impl From<DeliveryPage> for WebhookDeliveryPageResponse {
+    fn from(page: DeliveryPage) -> Self {
+        Self {
+            deliveries: page.deliveries.into_iter().map(Into::into).collect(),
+            page: page.page,
+            per_page: page.per_page,
+            total: page.total,
+        }
+    }
+}
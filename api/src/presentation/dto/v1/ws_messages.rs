@@ -0,0 +1,175 @@
+//! `/ws` でやり取りするメッセージ。どちらの向きも `type` でメッセージの種類を表すJSON。
+//! クライアントが `request_id` を付けると、結果が同じ `request_id` の `ack` / `error` で返る
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::entities::presence::{EditLock, PresenceSnapshot, Viewer};
+use crate::presentation::dto::v1::event_responses::TodoEventData;
+use crate::presentation::dto::v1::todo_requests::{CreateTodoRequest, UpdateTodoRequest};
+
+/// 接続時のクエリ。`user` は在席表示に使う名前
+#[derive(Deserialize)]
+pub struct WsConnectQuery {
+    pub user: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct ClientEnvelope {
+    pub request_id: Option<String>,
+    #[serde(flatten)]
+    pub message: ClientMessage,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    /// TODOの変更イベントと在席状況の配信を開始する
+    Subscribe,
+    Unsubscribe,
+    /// いま見ているTODO。`null` で何も見ていない状態に戻す
+    View {
+        todo_id: Option<i64>,
+    },
+    /// 編集ロックを取得する。期限内に送り直すと延長される
+    Lock {
+        todo_id: i64,
+    },
+    Unlock {
+        todo_id: i64,
+    },
+    CreateTodo {
+        #[serde(flatten)]
+        todo: CreateTodoRequest,
+    },
+    UpdateTodo {
+        id: u32,
+        #[serde(flatten)]
+        changes: UpdateTodoRequest,
+    },
+    DeleteTodo {
+        id: u32,
+    },
+    ReorderTodos {
+        ids: Vec<i64>,
+    },
+    Ping,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    /// 接続直後に1度だけ送る
+    Welcome {
+        connection_id: u64,
+        user: String,
+    },
+    Ack {
+        request_id: Option<String>,
+        result: serde_json::Value,
+    },
+    Error {
+        request_id: Option<String>,
+        code: ErrorCode,
+        message: String,
+    },
+    /// `id` はSSEのイベントIDと共通（`<起動時刻>-<通し番号>`）
+    Event {
+        id: String,
+        event: String,
+        data: TodoEventData,
+    },
+    Presence {
+        viewers: Vec<ViewerMessage>,
+        locks: Vec<LockMessage>,
+    },
+    Pong {
+        request_id: Option<String>,
+    },
+    /// 取りこぼしたイベントがあるので、一覧を取り直す必要がある
+    Resync,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    InvalidMessage,
+    ValidationFailed,
+    NotFound,
+    /// 他のユーザーが編集ロックを持っている
+    Locked,
+    Internal,
+}
+
+#[derive(Serialize)]
+pub struct ViewerMessage {
+    pub connection_id: u64,
+    pub user: String,
+    pub todo_id: Option<i64>,
+}
+
+impl From<Viewer> for ViewerMessage {
+    fn from(viewer: Viewer) -> Self {
+        Self {
+            connection_id: viewer.connection_id,
+            user: viewer.user,
+            todo_id: viewer.todo_id,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct LockMessage {
+    pub todo_id: i64,
+    pub connection_id: u64,
+    pub user: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl From<EditLock> for LockMessage {
+    fn from(lock: EditLock) -> Self {
+        Self {
+            todo_id: lock.todo_id,
+            connection_id: lock.connection_id,
+            user: lock.user,
+            expires_at: lock.expires_at,
+        }
+    }
+}
+
+impl From<PresenceSnapshot> for ServerMessage {
+    fn from(snapshot: PresenceSnapshot) -> Self {
+        Self::Presence {
+            viewers: snapshot.viewers.into_iter().map(Into::into).collect(),
+            locks: snapshot.locks.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ClientEnvelope, ClientMessage};
+
+    #[test]
+    fn update_message_keeps_null_due_at_distinct_from_missing() {
+        let envelope: ClientEnvelope = serde_json::from_str(
+            r#"{"type":"update_todo","request_id":"r1","id":3,"due_at":null}"#,
+        )
+        .unwrap();
+
+        assert_eq!(envelope.request_id.as_deref(), Some("r1"));
+        match envelope.message {
+            ClientMessage::UpdateTodo { id, changes } => {
+                assert_eq!(id, 3);
+                assert_eq!(changes.due_at, Some(None));
+                assert!(changes.title.is_none());
+            }
+            _ => panic!("expected update_todo"),
+        }
+    }
+
+    #[test]
+    fn unknown_type_is_rejected() {
+        assert!(serde_json::from_str::<ClientEnvelope>(r#"{"type":"explode"}"#).is_err());
+    }
+}
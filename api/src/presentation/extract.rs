@@ -0,0 +1,87 @@
+//! axumの抽出器を包み、リクエストの読み取りエラーも `AppError`（problem+json）で返す
+use async_trait::async_trait;
+use axum::{
+    extract::{
+        rejection::{JsonRejection, PathRejection, QueryRejection},
+        FromRequest, FromRequestParts, Request,
+    },
+    http::{request::Parts, StatusCode},
+};
+use serde::de::DeserializeOwned;
+
+use crate::application::errors::{AppError, ErrorMessage};
+
+/// JSONボディ
+pub struct JsonBody<T>(pub T);
+
+/// パスパラメータ
+pub struct PathParams<T>(pub T);
+
+/// クエリ文字列
+pub struct QueryParams<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for JsonBody<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        match axum::Json::<T>::from_request(request, state).await {
+            Ok(axum::Json(value)) => Ok(JsonBody(value)),
+            Err(JsonRejection::MissingJsonContentType(_)) => Err(AppError::UnsupportedMediaType(
+                ErrorMessage::new("json_content_type_required"),
+            )),
+            // `DefaultBodyLimit` を超えた
+            Err(rejection) if rejection.status() == StatusCode::PAYLOAD_TOO_LARGE => Err(
+                AppError::PayloadTooLarge(ErrorMessage::new("body_too_large")),
+            ),
+            Err(rejection) => Err(AppError::validation(
+                ErrorMessage::new("invalid_json").with_reason(rejection.body_text()),
+            )),
+        }
+    }
+}
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for PathParams<T>
+where
+    T: DeserializeOwned + Send,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        match axum::extract::Path::<T>::from_request_parts(parts, state).await {
+            Ok(axum::extract::Path(value)) => Ok(PathParams(value)),
+            Err(PathRejection::FailedToDeserializePathParams(rejection)) => {
+                Err(AppError::validation(
+                    ErrorMessage::new("invalid_path").with_reason(rejection.body_text()),
+                ))
+            }
+            Err(rejection) => Err(AppError::unexpected(rejection.body_text())),
+        }
+    }
+}
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for QueryParams<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        axum::extract::Query::<T>::from_request_parts(parts, state)
+            .await
+            .map(|axum::extract::Query(value)| QueryParams(value))
+            .map_err(|rejection: QueryRejection| {
+                AppError::validation(
+                    ErrorMessage::new("invalid_query").with_reason(rejection.body_text()),
+                )
+            })
+    }
+}
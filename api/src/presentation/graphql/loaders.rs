@@ -0,0 +1,53 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_graphql::dataloader::Loader;
+
+use crate::application::errors::AppError;
+use crate::application::ports::comment_repository::CommentRepository;
+use crate::application::ports::todo_repository::TodoRepository;
+use crate::domain::entities::todo::Todo;
+
+/// IDを指定したTODOの取得を `get_by_ids` の1回にまとめる
+pub struct TodoLoader {
+    todos: Arc<dyn TodoRepository>,
+}
+
+impl TodoLoader {
+    pub fn new(todos: Arc<dyn TodoRepository>) -> Self {
+        Self { todos }
+    }
+}
+
+impl Loader<u32> for TodoLoader {
+    type Value = Todo;
+    type Error = AppError;
+
+    async fn load(&self, keys: &[u32]) -> Result<HashMap<u32, Todo>, AppError> {
+        let todos = self.todos.get_by_ids(keys).await?;
+        Ok(todos
+            .into_iter()
+            .map(|todo| (todo.id as u32, todo))
+            .collect())
+    }
+}
+
+/// 一覧に並んだTODOのコメント数を `count_by_todos` の1回で数える
+pub struct CommentCountLoader {
+    comments: Arc<dyn CommentRepository>,
+}
+
+impl CommentCountLoader {
+    pub fn new(comments: Arc<dyn CommentRepository>) -> Self {
+        Self { comments }
+    }
+}
+
+impl Loader<u32> for CommentCountLoader {
+    type Value = u64;
+    type Error = AppError;
+
+    async fn load(&self, keys: &[u32]) -> Result<HashMap<u32, u64>, AppError> {
+        self.comments.count_by_todos(keys).await
+    }
+}
@@ -0,0 +1,70 @@
+//! GraphQLのスキーマ。RESTと同じ `usecases::todo` を呼び、入力チェックも v1 のDTOで行う
+mod loaders;
+mod mutation;
+mod query;
+mod subscription;
+mod types;
+
+use async_graphql::dataloader::DataLoader;
+use async_graphql::{Data, ErrorExtensions, Schema};
+use tracing::{error, warn};
+
+use crate::application::errors::AppError;
+use crate::presentation::i18n::{self, Lang};
+use crate::state::AppState;
+
+pub use loaders::{CommentCountLoader, TodoLoader};
+pub use mutation::MutationRoot;
+pub use query::QueryRoot;
+pub use subscription::SubscriptionRoot;
+
+pub type TodoSchema = Schema<QueryRoot, MutationRoot, SubscriptionRoot>;
+
+/// 入れ子にできる深さの上限。循環する参照はないが、極端なクエリを弾いておく
+const MAX_DEPTH: usize = 10;
+
+pub fn build_schema() -> TodoSchema {
+    Schema::build(QueryRoot, MutationRoot, SubscriptionRoot)
+        .limit_depth(MAX_DEPTH)
+        .finish()
+}
+
+/// リゾルバへ渡すデータ。DataLoader は1リクエスト（WebSocketなら1接続）の中でだけ問い合わせをまとめる
+pub fn request_data(state: &AppState, lang: Lang) -> Data {
+    let mut data = Data::default();
+    data.insert(state.clone());
+    data.insert(lang);
+    data.insert(DataLoader::new(
+        TodoLoader::new(state.todos.clone()),
+        tokio::spawn,
+    ));
+    data.insert(DataLoader::new(
+        CommentCountLoader::new(state.comments.clone()),
+        tokio::spawn,
+    ));
+    data
+}
+
+/// `AppError` をGraphQLのエラーにする。`extensions.code` はproblem+jsonの `code` と同じ
+fn graphql_error(lang: Lang, operation: &str, error: AppError) -> async_graphql::Error {
+    match &error {
+        AppError::Unexpected(_) => error!(operation, error = ?error, "graphql operation failed"),
+        _ => warn!(operation, error = ?error, "graphql operation failed"),
+    }
+    async_graphql::Error::new(error.localized_message(lang)).extend_with(|_, extensions| {
+        extensions.set("code", error.code().as_ref());
+        if let AppError::InvalidFields(fields) = &error {
+            let fields: Vec<async_graphql::Value> = fields
+                .iter()
+                .map(|field| {
+                    async_graphql::value!({
+                        "field": field.field.clone(),
+                        "code": field.message.code.to_string(),
+                        "message": i18n::render_field(lang, field),
+                    })
+                })
+                .collect();
+            extensions.set("fields", fields);
+        }
+    })
+}
@@ -0,0 +1,102 @@
+use async_graphql::{Context, Object, Result};
+use validator::Validate;
+
+use crate::application::errors::AppError;
+use crate::application::usecases::todo::edit_lock::Editor;
+use crate::application::usecases::todo::{
+    create as create_todo_usecase, delete as delete_todo_usecase, reorder as reorder_todos_usecase,
+    update as update_todo_usecase,
+};
+use crate::config::RouteGroup;
+use crate::presentation::dto::v1::todo_requests::{CreateTodoRequest, UpdateTodoRequest};
+use crate::presentation::graphql::graphql_error;
+use crate::presentation::graphql::types::{CreateTodoInput, TodoNode, UpdateTodoInput};
+use crate::presentation::i18n::Lang;
+use crate::state::AppState;
+
+pub struct MutationRoot;
+
+#[Object]
+impl MutationRoot {
+    async fn create_todo(&self, ctx: &Context<'_>, input: CreateTodoInput) -> Result<TodoNode> {
+        let state = ctx.data_unchecked::<AppState>();
+        let lang = *ctx.data_unchecked::<Lang>();
+        let payload = CreateTodoRequest { title: input.title };
+        payload
+            .validate()
+            .map_err(|e| graphql_error(lang, "createTodo", e.into()))?;
+        create_todo_usecase::execute(state.todos.as_ref(), state.events.as_ref(), payload.title)
+            .await
+            .map(TodoNode)
+            .map_err(|e| graphql_error(lang, "createTodo", e))
+    }
+
+    /// 指定した項目だけを更新する
+    async fn update_todo(
+        &self,
+        ctx: &Context<'_>,
+        id: i64,
+        input: UpdateTodoInput,
+    ) -> Result<TodoNode> {
+        let state = ctx.data_unchecked::<AppState>();
+        let lang = *ctx.data_unchecked::<Lang>();
+        let id =
+            u32::try_from(id).map_err(|_| graphql_error(lang, "updateTodo", AppError::NotFound))?;
+        let payload = UpdateTodoRequest {
+            title: input.title,
+            completed: input.completed,
+            due_at: input.due_at.into(),
+        };
+        payload
+            .validate()
+            .map_err(|e| graphql_error(lang, "updateTodo", e.into()))?;
+        match update_todo_usecase::execute(
+            state.todos.as_ref(),
+            state.events.as_ref(),
+            Editor::anonymous(state.presence.as_ref()),
+            id,
+            payload.title,
+            payload.completed,
+            payload.due_at,
+        )
+        .await
+        {
+            Ok(Some(todo)) => Ok(TodoNode(todo)),
+            Ok(None) => Err(graphql_error(lang, "updateTodo", AppError::NotFound)),
+            Err(e) => Err(graphql_error(lang, "updateTodo", e)),
+        }
+    }
+
+    /// TODOを削除する（コメント・添付・リマインダーも消える）
+    async fn delete_todo(&self, ctx: &Context<'_>, id: i64) -> Result<bool> {
+        let state = ctx.data_unchecked::<AppState>();
+        let lang = *ctx.data_unchecked::<Lang>();
+        let id =
+            u32::try_from(id).map_err(|_| graphql_error(lang, "deleteTodo", AppError::NotFound))?;
+        match delete_todo_usecase::execute(
+            state.todos.as_ref(),
+            state.attachments.as_ref(),
+            state.blobs.as_ref(),
+            state.events.as_ref(),
+            Editor::anonymous(state.presence.as_ref()),
+            id,
+        )
+        .await
+        {
+            Ok(true) => Ok(true),
+            Ok(false) => Err(graphql_error(lang, "deleteTodo", AppError::NotFound)),
+            Err(e) => Err(graphql_error(lang, "deleteTodo", e)),
+        }
+    }
+
+    /// 並べたい順にTODOのIDを列挙する
+    async fn reorder_todos(&self, ctx: &Context<'_>, ids: Vec<i64>) -> Result<bool> {
+        let state = ctx.data_unchecked::<AppState>();
+        let lang = *ctx.data_unchecked::<Lang>();
+        let max_ids = state.limits.route(RouteGroup::Graphql).max_list_len;
+        reorder_todos_usecase::execute(state.todos.as_ref(), state.events.as_ref(), ids, max_ids)
+            .await
+            .map(|_| true)
+            .map_err(|e| graphql_error(lang, "reorderTodos", e))
+    }
+}
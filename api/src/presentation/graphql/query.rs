@@ -0,0 +1,55 @@
+use async_graphql::dataloader::DataLoader;
+use async_graphql::{Context, Object, Result};
+
+use crate::application::usecases::todo::query::{self as query_todos, DEFAULT_LIMIT};
+use crate::presentation::graphql::types::{
+    TodoConnection, TodoFilterInput, TodoNode, TodoSortInput,
+};
+use crate::presentation::graphql::{graphql_error, TodoLoader};
+use crate::presentation::i18n::Lang;
+use crate::state::AppState;
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// TODOを絞り込み・並び替えて、`offset` 件目から最大 `limit` 件返す
+    async fn todos(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<TodoFilterInput>,
+        sort: Option<TodoSortInput>,
+        #[graphql(default_with = "DEFAULT_LIMIT")] limit: u32,
+        #[graphql(default)] offset: u32,
+    ) -> Result<TodoConnection> {
+        let state = ctx.data_unchecked::<AppState>();
+        let lang = *ctx.data_unchecked::<Lang>();
+        let page = query_todos::execute(
+            state.todos.as_ref(),
+            &filter.unwrap_or_default().into(),
+            sort.unwrap_or_default().into(),
+            limit,
+            offset,
+        )
+        .await
+        .map_err(|e| graphql_error(lang, "todos", e))?;
+        Ok(TodoConnection {
+            has_next_page: u64::from(offset) + (page.todos.len() as u64) < page.total,
+            total_count: page.total,
+            nodes: page.todos.into_iter().map(TodoNode).collect(),
+        })
+    }
+
+    /// IDでTODOを1件取得する。存在しなければ `null`
+    async fn todo(&self, ctx: &Context<'_>, id: i64) -> Result<Option<TodoNode>> {
+        let Ok(id) = u32::try_from(id) else {
+            return Ok(None);
+        };
+        let lang = *ctx.data_unchecked::<Lang>();
+        ctx.data_unchecked::<DataLoader<TodoLoader>>()
+            .load_one(id)
+            .await
+            .map(|todo| todo.map(TodoNode))
+            .map_err(|e| graphql_error(lang, "todo", e))
+    }
+}
@@ -0,0 +1,19 @@
+use async_graphql::{Context, Subscription};
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::{Stream, StreamExt};
+
+use crate::presentation::graphql::types::TodoChange;
+use crate::state::AppState;
+
+pub struct SubscriptionRoot;
+
+#[Subscription]
+impl SubscriptionRoot {
+    /// TODOの変更を流す。購読前の変更や、受信が追いつかずに溢れた変更は流れない
+    async fn todo_changed(&self, ctx: &Context<'_>) -> impl Stream<Item = TodoChange> {
+        let state = ctx.data_unchecked::<AppState>();
+        let receiver = state.event_stream.subscribe(None).receiver;
+        BroadcastStream::new(receiver)
+            .filter_map(|received| received.ok().map(|event| TodoChange::from(event.event)))
+    }
+}
@@ -0,0 +1,166 @@
+use async_graphql::dataloader::DataLoader;
+use async_graphql::{Context, Enum, InputObject, MaybeUndefined, Object, Result, SimpleObject};
+use chrono::{DateTime, Utc};
+
+use crate::application::usecases::todo::query;
+use crate::domain::entities::todo::Todo;
+use crate::domain::events::TodoEvent;
+use crate::presentation::graphql::{graphql_error, CommentCountLoader};
+use crate::presentation::i18n::Lang;
+
+pub struct TodoNode(pub Todo);
+
+#[Object(name = "Todo")]
+impl TodoNode {
+    async fn id(&self) -> i64 {
+        self.0.id
+    }
+
+    async fn title(&self) -> &str {
+        &self.0.title
+    }
+
+    async fn completed(&self) -> bool {
+        self.0.completed
+    }
+
+    async fn position(&self) -> i64 {
+        self.0.position
+    }
+
+    async fn due_at(&self) -> Option<DateTime<Utc>> {
+        self.0.due_at
+    }
+
+    /// コメント数。一覧で取得しても問い合わせは1回にまとまる
+    async fn comment_count(&self, ctx: &Context<'_>) -> Result<u64> {
+        let loader = ctx.data_unchecked::<DataLoader<CommentCountLoader>>();
+        loader
+            .load_one(self.0.id as u32)
+            .await
+            .map(|count| count.unwrap_or(0))
+            .map_err(|e| graphql_error(*ctx.data_unchecked::<Lang>(), "Todo.commentCount", e))
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct TodoConnection {
+    pub nodes: Vec<TodoNode>,
+    /// 絞り込み後、ページングする前の件数
+    pub total_count: u64,
+    pub has_next_page: bool,
+}
+
+#[derive(InputObject, Default)]
+pub struct TodoFilterInput {
+    pub completed: Option<bool>,
+    /// タイトルに含まれる文字列（英字の大文字小文字は区別しない）
+    pub title_contains: Option<String>,
+    /// 期限がこの日時より前のもの（期限なしは含めない）
+    pub due_before: Option<DateTime<Utc>>,
+    /// 期限がこの日時より後のもの（期限なしは含めない）
+    pub due_after: Option<DateTime<Utc>>,
+}
+
+impl From<TodoFilterInput> for query::TodoFilter {
+    fn from(input: TodoFilterInput) -> Self {
+        Self {
+            completed: input.completed,
+            title_contains: input.title_contains,
+            due_before: input.due_before,
+            due_after: input.due_after,
+        }
+    }
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Default)]
+pub enum TodoSortField {
+    #[default]
+    Position,
+    Title,
+    DueAt,
+    Id,
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Default)]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+#[derive(InputObject, Default)]
+pub struct TodoSortInput {
+    #[graphql(default)]
+    pub field: TodoSortField,
+    #[graphql(default)]
+    pub direction: SortDirection,
+}
+
+impl From<TodoSortInput> for query::TodoSort {
+    fn from(input: TodoSortInput) -> Self {
+        Self {
+            field: match input.field {
+                TodoSortField::Position => query::TodoSortField::Position,
+                TodoSortField::Title => query::TodoSortField::Title,
+                TodoSortField::DueAt => query::TodoSortField::DueAt,
+                TodoSortField::Id => query::TodoSortField::Id,
+            },
+            direction: match input.direction {
+                SortDirection::Asc => query::SortDirection::Asc,
+                SortDirection::Desc => query::SortDirection::Desc,
+            },
+        }
+    }
+}
+
+#[derive(InputObject)]
+pub struct CreateTodoInput {
+    pub title: String,
+}
+
+#[derive(InputObject)]
+pub struct UpdateTodoInput {
+    pub title: Option<String>,
+    pub completed: Option<bool>,
+    /// 省略時は変更なし、`null` で期限を解除する
+    pub due_at: MaybeUndefined<DateTime<Utc>>,
+}
+
+/// TODOに起きた変更。`event` はWebhookやSSEと同じ名前（`todo.created` など）
+#[derive(SimpleObject)]
+pub struct TodoChange {
+    pub event: String,
+    /// 作成・更新後のTODO
+    pub todo: Option<TodoNode>,
+    /// 変更されたTODOのID。並び替えでは空
+    pub todo_id: Option<i64>,
+    /// 並び替え後の順序
+    pub ids: Option<Vec<i64>>,
+}
+
+impl From<TodoEvent> for TodoChange {
+    fn from(event: TodoEvent) -> Self {
+        let name = event.name().to_string();
+        match event {
+            TodoEvent::Created(todo) | TodoEvent::Updated(todo) => Self {
+                event: name,
+                todo_id: Some(todo.id),
+                todo: Some(TodoNode(todo)),
+                ids: None,
+            },
+            TodoEvent::Deleted { id } => Self {
+                event: name,
+                todo: None,
+                todo_id: Some(id),
+                ids: None,
+            },
+            TodoEvent::Reordered { ids } => Self {
+                event: name,
+                todo: None,
+                todo_id: None,
+                ids: Some(ids),
+            },
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use chrono::{DateTime, Utc};
+use prost_types::Timestamp;
+
+use crate::application::errors::{AppError, ErrorMessage};
+use crate::domain::entities::todo::Todo;
+use crate::domain::events::TodoEvent;
+use crate::presentation::grpc::pb;
+
+impl From<Todo> for pb::Todo {
+    fn from(todo: Todo) -> Self {
+        Self {
+            id: todo.id,
+            title: todo.title,
+            completed: todo.completed,
+            position: todo.position,
+            due_at: todo.due_at.map(to_timestamp),
+        }
+    }
+}
+
+impl From<TodoEvent> for pb::TodoEvent {
+    fn from(event: TodoEvent) -> Self {
+        let name = event.name().to_string();
+        let payload = match event {
+            TodoEvent::Created(todo) | TodoEvent::Updated(todo) => {
+                pb::todo_event::Payload::Todo(todo.into())
+            }
+            TodoEvent::Deleted { id } => pb::todo_event::Payload::DeletedId(id),
+            TodoEvent::Reordered { ids } => {
+                pb::todo_event::Payload::Reordered(pb::Reordered { ids })
+            }
+        };
+        Self {
+            name,
+            payload: Some(payload),
+        }
+    }
+}
+
+fn to_timestamp(at: DateTime<Utc>) -> Timestamp {
+    Timestamp {
+        seconds: at.timestamp(),
+        nanos: at.timestamp_subsec_nanos() as i32,
+    }
+}
+
+pub(super) fn from_timestamp(timestamp: Timestamp) -> Result<DateTime<Utc>, AppError> {
+    u32::try_from(timestamp.nanos)
+        .ok()
+        .and_then(|nanos| DateTime::from_timestamp(timestamp.seconds, nanos))
+        .ok_or_else(|| AppError::validation(ErrorMessage::new("grpc_invalid_timestamp")))
+}
+
+/// リポジトリのIDは u32。範囲外のIDは存在しないものとして扱う
+pub(super) fn todo_id(id: i64) -> Result<u32, AppError> {
+    u32::try_from(id).map_err(|_| AppError::NotFound)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Timelike, Utc};
+
+    use super::{from_timestamp, to_timestamp};
+
+    #[test]
+    fn timestamps_round_trip() {
+        let at = Utc
+            .with_ymd_and_hms(2030, 1, 2, 3, 4, 5)
+            .unwrap()
+            .with_nanosecond(6)
+            .unwrap();
+
+        assert_eq!(from_timestamp(to_timestamp(at)).unwrap(), at);
+    }
+
+    #[test]
+    fn negative_nanos_are_rejected() {
+        let timestamp = prost_types::Timestamp {
+            seconds: 0,
+            nanos: -1,
+        };
+
+        assert!(from_timestamp(timestamp).is_err());
+    }
+}
@@ -0,0 +1,11 @@
+//! gRPCの `TodoService`。定義は proto/todo/v1/todo.proto、実装はHTTPと同じユースケースを呼ぶ
+mod convert;
+mod service;
+mod status;
+
+pub mod pb {
+    tonic::include_proto!("todo.v1");
+}
+
+pub use service::TodoGrpcService;
+pub use status::ERROR_CODE_METADATA;
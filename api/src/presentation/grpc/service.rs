@@ -0,0 +1,205 @@
+use std::pin::Pin;
+
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::{Stream, StreamExt};
+use tonic::{Request, Response, Status};
+use tracing::info;
+use validator::Validate;
+
+use crate::application::errors::AppError;
+use crate::application::usecases::todo::edit_lock::Editor;
+use crate::application::usecases::todo::{
+    create as create_todo, delete as delete_todo, get as get_todo, list as list_todos,
+    reorder as reorder_todos, update as update_todo,
+};
+use crate::config::RouteGroup;
+use crate::presentation::dto::v1::todo_requests::{CreateTodoRequest, UpdateTodoRequest};
+use crate::presentation::grpc::convert::{from_timestamp, todo_id};
+use crate::presentation::grpc::pb;
+use crate::presentation::grpc::pb::todo_service_server::{TodoService, TodoServiceServer};
+use crate::presentation::grpc::status::to_status;
+use crate::presentation::i18n::Lang;
+use crate::state::AppState;
+
+/// HTTPのルーターと同じ `AppState` を使うので、どちらからの変更も同じイベントとして流れる
+#[derive(Clone)]
+pub struct TodoGrpcService {
+    state: AppState,
+    /// `accept-language` メタデータで言語が決まらないときのエラーメッセージの言語
+    default_lang: Lang,
+}
+
+impl TodoGrpcService {
+    pub fn new(state: AppState, default_lang: Lang) -> Self {
+        Self {
+            state,
+            default_lang,
+        }
+    }
+
+    pub fn into_server(self) -> TodoServiceServer<Self> {
+        TodoServiceServer::new(self)
+    }
+
+    fn lang<T>(&self, request: &Request<T>) -> Lang {
+        request
+            .metadata()
+            .get("accept-language")
+            .and_then(|value| value.to_str().ok())
+            .and_then(Lang::from_accept_language)
+            .unwrap_or(self.default_lang)
+    }
+}
+
+type TodoEventStream = Pin<Box<dyn Stream<Item = Result<pb::TodoEvent, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl TodoService for TodoGrpcService {
+    async fn list_todos(
+        &self,
+        request: Request<pb::ListTodosRequest>,
+    ) -> Result<Response<pb::ListTodosResponse>, Status> {
+        let lang = self.lang(&request);
+        let todos = list_todos::execute(self.state.todos.as_ref())
+            .await
+            .map_err(|e| to_status(lang, "ListTodos", e))?;
+        info!(rpc = "ListTodos", count = todos.len(), "returned todos");
+        Ok(Response::new(pb::ListTodosResponse {
+            todos: todos.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    async fn get_todo(
+        &self,
+        request: Request<pb::GetTodoRequest>,
+    ) -> Result<Response<pb::Todo>, Status> {
+        let lang = self.lang(&request);
+        let id = todo_id(request.into_inner().id).map_err(|e| to_status(lang, "GetTodo", e))?;
+        match get_todo::execute(self.state.todos.as_ref(), id).await {
+            Ok(Some(todo)) => Ok(Response::new(todo.into())),
+            Ok(None) => Err(to_status(lang, "GetTodo", AppError::NotFound)),
+            Err(e) => Err(to_status(lang, "GetTodo", e)),
+        }
+    }
+
+    async fn create_todo(
+        &self,
+        request: Request<pb::CreateTodoRequest>,
+    ) -> Result<Response<pb::Todo>, Status> {
+        let lang = self.lang(&request);
+        let payload = CreateTodoRequest {
+            title: request.into_inner().title,
+        };
+        payload
+            .validate()
+            .map_err(|e| to_status(lang, "CreateTodo", e.into()))?;
+        let todo = create_todo::execute(
+            self.state.todos.as_ref(),
+            self.state.events.as_ref(),
+            payload.title,
+        )
+        .await
+        .map_err(|e| to_status(lang, "CreateTodo", e))?;
+        info!(rpc = "CreateTodo", todo_id = todo.id, "todo created");
+        Ok(Response::new(todo.into()))
+    }
+
+    async fn update_todo(
+        &self,
+        request: Request<pb::UpdateTodoRequest>,
+    ) -> Result<Response<pb::Todo>, Status> {
+        let lang = self.lang(&request);
+        let request = request.into_inner();
+        let id = todo_id(request.id).map_err(|e| to_status(lang, "UpdateTodo", e))?;
+        let due_at = match request.due {
+            None => None,
+            Some(pb::update_todo_request::Due::DueAt(at)) => Some(Some(
+                from_timestamp(at).map_err(|e| to_status(lang, "UpdateTodo", e))?,
+            )),
+            Some(pb::update_todo_request::Due::ClearDueAt(true)) => Some(None),
+            Some(pb::update_todo_request::Due::ClearDueAt(false)) => None,
+        };
+        let payload = UpdateTodoRequest {
+            title: request.title,
+            completed: request.completed,
+            due_at,
+        };
+        payload
+            .validate()
+            .map_err(|e| to_status(lang, "UpdateTodo", e.into()))?;
+        match update_todo::execute(
+            self.state.todos.as_ref(),
+            self.state.events.as_ref(),
+            Editor::anonymous(self.state.presence.as_ref()),
+            id,
+            payload.title,
+            payload.completed,
+            payload.due_at,
+        )
+        .await
+        {
+            Ok(Some(todo)) => Ok(Response::new(todo.into())),
+            Ok(None) => Err(to_status(lang, "UpdateTodo", AppError::NotFound)),
+            Err(e) => Err(to_status(lang, "UpdateTodo", e)),
+        }
+    }
+
+    async fn delete_todo(
+        &self,
+        request: Request<pb::DeleteTodoRequest>,
+    ) -> Result<Response<pb::DeleteTodoResponse>, Status> {
+        let lang = self.lang(&request);
+        let id = todo_id(request.into_inner().id).map_err(|e| to_status(lang, "DeleteTodo", e))?;
+        match delete_todo::execute(
+            self.state.todos.as_ref(),
+            self.state.attachments.as_ref(),
+            self.state.blobs.as_ref(),
+            self.state.events.as_ref(),
+            Editor::anonymous(self.state.presence.as_ref()),
+            id,
+        )
+        .await
+        {
+            Ok(true) => {
+                info!(rpc = "DeleteTodo", todo_id = id, "todo deleted");
+                Ok(Response::new(pb::DeleteTodoResponse {}))
+            }
+            Ok(false) => Err(to_status(lang, "DeleteTodo", AppError::NotFound)),
+            Err(e) => Err(to_status(lang, "DeleteTodo", e)),
+        }
+    }
+
+    async fn reorder_todos(
+        &self,
+        request: Request<pb::ReorderTodosRequest>,
+    ) -> Result<Response<pb::ReorderTodosResponse>, Status> {
+        let lang = self.lang(&request);
+        reorder_todos::execute(
+            self.state.todos.as_ref(),
+            self.state.events.as_ref(),
+            request.into_inner().ids,
+            self.state.limits.route(RouteGroup::Todos).max_list_len,
+        )
+        .await
+        .map_err(|e| to_status(lang, "ReorderTodos", e))?;
+        Ok(Response::new(pb::ReorderTodosResponse {}))
+    }
+
+    type WatchTodosStream = TodoEventStream;
+
+    /// 受信が追いつかずにイベントが溢れたら `DATA_LOSS` で終える。クライアントは一覧を取り直して再購読する
+    // ストリームの要素の型はtonicが `Result<_, Status>` に決めている
+    #[allow(clippy::result_large_err)]
+    async fn watch_todos(
+        &self,
+        _request: Request<pb::WatchTodosRequest>,
+    ) -> Result<Response<Self::WatchTodosStream>, Status> {
+        info!(rpc = "WatchTodos", "client subscribed");
+        let receiver = self.state.event_stream.subscribe(None).receiver;
+        let stream = BroadcastStream::new(receiver).map(|received| match received {
+            Ok(event) => Ok(event.event.into()),
+            Err(lagged) => Err(Status::data_loss(lagged.to_string())),
+        });
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
@@ -0,0 +1,101 @@
+use tonic::metadata::MetadataValue;
+use tonic::{Code, Status};
+use tracing::{error, warn};
+
+use crate::application::errors::AppError;
+use crate::presentation::i18n::{self, Lang};
+
+/// problem+json の `code` と同じ値を入れるメタデータのキー
+pub const ERROR_CODE_METADATA: &str = "x-error-code";
+
+impl AppError {
+    pub fn grpc_code(&self) -> Code {
+        match self {
+            AppError::NotFound => Code::NotFound,
+            AppError::MethodNotAllowed => Code::Unimplemented,
+            AppError::Validation(_)
+            | AppError::InvalidFields(_)
+            | AppError::UnsupportedMediaType(_) => Code::InvalidArgument,
+            AppError::Conflict(_) => Code::Aborted,
+            AppError::UnprocessableEntity(_) => Code::FailedPrecondition,
+            AppError::PayloadTooLarge(_) | AppError::TooManyRequests(_) => Code::ResourceExhausted,
+            AppError::Unexpected(_) => Code::Internal,
+        }
+    }
+
+    /// gRPCのステータスにする。入力エラーは項目ごとの説明もメッセージに並べる
+    pub fn to_status(&self, lang: Lang) -> Status {
+        let mut message = self.localized_message(lang);
+        if let AppError::InvalidFields(fields) = self {
+            for field in fields {
+                message.push_str("; ");
+                message.push_str(&i18n::render_field(lang, field));
+            }
+        }
+        let mut status = Status::new(self.grpc_code(), message);
+        if let Ok(code) = MetadataValue::try_from(self.code().as_ref()) {
+            status.metadata_mut().insert(ERROR_CODE_METADATA, code);
+        }
+        status
+    }
+}
+
+/// ログを残してから `Status` にする
+pub(super) fn to_status(lang: Lang, rpc: &str, error: AppError) -> Status {
+    match &error {
+        AppError::Unexpected(_) => error!(rpc, error = ?error, "grpc call failed"),
+        _ => warn!(rpc, error = ?error, "grpc call failed"),
+    }
+    error.to_status(lang)
+}
+
+#[cfg(test)]
+mod tests {
+    use tonic::Code;
+    use validator::Validate;
+
+    use super::ERROR_CODE_METADATA;
+    use crate::application::errors::AppError;
+    use crate::presentation::dto::v1::todo_requests::CreateTodoRequest;
+    use crate::presentation::i18n::Lang;
+
+    #[test]
+    fn app_errors_map_to_grpc_codes() {
+        assert_eq!(AppError::NotFound.grpc_code(), Code::NotFound);
+        assert_eq!(
+            AppError::unexpected("db is down").grpc_code(),
+            Code::Internal
+        );
+    }
+
+    #[test]
+    fn invalid_fields_become_invalid_argument_with_details() {
+        let request = CreateTodoRequest {
+            title: String::new(),
+        };
+        let error: AppError = request.validate().unwrap_err().into();
+
+        let status = error.to_status(Lang::En);
+
+        assert_eq!(status.code(), Code::InvalidArgument);
+        assert!(status
+            .message()
+            .starts_with("One or more fields are invalid; "));
+        assert_eq!(
+            status
+                .metadata()
+                .get(ERROR_CODE_METADATA)
+                .unwrap()
+                .to_str()
+                .unwrap(),
+            "invalid_fields"
+        );
+    }
+
+    #[test]
+    fn unexpected_errors_hide_internal_details() {
+        let status = AppError::unexpected("db is down").to_status(Lang::En);
+
+        assert!(!status.message().contains("db is down"));
+    }
+}
@@ -0,0 +1,39 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    middleware::Next,
+    response::Response,
+};
+
+use crate::infrastructure::metrics::Metrics;
+
+/// どのルートにも当たらなかったリクエストの `route` ラベル
+const UNMATCHED_ROUTE: &str = "unmatched";
+
+/// リクエストの件数と応答までの時間を、パスのテンプレート・メソッド・ステータスごとに記録する。
+/// ルーティングの後で動くよう `Router::layer` で付ける（`MatchedPath` を読むため）
+pub async fn record_http_metrics(
+    State(metrics): State<Arc<Metrics>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| UNMATCHED_ROUTE.to_string());
+    let method = request.method().clone();
+    let started = Instant::now();
+
+    let response = next.run(request).await;
+
+    metrics.observe_http(
+        method.as_str(),
+        &route,
+        response.status().as_u16(),
+        started.elapsed(),
+    );
+    response
+}
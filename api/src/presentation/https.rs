@@ -0,0 +1,82 @@
+//! HTTPSで待ち受けるときの `Strict-Transport-Security` と、HTTPからのリダイレクト
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderValue, StatusCode, Uri},
+    middleware::{self, Next},
+    response::{IntoResponse, Redirect, Response},
+    Router,
+};
+
+use crate::config::TlsConfig;
+
+/// `Strict-Transport-Security` の値。`max_age_secs` が0なら付けない
+fn hsts_header(max_age_secs: u64, include_subdomains: bool) -> Option<HeaderValue> {
+    if max_age_secs == 0 {
+        return None;
+    }
+    let mut value = format!("max-age={}", max_age_secs);
+    if include_subdomains {
+        value.push_str("; includeSubDomains");
+    }
+    Some(HeaderValue::from_str(&value).expect("HSTS value is ASCII"))
+}
+
+/// `tls.hsts_max_age_secs` が1以上なら、`app` の応答に `Strict-Transport-Security` を付ける
+pub fn with_hsts(app: Router, tls: &TlsConfig) -> Router {
+    match hsts_header(tls.hsts_max_age_secs, tls.hsts_include_subdomains) {
+        Some(value) => app.layer(middleware::from_fn_with_state(
+            value,
+            strict_transport_security,
+        )),
+        None => app,
+    }
+}
+
+/// HTTPSの応答すべてに `Strict-Transport-Security` を付ける
+async fn strict_transport_security(
+    State(value): State<HeaderValue>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let mut response = next.run(request).await;
+    response
+        .headers_mut()
+        .insert(header::STRICT_TRANSPORT_SECURITY, value);
+    response
+}
+
+/// どのパスへのリクエストも、同じホストの `https_port` へ308でリダイレクトするアプリ。
+/// 308なのでPOSTなどもメソッドとボディを変えずに送り直される
+pub fn https_redirect_app(https_port: u16) -> Router {
+    Router::new()
+        .fallback(redirect_to_https)
+        .with_state(https_port)
+}
+
+async fn redirect_to_https(State(https_port): State<u16>, request: Request) -> Response {
+    let host = request
+        .headers()
+        .get(header::HOST)
+        .and_then(|host| host.to_str().ok())
+        .and_then(|host| host.parse::<axum::http::uri::Authority>().ok());
+    let Some(host) = host else {
+        return (StatusCode::BAD_REQUEST, "missing Host header").into_response();
+    };
+    let authority = match https_port {
+        443 => host.host().to_string(),
+        port => format!("{}:{}", host.host(), port),
+    };
+    let path_and_query = request
+        .uri()
+        .path_and_query()
+        .map_or("/", |path_and_query| path_and_query.as_str());
+    match Uri::builder()
+        .scheme("https")
+        .authority(authority)
+        .path_and_query(path_and_query)
+        .build()
+    {
+        Ok(uri) => Redirect::permanent(&uri.to_string()).into_response(),
+        Err(_) => (StatusCode::BAD_REQUEST, "invalid Host header").into_response(),
+    }
+}
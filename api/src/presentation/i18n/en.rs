@@ -0,0 +1,107 @@
+pub const MESSAGES: &[(&str, &str)] = &[
+    // 問題の種類（problem+json の title）
+    ("problem.not_found", "Resource not found"),
+    ("problem.method_not_allowed", "Method not allowed"),
+    ("problem.validation_failed", "Validation failed"),
+    ("problem.payload_too_large", "Payload too large"),
+    ("problem.unsupported_media_type", "Unsupported media type"),
+    ("problem.conflict", "Conflict"),
+    ("problem.unprocessable_entity", "Unprocessable entity"),
+    ("problem.too_many_requests", "Too many requests"),
+    ("problem.internal_error", "Unexpected error"),
+    ("invalid_fields", "One or more fields are invalid"),
+    // 項目名
+    ("field.title", "Title"),
+    ("field.author", "Author"),
+    ("field.body", "Body"),
+    ("field.url", "URL"),
+    ("field.secret", "Secret"),
+    ("field.offset_minutes", "offset_minutes"),
+    ("field.ids", "ids"),
+    // 入力チェック（validator のコード）
+    (
+        "length",
+        "{field} must be between {min} and {max} characters",
+    ),
+    ("range", "{field} must be between {min} and {max}"),
+    ("url", "{field} must be a valid URL"),
+    (
+        "reminder_schedule",
+        "Specify exactly one of remind_at and offset_minutes",
+    ),
+    // リクエストの読み取り
+    ("invalid_json", "The request body is not valid JSON"),
+    (
+        "json_content_type_required",
+        "Content-Type must be application/json",
+    ),
+    ("invalid_path", "Invalid path parameter"),
+    ("invalid_query", "Invalid query string"),
+    ("invalid_multipart", "Invalid multipart body"),
+    ("body_too_large", "The request body is too large"),
+    ("unreadable_body", "Failed to read the request body"),
+    (
+        "invalid_idempotency_key",
+        "Idempotency-Key must be at most {max} printable ASCII characters",
+    ),
+    (
+        "idempotency_key_in_progress",
+        "A request with the same Idempotency-Key is still being processed; retry later",
+    ),
+    (
+        "idempotency_key_reused",
+        "This Idempotency-Key was already used for a different request",
+    ),
+    (
+        "rate_limited",
+        "Too many requests; retry after {retry_after} seconds",
+    ),
+    // ユースケース
+    ("todo_locked", "Todo {id} is being edited by {user}"),
+    ("page_too_small", "page must be 1 or greater"),
+    ("too_many_items", "{field} must have at most {max} items"),
+    (
+        "per_page_out_of_range",
+        "per_page must be between 1 and {max}",
+    ),
+    ("limit_out_of_range", "limit must be between 1 and {max}"),
+    (
+        "webhook_url_scheme",
+        "url must start with http:// or https://",
+    ),
+    (
+        "webhook_unknown_event",
+        "Unknown event '{event}' (expected one of: {expected})",
+    ),
+    ("reminder_offset_negative", "offset must not be negative"),
+    (
+        "reminder_schedule_required",
+        "remind_at or offset_minutes is required",
+    ),
+    ("attachment_empty", "The file must not be empty"),
+    (
+        "attachment_too_large",
+        "The file must be at most {max} bytes",
+    ),
+    (
+        "attachment_type_not_allowed",
+        "Content type {content_type} is not allowed",
+    ),
+    (
+        "attachment_field_required",
+        "Multipart field '{field}' is required",
+    ),
+    // WebSocket
+    (
+        "ws_user_required",
+        "The user query parameter is required (1-{max} characters)",
+    ),
+    ("ws_todo_not_found", "Todo {id} not found"),
+    // GraphQL
+    (
+        "graphql_ws_protocol_required",
+        "Sec-WebSocket-Protocol must be one of: {expected}",
+    ),
+    // gRPC
+    ("grpc_invalid_timestamp", "due_at must be a valid timestamp"),
+];
@@ -0,0 +1,113 @@
+pub const MESSAGES: &[(&str, &str)] = &[
+    // 問題の種類（problem+json の title）
+    ("problem.not_found", "見つかりません"),
+    ("problem.method_not_allowed", "このメソッドは使えません"),
+    ("problem.validation_failed", "入力内容に誤りがあります"),
+    ("problem.payload_too_large", "サイズが大きすぎます"),
+    ("problem.unsupported_media_type", "対応していない形式です"),
+    ("problem.conflict", "ほかの処理と競合しました"),
+    ("problem.unprocessable_entity", "処理できない内容です"),
+    ("problem.too_many_requests", "リクエストが多すぎます"),
+    ("problem.internal_error", "予期しないエラーが発生しました"),
+    ("invalid_fields", "入力内容に誤りのある項目があります"),
+    // 項目名
+    ("field.title", "タイトル"),
+    ("field.author", "投稿者名"),
+    ("field.body", "本文"),
+    ("field.url", "URL"),
+    ("field.secret", "secret"),
+    ("field.offset_minutes", "offset_minutes"),
+    ("field.ids", "ids"),
+    // 入力チェック（validator のコード）
+    (
+        "length",
+        "{field}は{min}文字以上{max}文字以下で入力してください",
+    ),
+    ("range", "{field}は{min}以上{max}以下で指定してください"),
+    ("url", "{field}が不正です"),
+    (
+        "reminder_schedule",
+        "remind_at と offset_minutes のどちらか一方を指定してください",
+    ),
+    // リクエストの読み取り
+    ("invalid_json", "リクエストボディをJSONとして読み取れません"),
+    (
+        "json_content_type_required",
+        "Content-Type は application/json にしてください",
+    ),
+    ("invalid_path", "パスの値が不正です"),
+    ("invalid_query", "クエリ文字列が不正です"),
+    ("invalid_multipart", "multipartの形式が不正です"),
+    ("body_too_large", "リクエストボディが大きすぎます"),
+    ("unreadable_body", "リクエストボディを読み取れません"),
+    (
+        "invalid_idempotency_key",
+        "Idempotency-Keyは{max}文字以内の英数字と記号で指定してください",
+    ),
+    (
+        "idempotency_key_in_progress",
+        "同じIdempotency-Keyのリクエストを処理中です。しばらく待ってから送り直してください",
+    ),
+    (
+        "idempotency_key_reused",
+        "このIdempotency-Keyは別の内容のリクエストで使われています",
+    ),
+    (
+        "rate_limited",
+        "リクエストが多すぎます。{retry_after}秒待ってから送り直してください",
+    ),
+    // ユースケース
+    ("todo_locked", "TODO {id} は {user} さんが編集中です"),
+    ("page_too_small", "pageは1以上で指定してください"),
+    ("too_many_items", "{field}は{max}件以下にしてください"),
+    (
+        "per_page_out_of_range",
+        "per_pageは1以上{max}以下で指定してください",
+    ),
+    (
+        "limit_out_of_range",
+        "limitは1以上{max}以下で指定してください",
+    ),
+    (
+        "webhook_url_scheme",
+        "urlは http:// か https:// で始めてください",
+    ),
+    (
+        "webhook_unknown_event",
+        "イベント '{event}' はありません（指定できるもの: {expected}）",
+    ),
+    ("reminder_offset_negative", "offsetに負の値は指定できません"),
+    (
+        "reminder_schedule_required",
+        "remind_at か offset_minutes を指定してください",
+    ),
+    ("attachment_empty", "空のファイルは添付できません"),
+    (
+        "attachment_too_large",
+        "ファイルは{max}バイト以下にしてください",
+    ),
+    (
+        "attachment_type_not_allowed",
+        "{content_type} のファイルは添付できません",
+    ),
+    (
+        "attachment_field_required",
+        "multipartのフィールド '{field}' が必要です",
+    ),
+    // WebSocket
+    (
+        "ws_user_required",
+        "userクエリパラメータ（1〜{max}文字）が必要です",
+    ),
+    ("ws_todo_not_found", "TODO {id} は見つかりません"),
+    // GraphQL
+    (
+        "graphql_ws_protocol_required",
+        "Sec-WebSocket-Protocolには次のいずれかを指定してください: {expected}",
+    ),
+    // gRPC
+    (
+        "grpc_invalid_timestamp",
+        "due_atには正しい日時を指定してください",
+    ),
+];
@@ -0,0 +1,185 @@
+//! 利用者に見せるメッセージの言語ごとのカタログ。
+//! メッセージはコード（`ErrorMessage::code`）で引き、`{name}` をパラメータで置き換える
+mod en;
+mod ja;
+
+use std::str::FromStr;
+
+use serde::Deserialize;
+
+use crate::application::errors::{ErrorMessage, FieldError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Lang {
+    #[default]
+    Ja,
+    En,
+}
+
+impl Lang {
+    pub const ALL: [Lang; 2] = [Lang::Ja, Lang::En];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Lang::Ja => "ja",
+            Lang::En => "en",
+        }
+    }
+
+    /// `ja` / `ja-JP` / `en-US` のような言語タグを読む。未対応の言語は None
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let primary = tag.trim().split(['-', '_']).next()?.to_ascii_lowercase();
+        match primary.as_str() {
+            "ja" => Some(Lang::Ja),
+            "en" => Some(Lang::En),
+            _ => None,
+        }
+    }
+
+    /// `Accept-Language` から、qの大きい順に対応している言語を選ぶ
+    pub fn from_accept_language(header: &str) -> Option<Self> {
+        let mut candidates: Vec<(f32, usize, Lang)> = header
+            .split(',')
+            .enumerate()
+            .filter_map(|(index, item)| {
+                let mut parts = item.split(';');
+                let lang = Lang::from_tag(parts.next()?)?;
+                let quality = parts
+                    .filter_map(|part| part.trim().strip_prefix("q="))
+                    .find_map(|q| q.trim().parse::<f32>().ok())
+                    .unwrap_or(1.0);
+                (quality > 0.0).then_some((quality, index, lang))
+            })
+            .collect();
+        // 同じqなら先に書かれたものを優先する
+        candidates.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)));
+        candidates.first().map(|(_, _, lang)| *lang)
+    }
+
+    fn catalog(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Lang::Ja => ja::MESSAGES,
+            Lang::En => en::MESSAGES,
+        }
+    }
+
+    fn lookup(&self, key: &str) -> Option<&'static str> {
+        self.catalog()
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, text)| *text)
+    }
+}
+
+impl FromStr for Lang {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Lang::from_tag(value).ok_or_else(|| format!("unsupported language: {}", value))
+    }
+}
+
+/// キーに対応する文言。どの言語にもなければキーそのものを返す
+pub fn text(lang: Lang, key: &str) -> String {
+    lang.lookup(key)
+        .or_else(|| Lang::En.lookup(key))
+        .unwrap_or(key)
+        .to_string()
+}
+
+pub fn render(lang: Lang, message: &ErrorMessage) -> String {
+    let params: Vec<(&str, &str)> = message
+        .params
+        .iter()
+        .map(|(name, value)| (name.as_ref(), value.as_str()))
+        .collect();
+    fill(&text(lang, &message.code), &params)
+}
+
+/// 項目名を言語に合わせて差し込んだうえで組み立てる
+pub fn render_field(lang: Lang, error: &FieldError) -> String {
+    let label = field_label(lang, &error.field);
+    let mut params: Vec<(&str, &str)> = vec![("field", label.as_str())];
+    params.extend(
+        error
+            .message
+            .params
+            .iter()
+            .map(|(name, value)| (name.as_ref(), value.as_str())),
+    );
+    fill(&text(lang, &error.message.code), &params)
+}
+
+fn field_label(lang: Lang, field: &str) -> String {
+    let key = format!("field.{}", field);
+    lang.lookup(&key)
+        .map(str::to_string)
+        .unwrap_or_else(|| field.to_string())
+}
+
+fn fill(template: &str, params: &[(&str, &str)]) -> String {
+    params
+        .iter()
+        .fold(template.to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{}}}", name), value)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{render, render_field, Lang};
+    use crate::application::errors::{ErrorMessage, FieldError};
+
+    #[test]
+    fn catalogs_define_the_same_keys() {
+        for lang in Lang::ALL {
+            for other in Lang::ALL {
+                for (key, _) in other.catalog() {
+                    assert!(
+                        lang.lookup(key).is_some(),
+                        "{} is missing in {}",
+                        key,
+                        lang.as_str()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn accept_language_prefers_highest_quality() {
+        assert_eq!(
+            Lang::from_accept_language("en-US,en;q=0.9,ja;q=0.8"),
+            Some(Lang::En)
+        );
+        assert_eq!(
+            Lang::from_accept_language("fr, en;q=0.5, ja-JP;q=0.7"),
+            Some(Lang::Ja)
+        );
+        assert_eq!(Lang::from_accept_language("fr, de"), None);
+        assert_eq!(Lang::from_accept_language("ja;q=0, en"), Some(Lang::En));
+    }
+
+    #[test]
+    fn render_fills_params_and_field_label() {
+        let message = ErrorMessage::new("per_page_out_of_range").with("max", 100);
+        assert_eq!(
+            render(Lang::En, &message),
+            "per_page must be between 1 and 100"
+        );
+
+        let error = FieldError {
+            field: "title".to_string(),
+            message: ErrorMessage::new("length").with("min", 1).with("max", 200),
+        };
+        assert_eq!(
+            render_field(Lang::Ja, &error),
+            "タイトルは1文字以上200文字以下で入力してください"
+        );
+        assert_eq!(
+            render_field(Lang::En, &error),
+            "Title must be between 1 and 200 characters"
+        );
+    }
+}
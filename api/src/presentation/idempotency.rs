@@ -0,0 +1,207 @@
+//! `Idempotency-Key` 付きのPOSTを一度だけ処理し、同じ内容の再送には最初の応答をそのまま返す
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::{
+    body::{Body, Bytes},
+    extract::{ConnectInfo, FromRequest, Request, State},
+    http::{header::AUTHORIZATION, request::Parts, HeaderName, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use sha2::{Digest, Sha256};
+use tracing::warn;
+
+use crate::application::errors::{AppError, ErrorMessage};
+use crate::application::ports::clock::Clock;
+use crate::application::ports::idempotency_store::{IdempotencyStore, StoredResponse};
+use crate::application::usecases::idempotency::{begin, finish};
+
+pub const IDEMPOTENCY_KEY_HEADER: HeaderName = HeaderName::from_static("idempotency-key");
+/// 保存しておいた応答を返したときに付ける
+pub const IDEMPOTENT_REPLAYED_HEADER: HeaderName = HeaderName::from_static("idempotent-replayed");
+
+const MAX_KEY_LEN: usize = 255;
+
+#[derive(Clone)]
+pub struct Idempotency {
+    pub store: Arc<dyn IdempotencyStore>,
+    pub clock: Arc<dyn Clock>,
+    /// 応答を保存しておく期間
+    pub ttl: chrono::Duration,
+    /// キー付きのリクエストの処理を打ち切るまでの時間
+    pub request_timeout: std::time::Duration,
+    /// 予約したまま応答が保存されないキーを、処理が止まったものとみなすまでの時間。
+    /// `request_timeout` より長くする（設定の検証で確かめる）
+    pub in_progress_timeout: chrono::Duration,
+}
+
+/// POSTのルートに `MethodRouter::layer` で付ける。ヘッダがなければそのまま処理する。
+/// ボディは `DefaultBodyLimit` の上限まで読んで指紋（メソッド・パス・ボディのSHA-256）を取る。
+/// キーはクライアントごとに分けるので、別のクライアントが同じキーを使っても応答は混ざらない
+pub async fn idempotent(
+    State(idempotency): State<Idempotency>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(key) = request.headers().get(&IDEMPOTENCY_KEY_HEADER) else {
+        return next.run(request).await;
+    };
+    let Some(key) = key
+        .to_str()
+        .ok()
+        .filter(|key| !key.is_empty() && key.len() <= MAX_KEY_LEN)
+        .map(str::to_string)
+    else {
+        return AppError::validation(
+            ErrorMessage::new("invalid_idempotency_key").with("max", MAX_KEY_LEN),
+        )
+        .into_response();
+    };
+
+    let (parts, body) = request.into_parts();
+    let key = format!("{}/{}", client_scope(&parts), key);
+    let body = match Bytes::from_request(Request::from_parts(parts.clone(), body), &()).await {
+        Ok(body) => body,
+        Err(rejection) if rejection.status() == StatusCode::PAYLOAD_TOO_LARGE => {
+            return AppError::PayloadTooLarge(ErrorMessage::new("body_too_large")).into_response()
+        }
+        Err(rejection) => {
+            return AppError::validation(
+                ErrorMessage::new("unreadable_body").with_reason(rejection.body_text()),
+            )
+            .into_response()
+        }
+    };
+    let fingerprint = {
+        let mut hasher = Sha256::new();
+        hasher.update(parts.method.as_str());
+        hasher.update(b"\n");
+        hasher.update(parts.uri.to_string());
+        hasher.update(b"\n");
+        hasher.update(&body);
+        hex::encode(hasher.finalize())
+    };
+
+    let token = match begin::execute(
+        idempotency.store.as_ref(),
+        idempotency.clock.as_ref(),
+        &key,
+        &fingerprint,
+        idempotency.ttl,
+        idempotency.in_progress_timeout,
+    )
+    .await
+    {
+        Ok(begin::Begin::Proceed { token }) => token,
+        Ok(begin::Begin::Replay(stored)) => return replay(stored),
+        Err(e) => {
+            warn!(
+                method = %parts.method,
+                uri = %parts.uri,
+                key = ?key,
+                error = ?e,
+                "failed to reserve the idempotency key"
+            );
+            return e.into_response();
+        }
+    };
+
+    // 予約が止まったものとみなされる前に、必ず処理を終える（打ち切る）
+    let request = Request::from_parts(parts, Body::from(body));
+    let processed = tokio::time::timeout(idempotency.request_timeout, async {
+        let response = next.run(request).await;
+        // 想定外のエラーは保存せず、同じキーで送り直せるようにする
+        if response.status().is_server_error() {
+            return Processed::Failed(response);
+        }
+        let (parts, body) = response.into_parts();
+        match axum::body::to_bytes(body, usize::MAX).await {
+            Ok(body) => Processed::Completed(parts, body),
+            Err(e) => Processed::Failed(
+                AppError::unexpected(format!("failed to read the response body: {}", e))
+                    .into_response(),
+            ),
+        }
+    })
+    .await
+    .unwrap_or_else(|_| {
+        Processed::TimedOut(
+            AppError::unexpected(format!(
+                "request timed out after {:?}",
+                idempotency.request_timeout
+            ))
+            .into_response(),
+        )
+    });
+    let (parts, body) = match processed {
+        Processed::Completed(parts, body) => (parts, body),
+        Processed::Failed(response) => {
+            if let Err(e) = finish::execute(idempotency.store.as_ref(), &key, &token, None).await {
+                warn!(key = ?key, error = ?e, "failed to release the idempotency key");
+            }
+            return response;
+        }
+        Processed::TimedOut(response) => {
+            warn!(key = ?key, "idempotent request timed out; keeping the key reserved");
+            return response;
+        }
+    };
+    let stored = StoredResponse {
+        status: parts.status.as_u16(),
+        headers: parts
+            .headers
+            .iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+            .collect(),
+        body: body.to_vec(),
+    };
+    if let Err(e) = finish::execute(idempotency.store.as_ref(), &key, &token, Some(&stored)).await {
+        warn!(
+            key = ?key,
+            error = ?e,
+            "failed to store the response for the idempotency key"
+        );
+    }
+    Response::from_parts(parts, Body::from(body))
+}
+
+/// キー付きのリクエストを処理した結果
+enum Processed {
+    /// 保存して返す応答
+    Completed(axum::http::response::Parts, Bytes),
+    /// 保存せず、キーを解放して返す応答
+    Failed(Response),
+    /// 打ち切ったときの応答。ハンドラがすでに書き込んだかもしれないので、キーは解放せず、
+    /// `in_progress_timeout` が過ぎるまで同じキーの再送を処理中として断る
+    TimedOut(Response),
+}
+
+/// キーを分ける単位。`Authorization` があればその値ごと、なければ接続元のIPアドレスごと。
+/// トークンはそのまま保存しないようハッシュにする
+fn client_scope(parts: &Parts) -> String {
+    if let Some(authorization) = parts.headers.get(AUTHORIZATION) {
+        return format!(
+            "auth:{}",
+            hex::encode(Sha256::digest(authorization.as_bytes()))
+        );
+    }
+    match parts.extensions.get::<ConnectInfo<SocketAddr>>() {
+        Some(ConnectInfo(addr)) => format!("ip:{}", addr.ip()),
+        // `oneshot` で呼ぶテストなど、接続の情報がないとき
+        None => "ip:unknown".to_string(),
+    }
+}
+
+fn replay(stored: StoredResponse) -> Response {
+    let mut response = Response::new(Body::from(stored.body));
+    *response.status_mut() = StatusCode::from_u16(stored.status).unwrap_or(StatusCode::OK);
+    let headers = response.headers_mut();
+    for (name, value) in stored.headers {
+        if let (Ok(name), Ok(value)) = (HeaderName::try_from(name), HeaderValue::try_from(value)) {
+            headers.append(name, value);
+        }
+    }
+    headers.insert(IDEMPOTENT_REPLAYED_HEADER, HeaderValue::from_static("true"));
+    response
+}
@@ -1 +1,13 @@
+pub mod allow;
+pub mod deprecation;
 pub mod dto;
+pub mod extract;
+pub mod graphql;
+pub mod grpc;
+pub mod http_metrics;
+pub mod https;
+pub mod i18n;
+pub mod idempotency;
+pub mod problem;
+pub mod rate_limit;
+pub mod request_context;
@@ -0,0 +1,224 @@
+use std::borrow::Cow;
+
+use axum::{
+    http::{header, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use validator::{ValidationErrors, ValidationErrorsKind};
+
+use crate::application::errors::{AppError, ErrorMessage, FieldError};
+use crate::presentation::dto::error_responses::{
+    FieldErrorResponse, ProblemDetails, PROBLEM_CONTENT_TYPE,
+};
+use crate::presentation::i18n::{self, Lang};
+use crate::presentation::request_context;
+
+impl AppError {
+    pub fn status(&self) -> StatusCode {
+        match self {
+            AppError::NotFound => StatusCode::NOT_FOUND,
+            AppError::MethodNotAllowed => StatusCode::METHOD_NOT_ALLOWED,
+            AppError::Validation(_) | AppError::InvalidFields(_) => StatusCode::BAD_REQUEST,
+            AppError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            AppError::UnsupportedMediaType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            AppError::Conflict(_) => StatusCode::CONFLICT,
+            AppError::UnprocessableEntity(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            AppError::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
+            AppError::Unexpected(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    /// エラーの種類。`type` のURIとタイトルの引き当てに使う
+    fn kind(&self) -> &'static str {
+        match self {
+            AppError::NotFound => "not_found",
+            AppError::MethodNotAllowed => "method_not_allowed",
+            AppError::Validation(_) | AppError::InvalidFields(_) => "validation_failed",
+            AppError::PayloadTooLarge(_) => "payload_too_large",
+            AppError::UnsupportedMediaType(_) => "unsupported_media_type",
+            AppError::Conflict(_) => "conflict",
+            AppError::UnprocessableEntity(_) => "unprocessable_entity",
+            AppError::TooManyRequests(_) => "too_many_requests",
+            AppError::Unexpected(_) => "internal_error",
+        }
+    }
+
+    /// クライアントが分岐に使える、言語によらないコード
+    pub fn code(&self) -> Cow<'static, str> {
+        match self {
+            AppError::Validation(message)
+            | AppError::PayloadTooLarge(message)
+            | AppError::UnsupportedMediaType(message)
+            | AppError::Conflict(message)
+            | AppError::UnprocessableEntity(message)
+            | AppError::TooManyRequests(message) => message.code.clone(),
+            AppError::InvalidFields(_) => Cow::Borrowed("invalid_fields"),
+            _ => Cow::Borrowed(self.kind()),
+        }
+    }
+
+    /// 利用者向けの説明。内部の事情はクライアントに見せない（ログには各ハンドラで出している）
+    pub fn localized_message(&self, lang: Lang) -> String {
+        match self {
+            AppError::Validation(message)
+            | AppError::PayloadTooLarge(message)
+            | AppError::UnsupportedMediaType(message)
+            | AppError::Conflict(message)
+            | AppError::UnprocessableEntity(message)
+            | AppError::TooManyRequests(message) => i18n::render(lang, message),
+            AppError::InvalidFields(_) => i18n::text(lang, "invalid_fields"),
+            _ => i18n::text(lang, &format!("problem.{}", self.kind())),
+        }
+    }
+
+    pub fn to_problem(&self) -> ProblemDetails {
+        let context = request_context::current();
+        let lang = request_context::current_lang();
+        let errors = match self {
+            AppError::InvalidFields(fields) => fields
+                .iter()
+                .map(|field| FieldErrorResponse {
+                    field: field.field.clone(),
+                    code: field.message.code.to_string(),
+                    message: i18n::render_field(lang, field),
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        let reason = match self {
+            AppError::Validation(message)
+            | AppError::PayloadTooLarge(message)
+            | AppError::UnsupportedMediaType(message)
+            | AppError::Conflict(message)
+            | AppError::UnprocessableEntity(message)
+            | AppError::TooManyRequests(message) => message.reason.clone(),
+            _ => None,
+        };
+        let detail = match self {
+            AppError::NotFound | AppError::MethodNotAllowed | AppError::Unexpected(_) => None,
+            _ => Some(self.localized_message(lang)),
+        };
+        ProblemDetails {
+            problem_type: format!("/problems/{}", self.kind().replace('_', "-")),
+            title: i18n::text(lang, &format!("problem.{}", self.kind())),
+            status: self.status().as_u16(),
+            code: self.code().to_string(),
+            detail,
+            reason,
+            instance: context.as_ref().map(|context| context.path.clone()),
+            errors,
+            request_id: context
+                .map(|context| context.request_id)
+                .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+        }
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        let mut response = (self.status(), Json(self.to_problem())).into_response();
+        response.headers_mut().insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static(PROBLEM_CONTENT_TYPE),
+        );
+        response
+    }
+}
+
+impl From<ValidationErrors> for AppError {
+    fn from(errors: ValidationErrors) -> Self {
+        let mut fields = Vec::new();
+        collect_field_errors(&errors, "", &mut fields);
+        // HashMap由来で順序が揺れるので項目名で並べる
+        fields.sort_by(|a, b| a.field.cmp(&b.field));
+        AppError::InvalidFields(fields)
+    }
+}
+
+fn collect_field_errors(errors: &ValidationErrors, prefix: &str, out: &mut Vec<FieldError>) {
+    for (field, kind) in errors.errors() {
+        let field = if prefix.is_empty() {
+            field.to_string()
+        } else {
+            format!("{}.{}", prefix, field)
+        };
+        match kind {
+            ValidationErrorsKind::Field(errors) => {
+                out.extend(errors.iter().map(|error| FieldError {
+                    field: field.clone(),
+                    message: field_message(error),
+                }))
+            }
+            ValidationErrorsKind::Struct(errors) => collect_field_errors(errors, &field, out),
+            ValidationErrorsKind::List(items) => {
+                for (index, errors) in items {
+                    collect_field_errors(errors, &format!("{}[{}]", field, index), out);
+                }
+            }
+        }
+    }
+}
+
+/// validatorのコードと min / max などの値をそのままメッセージの材料にする
+fn field_message(error: &validator::ValidationError) -> ErrorMessage {
+    let mut params: Vec<_> = error
+        .params
+        .iter()
+        .filter(|(name, _)| *name != "value")
+        .map(|(name, value)| {
+            let value = match value {
+                serde_json::Value::String(text) => text.clone(),
+                other => other.to_string(),
+            };
+            (name.clone(), value)
+        })
+        .collect();
+    params.sort();
+    ErrorMessage {
+        code: error.code.clone(),
+        params,
+        reason: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::to_bytes;
+    use axum::response::IntoResponse;
+    use validator::Validate;
+
+    use crate::application::errors::AppError;
+    use crate::presentation::dto::v1::todo_requests::CreateTodoRequest;
+
+    #[tokio::test]
+    async fn validation_errors_become_field_level_problem() {
+        let request = CreateTodoRequest {
+            title: String::new(),
+        };
+        let error: AppError = request.validate().unwrap_err().into();
+
+        let response = error.into_response();
+        assert_eq!(response.status(), 400);
+        assert_eq!(
+            response.headers()["content-type"],
+            "application/problem+json"
+        );
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let problem: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(problem["type"], "/problems/validation-failed");
+        assert_eq!(problem["status"], 400);
+        assert_eq!(problem["code"], "invalid_fields");
+        assert_eq!(problem["errors"][0]["field"], "title");
+        assert_eq!(problem["errors"][0]["code"], "length");
+        assert!(problem["request_id"].is_string());
+    }
+
+    #[test]
+    fn unexpected_error_hides_internal_message() {
+        let problem = AppError::unexpected("database is locked").to_problem();
+        assert_eq!(problem.status, 500);
+        assert_eq!(problem.code, "internal_error");
+        assert_eq!(problem.detail, None);
+    }
+}
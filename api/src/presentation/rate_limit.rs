@@ -0,0 +1,185 @@
+//! ルートのグループごとの制限（クライアントごとのレート制限とリクエストボディの上限）
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+
+use axum::{
+    extract::{ConnectInfo, DefaultBodyLimit, Request, State},
+    http::{header, HeaderMap, HeaderValue},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    Router,
+};
+use sha2::{Digest, Sha256};
+
+use crate::application::errors::{AppError, ErrorMessage};
+use crate::config::{LimitsConfig, RouteGroup};
+use crate::infrastructure::rate_limit::RateLimiter;
+
+/// グループごとのバケット。同じルートを別名（プレフィックスなし）でも登録するので、
+/// 一度だけ作って共有し、別名経由でも同じ枠を数える
+#[derive(Clone)]
+pub struct RouteLimitLayers {
+    limits: Arc<LimitsConfig>,
+    limiters: HashMap<RouteGroup, ClientLimiter>,
+}
+
+/// グループのバケットと、クライアントを見分けるための設定
+#[derive(Clone)]
+pub struct ClientLimiter {
+    limiter: Arc<RateLimiter>,
+    trusted_proxies: Arc<[IpAddr]>,
+}
+
+impl RouteLimitLayers {
+    pub fn new(limits: Arc<LimitsConfig>) -> Self {
+        let limiters = if limits.rate_limit_enabled {
+            let trusted_proxies: Arc<[IpAddr]> = limits.trusted_proxies.clone().into();
+            RouteGroup::ALL
+                .into_iter()
+                .map(|group| {
+                    let route = limits.route(group);
+                    let limiter = RateLimiter::new(route.requests_per_minute, route.burst);
+                    let limiter = ClientLimiter {
+                        limiter: Arc::new(limiter),
+                        trusted_proxies: trusted_proxies.clone(),
+                    };
+                    (group, limiter)
+                })
+                .collect()
+        } else {
+            HashMap::new()
+        };
+        Self { limits, limiters }
+    }
+
+    /// `router` のルートにグループの制限を掛ける。ルートに個別の `DefaultBodyLimit` があればそちらが優先される
+    pub fn apply<S>(&self, group: RouteGroup, router: Router<S>) -> Router<S>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        let router = router.layer(DefaultBodyLimit::max(
+            self.limits.route(group).max_body_bytes,
+        ));
+        match self.limiters.get(&group) {
+            Some(limiter) => {
+                router.layer(middleware::from_fn_with_state(limiter.clone(), limit_rate))
+            }
+            None => router,
+        }
+    }
+}
+
+/// クライアントのバケットが空なら、ハンドラを呼ばずに429と `Retry-After` を返す
+pub async fn limit_rate(
+    State(limiter): State<ClientLimiter>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let key = client_key(&request, &limiter.trusted_proxies);
+    match limiter.limiter.acquire(&key) {
+        Ok(()) => next.run(request).await,
+        Err(wait) => {
+            // 端数は切り上げ、少なくとも1秒待たせる
+            let retry_after = wait.as_secs() + u64::from(wait.subsec_nanos() > 0);
+            let retry_after = retry_after.max(1);
+            let mut response = AppError::TooManyRequests(
+                ErrorMessage::new("rate_limited").with("retry_after", retry_after),
+            )
+            .into_response();
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(retry_after));
+            response
+        }
+    }
+}
+
+/// バケットを分ける単位。`Authorization` があればその値ごと（保存しないようハッシュにする）、
+/// なければクライアントのIPアドレスごと。クエリなど、クライアントが自由に変えられる値は使わない
+fn client_key(request: &Request, trusted_proxies: &[IpAddr]) -> String {
+    if let Some(authorization) = request.headers().get(header::AUTHORIZATION) {
+        return format!(
+            "auth:{}",
+            hex::encode(Sha256::digest(authorization.as_bytes()))
+        );
+    }
+    match request.extensions().get::<ConnectInfo<SocketAddr>>() {
+        Some(ConnectInfo(addr)) => format!(
+            "ip:{}",
+            client_ip(addr.ip(), request.headers(), trusted_proxies)
+        ),
+        // `oneshot` で呼ぶテストなど、接続の情報がないとき
+        None => "ip:unknown".to_string(),
+    }
+}
+
+/// 接続元が信頼するプロキシなら、`Forwarded`（なければ `X-Forwarded-For`）を後ろからたどり、
+/// 信頼するプロキシでない最初のアドレスをクライアントとみなす。
+/// 読めない値（`unknown` や隠したアドレス）に当たったら、そこを書いたプロキシのアドレスで数える
+fn client_ip(peer: IpAddr, headers: &HeaderMap, trusted_proxies: &[IpAddr]) -> IpAddr {
+    if !trusted_proxies.contains(&peer) {
+        return peer;
+    }
+    let mut hops = forwarded_hops(headers);
+    if hops.is_empty() {
+        hops = x_forwarded_for_hops(headers);
+    }
+    let mut client = peer;
+    for hop in hops.into_iter().rev() {
+        match hop {
+            Some(ip) => {
+                client = ip;
+                if !trusted_proxies.contains(&ip) {
+                    break;
+                }
+            }
+            None => break,
+        }
+    }
+    client
+}
+
+/// `Forwarded: for=192.0.2.1, for="[2001:db8::1]:4711"` の `for` を順に読む
+fn forwarded_hops(headers: &HeaderMap) -> Vec<Option<IpAddr>> {
+    header_items(headers, header::FORWARDED)
+        .map(|element| {
+            element
+                .split(';')
+                .filter_map(|pair| pair.split_once('='))
+                .find(|(name, _)| name.trim().eq_ignore_ascii_case("for"))
+                .and_then(|(_, value)| parse_node(value.trim().trim_matches('"')))
+        })
+        .collect()
+}
+
+/// `X-Forwarded-For: 192.0.2.1, 10.0.0.2` を順に読む
+fn x_forwarded_for_hops(headers: &HeaderMap) -> Vec<Option<IpAddr>> {
+    header_items(headers, "x-forwarded-for")
+        .map(parse_node)
+        .collect()
+}
+
+/// 同じ名前のヘッダをすべてつなげ、カンマで区切った要素
+fn header_items(
+    headers: &HeaderMap,
+    name: impl header::AsHeaderName,
+) -> impl Iterator<Item = &str> {
+    headers
+        .get_all(name)
+        .into_iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+}
+
+/// `192.0.2.1`・`192.0.2.1:4711`・`[2001:db8::1]`・`[2001:db8::1]:4711`・`2001:db8::1`
+fn parse_node(node: &str) -> Option<IpAddr> {
+    if let Some(rest) = node.strip_prefix('[') {
+        let (ip, _) = rest.split_once(']')?;
+        return ip.parse().ok();
+    }
+    node.parse()
+        .ok()
+        .or_else(|| node.parse::<SocketAddr>().ok().map(|addr| addr.ip()))
+}
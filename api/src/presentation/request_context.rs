@@ -0,0 +1,87 @@
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+
+use crate::presentation::i18n::Lang;
+
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// 受け取ったIDをそのまま使う上限の長さ。超えたら採番し直す
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// 処理中のリクエストの情報。エラーレスポンスに載せるために保持する
+#[derive(Debug, Clone)]
+pub struct RequestContext {
+    pub request_id: String,
+    pub path: String,
+    /// `Accept-Language` から選んだ応答の言語
+    pub lang: Lang,
+}
+
+tokio::task_local! {
+    static CURRENT: RequestContext;
+}
+
+/// 処理中のリクエストの情報を返す。ミドルウェアの外（単体テストなど）では None
+pub fn current() -> Option<RequestContext> {
+    CURRENT.try_with(Clone::clone).ok()
+}
+
+/// 処理中のリクエストのID。ミドルウェアの外では None
+pub fn current_request_id() -> Option<String> {
+    CURRENT.try_with(|context| context.request_id.clone()).ok()
+}
+
+/// 処理中のリクエストの言語。ミドルウェアの外では既定の日本語
+pub fn current_lang() -> Lang {
+    CURRENT.try_with(|context| context.lang).unwrap_or_default()
+}
+
+/// リクエストIDを決めてハンドラの処理中に参照できるようにし、レスポンスにも付ける。
+/// クライアントが `X-Request-Id` を送ってきた場合はその値を引き継ぐ。
+/// 言語は `Accept-Language` から選び、対応する言語がなければ `default_lang` にする
+pub async fn track_request(
+    State(default_lang): State<Lang>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let request_id = request
+        .headers()
+        .get(&REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|id| is_acceptable_id(id))
+        .map(str::to_string)
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let lang = request
+        .headers()
+        .get(header::ACCEPT_LANGUAGE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(Lang::from_accept_language)
+        .unwrap_or(default_lang);
+    let context = RequestContext {
+        request_id: request_id.clone(),
+        path: request.uri().path().to_string(),
+        lang,
+    };
+
+    let mut response = CURRENT.scope(context, next.run(request)).await;
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response.headers_mut().insert(
+        header::CONTENT_LANGUAGE,
+        HeaderValue::from_static(lang.as_str()),
+    );
+    response
+}
+
+fn is_acceptable_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= MAX_REQUEST_ID_LEN
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
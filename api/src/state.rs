@@ -0,0 +1,54 @@
+use std::sync::Arc;
+
+use axum::extract::FromRef;
+
+use crate::application::ports::attachment_repository::AttachmentRepository;
+use crate::application::ports::blob_storage::BlobStorage;
+use crate::application::ports::comment_repository::CommentRepository;
+use crate::application::ports::event_publisher::EventPublisher;
+use crate::application::ports::idempotency_store::IdempotencyStore;
+use crate::application::ports::reminder_repository::ReminderRepository;
+use crate::application::ports::todo_repository::TodoRepository;
+use crate::application::ports::webhook_outbox::WebhookOutbox;
+use crate::application::ports::webhook_repository::WebhookRepository;
+use crate::application::usecases::attachment::upload::AttachmentLimits;
+use crate::config::LimitsConfig;
+use crate::infrastructure::events::broadcaster::EventBroadcaster;
+use crate::infrastructure::realtime::presence_registry::PresenceRegistry;
+
+// ルーターで共有する状態。各ハンドラは必要なリポジトリだけを `State` で取り出す
+#[derive(Clone)]
+pub struct AppState {
+    pub todos: Arc<dyn TodoRepository>,
+    pub comments: Arc<dyn CommentRepository>,
+    pub attachments: Arc<dyn AttachmentRepository>,
+    pub blobs: Arc<dyn BlobStorage>,
+    pub attachment_limits: Arc<AttachmentLimits>,
+    pub reminders: Arc<dyn ReminderRepository>,
+    pub webhooks: Arc<dyn WebhookRepository>,
+    pub webhook_outbox: Arc<dyn WebhookOutbox>,
+    /// TODOの変更を通知する先。ユースケースへそのまま渡す
+    pub events: Arc<dyn EventPublisher>,
+    /// `events` に流れたイベントをSSEの接続へ配るチャネル
+    pub event_stream: Arc<EventBroadcaster>,
+    /// WebSocket接続の在席状況と編集ロック
+    pub presence: Arc<PresenceRegistry>,
+    /// ルートのグループごとの制限。ボディの中身の上限（IDの数など）はハンドラで見る
+    pub limits: Arc<LimitsConfig>,
+    /// `Idempotency-Key` ごとに保存した応答
+    pub idempotency: Arc<dyn IdempotencyStore>,
+    /// `server.legacy_status_codes`。作成と並び替えを以前のステータスで返す
+    pub legacy_status_codes: bool,
+}
+
+impl FromRef<AppState> for Arc<dyn TodoRepository> {
+    fn from_ref(state: &AppState) -> Self {
+        state.todos.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<dyn CommentRepository> {
+    fn from_ref(state: &AppState) -> Self {
+        state.comments.clone()
+    }
+}
@@ -0,0 +1,178 @@
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    Router,
+};
+use rust_todo_app::create_test_app;
+use tower::util::ServiceExt;
+
+const BOUNDARY: &str = "----rust-todo-app-boundary";
+
+/// レスポンスボディをJSONとして取得するヘルパー
+async fn response_json(response: axum::response::Response) -> serde_json::Value {
+    let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&body_bytes).unwrap()
+}
+
+/// TODOを作成してIDを返すヘルパー
+async fn create_todo(app: &Router, title: &str) -> i64 {
+    let request = Request::builder()
+        .method("POST")
+        .uri("/todos")
+        .header("content-type", "application/json")
+        .body(Body::from(
+            serde_json::json!({ "title": title }).to_string(),
+        ))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    response_json(response).await["id"].as_i64().unwrap()
+}
+
+/// multipartでファイルを1つアップロードするヘルパー
+async fn upload(
+    app: &Router,
+    todo_id: i64,
+    filename: &str,
+    content_type: &str,
+    data: &[u8],
+) -> axum::response::Response {
+    let mut body = format!(
+        "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{filename}\"\r\nContent-Type: {content_type}\r\n\r\n"
+    )
+    .into_bytes();
+    body.extend_from_slice(data);
+    body.extend_from_slice(format!("\r\n--{BOUNDARY}--\r\n").as_bytes());
+
+    let request = Request::builder()
+        .method("POST")
+        .uri(format!("/todos/{}/attachments", todo_id))
+        .header(
+            "content-type",
+            format!("multipart/form-data; boundary={BOUNDARY}"),
+        )
+        .body(Body::from(body))
+        .unwrap();
+    app.clone().oneshot(request).await.unwrap()
+}
+
+async fn get(app: &Router, uri: &str) -> axum::response::Response {
+    let request = Request::builder()
+        .method("GET")
+        .uri(uri)
+        .body(Body::empty())
+        .unwrap();
+    app.clone().oneshot(request).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_upload_list_and_download_attachment() {
+    let app = create_test_app().await;
+    let todo_id = create_todo(&app, "添付テスト").await;
+
+    let response = upload(&app, todo_id, "memo.txt", "text/plain", b"hello").await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let created = response_json(response).await;
+    assert_eq!(created["filename"], "memo.txt");
+    assert_eq!(created["content_type"], "text/plain");
+    assert_eq!(created["size_bytes"], 5);
+    assert_eq!(
+        created["sha256"],
+        "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+    );
+
+    let response = get(&app, &format!("/todos/{}/attachments", todo_id)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let listed = response_json(response).await;
+    assert_eq!(listed.as_array().unwrap().len(), 1);
+
+    let response = get(
+        &app,
+        &format!("/todos/{}/attachments/{}/content", todo_id, created["id"]),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "text/plain");
+    assert!(response.headers()[header::CONTENT_DISPOSITION]
+        .to_str()
+        .unwrap()
+        .contains("memo.txt"));
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(&body[..], b"hello");
+}
+
+#[tokio::test]
+async fn test_upload_rejects_disallowed_content_type() {
+    let app = create_test_app().await;
+    let todo_id = create_todo(&app, "添付テスト").await;
+
+    let response = upload(&app, todo_id, "tool.exe", "application/x-msdownload", b"MZ").await;
+    assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+}
+
+#[tokio::test]
+async fn test_upload_to_missing_todo() {
+    let app = create_test_app().await;
+
+    let response = upload(&app, 99999, "memo.txt", "text/plain", b"hello").await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_delete_attachment() {
+    let app = create_test_app().await;
+    let todo_id = create_todo(&app, "添付テスト").await;
+    let created = response_json(upload(&app, todo_id, "a.txt", "text/plain", b"abc").await).await;
+    let uri = format!("/todos/{}/attachments/{}", todo_id, created["id"]);
+
+    let request = Request::builder()
+        .method("DELETE")
+        .uri(&uri)
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let response = get(&app, &uri).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_deleting_todo_removes_its_attachments() {
+    let app = create_test_app().await;
+    let todo_id = create_todo(&app, "消えるTODO").await;
+    let created = response_json(upload(&app, todo_id, "a.txt", "text/plain", b"abc").await).await;
+    // 同じ内容を別のTODOにも添付しておく
+    let other_id = create_todo(&app, "残るTODO").await;
+    let other = response_json(upload(&app, other_id, "b.txt", "text/plain", b"abc").await).await;
+
+    let request = Request::builder()
+        .method("DELETE")
+        .uri(format!("/todos/{}", todo_id))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let response = get(
+        &app,
+        &format!("/todos/{}/attachments/{}", todo_id, created["id"]),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    // まだ参照されている本体は消さない
+    let response = get(
+        &app,
+        &format!("/todos/{}/attachments/{}/content", other_id, other["id"]),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(&body[..], b"abc");
+}
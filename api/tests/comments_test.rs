@@ -0,0 +1,190 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use rust_todo_app::create_test_app;
+use tower::util::ServiceExt;
+
+/// レスポンスボディをJSONとして取得するヘルパー
+async fn response_json(response: axum::response::Response) -> serde_json::Value {
+    let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&body_bytes).unwrap()
+}
+
+/// TODOを作成してIDを返すヘルパー
+async fn create_todo(app: &Router, title: &str) -> i64 {
+    let request = Request::builder()
+        .method("POST")
+        .uri("/todos")
+        .header("content-type", "application/json")
+        .body(Body::from(
+            serde_json::json!({ "title": title }).to_string(),
+        ))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    response_json(response).await["id"].as_i64().unwrap()
+}
+
+/// コメントを投稿してレスポンスを返すヘルパー
+async fn post_comment(app: &Router, todo_id: i64, body: &str) -> axum::response::Response {
+    let request = Request::builder()
+        .method("POST")
+        .uri(format!("/todos/{}/comments", todo_id))
+        .header("content-type", "application/json")
+        .body(Body::from(
+            serde_json::json!({ "author": "alice", "body": body }).to_string(),
+        ))
+        .unwrap();
+    app.clone().oneshot(request).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_create_and_get_comment() {
+    let app = create_test_app().await;
+    let todo_id = create_todo(&app, "コメント対象").await;
+
+    let response = post_comment(&app, todo_id, "# 見出し\n\n本文").await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let created = response_json(response).await;
+    assert_eq!(created["author"], "alice");
+    assert_eq!(created["body"], "# 見出し\n\n本文");
+    assert_eq!(created["todo_id"], todo_id);
+    assert!(created["created_at"].is_string());
+    assert!(created["edited_at"].is_null());
+
+    let request = Request::builder()
+        .method("GET")
+        .uri(format!("/todos/{}/comments/{}", todo_id, created["id"]))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let fetched = response_json(response).await;
+    assert_eq!(fetched["id"], created["id"]);
+}
+
+#[tokio::test]
+async fn test_create_comment_for_missing_todo() {
+    let app = create_test_app().await;
+
+    let response = post_comment(&app, 99999, "誰もいない").await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_create_comment_validation_error() {
+    let app = create_test_app().await;
+    let todo_id = create_todo(&app, "コメント対象").await;
+
+    let response = post_comment(&app, todo_id, "").await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let error = response_json(response).await;
+    assert_eq!(error["code"], "invalid_fields");
+    assert_eq!(error["errors"][0]["field"], "body");
+}
+
+#[tokio::test]
+async fn test_list_comments_paginates() {
+    let app = create_test_app().await;
+    let todo_id = create_todo(&app, "コメント対象").await;
+    for i in 1..=5 {
+        let response = post_comment(&app, todo_id, &format!("comment {}", i)).await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    let request = Request::builder()
+        .method("GET")
+        .uri(format!("/todos/{}/comments?page=2&per_page=2", todo_id))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let page = response_json(response).await;
+    assert_eq!(page["total"], 5);
+    assert_eq!(page["page"], 2);
+    assert_eq!(page["per_page"], 2);
+    let comments = page["comments"].as_array().unwrap();
+    assert_eq!(comments.len(), 2);
+    assert_eq!(comments[0]["body"], "comment 3");
+    assert_eq!(comments[1]["body"], "comment 4");
+
+    let request = Request::builder()
+        .method("GET")
+        .uri(format!("/todos/{}/comments?per_page=500", todo_id))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_edit_comment_sets_edited_at() {
+    let app = create_test_app().await;
+    let todo_id = create_todo(&app, "コメント対象").await;
+    let created = response_json(post_comment(&app, todo_id, "before").await).await;
+
+    let request = Request::builder()
+        .method("PUT")
+        .uri(format!("/todos/{}/comments/{}", todo_id, created["id"]))
+        .header("content-type", "application/json")
+        .body(Body::from(r#"{"body": "after"}"#))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let edited = response_json(response).await;
+    assert_eq!(edited["body"], "after");
+    assert_eq!(edited["author"], "alice");
+    assert!(edited["edited_at"].is_string());
+}
+
+#[tokio::test]
+async fn test_delete_comment() {
+    let app = create_test_app().await;
+    let todo_id = create_todo(&app, "コメント対象").await;
+    let created = response_json(post_comment(&app, todo_id, "消す").await).await;
+    let uri = format!("/todos/{}/comments/{}", todo_id, created["id"]);
+
+    let request = Request::builder()
+        .method("DELETE")
+        .uri(&uri)
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let request = Request::builder()
+        .method("DELETE")
+        .uri(&uri)
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_deleting_todo_removes_its_comments() {
+    let app = create_test_app().await;
+    let todo_id = create_todo(&app, "消えるTODO").await;
+    let created = response_json(post_comment(&app, todo_id, "道連れ").await).await;
+
+    let request = Request::builder()
+        .method("DELETE")
+        .uri(format!("/todos/{}", todo_id))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let request = Request::builder()
+        .method("GET")
+        .uri(format!("/todos/{}/comments/{}", todo_id, created["id"]))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,91 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use rust_todo_app::config::Config;
+use rust_todo_app::create_app;
+use rust_todo_app::infrastructure::persistence::schema::create_tables;
+use sqlx::sqlite::SqlitePoolOptions;
+use tower::util::ServiceExt;
+
+async fn app_with(config: Config) -> Router {
+    let pool = SqlitePoolOptions::new()
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    create_tables(&pool).await.unwrap();
+    create_app(pool, &config)
+}
+
+async fn get(app: &Router, uri: &str) -> StatusCode {
+    let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+    app.clone().oneshot(request).await.unwrap().status()
+}
+
+/// 設定したオリジンにだけCORSを許可すること
+#[tokio::test]
+async fn test_cors_allows_only_configured_origins() {
+    let mut config = Config::default();
+    config.cors.allowed_origins = vec!["https://todo.example.com".to_string()];
+    let app = app_with(config).await;
+
+    for (origin, allowed) in [
+        ("https://todo.example.com", true),
+        ("https://evil.example.com", false),
+    ] {
+        let request = Request::builder()
+            .method("OPTIONS")
+            .uri("/api/v1/todos")
+            .header("origin", origin)
+            .header("access-control-request-method", "POST")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        let allow_origin = response.headers().get("access-control-allow-origin");
+        assert_eq!(
+            allow_origin.map(|value| value.to_str().unwrap()),
+            allowed.then_some(origin),
+            "{}",
+            origin
+        );
+    }
+
+    // 既定ではどのオリジンも許可しない
+    let app = app_with(Config::default()).await;
+    let request = Request::builder()
+        .method("OPTIONS")
+        .uri("/api/v1/todos")
+        .header("origin", "https://todo.example.com")
+        .header("access-control-request-method", "POST")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert!(response
+        .headers()
+        .get("access-control-allow-origin")
+        .is_none());
+}
+
+/// 無効にした機能のルートは登録されないこと
+#[tokio::test]
+async fn test_disabled_features_are_not_routed() {
+    let mut config = Config::default();
+    config.features.graphql = false;
+    config.features.api_docs = false;
+    config.features.legacy_paths = false;
+    config.features.metrics = false;
+    let app = app_with(config).await;
+
+    assert_eq!(get(&app, "/api/v1/todos").await, StatusCode::OK);
+    assert_eq!(get(&app, "/todos").await, StatusCode::NOT_FOUND);
+    assert_eq!(get(&app, "/graphql").await, StatusCode::NOT_FOUND);
+    assert_eq!(get(&app, "/openapi.json").await, StatusCode::NOT_FOUND);
+    assert_eq!(get(&app, "/docs").await, StatusCode::NOT_FOUND);
+    assert_eq!(get(&app, "/metrics").await, StatusCode::NOT_FOUND);
+
+    let app = app_with(Config::default()).await;
+    assert_eq!(get(&app, "/todos").await, StatusCode::OK);
+    assert_eq!(get(&app, "/openapi.json").await, StatusCode::OK);
+    assert_eq!(get(&app, "/metrics").await, StatusCode::OK);
+}
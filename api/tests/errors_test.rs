@@ -0,0 +1,215 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use rust_todo_app::create_test_app;
+use tower::util::ServiceExt;
+
+/// レスポンスボディをJSONとして取得するヘルパー
+async fn response_json(response: axum::response::Response) -> serde_json::Value {
+    let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&body_bytes).unwrap()
+}
+
+async fn send(app: &Router, request: Request<Body>) -> axum::response::Response {
+    app.clone().oneshot(request).await.unwrap()
+}
+
+fn content_type(response: &axum::response::Response) -> &str {
+    response.headers()["content-type"].to_str().unwrap()
+}
+
+/// 存在しないTODOはproblem+jsonの404になり、instanceとリクエストIDが入ること
+#[tokio::test]
+async fn test_not_found_is_problem_json() {
+    let app = create_test_app().await;
+
+    let request = Request::builder()
+        .uri("/todos/99999")
+        .body(Body::empty())
+        .unwrap();
+    let response = send(&app, request).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(content_type(&response), "application/problem+json");
+    let request_id = response.headers()["x-request-id"]
+        .to_str()
+        .unwrap()
+        .to_string();
+
+    let problem = response_json(response).await;
+    assert_eq!(problem["type"], "/problems/not-found");
+    assert_eq!(problem["status"], 404);
+    assert_eq!(problem["instance"], "/todos/99999");
+    assert_eq!(problem["request_id"], request_id);
+}
+
+/// ルートのないパスと、パスはあるが受け付けないメソッドもproblem+jsonで返し、405は `Allow` を残すこと
+#[tokio::test]
+async fn test_unknown_routes_and_methods_are_problem_json() {
+    let app = create_test_app().await;
+
+    let request = Request::builder()
+        .uri("/no-such-route")
+        .body(Body::empty())
+        .unwrap();
+    let response = send(&app, request).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(content_type(&response), "application/problem+json");
+    let problem = response_json(response).await;
+    assert_eq!(problem["type"], "/problems/not-found");
+    assert_eq!(problem["instance"], "/no-such-route");
+
+    let request = Request::builder()
+        .method("PATCH")
+        .uri("/api/v1/todos")
+        .header("accept-language", "en")
+        .body(Body::empty())
+        .unwrap();
+    let response = send(&app, request).await;
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(content_type(&response), "application/problem+json");
+    assert_eq!(response.headers()["allow"], "GET,HEAD,POST,OPTIONS");
+    let problem = response_json(response).await;
+    assert_eq!(problem["type"], "/problems/method-not-allowed");
+    assert_eq!(problem["status"], 405);
+    assert_eq!(problem["title"], "Method not allowed");
+    assert_eq!(problem["code"], "method_not_allowed");
+}
+
+/// クライアントが送ったX-Request-Idを引き継ぐこと
+#[tokio::test]
+async fn test_request_id_is_propagated() {
+    let app = create_test_app().await;
+
+    let request = Request::builder()
+        .method("DELETE")
+        .uri("/todos/99999")
+        .header("x-request-id", "client-req-42")
+        .body(Body::empty())
+        .unwrap();
+    let response = send(&app, request).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(response.headers()["x-request-id"], "client-req-42");
+    assert_eq!(response_json(response).await["request_id"], "client-req-42");
+}
+
+/// 入力エラーは項目ごとの内訳を返すこと
+#[tokio::test]
+async fn test_validation_problem_lists_field_errors() {
+    let app = create_test_app().await;
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/todos")
+        .header("content-type", "application/json")
+        .body(Body::from(r#"{"title": ""}"#))
+        .unwrap();
+    let response = send(&app, request).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(content_type(&response), "application/problem+json");
+
+    let problem = response_json(response).await;
+    assert_eq!(problem["type"], "/problems/validation-failed");
+    assert_eq!(problem["instance"], "/todos");
+    assert_eq!(problem["errors"][0]["field"], "title");
+    assert!(problem["errors"][0]["message"].is_string());
+}
+
+/// JSONとして読めないボディやContent-Typeの誤りもproblem+jsonで返すこと
+#[tokio::test]
+async fn test_malformed_requests_are_problem_json() {
+    let app = create_test_app().await;
+
+    let broken = Request::builder()
+        .method("POST")
+        .uri("/todos")
+        .header("content-type", "application/json")
+        .body(Body::from("{"))
+        .unwrap();
+    let response = send(&app, broken).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(content_type(&response), "application/problem+json");
+    // 翻訳した説明にaxumの英語の原文を混ぜず、原文は reason で返す
+    let problem = response_json(response).await;
+    assert_eq!(problem["code"], "invalid_json");
+    assert_eq!(
+        problem["detail"],
+        "リクエストボディをJSONとして読み取れません"
+    );
+    assert!(problem["reason"]
+        .as_str()
+        .is_some_and(|reason| !reason.is_empty()));
+
+    let no_content_type = Request::builder()
+        .method("POST")
+        .uri("/todos")
+        .body(Body::from(r#"{"title": "x"}"#))
+        .unwrap();
+    let response = send(&app, no_content_type).await;
+    assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert_eq!(
+        response_json(response).await["type"],
+        "/problems/unsupported-media-type"
+    );
+
+    let bad_id = Request::builder()
+        .uri("/todos/abc")
+        .body(Body::empty())
+        .unwrap();
+    let response = send(&app, bad_id).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(content_type(&response), "application/problem+json");
+}
+
+async fn post_empty_title(app: &Router, accept_language: Option<&str>) -> serde_json::Value {
+    let mut request = Request::builder()
+        .method("POST")
+        .uri("/todos")
+        .header("content-type", "application/json");
+    if let Some(lang) = accept_language {
+        request = request.header("accept-language", lang);
+    }
+    let request = request.body(Body::from(r#"{"title": ""}"#)).unwrap();
+    let response = send(app, request).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    response_json(response).await
+}
+
+/// Accept-Languageに合わせてメッセージを翻訳し、コードは言語によらず同じであること
+#[tokio::test]
+async fn test_messages_follow_accept_language() {
+    let app = create_test_app().await;
+
+    let en = post_empty_title(&app, Some("en-US,en;q=0.9")).await;
+    assert_eq!(en["title"], "Validation failed");
+    assert_eq!(
+        en["errors"][0]["message"],
+        "Title must be between 1 and 200 characters"
+    );
+
+    let ja = post_empty_title(&app, Some("ja")).await;
+    assert_eq!(ja["title"], "入力内容に誤りがあります");
+    assert_eq!(
+        ja["errors"][0]["message"],
+        "タイトルは1文字以上200文字以下で入力してください"
+    );
+
+    for problem in [&en, &ja] {
+        assert_eq!(problem["code"], "invalid_fields");
+        assert_eq!(problem["errors"][0]["code"], "length");
+    }
+}
+
+/// 対応していない言語やヘッダなしの場合は既定の言語（日本語）になること
+#[tokio::test]
+async fn test_unsupported_language_falls_back_to_default() {
+    let app = create_test_app().await;
+
+    for accept_language in [Some("fr-FR"), None] {
+        let problem = post_empty_title(&app, accept_language).await;
+        assert_eq!(problem["title"], "入力内容に誤りがあります");
+    }
+}
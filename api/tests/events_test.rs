@@ -0,0 +1,112 @@
+use std::time::Duration;
+
+use axum::{
+    body::{Body, BodyDataStream},
+    http::{Request, StatusCode},
+    Router,
+};
+use rust_todo_app::create_test_app;
+use tokio_stream::StreamExt;
+use tower::util::ServiceExt;
+
+async fn create_todo(app: &Router, title: &str) {
+    let request = Request::builder()
+        .method("POST")
+        .uri("/todos")
+        .header("content-type", "application/json")
+        .body(Body::from(
+            serde_json::json!({ "title": title }).to_string(),
+        ))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+}
+
+async fn open_stream(app: &Router, last_event_id: Option<&str>) -> BodyDataStream {
+    let mut request = Request::builder().uri("/events");
+    if let Some(id) = last_event_id {
+        request = request.header("last-event-id", id);
+    }
+    let response = app
+        .clone()
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "text/event-stream");
+    response.into_body().into_data_stream()
+}
+
+/// `needle` を含むところまでストリームを読み進めて、読んだ内容を返す
+async fn read_until(stream: &mut BodyDataStream, needle: &str) -> String {
+    let mut received = String::new();
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while !received.contains(needle) {
+            let chunk = stream.next().await.expect("stream ended").unwrap();
+            received.push_str(std::str::from_utf8(&chunk).unwrap());
+        }
+    })
+    .await
+    .unwrap_or_else(|_| panic!("timed out waiting for {:?}, got {:?}", needle, received));
+    received
+}
+
+/// 受け取ったイベントのID
+fn event_ids(received: &str) -> Vec<&str> {
+    received
+        .lines()
+        .filter_map(|line| line.strip_prefix("id: "))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_events_stream_live_changes() {
+    let app = create_test_app().await;
+    let mut stream = open_stream(&app, None).await;
+
+    create_todo(&app, "リアルタイム").await;
+
+    let received = read_until(&mut stream, "\n\n").await;
+    assert!(received.contains("event: todo.created\n"));
+    assert!(received.contains(r#""title":"リアルタイム""#));
+    // `<起動時刻>-<通し番号>`
+    let ids = event_ids(&received);
+    let (epoch, seq) = ids[0].split_once('-').unwrap();
+    assert!(epoch.parse::<u64>().is_ok());
+    assert_eq!(seq, "1");
+}
+
+#[tokio::test]
+async fn test_events_resume_from_last_event_id() {
+    let app = create_test_app().await;
+    let mut live = open_stream(&app, None).await;
+    create_todo(&app, "first").await;
+    create_todo(&app, "second").await;
+    create_todo(&app, "third").await;
+    let received = read_until(&mut live, r#""title":"third""#).await;
+    let ids: Vec<String> = event_ids(&received).into_iter().map(String::from).collect();
+    assert_eq!(ids.len(), 3);
+
+    let mut stream = open_stream(&app, Some(&ids[0])).await;
+    create_todo(&app, "fourth").await;
+
+    let received = read_until(&mut stream, r#""title":"fourth""#).await;
+    assert!(!received.contains("\"first\""));
+    let resumed = event_ids(&received);
+    assert_eq!(resumed.len(), 3);
+    assert_eq!(resumed[..2], [ids[1].as_str(), ids[2].as_str()]);
+}
+
+/// 読めないIDや、前の起動のIDを送ってきたら一覧を取り直してもらうこと
+#[tokio::test]
+async fn test_events_ask_to_resync_for_unknown_id() {
+    let app = create_test_app().await;
+    create_todo(&app, "only").await;
+
+    for id in ["42", "1-1"] {
+        let mut stream = open_stream(&app, Some(id)).await;
+
+        let received = read_until(&mut stream, "\n\n").await;
+        assert!(received.contains("event: resync\n"), "{}", id);
+    }
+}
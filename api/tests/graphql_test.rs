@@ -0,0 +1,352 @@
+use std::time::Duration;
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use futures_util::{SinkExt, StreamExt};
+use rust_todo_app::create_test_app;
+use tokio::net::TcpStream;
+use tokio_tungstenite::{
+    connect_async,
+    tungstenite::{client::IntoClientRequest, Message},
+    MaybeTlsStream, WebSocketStream,
+};
+use tower::util::ServiceExt;
+
+type Client = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// レスポンスボディをJSONとして取得するヘルパー
+async fn response_json(response: axum::response::Response) -> serde_json::Value {
+    let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&body_bytes).unwrap()
+}
+
+async fn graphql_with_language(
+    app: &Router,
+    query: &str,
+    variables: serde_json::Value,
+    language: &str,
+) -> serde_json::Value {
+    let request = Request::builder()
+        .method("POST")
+        .uri("/graphql")
+        .header("content-type", "application/json")
+        .header("accept-language", language)
+        .body(Body::from(
+            serde_json::json!({"query": query, "variables": variables}).to_string(),
+        ))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    // GraphQLのエラーもHTTPとしては200で返る
+    assert_eq!(response.status(), StatusCode::OK);
+    response_json(response).await
+}
+
+async fn graphql(app: &Router, query: &str, variables: serde_json::Value) -> serde_json::Value {
+    graphql_with_language(app, query, variables, "ja").await
+}
+
+async fn create_todo(app: &Router, title: &str) -> i64 {
+    let result = graphql(
+        app,
+        "mutation($title: String!) { createTodo(input: {title: $title}) { id title completed } }",
+        serde_json::json!({"title": title}),
+    )
+    .await;
+    assert!(result["errors"].is_null(), "{}", result);
+    result["data"]["createTodo"]["id"].as_i64().unwrap()
+}
+
+/// 期限での絞り込みと並び替えがDB上でも期限なしを除き・末尾に回すこと
+#[tokio::test]
+async fn test_graphql_query_filters_and_sorts_by_due_date() {
+    let app = create_test_app().await;
+    let mut ids = Vec::new();
+    for (title, due_at) in [
+        ("late", Some("2025-01-03T00:00:00Z")),
+        ("undated", None),
+        ("early", Some("2025-01-01T00:00:00Z")),
+        ("fractional", Some("2025-01-01T00:00:00.500Z")),
+    ] {
+        let id = create_todo(&app, title).await;
+        if let Some(due_at) = due_at {
+            let result = graphql(
+                &app,
+                "mutation($id: Int!, $due: DateTime!) { updateTodo(id: $id, input: {dueAt: $due}) { id } }",
+                serde_json::json!({"id": id, "due": due_at}),
+            )
+            .await;
+            assert!(result["errors"].is_null(), "{}", result);
+        }
+        ids.push(id);
+    }
+    let [late, undated, early, fractional] = ids[..] else {
+        unreachable!()
+    };
+
+    let query = "query($filter: TodoFilterInput, $sort: TodoSortInput) { todos(filter: $filter, sort: $sort) { totalCount nodes { id } } }";
+    let listed = |result: serde_json::Value| -> Vec<i64> {
+        result["data"]["todos"]["nodes"]
+            .as_array()
+            .unwrap_or_else(|| panic!("{}", result))
+            .iter()
+            .map(|node| node["id"].as_i64().unwrap())
+            .collect()
+    };
+
+    let result = graphql(
+        &app,
+        query,
+        serde_json::json!({"sort": {"field": "DUE_AT", "direction": "DESC"}}),
+    )
+    .await;
+    assert_eq!(listed(result), vec![late, fractional, early, undated]);
+
+    let result = graphql(
+        &app,
+        query,
+        serde_json::json!({"filter": {"dueAfter": "2025-01-01T00:00:00Z", "dueBefore": "2025-01-03T00:00:00Z"}}),
+    )
+    .await;
+    assert_eq!(result["data"]["todos"]["totalCount"], 1);
+    assert_eq!(listed(result), vec![fractional]);
+}
+
+#[tokio::test]
+async fn test_graphql_query_filters_sorts_and_counts_comments() {
+    let app = create_test_app().await;
+    let milk = create_todo(&app, "Buy milk").await;
+    let report = create_todo(&app, "Write report").await;
+    let bread = create_todo(&app, "Buy bread").await;
+
+    graphql(
+        &app,
+        "mutation($id: Int!) { updateTodo(id: $id, input: {completed: true}) { id } }",
+        serde_json::json!({"id": report}),
+    )
+    .await;
+    for body in ["one", "two"] {
+        let request = Request::builder()
+            .method("POST")
+            .uri(format!("/api/v1/todos/{}/comments", bread))
+            .header("content-type", "application/json")
+            .body(Body::from(
+                serde_json::json!({"author": "alice", "body": body}).to_string(),
+            ))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    let result = graphql(
+        &app,
+        r#"{
+            todos(filter: {completed: false, titleContains: "buy"}, sort: {field: TITLE}, limit: 1) {
+                totalCount
+                hasNextPage
+                nodes { id title commentCount }
+            }
+        }"#,
+        serde_json::json!({}),
+    )
+    .await;
+    let todos = &result["data"]["todos"];
+    assert_eq!(todos["totalCount"], 2);
+    assert_eq!(todos["hasNextPage"], true);
+    assert_eq!(todos["nodes"][0]["id"], bread);
+    assert_eq!(todos["nodes"][0]["commentCount"], 2);
+
+    let result = graphql(
+        &app,
+        "{ todos(sort: {direction: DESC}) { nodes { id commentCount } } }",
+        serde_json::json!({}),
+    )
+    .await;
+    let ids: Vec<i64> = result["data"]["todos"]["nodes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|node| node["id"].as_i64().unwrap())
+        .collect();
+    assert_eq!(ids, vec![bread, report, milk]);
+    assert_eq!(result["data"]["todos"]["nodes"][1]["commentCount"], 0);
+
+    let result = graphql(
+        &app,
+        "query($a: Int!, $b: Int!) { a: todo(id: $a) { title } b: todo(id: $b) { title } missing: todo(id: 999) { title } }",
+        serde_json::json!({"a": milk, "b": report}),
+    )
+    .await;
+    assert_eq!(result["data"]["a"]["title"], "Buy milk");
+    assert_eq!(result["data"]["b"]["title"], "Write report");
+    assert!(result["data"]["missing"].is_null());
+}
+
+#[tokio::test]
+async fn test_graphql_mutations_mirror_rest() {
+    let app = create_test_app().await;
+    let first = create_todo(&app, "first").await;
+    let second = create_todo(&app, "second").await;
+
+    let result = graphql(
+        &app,
+        r#"mutation($id: Int!) {
+            updateTodo(id: $id, input: {title: "renamed", dueAt: "2030-01-01T00:00:00Z"}) { title dueAt }
+        }"#,
+        serde_json::json!({"id": first}),
+    )
+    .await;
+    assert_eq!(result["data"]["updateTodo"]["title"], "renamed");
+    assert_eq!(
+        result["data"]["updateTodo"]["dueAt"],
+        "2030-01-01T00:00:00+00:00"
+    );
+
+    let result = graphql(
+        &app,
+        "mutation($id: Int!) { updateTodo(id: $id, input: {dueAt: null}) { dueAt } }",
+        serde_json::json!({"id": first}),
+    )
+    .await;
+    assert!(result["data"]["updateTodo"]["dueAt"].is_null());
+
+    let result = graphql(
+        &app,
+        "mutation($ids: [Int!]!) { reorderTodos(ids: $ids) }",
+        serde_json::json!({"ids": [second, first]}),
+    )
+    .await;
+    assert_eq!(result["data"]["reorderTodos"], true);
+
+    let result = graphql(
+        &app,
+        "mutation($id: Int!) { deleteTodo(id: $id) }",
+        serde_json::json!({"id": second}),
+    )
+    .await;
+    assert_eq!(result["data"]["deleteTodo"], true);
+
+    // REST側からも同じ状態が見える
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/api/v1/todos")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let todos = response_json(response).await;
+    assert_eq!(todos.as_array().unwrap().len(), 1);
+    assert_eq!(todos[0]["title"], "renamed");
+}
+
+#[tokio::test]
+async fn test_graphql_errors_carry_code_and_localized_message() {
+    let app = create_test_app().await;
+
+    let result = graphql_with_language(
+        &app,
+        r#"mutation { createTodo(input: {title: ""}) { id } }"#,
+        serde_json::json!({}),
+        "en",
+    )
+    .await;
+    let error = &result["errors"][0];
+    assert_eq!(error["extensions"]["code"], "invalid_fields");
+    assert_eq!(error["message"], "One or more fields are invalid");
+    assert_eq!(error["extensions"]["fields"][0]["field"], "title");
+    assert_eq!(error["extensions"]["fields"][0]["code"], "length");
+
+    let result = graphql(
+        &app,
+        "mutation { deleteTodo(id: 42) }",
+        serde_json::json!({}),
+    )
+    .await;
+    assert_eq!(result["errors"][0]["extensions"]["code"], "not_found");
+
+    let result = graphql(
+        &app,
+        "{ todos(limit: 0) { totalCount } }",
+        serde_json::json!({}),
+    )
+    .await;
+    assert_eq!(
+        result["errors"][0]["extensions"]["code"],
+        "limit_out_of_range"
+    );
+}
+
+#[tokio::test]
+async fn test_graphql_subscription_streams_todo_changes() {
+    let app = create_test_app().await;
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    let mut request = format!("ws://{}/graphql/ws", addr)
+        .into_client_request()
+        .unwrap();
+    request.headers_mut().insert(
+        "sec-websocket-protocol",
+        "graphql-transport-ws".parse().unwrap(),
+    );
+    let (mut client, response) = connect_async(request).await.unwrap();
+    assert_eq!(
+        response.headers()["sec-websocket-protocol"],
+        "graphql-transport-ws"
+    );
+
+    client
+        .send(Message::Text(
+            serde_json::json!({"type": "connection_init"}).to_string(),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(recv_json(&mut client).await["type"], "connection_ack");
+    client
+        .send(Message::Text(
+            serde_json::json!({
+                "type": "subscribe",
+                "id": "1",
+                "payload": {"query": "subscription { todoChanged { event todoId todo { title } } }"}
+            })
+            .to_string(),
+        ))
+        .await
+        .unwrap();
+    // 購読の登録が済むまで少し待ってから変更を起こす
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    reqwest::Client::new()
+        .post(format!("http://{}/api/v1/todos", addr))
+        .json(&serde_json::json!({"title": "届くはず"}))
+        .send()
+        .await
+        .unwrap();
+
+    let message = recv_json(&mut client).await;
+    assert_eq!(message["type"], "next");
+    assert_eq!(message["id"], "1");
+    let change = &message["payload"]["data"]["todoChanged"];
+    assert_eq!(change["event"], "todo.created");
+    assert_eq!(change["todo"]["title"], "届くはず");
+}
+
+async fn recv_json(client: &mut Client) -> serde_json::Value {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            if let Message::Text(text) = client.next().await.unwrap().unwrap() {
+                return serde_json::from_str::<serde_json::Value>(&text).unwrap();
+            }
+        }
+    })
+    .await
+    .expect("timed out waiting for message")
+}
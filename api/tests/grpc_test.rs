@@ -0,0 +1,176 @@
+use std::time::Duration;
+
+use rust_todo_app::config::Config;
+use rust_todo_app::infrastructure::persistence::schema::create_tables;
+use rust_todo_app::presentation::grpc::pb::{
+    self, todo_service_client::TodoServiceClient, update_todo_request::Due,
+};
+use rust_todo_app::presentation::grpc::ERROR_CODE_METADATA;
+use rust_todo_app::{create_app_with_grpc, spawn_grpc_server};
+use sqlx::sqlite::SqlitePoolOptions;
+use tokio_util::sync::CancellationToken;
+use tonic::transport::Channel;
+use tonic::Code;
+
+/// HTTPとgRPCを同じ状態で実際のポートに起動し、HTTPのアドレスとgRPCのクライアントを返す
+async fn start_servers() -> (String, TodoServiceClient<Channel>) {
+    let pool = SqlitePoolOptions::new()
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    create_tables(&pool).await.unwrap();
+    let services = create_app_with_grpc(pool, &Config::default());
+
+    let http_listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let http_addr = http_listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(http_listener, services.http).await.unwrap() });
+
+    let grpc_listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let grpc_addr = grpc_listener.local_addr().unwrap();
+    spawn_grpc_server(grpc_listener, services.grpc, CancellationToken::new());
+    let client = TodoServiceClient::connect(format!("http://{}", grpc_addr))
+        .await
+        .unwrap();
+
+    (format!("http://{}", http_addr), client)
+}
+
+async fn create(client: &mut TodoServiceClient<Channel>, title: &str) -> pb::Todo {
+    client
+        .create_todo(pb::CreateTodoRequest {
+            title: title.to_string(),
+        })
+        .await
+        .unwrap()
+        .into_inner()
+}
+
+#[tokio::test]
+async fn test_grpc_crud_and_reorder() {
+    let (_, mut client) = start_servers().await;
+    let first = create(&mut client, "first").await;
+    let second = create(&mut client, "second").await;
+
+    let updated = client
+        .update_todo(pb::UpdateTodoRequest {
+            id: first.id,
+            title: Some("renamed".to_string()),
+            completed: Some(true),
+            due: Some(Due::DueAt(prost_types::Timestamp {
+                seconds: 1_900_000_000,
+                nanos: 0,
+            })),
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(updated.title, "renamed");
+    assert!(updated.completed);
+    assert_eq!(updated.due_at.unwrap().seconds, 1_900_000_000);
+
+    let cleared = client
+        .update_todo(pb::UpdateTodoRequest {
+            id: first.id,
+            due: Some(Due::ClearDueAt(true)),
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(cleared.due_at.is_none());
+    assert_eq!(cleared.title, "renamed");
+
+    client
+        .reorder_todos(pb::ReorderTodosRequest {
+            ids: vec![second.id, first.id],
+        })
+        .await
+        .unwrap();
+    let listed = client
+        .list_todos(pb::ListTodosRequest {})
+        .await
+        .unwrap()
+        .into_inner();
+    let ids: Vec<i64> = listed.todos.iter().map(|todo| todo.id).collect();
+    assert_eq!(ids, vec![second.id, first.id]);
+
+    client
+        .delete_todo(pb::DeleteTodoRequest { id: second.id })
+        .await
+        .unwrap();
+    let status = client
+        .get_todo(pb::GetTodoRequest { id: second.id })
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::NotFound);
+    assert_eq!(
+        status
+            .metadata()
+            .get(ERROR_CODE_METADATA)
+            .unwrap()
+            .to_str()
+            .unwrap(),
+        "not_found"
+    );
+}
+
+#[tokio::test]
+async fn test_grpc_validation_errors_are_invalid_argument() {
+    let (_, mut client) = start_servers().await;
+
+    let mut request = tonic::Request::new(pb::CreateTodoRequest {
+        title: String::new(),
+    });
+    request
+        .metadata_mut()
+        .insert("accept-language", "en".parse().unwrap());
+    let status = client.create_todo(request).await.unwrap_err();
+
+    assert_eq!(status.code(), Code::InvalidArgument);
+    assert_eq!(
+        status
+            .metadata()
+            .get(ERROR_CODE_METADATA)
+            .unwrap()
+            .to_str()
+            .unwrap(),
+        "invalid_fields"
+    );
+    assert!(status
+        .message()
+        .starts_with("One or more fields are invalid"));
+}
+
+#[tokio::test]
+async fn test_grpc_watch_sees_changes_from_http() {
+    let (http_addr, mut client) = start_servers().await;
+    let mut events = client
+        .watch_todos(pb::WatchTodosRequest {})
+        .await
+        .unwrap()
+        .into_inner();
+
+    // HTTPで作ったTODOも同じイベントとして流れる
+    reqwest::Client::new()
+        .post(format!("{}/api/v1/todos", http_addr))
+        .json(&serde_json::json!({"title": "HTTPから"}))
+        .send()
+        .await
+        .unwrap();
+    let created = create(&mut client, "gRPCから").await;
+
+    let mut titles = Vec::new();
+    for _ in 0..2 {
+        let event = tokio::time::timeout(Duration::from_secs(5), events.message())
+            .await
+            .expect("timed out waiting for event")
+            .unwrap()
+            .unwrap();
+        assert_eq!(event.name, "todo.created");
+        match event.payload {
+            Some(pb::todo_event::Payload::Todo(todo)) => titles.push(todo.title),
+            other => panic!("unexpected payload: {:?}", other),
+        }
+    }
+    assert_eq!(titles, vec!["HTTPから", created.title.as_str()]);
+}
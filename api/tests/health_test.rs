@@ -0,0 +1,149 @@
+use std::time::Duration;
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use rust_todo_app::config::Config;
+use rust_todo_app::infrastructure::persistence::schema::create_tables;
+use rust_todo_app::{create_app, create_app_with_grpc, create_test_app, spawn_webhook_dispatcher};
+use sqlx::sqlite::{SqlitePool, SqlitePoolOptions};
+use tokio_util::sync::CancellationToken;
+use tower::util::ServiceExt;
+
+async fn memory_pool() -> SqlitePool {
+    SqlitePoolOptions::new()
+        .connect("sqlite::memory:")
+        .await
+        .unwrap()
+}
+
+async fn get(app: &Router, uri: &str) -> (StatusCode, String) {
+    let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+async fn get_json(app: &Router, uri: &str) -> (StatusCode, serde_json::Value) {
+    let (status, body) = get(app, uri).await;
+    (status, serde_json::from_str(&body).unwrap())
+}
+
+/// 名前で確認項目を探す
+fn check<'a>(body: &'a serde_json::Value, name: &str) -> &'a serde_json::Value {
+    body["checks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|check| check["name"] == name)
+        .unwrap_or_else(|| panic!("check {} not found in {}", name, body))
+}
+
+#[tokio::test]
+async fn test_healthz_reports_the_process_is_alive() {
+    let app = create_test_app().await;
+
+    assert_eq!(
+        get(&app, "/healthz").await,
+        (StatusCode::OK, "ok".to_string())
+    );
+    let (status, body) = get_json(&app, "/healthz?verbose").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "ok");
+    assert!(body["uptime_secs"].is_u64());
+}
+
+#[tokio::test]
+async fn test_readyz_passes_with_a_migrated_database() {
+    let app = create_test_app().await;
+
+    assert_eq!(
+        get(&app, "/readyz").await,
+        (StatusCode::OK, "ok".to_string())
+    );
+    assert_eq!(
+        get(&app, "/readyz?verbose=false").await,
+        (StatusCode::OK, "ok".to_string())
+    );
+
+    let (status, body) = get_json(&app, "/readyz?verbose").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "ok");
+    for name in ["database", "schema"] {
+        let check = check(&body, name);
+        assert_eq!(check["status"], "ok", "{}", check);
+        assert!(check["latency_ms"].as_f64().unwrap() >= 0.0);
+        assert!(check.get("detail").is_none());
+    }
+}
+
+/// テーブルを作っていなければ準備できていないと答えること
+#[tokio::test]
+async fn test_readyz_fails_before_tables_are_created() {
+    let app = create_app(memory_pool().await, &Config::default());
+
+    let (status, body) = get(&app, "/readyz").await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body, "unavailable: schema");
+
+    let (_, body) = get_json(&app, "/readyz?verbose").await;
+    assert_eq!(body["status"], "unavailable");
+    assert_eq!(check(&body, "database")["status"], "ok");
+    let schema = check(&body, "schema");
+    assert_eq!(schema["status"], "fail");
+    assert!(schema["detail"]
+        .as_str()
+        .unwrap()
+        .starts_with("missing todos, comments"));
+}
+
+/// データベースにつながらなければ準備できていないと答え、`/healthz` は通ること
+#[tokio::test]
+async fn test_readyz_fails_when_the_database_is_unreachable() {
+    let pool = memory_pool().await;
+    create_tables(&pool).await.unwrap();
+    let app = create_app(pool.clone(), &Config::default());
+    pool.close().await;
+
+    let (status, body) = get_json(&app, "/readyz?verbose").await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    let database = check(&body, "database");
+    assert_eq!(database["status"], "fail");
+    assert!(database["detail"].is_string());
+
+    assert_eq!(get(&app, "/healthz").await.0, StatusCode::OK);
+}
+
+/// 動いているバックグラウンドタスクを報告し、止まったら準備できていないと答えること
+#[tokio::test]
+async fn test_readyz_reports_background_workers() {
+    let pool = memory_pool().await;
+    create_tables(&pool).await.unwrap();
+    let services = create_app_with_grpc(pool.clone(), &Config::default());
+    let shutdown = CancellationToken::new();
+    let dispatcher = spawn_webhook_dispatcher(
+        pool,
+        Duration::from_secs(3600),
+        &services.workers,
+        shutdown.clone(),
+    );
+    let app = services.http;
+
+    let (status, body) = get_json(&app, "/readyz?verbose").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(check(&body, "worker:webhook_dispatcher")["status"], "ok");
+
+    shutdown.cancel();
+    dispatcher.await.unwrap();
+
+    let (status, body) = get_json(&app, "/readyz?verbose").await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    let worker = check(&body, "worker:webhook_dispatcher");
+    assert_eq!(worker["status"], "fail");
+    assert_eq!(worker["detail"], "stopped");
+}
@@ -0,0 +1,208 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post as post_route,
+    Router,
+};
+use rust_todo_app::create_test_app;
+use rust_todo_app::infrastructure::clock::SystemClock;
+use rust_todo_app::infrastructure::persistence::schema::create_tables;
+use rust_todo_app::infrastructure::persistence::sqlite_idempotency_store::IdempotencyKeyStore;
+use rust_todo_app::presentation::idempotency::{idempotent, Idempotency};
+use sqlx::sqlite::SqlitePoolOptions;
+use tower::util::ServiceExt;
+
+async fn send(app: &Router, request: Request<Body>) -> axum::response::Response {
+    app.clone().oneshot(request).await.unwrap()
+}
+
+async fn response_json(response: axum::response::Response) -> serde_json::Value {
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+fn post(uri: &str, key: Option<&str>, body: &str) -> Request<Body> {
+    let mut request = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("content-type", "application/json");
+    if let Some(key) = key {
+        request = request.header("idempotency-key", key);
+    }
+    request.body(Body::from(body.to_string())).unwrap()
+}
+
+async fn todo_count(app: &Router) -> usize {
+    let request = Request::builder()
+        .uri("/api/v1/todos")
+        .body(Body::empty())
+        .unwrap();
+    response_json(send(app, request).await)
+        .await
+        .as_array()
+        .unwrap()
+        .len()
+}
+
+/// 同じキーで再送したPOSTは一度だけ処理され、最初と同じ応答が返ること
+#[tokio::test]
+async fn test_retried_create_is_processed_once() {
+    let app = create_test_app().await;
+    let body = r#"{"title":"牛乳を買う"}"#;
+
+    let response = send(&app, post("/api/v1/todos", Some("create-1"), body)).await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    assert!(response.headers().get("idempotent-replayed").is_none());
+    let created = response_json(response).await;
+    let created_id = created["id"].as_i64().unwrap();
+
+    let response = send(&app, post("/api/v1/todos", Some("create-1"), body)).await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    assert_eq!(response.headers()["idempotent-replayed"], "true");
+    assert_eq!(response.headers()["content-type"], "application/json");
+    assert_eq!(
+        response.headers()["location"],
+        format!("/api/v1/todos/{}", created_id)
+    );
+    assert_eq!(response_json(response).await, created);
+
+    // 旧パスから送り直しても同じリクエストとして扱う
+    let response = send(&app, post("/todos", Some("create-1"), body)).await;
+    assert_eq!(response.headers()["idempotent-replayed"], "true");
+
+    assert_eq!(todo_count(&app).await, 1);
+
+    // キーがなければ従来どおり毎回作る
+    send(&app, post("/api/v1/todos", None, body)).await;
+    send(&app, post("/api/v1/todos", None, body)).await;
+    assert_eq!(todo_count(&app).await, 3);
+}
+
+/// 同じキーを別の内容のリクエストに使うと422になること
+#[tokio::test]
+async fn test_reusing_a_key_with_a_different_body_is_rejected() {
+    let app = create_test_app().await;
+
+    let response = send(
+        &app,
+        post("/api/v1/todos", Some("create-2"), r#"{"title":"A"}"#),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let todo = response_json(response).await;
+
+    let response = send(
+        &app,
+        post("/api/v1/todos", Some("create-2"), r#"{"title":"B"}"#),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(
+        response.headers()["content-type"],
+        "application/problem+json"
+    );
+    let problem = response_json(response).await;
+    assert_eq!(problem["type"], "/problems/unprocessable-entity");
+    assert_eq!(problem["code"], "idempotency_key_reused");
+
+    // 別のエンドポイントでも同じキーは使えない
+    let response = send(
+        &app,
+        post(
+            &format!("/api/v1/todos/{}/comments", todo["id"]),
+            Some("create-2"),
+            r#"{"author":"alice","body":"hi"}"#,
+        ),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(todo_count(&app).await, 1);
+}
+
+/// 入力エラーの応答も保存して返し、長すぎるキーは受け付けないこと
+#[tokio::test]
+async fn test_error_responses_are_replayed_and_keys_are_validated() {
+    let app = create_test_app().await;
+
+    for _ in 0..2 {
+        let response = send(
+            &app,
+            post("/api/v1/todos", Some("invalid-1"), r#"{"title":""}"#),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let problem = response_json(response).await;
+        assert_eq!(problem["code"], "invalid_fields");
+    }
+
+    let key = "k".repeat(256);
+    let response = send(&app, post("/api/v1/todos", Some(&key), r#"{"title":"A"}"#)).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let problem = response_json(response).await;
+    assert_eq!(problem["code"], "invalid_idempotency_key");
+    assert_eq!(todo_count(&app).await, 0);
+}
+
+/// キーはクライアントごとに分かれ、別のクライアントの応答が返らないこと
+#[tokio::test]
+async fn test_keys_are_scoped_per_client() {
+    let app = create_test_app().await;
+    let with_token = |token: &str, title: &str| {
+        let mut request = post("/api/v1/todos", Some("shared"), title);
+        request.headers_mut().insert(
+            "authorization",
+            format!("Bearer {}", token).parse().unwrap(),
+        );
+        request
+    };
+
+    let alice = send(&app, with_token("alice", r#"{"title":"A"}"#)).await;
+    assert_eq!(alice.status(), StatusCode::CREATED);
+    let bob = send(&app, with_token("bob", r#"{"title":"B"}"#)).await;
+    assert_eq!(bob.status(), StatusCode::CREATED);
+    assert!(bob.headers().get("idempotent-replayed").is_none());
+    assert_eq!(response_json(bob).await["title"], "B");
+
+    // 同じクライアントからの再送には保存した応答を返す
+    let retry = send(&app, with_token("alice", r#"{"title":"A"}"#)).await;
+    assert_eq!(retry.headers()["idempotent-replayed"], "true");
+    assert_eq!(todo_count(&app).await, 2);
+}
+
+/// 打ち切ったリクエストは書き込みを終えているかもしれないので、キーを解放せず、
+/// 同じキーの再送を処理中として断ること
+#[tokio::test]
+async fn test_timed_out_requests_keep_the_key_reserved() {
+    let pool = SqlitePoolOptions::new()
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    create_tables(&pool).await.unwrap();
+    let idempotency = Idempotency {
+        store: Arc::new(IdempotencyKeyStore::new(pool)),
+        clock: Arc::new(SystemClock),
+        ttl: chrono::Duration::hours(1),
+        request_timeout: Duration::from_millis(50),
+        in_progress_timeout: chrono::Duration::minutes(1),
+    };
+    let app = Router::new().route(
+        "/slow",
+        post_route(std::future::pending::<&'static str>)
+            .layer(middleware::from_fn_with_state(idempotency, idempotent)),
+    );
+
+    let response = send(&app, post("/slow", Some("k"), "{}")).await;
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    let retry = send(&app, post("/slow", Some("k"), "{}")).await;
+    assert_eq!(retry.status(), StatusCode::CONFLICT);
+    assert_eq!(
+        response_json(retry).await["code"],
+        "idempotency_key_in_progress"
+    );
+}
@@ -2,7 +2,10 @@ use axum::{
     body::Body,
     http::{Request, StatusCode},
 };
-use rust_todo_app::create_test_app;
+use rust_todo_app::config::Config;
+use rust_todo_app::infrastructure::persistence::schema::create_tables;
+use rust_todo_app::{create_app, create_test_app};
+use sqlx::sqlite::SqlitePoolOptions;
 use tower::util::ServiceExt;
 
 /// レスポンスボディをJSONとして取得するヘルパー
@@ -13,6 +16,18 @@ async fn response_json(response: axum::response::Response) -> serde_json::Value
     serde_json::from_slice(&body_bytes).unwrap()
 }
 
+/// `server.legacy_status_codes` を有効にした、古いクライアント向けのアプリ
+async fn create_legacy_app() -> axum::Router {
+    let pool = SqlitePoolOptions::new()
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    create_tables(&pool).await.unwrap();
+    let mut config = Config::default();
+    config.server.legacy_status_codes = true;
+    create_app(pool, &config)
+}
+
 #[tokio::test]
 async fn test_create_todo() {
     let app = create_test_app().await;
@@ -25,12 +40,14 @@ async fn test_create_todo() {
         .unwrap();
 
     let response = app.oneshot(request).await.unwrap();
-    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let location = response.headers()["location"].to_str().unwrap().to_string();
 
     let todo = response_json(response).await;
     assert_eq!(todo["title"], "テストTODO");
     assert_eq!(todo["completed"], false);
     assert!(todo["id"].as_i64().unwrap() > 0);
+    assert_eq!(location, format!("/todos/{}", todo["id"]));
 }
 
 #[tokio::test]
@@ -46,7 +63,7 @@ async fn test_get_todos() {
             .body(Body::from(serde_json::json!({"title": title}).to_string()))
             .unwrap();
         let response = app.clone().oneshot(request).await.unwrap();
-        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.status(), StatusCode::CREATED);
     }
 
     // 一覧取得
@@ -225,7 +242,7 @@ async fn test_reorder_todos() {
             .body(Body::from(serde_json::json!({"title": title}).to_string()))
             .unwrap();
         let response = app.clone().oneshot(request).await.unwrap();
-        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.status(), StatusCode::CREATED);
         let created = response_json(response).await;
         ids.push(created["id"].as_i64().unwrap());
     }
@@ -240,7 +257,7 @@ async fn test_reorder_todos() {
         ))
         .unwrap();
     let reorder_response = app.clone().oneshot(reorder_request).await.unwrap();
-    assert_eq!(reorder_response.status(), StatusCode::OK);
+    assert_eq!(reorder_response.status(), StatusCode::NO_CONTENT);
 
     // 一覧取得して順序を確認
     let get_request = Request::builder()
@@ -273,7 +290,7 @@ async fn test_reorder_todos_empty_ids() {
         .body(Body::from(r#"{"ids": []}"#))
         .unwrap();
     let response = app.oneshot(request).await.unwrap();
-    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
 }
 
 #[tokio::test]
@@ -290,8 +307,8 @@ async fn test_validation_error_empty_title() {
     assert_eq!(response.status(), StatusCode::BAD_REQUEST);
 
     let error = response_json(response).await;
-    assert_eq!(error["error"], "Validation failed");
-    assert!(error["details"].is_array());
+    assert_eq!(error["code"], "invalid_fields");
+    assert_eq!(error["errors"][0]["field"], "title");
 }
 
 #[tokio::test]
@@ -311,8 +328,8 @@ async fn test_validation_error_too_long_title() {
     assert_eq!(response.status(), StatusCode::BAD_REQUEST);
 
     let error = response_json(response).await;
-    assert_eq!(error["error"], "Validation failed");
-    assert!(error["details"].is_array());
+    assert_eq!(error["code"], "invalid_fields");
+    assert_eq!(error["errors"][0]["field"], "title");
 }
 
 #[tokio::test]
@@ -339,8 +356,8 @@ async fn test_update_validation_error_empty_title() {
     assert_eq!(response.status(), StatusCode::BAD_REQUEST);
 
     let error = response_json(response).await;
-    assert_eq!(error["error"], "Validation failed");
-    assert!(error["details"].is_array());
+    assert_eq!(error["code"], "invalid_fields");
+    assert_eq!(error["errors"][0]["field"], "title");
 }
 
 #[tokio::test]
@@ -361,3 +378,92 @@ async fn test_handler_hello() {
     let body = std::str::from_utf8(&body_bytes).unwrap();
     assert_eq!(body, "Hello, World!");
 }
+
+#[tokio::test]
+async fn test_legacy_status_codes() {
+    let app = create_legacy_app().await;
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/todos")
+        .header("content-type", "application/json")
+        .body(Body::from(r#"{"title": "テストTODO"}"#))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().get("location").is_none());
+    let todo = response_json(response).await;
+
+    let request = Request::builder()
+        .method("PUT")
+        .uri("/todos/reorder")
+        .header("content-type", "application/json")
+        .body(Body::from(
+            serde_json::json!({ "ids": [todo["id"]] }).to_string(),
+        ))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_head_and_options() {
+    let app = create_test_app().await;
+
+    let request = Request::builder()
+        .method("HEAD")
+        .uri("/todos")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "application/json");
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert!(body.is_empty());
+
+    let request = Request::builder()
+        .method("OPTIONS")
+        .uri("/todos/1")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert_eq!(response.headers()["allow"], "GET,HEAD,PUT,DELETE,OPTIONS");
+}
+
+#[tokio::test]
+async fn test_wrong_method_is_not_allowed() {
+    let app = create_test_app().await;
+
+    for (method, uri, allow) in [
+        ("PATCH", "/todos", "GET,HEAD,POST,OPTIONS"),
+        ("POST", "/todos/1", "GET,HEAD,PUT,DELETE,OPTIONS"),
+        ("GET", "/todos/reorder", "PUT,OPTIONS"),
+    ] {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(
+            response.status(),
+            StatusCode::METHOD_NOT_ALLOWED,
+            "{} {}",
+            method,
+            uri
+        );
+        assert_eq!(response.headers()["allow"], allow);
+    }
+
+    // パスに当たらなければ404のまま
+    let request = Request::builder()
+        .method("GET")
+        .uri("/nope")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
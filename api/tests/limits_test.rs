@@ -0,0 +1,245 @@
+use std::net::SocketAddr;
+
+use axum::{
+    body::Body,
+    extract::ConnectInfo,
+    http::{Request, StatusCode},
+    Router,
+};
+use rust_todo_app::config::Config;
+use rust_todo_app::create_app;
+use rust_todo_app::infrastructure::persistence::schema::create_tables;
+use sqlx::sqlite::SqlitePoolOptions;
+use tower::util::ServiceExt;
+
+async fn app_with(config: Config) -> Router {
+    let pool = SqlitePoolOptions::new()
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    create_tables(&pool).await.unwrap();
+    create_app(pool, &config)
+}
+
+async fn send(app: &Router, request: Request<Body>) -> axum::response::Response {
+    app.clone().oneshot(request).await.unwrap()
+}
+
+/// `ip` から接続してきたリクエスト。`token` があれば `Authorization` に載せる
+fn get(uri: &str, ip: [u8; 4], token: Option<&str>) -> Request<Body> {
+    let mut request = Request::builder().uri(uri);
+    if let Some(token) = token {
+        request = request.header("authorization", format!("Bearer {}", token));
+    }
+    let mut request = request.body(Body::empty()).unwrap();
+    request
+        .extensions_mut()
+        .insert(ConnectInfo(SocketAddr::from((ip, 40000))));
+    request
+}
+
+fn json(method: &str, uri: &str, body: String) -> Request<Body> {
+    Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("content-type", "application/json")
+        .body(Body::from(body))
+        .unwrap()
+}
+
+async fn response_json(response: axum::response::Response) -> serde_json::Value {
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+/// バケットが空になったクライアント（接続元のIPアドレス）には429と `Retry-After` を返し、
+/// 別のクライアントや別のグループには影響しないこと
+#[tokio::test]
+async fn test_rate_limit_is_per_client_and_route_group() {
+    let mut config = Config::default();
+    config.limits.todos.requests_per_minute = Some(60);
+    config.limits.todos.burst = Some(2);
+    let app = app_with(config).await;
+    let alice = [10, 0, 0, 1];
+
+    for _ in 0..2 {
+        let response = send(&app, get("/api/v1/todos", alice, None)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+    let response = send(&app, get("/api/v1/todos", alice, None)).await;
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(response.headers()["retry-after"], "1");
+    assert_eq!(
+        response.headers()["content-type"],
+        "application/problem+json"
+    );
+    let problem = response_json(response).await;
+    assert_eq!(problem["type"], "/problems/too-many-requests");
+    assert_eq!(problem["code"], "rate_limited");
+
+    // 旧パスも同じ枠で数える
+    let response = send(&app, get("/todos", alice, None)).await;
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    let response = send(&app, get("/api/v1/todos", [10, 0, 0, 2], None)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = send(&app, get("/api/v1/webhooks", alice, None)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+/// `Authorization` があればその値ごとに数え、`user` などのクエリではバケットを分けないこと
+#[tokio::test]
+async fn test_tokens_split_the_bucket_but_queries_do_not() {
+    let mut config = Config::default();
+    config.limits.todos.burst = Some(2);
+    let app = app_with(config).await;
+    let ip = [10, 0, 0, 3];
+
+    send(&app, get("/api/v1/todos?user=a", ip, None)).await;
+    send(&app, get("/api/v1/todos?user=b", ip, None)).await;
+    let response = send(&app, get("/api/v1/todos?user=c", ip, None)).await;
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    // 同じ接続元でも、トークンごとに別の枠になる
+    for _ in 0..2 {
+        let response = send(&app, get("/api/v1/todos", ip, Some("token-1"))).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+    let response = send(&app, get("/api/v1/todos", ip, Some("token-1"))).await;
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    let response = send(&app, get("/api/v1/todos", ip, Some("token-2"))).await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+/// 信頼するプロキシから来たリクエストだけ、`X-Forwarded-For` / `Forwarded` の元のクライアントで数えること
+#[tokio::test]
+async fn test_forwarded_client_is_used_only_behind_trusted_proxies() {
+    let mut config = Config::default();
+    config.limits.todos.burst = Some(1);
+    config.limits.trusted_proxies = vec!["10.0.0.10".parse().unwrap()];
+    let app = app_with(config).await;
+    let proxy = [10, 0, 0, 10];
+    let forwarded = |ip: [u8; 4], name: &str, value: &str| {
+        let mut request = get("/api/v1/todos", ip, None);
+        request.headers_mut().insert(
+            name.parse::<axum::http::HeaderName>().unwrap(),
+            value.parse().unwrap(),
+        );
+        request
+    };
+
+    let response = send(&app, forwarded(proxy, "x-forwarded-for", "192.0.2.1")).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    // プロキシの後ろの別のクライアントは別の枠
+    let response = send(
+        &app,
+        forwarded(proxy, "forwarded", "for=\"[2001:db8::1]:4711\";proto=https"),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    // クライアントが先頭に足した値ではなく、信頼するプロキシが書いた値を使う
+    let response = send(
+        &app,
+        forwarded(proxy, "x-forwarded-for", "198.51.100.7, 192.0.2.1"),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    // 信頼していない接続元のヘッダは読まない
+    let other = [10, 0, 0, 11];
+    let response = send(&app, forwarded(other, "x-forwarded-for", "192.0.2.2")).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = send(&app, forwarded(other, "x-forwarded-for", "192.0.2.3")).await;
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+}
+
+/// レート制限を切ると何度でも受け付けること
+#[tokio::test]
+async fn test_rate_limit_can_be_disabled() {
+    let mut config = Config::default();
+    config.limits.rate_limit_enabled = false;
+    config.limits.burst = 1;
+    let app = app_with(config).await;
+
+    for _ in 0..3 {
+        let response = send(&app, get("/api/v1/todos", [10, 0, 0, 1], None)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
+
+/// グループの上限を超えるボディは413になること
+#[tokio::test]
+async fn test_body_over_the_group_limit_is_rejected() {
+    let mut config = Config::default();
+    config.limits.todos.max_body_bytes = Some(64);
+    let app = app_with(config).await;
+
+    let title = "a".repeat(100);
+    let response = send(
+        &app,
+        json(
+            "POST",
+            "/api/v1/todos",
+            format!(r#"{{"title":"{}"}}"#, title),
+        ),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    let problem = response_json(response).await;
+    assert_eq!(problem["type"], "/problems/payload-too-large");
+    assert_eq!(problem["code"], "body_too_large");
+
+    // ほかのグループは既定の上限のまま
+    let response = send(
+        &app,
+        json("POST", "/api/v1/todos", r#"{"title":"short"}"#.to_string()),
+    )
+    .await;
+    assert!(response.status().is_success());
+    let todo = response_json(response).await;
+    let response = send(
+        &app,
+        json(
+            "POST",
+            &format!("/api/v1/todos/{}/comments", todo["id"]),
+            format!(r#"{{"author":"alice","body":"{}"}}"#, title),
+        ),
+    )
+    .await;
+    assert!(response.status().is_success());
+}
+
+/// 並び替えに上限を超える数のIDを送ると400になること
+#[tokio::test]
+async fn test_reorder_rejects_too_many_ids() {
+    let mut config = Config::default();
+    config.limits.max_list_len = 2;
+    let app = app_with(config).await;
+
+    let response = send(
+        &app,
+        json(
+            "PUT",
+            "/api/v1/todos/reorder",
+            r#"{"ids":[3,1,2]}"#.to_string(),
+        ),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let problem = response_json(response).await;
+    assert_eq!(problem["errors"][0]["field"], "ids");
+    assert_eq!(problem["errors"][0]["code"], "too_many_items");
+    assert_eq!(
+        problem["errors"][0]["message"],
+        "idsは2件以下にしてください"
+    );
+
+    let response = send(
+        &app,
+        json("PUT", "/api/v1/todos/reorder", r#"{"ids":[]}"#.to_string()),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+}
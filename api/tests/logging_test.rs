@@ -0,0 +1,95 @@
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use rust_todo_app::create_test_app;
+use tower::util::ServiceExt;
+
+/// ログの出力先。書かれた内容をためておく
+#[derive(Clone, Default)]
+struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+impl Write for CapturedLogs {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl CapturedLogs {
+    /// 1行1つのJSONとして読む
+    fn lines(&self) -> Vec<serde_json::Value> {
+        let logs = self.0.lock().unwrap();
+        String::from_utf8_lossy(&logs)
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+}
+
+/// JSONで出したログに、処理中のリクエストのIDが `http_request` spanのフィールドとして入ること
+#[tokio::test]
+async fn test_json_logs_carry_the_request_id() {
+    let logs = CapturedLogs::default();
+    let subscriber = tracing_subscriber::fmt()
+        .json()
+        .with_writer({
+            let logs = logs.clone();
+            move || logs.clone()
+        })
+        .finish();
+    let _guard = tracing::subscriber::set_default(subscriber);
+    let app = create_test_app().await;
+
+    let request = Request::builder()
+        .method("PUT")
+        .uri("/api/v1/todos/99999")
+        .header("content-type", "application/json")
+        .header("x-request-id", "client-req-7")
+        .body(Body::from(r#"{"completed": true}"#))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(response.headers()["x-request-id"], "client-req-7");
+
+    let request = Request::builder()
+        .uri("/api/v1/todos")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    let generated = response.headers()["x-request-id"]
+        .to_str()
+        .unwrap()
+        .to_string();
+
+    let lines = logs.lines();
+    let request_ids = |message: &str| -> Vec<String> {
+        lines
+            .iter()
+            .filter(|line| line["fields"]["message"] == message)
+            .map(|line| {
+                assert_eq!(line["span"]["name"], "http_request", "{}", line);
+                line["span"]["request_id"].as_str().unwrap().to_string()
+            })
+            .collect()
+    };
+    assert_eq!(
+        request_ids("request completed"),
+        vec!["client-req-7".to_string(), generated]
+    );
+    // ハンドラが出したログにも同じIDが付く
+    assert_eq!(request_ids("updating todo"), vec!["client-req-7"]);
+    // ハンドラのログは値を文言に埋め込まず、項目として出す
+    let updating = lines
+        .iter()
+        .find(|line| line["fields"]["message"] == "updating todo")
+        .unwrap();
+    assert_eq!(updating["fields"]["todo_id"], 99999);
+}
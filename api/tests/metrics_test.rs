@@ -0,0 +1,165 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use rust_todo_app::config::Config;
+use rust_todo_app::create_app;
+use rust_todo_app::infrastructure::persistence::schema::create_tables;
+use sqlx::sqlite::{SqlitePool, SqlitePoolOptions};
+use tower::util::ServiceExt;
+
+async fn app_with_pool() -> (Router, SqlitePool) {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    create_tables(&pool).await.unwrap();
+    (create_app(pool.clone(), &Config::default()), pool)
+}
+
+async fn send(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    body: Option<serde_json::Value>,
+) -> StatusCode {
+    let mut request = Request::builder().method(method).uri(uri);
+    let body = match body {
+        Some(json) => {
+            request = request.header("content-type", "application/json");
+            Body::from(json.to_string())
+        }
+        None => Body::empty(),
+    };
+    app.clone()
+        .oneshot(request.body(body).unwrap())
+        .await
+        .unwrap()
+        .status()
+}
+
+async fn scrape(app: &Router) -> String {
+    let request = Request::builder()
+        .uri("/metrics")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers()["content-type"]
+        .to_str()
+        .unwrap()
+        .starts_with("text/plain"));
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+/// 指定した系列の行があり、値が一致すること
+fn assert_sample(metrics: &str, series: &str, value: &str) {
+    let line = metrics
+        .lines()
+        .find(|line| line.starts_with(series) && line[series.len()..].starts_with(' '))
+        .unwrap_or_else(|| panic!("{} not found in\n{}", series, metrics));
+    assert_eq!(&line[series.len() + 1..], value, "{}", line);
+}
+
+/// HTTPはパスのテンプレートごと、リポジトリはメソッドごとに記録し、TODOの件数も出すこと
+#[tokio::test]
+async fn test_metrics_cover_http_repository_pool_and_todos() {
+    let (app, _pool) = app_with_pool().await;
+
+    let created = send(
+        &app,
+        "POST",
+        "/api/v1/todos",
+        Some(serde_json::json!({ "title": "計測" })),
+    )
+    .await;
+    assert_eq!(created, StatusCode::CREATED);
+    send(
+        &app,
+        "PUT",
+        "/api/v1/todos/1",
+        Some(serde_json::json!({ "completed": true })),
+    )
+    .await;
+    send(&app, "GET", "/api/v1/todos/1", None).await;
+    send(&app, "GET", "/api/v1/todos/99", None).await;
+    send(&app, "GET", "/no/such/path", None).await;
+
+    let metrics = scrape(&app).await;
+
+    assert_sample(
+        &metrics,
+        r#"http_requests_total{method="POST",route="/api/v1/todos",status="201"}"#,
+        "1",
+    );
+    assert_sample(
+        &metrics,
+        r#"http_requests_total{method="GET",route="/api/v1/todos/:id",status="200"}"#,
+        "1",
+    );
+    assert_sample(
+        &metrics,
+        r#"http_requests_total{method="GET",route="/api/v1/todos/:id",status="404"}"#,
+        "1",
+    );
+    assert_sample(
+        &metrics,
+        r#"http_requests_total{method="GET",route="unmatched",status="404"}"#,
+        "1",
+    );
+    assert_sample(
+        &metrics,
+        r#"http_request_duration_seconds_count{method="POST",route="/api/v1/todos",status="201"}"#,
+        "1",
+    );
+    assert_sample(
+        &metrics,
+        r#"todo_repository_duration_seconds_count{method="create"}"#,
+        "1",
+    );
+    assert_sample(
+        &metrics,
+        r#"todo_repository_errors_total{method="get_all"}"#,
+        "0",
+    );
+    assert_sample(
+        &metrics,
+        r#"todo_repository_errors_total{method="query"}"#,
+        "0",
+    );
+    assert_sample(&metrics, "sqlite_pool_max_connections", "1");
+    assert_sample(&metrics, "todos", "1");
+    assert_sample(&metrics, "todos_completed", "1");
+    // スクレイプ自体は数えない
+    assert!(!metrics.contains(r#"route="/metrics""#));
+}
+
+/// リポジトリがエラーを返したらメソッドごとに数えること
+#[tokio::test]
+async fn test_repository_errors_are_counted() {
+    let (app, pool) = app_with_pool().await;
+    sqlx::query("DROP TABLE todos")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let status = send(&app, "GET", "/api/v1/todos", None).await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+
+    let metrics = scrape(&app).await;
+    assert_sample(
+        &metrics,
+        r#"todo_repository_errors_total{method="get_all"}"#,
+        "1",
+    );
+    assert_sample(
+        &metrics,
+        r#"http_requests_total{method="GET",route="/api/v1/todos",status="500"}"#,
+        "1",
+    );
+}
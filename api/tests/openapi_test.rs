@@ -0,0 +1,169 @@
+use std::collections::BTreeMap;
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use rust_todo_app::{create_test_app, API_V1_PREFIX, V1_ROUTES};
+use tower::util::ServiceExt;
+
+/// レスポンスボディをJSONとして取得するヘルパー
+async fn response_json(response: axum::response::Response) -> serde_json::Value {
+    let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&body_bytes).unwrap()
+}
+
+async fn get(app: &Router, uri: &str) -> axum::response::Response {
+    let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+    app.clone().oneshot(request).await.unwrap()
+}
+
+async fn openapi_document() -> serde_json::Value {
+    let app = create_test_app().await;
+    let response = get(&app, "/openapi.json").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    response_json(response).await
+}
+
+/// `/todos/:id` → `/todos/{id}`
+fn to_openapi_path(path: &str) -> String {
+    path.split('/')
+        .map(|segment| match segment.strip_prefix(':') {
+            Some(name) => format!("{{{}}}", name),
+            None => segment.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// ルーターが実際にそのパスで受け付けるメソッド（`OPTIONS` への `Allow`）。
+/// パスのパラメータは適当な値で埋める
+async fn allowed_methods(app: &Router, openapi_path: &str) -> Vec<String> {
+    let uri = openapi_path
+        .split('/')
+        .map(|segment| {
+            if segment.starts_with('{') {
+                "1"
+            } else {
+                segment
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+    let request = Request::builder()
+        .method("OPTIONS")
+        .uri(&uri)
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let Some(allow) = response.headers().get("allow") else {
+        return Vec::new();
+    };
+    allow
+        .to_str()
+        .unwrap()
+        .split(',')
+        .map(|method| method.trim().to_lowercase())
+        .filter(|method| method != "head" && method != "options")
+        .collect()
+}
+
+/// ルートの表どおりにルーターが組まれ、表のルートがすべてOpenAPIに記載されていること
+#[tokio::test]
+async fn test_every_route_is_documented() {
+    let app = create_test_app().await;
+    let document = openapi_document().await;
+
+    let mut routes: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (_, method, path) in &V1_ROUTES {
+        let path = format!("{}{}", API_V1_PREFIX, to_openapi_path(path));
+        routes
+            .entry(path)
+            .or_default()
+            .push(method.as_str().to_lowercase());
+    }
+    for (path, methods) in &routes {
+        let mut allowed = allowed_methods(&app, path).await;
+        let mut expected = methods.clone();
+        allowed.sort();
+        expected.sort();
+        assert_eq!(allowed, expected, "router and V1_ROUTES differ on {}", path);
+
+        for method in methods {
+            assert!(
+                !document["paths"][path.as_str()][method.as_str()].is_null(),
+                "undocumented route: {} {}",
+                method,
+                path
+            );
+        }
+    }
+}
+
+/// OpenAPIに記載したエンドポイントがすべてルーターにあること
+#[tokio::test]
+async fn test_every_documented_route_exists() {
+    let app = create_test_app().await;
+    let document = openapi_document().await;
+
+    let paths = document["paths"].as_object().unwrap();
+    assert!(paths.len() > 15, "{:?}", paths.keys());
+    for (path, item) in paths {
+        let allowed = allowed_methods(&app, path).await;
+        for method in item.as_object().unwrap().keys() {
+            assert!(
+                allowed.contains(method),
+                "documented but not routed: {} {} (allowed: {:?})",
+                method,
+                path,
+                allowed
+            );
+        }
+    }
+}
+
+/// リクエストDTOのバリデーション条件がスキーマに反映されていること
+#[tokio::test]
+async fn test_openapi_includes_validation_constraints() {
+    let document = openapi_document().await;
+    let schemas = &document["components"]["schemas"];
+
+    let title = &schemas["CreateTodoRequest"]["properties"]["title"];
+    assert_eq!(title["minLength"], 1);
+    assert_eq!(title["maxLength"], 200);
+    assert_eq!(schemas["CreateTodoRequest"]["required"][0], "title");
+
+    let update_title = &schemas["UpdateTodoRequest"]["properties"]["title"];
+    assert_eq!(update_title["maxLength"], 200);
+
+    let offset = &schemas["CreateReminderRequest"]["properties"]["offset_minutes"];
+    assert_eq!(offset["maximum"], 525600.0);
+
+    let create = &document["paths"]["/api/v1/todos"]["post"];
+    assert_eq!(
+        create["requestBody"]["content"]["application/json"]["schema"]["$ref"],
+        "#/components/schemas/CreateTodoRequest"
+    );
+    assert!(!create["responses"]["400"].is_null());
+}
+
+/// /docs でSwagger UIのページが返ること
+#[tokio::test]
+async fn test_docs_serves_swagger_ui() {
+    let app = create_test_app().await;
+
+    let response = get(&app, "/docs").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers()["content-type"]
+        .to_str()
+        .unwrap()
+        .starts_with("text/html"));
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let html = String::from_utf8(body.to_vec()).unwrap();
+    assert!(html.contains("/openapi.json"));
+}
@@ -0,0 +1,175 @@
+#![cfg(feature = "otel")]
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use axum::{body::Body, http::Request};
+use opentelemetry_proto::tonic::collector::trace::v1::trace_service_server::{
+    TraceService, TraceServiceServer,
+};
+use opentelemetry_proto::tonic::collector::trace::v1::{
+    ExportTraceServiceRequest, ExportTraceServiceResponse,
+};
+use opentelemetry_proto::tonic::common::v1::{any_value, KeyValue};
+use opentelemetry_proto::tonic::trace::v1::{span::SpanKind, ResourceSpans, Span};
+use rust_todo_app::config::OtelConfig;
+use rust_todo_app::create_test_app;
+use rust_todo_app::infrastructure::telemetry;
+use tokio_stream::wrappers::TcpListenerStream;
+use tower::util::ServiceExt;
+use tracing_subscriber::layer::SubscriberExt;
+
+const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+const PARENT_SPAN_ID: &str = "00f067aa0ba902b7";
+
+/// OTLPのコレクタの代わり。受け取ったspanをためておく
+#[derive(Clone, Default)]
+struct Collector {
+    received: Arc<Mutex<Vec<ResourceSpans>>>,
+}
+
+impl Collector {
+    fn has_span(&self, name: &str) -> bool {
+        self.received
+            .lock()
+            .unwrap()
+            .iter()
+            .flat_map(|resource_spans| &resource_spans.scope_spans)
+            .flat_map(|scope_spans| &scope_spans.spans)
+            .any(|span| span.name == name)
+    }
+}
+
+#[tonic::async_trait]
+impl TraceService for Collector {
+    async fn export(
+        &self,
+        request: tonic::Request<ExportTraceServiceRequest>,
+    ) -> Result<tonic::Response<ExportTraceServiceResponse>, tonic::Status> {
+        self.received
+            .lock()
+            .unwrap()
+            .extend(request.into_inner().resource_spans);
+        Ok(tonic::Response::new(ExportTraceServiceResponse {
+            partial_success: None,
+        }))
+    }
+}
+
+/// コレクタを起動し、送信先のURLを返す
+async fn start_collector(collector: Collector) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(
+        tonic::transport::Server::builder()
+            .add_service(TraceServiceServer::new(collector))
+            .serve_with_incoming(TcpListenerStream::new(listener)),
+    );
+    format!("http://{}", addr)
+}
+
+fn string_attribute<'a>(attributes: &'a [KeyValue], key: &str) -> Option<&'a str> {
+    attributes
+        .iter()
+        .find(|attribute| attribute.key == key)
+        .and_then(
+            |attribute| match attribute.value.as_ref()?.value.as_ref()? {
+                any_value::Value::StringValue(value) => Some(value.as_str()),
+                _ => None,
+            },
+        )
+}
+
+fn find_span(spans: &[Span], predicate: impl Fn(&Span) -> bool) -> &Span {
+    spans
+        .iter()
+        .find(|span| predicate(span))
+        .unwrap_or_else(|| {
+            let names: Vec<_> = spans.iter().map(|span| span.name.as_str()).collect();
+            panic!("span not found in {:?}", names)
+        })
+}
+
+/// `traceparent` を親にしたHTTPのspanの下に、ユースケースとSQLのspanが送られること
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_spans_are_exported_under_the_incoming_trace() {
+    let collector = Collector::default();
+    let endpoint = start_collector(collector.clone()).await;
+    let telemetry = telemetry::init(&OtelConfig {
+        enabled: true,
+        endpoint,
+        service_name: "todo-test".to_string(),
+    })
+    .unwrap();
+    let subscriber = tracing_subscriber::registry().with(telemetry.layer());
+    // spanはsqlxのSQLiteのワーカースレッドで閉じることもあるので、スレッドごとではなく全体に設定する。
+    // 設定できるのはプロセスで1回だけなので、このファイルのテストは1つにしておく
+    tracing::subscriber::set_global_default(subscriber).unwrap();
+
+    let app = create_test_app().await;
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/v1/todos")
+        .header("content-type", "application/json")
+        .header(
+            "traceparent",
+            format!("00-{}-{}-01", TRACE_ID, PARENT_SPAN_ID),
+        )
+        .body(Body::from(r#"{"title":"追跡"}"#))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert!(response.status().is_success());
+    // HTTPのspanはボディを読み終えたときに閉じる
+    axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+
+    // SQLのspanはワーカースレッドが応答を返した後に手放すので、HTTPのspanが閉じて届くまで送り直す
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+    while !collector.has_span("POST /api/v1/todos") {
+        assert!(
+            tokio::time::Instant::now() < deadline,
+            "spans were not exported"
+        );
+        // 送信の完了を同期で待つので、ランタイムのスレッドをふさがないようにする
+        tokio::task::block_in_place(|| telemetry.flush());
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    tokio::task::block_in_place(|| telemetry.shutdown());
+
+    let received = collector.received.lock().unwrap();
+    let resource = received[0].resource.as_ref().unwrap();
+    assert_eq!(
+        string_attribute(&resource.attributes, "service.name"),
+        Some("todo-test")
+    );
+    assert_eq!(
+        string_attribute(&resource.attributes, "service.version"),
+        Some(env!("CARGO_PKG_VERSION"))
+    );
+    let spans: Vec<Span> = received
+        .iter()
+        .flat_map(|resource_spans| &resource_spans.scope_spans)
+        .flat_map(|scope_spans| scope_spans.spans.clone())
+        .collect();
+
+    let http = find_span(&spans, |span| span.name == "POST /api/v1/todos");
+    assert_eq!(http.kind, SpanKind::Server as i32);
+    assert_eq!(hex::encode(&http.trace_id), TRACE_ID);
+    assert_eq!(hex::encode(&http.parent_span_id), PARENT_SPAN_ID);
+
+    let usecase = find_span(&spans, |span| span.name == "usecase.todo.create");
+    assert_eq!(usecase.trace_id, http.trace_id);
+    assert_eq!(usecase.parent_span_id, http.span_id);
+
+    let insert = find_span(&spans, |span| {
+        string_attribute(&span.attributes, "db.statement")
+            .is_some_and(|sql| sql.starts_with("INSERT INTO todos"))
+    });
+    assert_eq!(insert.kind, SpanKind::Client as i32);
+    assert_eq!(insert.parent_span_id, usecase.span_id);
+    assert_eq!(
+        string_attribute(&insert.attributes, "db.system"),
+        Some("sqlite")
+    );
+}
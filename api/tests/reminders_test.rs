@@ -0,0 +1,236 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration as StdDuration;
+
+use async_trait::async_trait;
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::post,
+    Json, Router,
+};
+use chrono::{Duration, TimeZone, Utc};
+use rust_todo_app::application::errors::AppError;
+use rust_todo_app::application::ports::notifier::{Notifier, ReminderNotification};
+use rust_todo_app::application::ports::reminder_repository::ReminderRepository;
+use rust_todo_app::application::ports::todo_repository::TodoRepository;
+use rust_todo_app::create_test_app;
+use rust_todo_app::domain::entities::reminder::ReminderSchedule;
+use rust_todo_app::infrastructure::clock::ManualClock;
+use rust_todo_app::infrastructure::notifiers::webhook_notifier::WebhookNotifier;
+use rust_todo_app::infrastructure::persistence::schema::create_tables;
+use rust_todo_app::infrastructure::persistence::sqlite_reminder_repo::ReminderStore;
+use rust_todo_app::infrastructure::persistence::sqlite_todo_repo::TodoStore;
+use rust_todo_app::infrastructure::workers::reminder_scheduler::ReminderScheduler;
+use sqlx::sqlite::SqlitePoolOptions;
+use tower::util::ServiceExt;
+
+/// レスポンスボディをJSONとして取得するヘルパー
+async fn response_json(response: axum::response::Response) -> serde_json::Value {
+    let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&body_bytes).unwrap()
+}
+
+async fn send_json(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    body: serde_json::Value,
+) -> axum::response::Response {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    app.clone().oneshot(request).await.unwrap()
+}
+
+/// 通知内容を記録するだけの Notifier
+#[derive(Default)]
+struct RecordingNotifier {
+    sent: Mutex<Vec<ReminderNotification>>,
+}
+
+#[async_trait]
+impl Notifier for RecordingNotifier {
+    async fn notify(&self, notification: &ReminderNotification) -> Result<(), AppError> {
+        self.sent.lock().unwrap().push(notification.clone());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_offset_reminder_follows_due_date() {
+    let app = create_test_app().await;
+    let created = response_json(
+        send_json(
+            &app,
+            "POST",
+            "/todos",
+            serde_json::json!({"title": "締切あり"}),
+        )
+        .await,
+    )
+    .await;
+    let id = created["id"].as_i64().unwrap();
+
+    let response = send_json(
+        &app,
+        "PUT",
+        &format!("/todos/{}", id),
+        serde_json::json!({"due_at": "2030-01-01T09:00:00Z"}),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response_json(response).await["due_at"],
+        "2030-01-01T09:00:00Z"
+    );
+
+    let response = send_json(
+        &app,
+        "POST",
+        &format!("/todos/{}/reminders", id),
+        serde_json::json!({"offset_minutes": 30}),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let reminder = response_json(response).await;
+    assert_eq!(reminder["offset_minutes"], 30);
+    assert_eq!(reminder["fire_at"], "2030-01-01T08:30:00Z");
+    assert!(reminder["delivered_at"].is_null());
+
+    // 期限を動かすと通知時刻も追従する
+    send_json(
+        &app,
+        "PUT",
+        &format!("/todos/{}", id),
+        serde_json::json!({"due_at": "2030-01-02T09:00:00Z"}),
+    )
+    .await;
+    let request = Request::builder()
+        .uri(format!("/todos/{}/reminders", id))
+        .body(Body::empty())
+        .unwrap();
+    let reminders = response_json(app.clone().oneshot(request).await.unwrap()).await;
+    assert_eq!(reminders[0]["fire_at"], "2030-01-02T08:30:00Z");
+
+    // 期限を外すと未スケジュールになる
+    send_json(
+        &app,
+        "PUT",
+        &format!("/todos/{}", id),
+        serde_json::json!({"due_at": null}),
+    )
+    .await;
+    let request = Request::builder()
+        .uri(format!("/todos/{}/reminders", id))
+        .body(Body::empty())
+        .unwrap();
+    let reminders = response_json(app.oneshot(request).await.unwrap()).await;
+    assert!(reminders[0]["fire_at"].is_null());
+}
+
+#[tokio::test]
+async fn test_reminder_requires_exactly_one_schedule() {
+    let app = create_test_app().await;
+    let created =
+        response_json(send_json(&app, "POST", "/todos", serde_json::json!({"title": "t"})).await)
+            .await;
+    let uri = format!("/todos/{}/reminders", created["id"]);
+
+    let both = send_json(
+        &app,
+        "POST",
+        &uri,
+        serde_json::json!({"remind_at": "2030-01-01T09:00:00Z", "offset_minutes": 5}),
+    )
+    .await;
+    let neither = send_json(&app, "POST", &uri, serde_json::json!({})).await;
+
+    assert_eq!(both.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(neither.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_scheduler_delivers_due_reminders_once() {
+    let pool = SqlitePoolOptions::new()
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    create_tables(&pool).await.unwrap();
+    let todos = TodoStore::new(pool.clone());
+    let reminders = Arc::new(ReminderStore::new(pool));
+
+    let todo = todos.create("会議".to_string()).await.unwrap();
+    let start = Utc.with_ymd_and_hms(2030, 1, 1, 9, 0, 0).unwrap();
+    let at = start + Duration::minutes(10);
+    reminders
+        .create(todo.id as u32, ReminderSchedule::At(at), Some(at))
+        .await
+        .unwrap();
+
+    let clock = Arc::new(ManualClock::new(start));
+    let notifier = Arc::new(RecordingNotifier::default());
+    let scheduler = ReminderScheduler {
+        reminders: reminders.clone(),
+        notifier: notifier.clone(),
+        clock: clock.clone(),
+        poll_interval: StdDuration::from_secs(60),
+    };
+
+    scheduler.tick().await;
+    assert!(notifier.sent.lock().unwrap().is_empty());
+
+    clock.advance(Duration::minutes(10));
+    scheduler.tick().await;
+    scheduler.tick().await;
+
+    let sent = notifier.sent.lock().unwrap().clone();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].todo_title, "会議");
+    assert_eq!(sent[0].fire_at, at);
+
+    let stored = reminders.list_by_todo(todo.id as u32).await.unwrap();
+    assert_eq!(stored[0].delivered_at, Some(at));
+    assert_eq!(stored[0].attempts, 1);
+}
+
+#[tokio::test]
+async fn test_webhook_notifier_posts_json() {
+    let received: Arc<Mutex<Vec<serde_json::Value>>> = Arc::default();
+    let sink = received.clone();
+    let receiver = Router::new().route(
+        "/hook",
+        post(move |Json(body): Json<serde_json::Value>| {
+            let sink = sink.clone();
+            async move {
+                sink.lock().unwrap().push(body);
+                StatusCode::NO_CONTENT
+            }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, receiver).await.unwrap() });
+
+    let notifier = WebhookNotifier::new(format!("http://{}/hook", addr));
+    let notification = ReminderNotification {
+        reminder_id: 1,
+        todo_id: 2,
+        todo_title: "レビュー".to_string(),
+        due_at: None,
+        fire_at: Utc.with_ymd_and_hms(2030, 1, 1, 9, 0, 0).unwrap(),
+    };
+    notifier.notify(&notification).await.unwrap();
+
+    let received = received.lock().unwrap().clone();
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0]["todo_title"], "レビュー");
+    assert_eq!(received[0]["fire_at"], "2030-01-01T09:00:00Z");
+
+    let failing = WebhookNotifier::new(format!("http://{}/missing", addr));
+    assert!(failing.notify(&notification).await.is_err());
+}
@@ -0,0 +1,151 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{routing::get, Router};
+use rust_todo_app::config::Config;
+use rust_todo_app::infrastructure::events::broadcaster::EventBroadcaster;
+use rust_todo_app::infrastructure::persistence::schema::create_tables;
+use rust_todo_app::infrastructure::workers::monitor::{WorkerMonitor, WorkerState};
+use rust_todo_app::{create_app_with_grpc, serve_http, spawn_webhook_dispatcher};
+use sqlx::sqlite::{SqlitePool, SqlitePoolOptions};
+use tokio::net::TcpListener;
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+use tokio::time::timeout;
+use tokio_util::sync::CancellationToken;
+
+struct Running {
+    url: String,
+    shutdown: CancellationToken,
+    event_stream: Arc<EventBroadcaster>,
+    server: JoinHandle<std::io::Result<()>>,
+    /// `/slow` の処理が始まったら通知される
+    slow_started: Arc<Notify>,
+}
+
+async fn memory_pool() -> SqlitePool {
+    let pool = SqlitePoolOptions::new()
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    create_tables(&pool).await.unwrap();
+    pool
+}
+
+/// `slow` だけかかる `/slow` を足したアプリを実際のポートで起動する
+async fn start(slow: Duration, drain_timeout: Duration) -> Running {
+    let services = create_app_with_grpc(memory_pool().await, &Config::default());
+    let slow_started = Arc::new(Notify::new());
+    let started = slow_started.clone();
+    let app: Router = services.http.route(
+        "/slow",
+        get(move || async move {
+            started.notify_one();
+            tokio::time::sleep(slow).await;
+            "done"
+        }),
+    );
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let shutdown = CancellationToken::new();
+    let server = tokio::spawn(serve_http(listener, app, shutdown.clone(), drain_timeout));
+
+    Running {
+        url,
+        shutdown,
+        event_stream: services.event_stream,
+        server,
+        slow_started,
+    }
+}
+
+/// 終了を始めても、処理中のリクエストは最後まで応答されること
+#[tokio::test]
+async fn test_in_flight_requests_complete_during_shutdown() {
+    let running = start(Duration::from_millis(300), Duration::from_secs(5)).await;
+
+    let request = tokio::spawn(reqwest::get(format!("{}/slow", running.url)));
+    running.slow_started.notified().await;
+    running.shutdown.cancel();
+
+    let response = request.await.unwrap().unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    assert_eq!(response.text().await.unwrap(), "done");
+
+    timeout(Duration::from_secs(2), running.server)
+        .await
+        .expect("server did not stop after draining")
+        .unwrap()
+        .unwrap();
+    // 止まったあとは新しい接続を受け付けない
+    assert!(reqwest::get(format!("{}/api/v1/todos", running.url))
+        .await
+        .is_err());
+}
+
+/// 待ち時間を過ぎても終わらないリクエストがあれば、待たずに戻ること
+#[tokio::test]
+async fn test_drain_timeout_closes_remaining_requests() {
+    let running = start(Duration::from_secs(60), Duration::from_millis(200)).await;
+
+    let request = tokio::spawn(reqwest::get(format!("{}/slow", running.url)));
+    running.slow_started.notified().await;
+    running.shutdown.cancel();
+
+    timeout(Duration::from_secs(2), running.server)
+        .await
+        .expect("server did not stop after the drain timeout")
+        .unwrap()
+        .unwrap();
+    assert!(!request.is_finished());
+    request.abort();
+}
+
+/// 配信を閉じるとSSEの接続が終わり、待ち時間いっぱい待たずに止まること
+#[tokio::test]
+async fn test_event_streams_end_on_shutdown() {
+    let running = start(Duration::ZERO, Duration::from_secs(30)).await;
+
+    let response = reqwest::get(format!("{}/api/v1/events", running.url))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+
+    running.event_stream.close();
+    running.shutdown.cancel();
+
+    timeout(Duration::from_secs(2), response.text())
+        .await
+        .expect("event stream did not end")
+        .unwrap();
+    timeout(Duration::from_secs(2), running.server)
+        .await
+        .expect("server waited for the event stream")
+        .unwrap()
+        .unwrap();
+}
+
+/// バックグラウンドタスクは終了の合図で止まること
+#[tokio::test]
+async fn test_background_tasks_stop_on_shutdown() {
+    let pool = memory_pool().await;
+    let shutdown = CancellationToken::new();
+    let workers = Arc::new(WorkerMonitor::new());
+    let dispatcher = spawn_webhook_dispatcher(
+        pool.clone(),
+        Duration::from_secs(3600),
+        &workers,
+        shutdown.clone(),
+    );
+
+    shutdown.cancel();
+
+    timeout(Duration::from_secs(2), dispatcher)
+        .await
+        .expect("webhook dispatcher did not stop")
+        .unwrap();
+    assert_eq!(workers.statuses()[0].state, WorkerState::Stopped);
+    pool.close().await;
+    assert!(pool.is_closed());
+}
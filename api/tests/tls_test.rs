@@ -0,0 +1,147 @@
+use std::net::SocketAddr;
+use std::path::Path;
+use std::time::Duration;
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use rust_todo_app::config::TlsConfig;
+use rust_todo_app::infrastructure::tls::{spawn_certificate_reloader, ReloadingCertificate};
+use rust_todo_app::presentation::https::{https_redirect_app, with_hsts};
+use rust_todo_app::{create_test_app, serve_https};
+use tokio::io::AsyncReadExt;
+use tokio_util::sync::CancellationToken;
+use tower::util::ServiceExt;
+
+/// `localhost` 向けの自己署名証明書を書き出し、クライアントが信頼する証明書として返す
+fn write_self_signed(dir: &Path) -> reqwest::Certificate {
+    let cert = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+    std::fs::write(dir.join("cert.pem"), cert.cert.pem()).unwrap();
+    std::fs::write(dir.join("key.pem"), cert.key_pair.serialize_pem()).unwrap();
+    reqwest::Certificate::from_pem(cert.cert.pem().as_bytes()).unwrap()
+}
+
+/// `trusted` だけを信頼するクライアント
+fn client(trusted: reqwest::Certificate, addr: SocketAddr) -> reqwest::Client {
+    reqwest::Client::builder()
+        .tls_built_in_root_certs(false)
+        .add_root_certificate(trusted)
+        .resolve("localhost", addr)
+        .build()
+        .unwrap()
+}
+
+async fn start_https(dir: &Path, tls: &TlsConfig, shutdown: &CancellationToken) -> SocketAddr {
+    let certificate =
+        ReloadingCertificate::load(&dir.join("cert.pem"), &dir.join("key.pem")).unwrap();
+    let acceptor = certificate.acceptor().unwrap();
+    spawn_certificate_reloader(certificate, Duration::from_millis(20), shutdown.clone());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let app = with_hsts(create_test_app().await, tls);
+    tokio::spawn(serve_https(
+        listener,
+        app,
+        acceptor,
+        shutdown.clone(),
+        Duration::from_secs(1),
+        Duration::from_millis(200),
+    ));
+    addr
+}
+
+/// HTTPSでAPIに応答し、`Strict-Transport-Security` を付けること
+#[tokio::test]
+async fn test_https_serves_the_api_with_hsts() {
+    let dir = tempfile::tempdir().unwrap();
+    let trusted = write_self_signed(dir.path());
+    let shutdown = CancellationToken::new();
+    let tls = TlsConfig {
+        hsts_include_subdomains: true,
+        ..TlsConfig::default()
+    };
+    let addr = start_https(dir.path(), &tls, &shutdown).await;
+
+    let url = format!("https://localhost:{}/api/v1/todos", addr.port());
+    let response = client(trusted, addr).get(&url).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()["strict-transport-security"],
+        "max-age=31536000; includeSubDomains"
+    );
+    assert_eq!(response.text().await.unwrap(), "[]");
+
+    shutdown.cancel();
+}
+
+/// 証明書のファイルを差し替えると、次の接続から新しい証明書を使うこと
+#[tokio::test]
+async fn test_certificate_is_reloaded_when_the_files_change() {
+    let dir = tempfile::tempdir().unwrap();
+    let old = write_self_signed(dir.path());
+    let shutdown = CancellationToken::new();
+    let addr = start_https(dir.path(), &TlsConfig::default(), &shutdown).await;
+    let url = format!("https://localhost:{}/healthz", addr.port());
+
+    let response = client(old.clone(), addr).get(&url).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let new = write_self_signed(dir.path());
+    let mut reloaded = false;
+    for _ in 0..100 {
+        if client(new.clone(), addr).get(&url).send().await.is_ok() {
+            reloaded = true;
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert!(reloaded, "the new certificate was not picked up");
+    assert!(client(old, addr).get(&url).send().await.is_err());
+
+    shutdown.cancel();
+}
+
+/// ハンドシェイクを始めない接続は、待ち時間が過ぎたら切ること
+#[tokio::test]
+async fn test_stalled_handshakes_are_closed() {
+    let dir = tempfile::tempdir().unwrap();
+    write_self_signed(dir.path());
+    let shutdown = CancellationToken::new();
+    let addr = start_https(dir.path(), &TlsConfig::default(), &shutdown).await;
+
+    let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+    let mut buf = [0u8; 16];
+    let read = tokio::time::timeout(Duration::from_secs(5), stream.read(&mut buf))
+        .await
+        .expect("the server kept the stalled connection open");
+    assert_eq!(read.unwrap_or(0), 0);
+
+    shutdown.cancel();
+}
+
+/// HTTPのリクエストを、パスとクエリを保ったまま同じホストのHTTPSへ308で案内すること
+#[tokio::test]
+async fn test_http_is_redirected_to_https() {
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/v1/todos?user=alice")
+        .header("host", "todo.example:8080")
+        .body(Body::empty())
+        .unwrap();
+    let response = https_redirect_app(8443).oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+    assert_eq!(
+        response.headers()["location"],
+        "https://todo.example:8443/api/v1/todos?user=alice"
+    );
+
+    // 443なら既定のポートなので付けない
+    let request = Request::builder()
+        .uri("/")
+        .header("host", "todo.example")
+        .body(Body::empty())
+        .unwrap();
+    let response = https_redirect_app(443).oneshot(request).await.unwrap();
+    assert_eq!(response.headers()["location"], "https://todo.example/");
+}
@@ -0,0 +1,105 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use rust_todo_app::create_test_app;
+use tower::util::ServiceExt;
+
+/// レスポンスボディをJSONとして取得するヘルパー
+async fn response_json(response: axum::response::Response) -> serde_json::Value {
+    let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&body_bytes).unwrap()
+}
+
+async fn send(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    body: Option<serde_json::Value>,
+) -> axum::response::Response {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("content-type", "application/json")
+        .body(body.map_or_else(Body::empty, |b| Body::from(b.to_string())))
+        .unwrap();
+    app.clone().oneshot(request).await.unwrap()
+}
+
+/// /api/v1 配下で操作でき、廃止予定のヘッダは付かないこと
+#[tokio::test]
+async fn test_v1_routes_are_not_deprecated() {
+    let app = create_test_app().await;
+
+    let response = send(
+        &app,
+        "POST",
+        "/api/v1/todos",
+        Some(serde_json::json!({ "title": "v1で作成" })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    assert!(response.headers().get("deprecation").is_none());
+    assert!(response.headers().get("sunset").is_none());
+    let created = response_json(response).await;
+
+    let response = send(
+        &app,
+        "GET",
+        &format!("/api/v1/todos/{}", created["id"]),
+        None,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response_json(response).await["title"], "v1で作成");
+}
+
+/// プレフィックスなしのパスは同じデータを返しつつ、廃止予定と移行先を知らせること
+#[tokio::test]
+async fn test_bare_paths_are_deprecated_aliases() {
+    let app = create_test_app().await;
+
+    send(
+        &app,
+        "POST",
+        "/api/v1/todos",
+        Some(serde_json::json!({ "title": "共有" })),
+    )
+    .await;
+
+    let response = send(&app, "GET", "/todos", None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let headers = response.headers();
+    assert!(headers["deprecation"].to_str().unwrap().starts_with('@'));
+    assert!(headers["sunset"].to_str().unwrap().ends_with("GMT"));
+    assert_eq!(
+        headers["link"],
+        "</api/v1/todos>; rel=\"successor-version\""
+    );
+    let todos = response_json(response).await;
+    assert_eq!(todos[0]["title"], "共有");
+
+    // エラーの応答にも付く
+    let response = send(&app, "GET", "/todos/99999", None).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert!(response.headers().get("deprecation").is_some());
+}
+
+/// バージョンによらないエンドポイントはプレフィックスなしのまま
+#[tokio::test]
+async fn test_unversioned_endpoints_stay_at_root() {
+    let app = create_test_app().await;
+
+    let response = send(&app, "GET", "/openapi.json", None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().get("deprecation").is_none());
+    let document = response_json(response).await;
+    assert!(document["paths"]["/api/v1/todos"].is_object());
+    assert!(document["paths"]["/todos"].is_null());
+
+    let response = send(&app, "GET", "/api/v1/openapi.json", None).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,379 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration as StdDuration;
+
+use axum::{
+    body::{Body, Bytes},
+    http::{HeaderMap, Request, StatusCode},
+    routing::post,
+    Router,
+};
+use chrono::{Duration, Utc};
+use rust_todo_app::application::ports::todo_repository::TodoRepository;
+use rust_todo_app::config::Config;
+use rust_todo_app::infrastructure::clock::ManualClock;
+use rust_todo_app::infrastructure::persistence::schema::create_tables;
+use rust_todo_app::infrastructure::persistence::sqlite_todo_repo::TodoStore;
+use rust_todo_app::infrastructure::persistence::sqlite_webhook_outbox::WebhookOutboxStore;
+use rust_todo_app::infrastructure::webhooks::http_webhook_sender::{
+    sign_payload, HttpWebhookSender, EVENT_HEADER, SIGNATURE_HEADER,
+};
+use rust_todo_app::infrastructure::workers::webhook_dispatcher::WebhookDispatcher;
+use rust_todo_app::{create_app, create_test_app};
+use sqlx::sqlite::{SqlitePool, SqlitePoolOptions};
+use tower::util::ServiceExt;
+
+/// レスポンスボディをJSONとして取得するヘルパー
+async fn response_json(response: axum::response::Response) -> serde_json::Value {
+    let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&body_bytes).unwrap()
+}
+
+async fn send(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    body: Option<serde_json::Value>,
+) -> axum::response::Response {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("content-type", "application/json")
+        .body(body.map_or_else(Body::empty, |b| Body::from(b.to_string())))
+        .unwrap();
+    app.clone().oneshot(request).await.unwrap()
+}
+
+/// 受け取ったWebhookを記録するテスト用の受信サーバ
+#[derive(Clone, Default)]
+struct Receiver {
+    received: Arc<Mutex<Vec<(HeaderMap, String)>>>,
+    /// 最初のこの回数だけ500を返す
+    failures_left: Arc<AtomicUsize>,
+}
+
+impl Receiver {
+    async fn start(&self) -> String {
+        let receiver = self.clone();
+        let router = Router::new().route(
+            "/hook",
+            post(move |headers: HeaderMap, body: Bytes| {
+                let receiver = receiver.clone();
+                async move {
+                    receiver
+                        .received
+                        .lock()
+                        .unwrap()
+                        .push((headers, String::from_utf8(body.to_vec()).unwrap()));
+                    let failing = receiver
+                        .failures_left
+                        .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                        .is_ok();
+                    if failing {
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    } else {
+                        StatusCode::NO_CONTENT
+                    }
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        format!("http://{}/hook", addr)
+    }
+
+    fn received(&self) -> Vec<(HeaderMap, String)> {
+        self.received.lock().unwrap().clone()
+    }
+}
+
+async fn app_with_pool() -> (Router, SqlitePool) {
+    let pool = SqlitePoolOptions::new()
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    create_tables(&pool).await.unwrap();
+    // 添付ファイルは使わないので保存先はデフォルトのままでよい
+    (create_app(pool.clone(), &Config::default()), pool)
+}
+
+fn dispatcher(pool: &SqlitePool, clock: Arc<ManualClock>) -> WebhookDispatcher {
+    WebhookDispatcher {
+        outbox: Arc::new(WebhookOutboxStore::new(pool.clone())),
+        sender: Arc::new(HttpWebhookSender::new()),
+        clock,
+        poll_interval: StdDuration::from_secs(60),
+    }
+}
+
+#[tokio::test]
+async fn test_webhook_crud_hides_secret() {
+    let app = create_test_app().await;
+
+    let response = send(
+        &app,
+        "POST",
+        "/webhooks",
+        Some(serde_json::json!({
+            "url": "https://example.com/hook",
+            "secret": "0123456789abcdef",
+            "events": ["todo.created"]
+        })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let created = response_json(response).await;
+    assert_eq!(created["events"], serde_json::json!(["todo.created"]));
+    assert!(created.get("secret").is_none());
+    let uri = format!("/webhooks/{}", created["id"]);
+
+    let response = send(&app, "PUT", &uri, Some(serde_json::json!({"events": []}))).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response_json(response).await["events"],
+        serde_json::json!([])
+    );
+
+    let listed = response_json(send(&app, "GET", "/webhooks", None).await).await;
+    assert_eq!(listed.as_array().unwrap().len(), 1);
+
+    let response = send(&app, "DELETE", &uri, None).await;
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    let response = send(&app, "GET", &uri, None).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(response_json(response).await["type"], "/problems/not-found");
+}
+
+#[tokio::test]
+async fn test_webhook_rejects_invalid_subscription() {
+    let app = create_test_app().await;
+
+    for body in [
+        serde_json::json!({"url": "not a url", "secret": "0123456789abcdef"}),
+        serde_json::json!({"url": "https://example.com", "secret": "short"}),
+        serde_json::json!({
+            "url": "https://example.com",
+            "secret": "0123456789abcdef",
+            "events": ["todo.exploded"]
+        }),
+    ] {
+        let response = send(&app, "POST", "/webhooks", Some(body)).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
+
+#[tokio::test]
+async fn test_todo_events_are_signed_and_filtered() {
+    let (app, pool) = app_with_pool().await;
+    let receiver = Receiver::default();
+    let url = receiver.start().await;
+    let secret = "0123456789abcdef";
+
+    let webhook = response_json(
+        send(
+            &app,
+            "POST",
+            "/webhooks",
+            Some(serde_json::json!({
+                "url": url,
+                "secret": secret,
+                "events": ["todo.created", "todo.deleted"]
+            })),
+        )
+        .await,
+    )
+    .await;
+
+    let todo = response_json(
+        send(
+            &app,
+            "POST",
+            "/todos",
+            Some(serde_json::json!({"title": "通知して"})),
+        )
+        .await,
+    )
+    .await;
+    let todo_uri = format!("/todos/{}", todo["id"]);
+    send(
+        &app,
+        "PUT",
+        &todo_uri,
+        Some(serde_json::json!({"completed": true})),
+    )
+    .await;
+    send(&app, "DELETE", &todo_uri, None).await;
+
+    let clock = Arc::new(ManualClock::new(Utc::now() + Duration::minutes(1)));
+    dispatcher(&pool, clock).tick().await;
+
+    let received = receiver.received();
+    assert_eq!(received.len(), 2);
+    let events: Vec<&str> = received
+        .iter()
+        .map(|(headers, _)| headers[EVENT_HEADER].to_str().unwrap())
+        .collect();
+    assert_eq!(events, vec!["todo.created", "todo.deleted"]);
+    for (headers, body) in &received {
+        assert_eq!(
+            headers[SIGNATURE_HEADER].to_str().unwrap(),
+            sign_payload(secret, body)
+        );
+    }
+    let created: serde_json::Value = serde_json::from_str(&received[0].1).unwrap();
+    assert_eq!(created["data"]["title"], "通知して");
+
+    let deliveries = response_json(
+        send(
+            &app,
+            "GET",
+            &format!("/webhooks/{}/deliveries", webhook["id"]),
+            None,
+        )
+        .await,
+    )
+    .await;
+    assert_eq!(deliveries["total"], 2);
+    assert_eq!(deliveries["deliveries"][0]["event"], "todo.deleted");
+    assert_eq!(deliveries["deliveries"][0]["status"], "delivered");
+    assert_eq!(deliveries["deliveries"][0]["response_status"], 204);
+}
+
+#[tokio::test]
+async fn test_failed_delivery_is_retried_with_backoff() {
+    let (app, pool) = app_with_pool().await;
+    let receiver = Receiver::default();
+    receiver.failures_left.store(1, Ordering::SeqCst);
+    let url = receiver.start().await;
+
+    let webhook = response_json(
+        send(
+            &app,
+            "POST",
+            "/webhooks",
+            Some(serde_json::json!({"url": url, "secret": "0123456789abcdef"})),
+        )
+        .await,
+    )
+    .await;
+    send(
+        &app,
+        "PUT",
+        "/todos/reorder",
+        Some(serde_json::json!({"ids": []})),
+    )
+    .await;
+    let deliveries_uri = format!("/webhooks/{}/deliveries", webhook["id"]);
+
+    let clock = Arc::new(ManualClock::new(Utc::now() + Duration::minutes(1)));
+    let dispatcher = dispatcher(&pool, clock.clone());
+    dispatcher.tick().await;
+
+    let deliveries = response_json(send(&app, "GET", &deliveries_uri, None).await).await;
+    let delivery = &deliveries["deliveries"][0];
+    assert_eq!(delivery["event"], "todo.reordered");
+    assert_eq!(delivery["status"], "pending");
+    assert_eq!(delivery["attempts"], 1);
+    assert_eq!(delivery["response_status"], 500);
+
+    // 再送時刻までは送らない
+    dispatcher.tick().await;
+    assert_eq!(receiver.received().len(), 1);
+
+    clock.advance(Duration::seconds(10));
+    dispatcher.tick().await;
+    assert_eq!(receiver.received().len(), 2);
+
+    let deliveries = response_json(send(&app, "GET", &deliveries_uri, None).await).await;
+    let delivery = &deliveries["deliveries"][0];
+    assert_eq!(delivery["status"], "delivered");
+    assert_eq!(delivery["attempts"], 2);
+    assert!(delivery["last_error"].is_null());
+}
+
+/// リダイレクトは追わず、3xxを失敗として記録すること（署名付きのペイロードを別の宛先へ送らない）
+#[tokio::test]
+async fn test_redirects_are_not_followed() {
+    let (app, pool) = app_with_pool().await;
+    let followed = Arc::new(AtomicUsize::new(0));
+    let router = Router::new()
+        .route(
+            "/hook",
+            post(|| async { axum::response::Redirect::temporary("/elsewhere") }),
+        )
+        .route(
+            "/elsewhere",
+            post({
+                let followed = followed.clone();
+                move || async move {
+                    followed.fetch_add(1, Ordering::SeqCst);
+                    StatusCode::NO_CONTENT
+                }
+            }),
+        );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+    let webhook = response_json(
+        send(
+            &app,
+            "POST",
+            "/webhooks",
+            Some(serde_json::json!({
+                "url": format!("http://{}/hook", addr),
+                "secret": "0123456789abcdef"
+            })),
+        )
+        .await,
+    )
+    .await;
+    send(
+        &app,
+        "POST",
+        "/todos",
+        Some(serde_json::json!({"title": "redirect"})),
+    )
+    .await;
+
+    let clock = Arc::new(ManualClock::new(Utc::now() + Duration::minutes(1)));
+    dispatcher(&pool, clock).tick().await;
+
+    assert_eq!(followed.load(Ordering::SeqCst), 0);
+    let deliveries_uri = format!("/webhooks/{}/deliveries", webhook["id"]);
+    let deliveries = response_json(send(&app, "GET", &deliveries_uri, None).await).await;
+    assert_eq!(deliveries["deliveries"][0]["status"], "pending");
+    assert_eq!(deliveries["deliveries"][0]["response_status"], 307);
+}
+
+/// 配信を積めなければ、TODOの変更も残らないこと（同じトランザクション）
+#[tokio::test]
+async fn test_todo_change_is_rolled_back_when_the_outbox_write_fails() {
+    let pool = SqlitePoolOptions::new()
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    create_tables(&pool).await.unwrap();
+    let store =
+        TodoStore::with_webhook_outbox(pool.clone(), Arc::new(ManualClock::new(Utc::now())));
+    let todo = store.create("before".to_string()).await.unwrap();
+
+    sqlx::query("DROP TABLE webhook_deliveries")
+        .execute(&pool)
+        .await
+        .unwrap();
+    assert!(store.create("after".to_string()).await.is_err());
+    assert!(store
+        .update(todo.id as u32, Some("changed".to_string()), None, None)
+        .await
+        .is_err());
+
+    let titles: Vec<String> = sqlx::query_scalar("SELECT title FROM todos")
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    assert_eq!(titles, vec!["before".to_string()]);
+}
@@ -0,0 +1,240 @@
+use std::time::Duration;
+
+use futures_util::{SinkExt, StreamExt};
+use rust_todo_app::create_test_app;
+use tokio::net::TcpStream;
+use tokio_tungstenite::{connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream};
+
+type Client = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// テスト用にアプリを実際のポートで起動し、アドレスを返す
+async fn start_server() -> String {
+    let app = create_test_app().await;
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    addr.to_string()
+}
+
+async fn connect(addr: &str, user: &str) -> Client {
+    let (mut client, _) = connect_async(format!("ws://{}/ws?user={}", addr, user))
+        .await
+        .unwrap();
+    let welcome = recv_until(&mut client, |m| m["type"] == "welcome").await;
+    assert_eq!(welcome["user"], user);
+    client
+}
+
+async fn send(client: &mut Client, message: serde_json::Value) {
+    client
+        .send(Message::Text(message.to_string()))
+        .await
+        .unwrap();
+}
+
+/// 条件に合うメッセージが届くまで読み進める（それ以外は読み捨てる）
+async fn recv_until(
+    client: &mut Client,
+    matches: impl Fn(&serde_json::Value) -> bool,
+) -> serde_json::Value {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            let message = client.next().await.expect("socket closed").unwrap();
+            if let Message::Text(text) = message {
+                let json: serde_json::Value = serde_json::from_str(&text).unwrap();
+                if matches(&json) {
+                    return json;
+                }
+            }
+        }
+    })
+    .await
+    .expect("timed out waiting for message")
+}
+
+async fn reply_to(client: &mut Client, request_id: &str) -> serde_json::Value {
+    recv_until(client, |m| m["request_id"] == request_id).await
+}
+
+#[tokio::test]
+async fn test_ws_mutations_are_acked_and_broadcast() {
+    let addr = start_server().await;
+    let mut alice = connect(&addr, "alice").await;
+    let mut bob = connect(&addr, "bob").await;
+    send(
+        &mut bob,
+        serde_json::json!({"type": "subscribe", "request_id": "s1"}),
+    )
+    .await;
+    assert_eq!(reply_to(&mut bob, "s1").await["type"], "ack");
+
+    send(
+        &mut alice,
+        serde_json::json!({"type": "create_todo", "request_id": "c1", "title": "一緒に編集"}),
+    )
+    .await;
+    let ack = reply_to(&mut alice, "c1").await;
+    assert_eq!(ack["type"], "ack");
+    assert_eq!(ack["result"]["title"], "一緒に編集");
+
+    let event = recv_until(&mut bob, |m| m["type"] == "event").await;
+    assert_eq!(event["event"], "todo.created");
+    assert_eq!(event["data"]["id"], ack["result"]["id"]);
+    // SSEと同じ `<起動時刻>-<通し番号>`
+    let (epoch, seq) = event["id"].as_str().unwrap().split_once('-').unwrap();
+    assert!(epoch.parse::<u64>().is_ok());
+    assert!(seq.parse::<u64>().unwrap() >= 1);
+
+    send(
+        &mut alice,
+        serde_json::json!({"type": "create_todo", "request_id": "c2", "title": ""}),
+    )
+    .await;
+    let error = reply_to(&mut alice, "c2").await;
+    assert_eq!(error["type"], "error");
+    assert_eq!(error["code"], "validation_failed");
+}
+
+#[tokio::test]
+async fn test_ws_editing_locks_and_presence() {
+    let addr = start_server().await;
+    let mut alice = connect(&addr, "alice").await;
+    let mut bob = connect(&addr, "bob").await;
+
+    send(
+        &mut alice,
+        serde_json::json!({"type": "create_todo", "request_id": "c1", "title": "lock me"}),
+    )
+    .await;
+    let id = reply_to(&mut alice, "c1").await["result"]["id"].clone();
+
+    send(
+        &mut bob,
+        serde_json::json!({"type": "subscribe", "request_id": "s1"}),
+    )
+    .await;
+    reply_to(&mut bob, "s1").await;
+
+    send(
+        &mut alice,
+        serde_json::json!({"type": "view", "request_id": "v1", "todo_id": id}),
+    )
+    .await;
+    reply_to(&mut alice, "v1").await;
+    send(
+        &mut alice,
+        serde_json::json!({"type": "lock", "request_id": "l1", "todo_id": id}),
+    )
+    .await;
+    let lock = reply_to(&mut alice, "l1").await;
+    assert_eq!(lock["type"], "ack");
+    assert_eq!(lock["result"]["user"], "alice");
+
+    let presence = recv_until(&mut bob, |m| {
+        m["type"] == "presence" && m["locks"].as_array().is_some_and(|l| !l.is_empty())
+    })
+    .await;
+    assert_eq!(presence["locks"][0]["todo_id"], id);
+    let alice_view = presence["viewers"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|v| v["user"] == "alice")
+        .unwrap();
+    assert_eq!(alice_view["todo_id"], id);
+
+    send(
+        &mut bob,
+        serde_json::json!({"type": "update_todo", "request_id": "u1", "id": id, "completed": true}),
+    )
+    .await;
+    let error = reply_to(&mut bob, "u1").await;
+    assert_eq!(error["code"], "locked");
+
+    // 切断するとロックが外れ、他の人が編集できるようになる
+    alice.close(None).await.unwrap();
+    recv_until(&mut bob, |m| {
+        m["type"] == "presence" && m["locks"].as_array().is_some_and(|l| l.is_empty())
+    })
+    .await;
+    send(
+        &mut bob,
+        serde_json::json!({"type": "update_todo", "request_id": "u2", "id": id, "completed": true}),
+    )
+    .await;
+    let ack = reply_to(&mut bob, "u2").await;
+    assert_eq!(ack["type"], "ack");
+    assert_eq!(ack["result"]["completed"], true);
+}
+
+#[tokio::test]
+async fn test_ws_rejects_malformed_messages() {
+    let addr = start_server().await;
+    let mut client = connect(&addr, "carol").await;
+
+    client.send(Message::Text("not json".into())).await.unwrap();
+    let error = recv_until(&mut client, |m| m["type"] == "error").await;
+    assert_eq!(error["code"], "invalid_message");
+
+    send(
+        &mut client,
+        serde_json::json!({"type": "ping", "request_id": "p1"}),
+    )
+    .await;
+    assert_eq!(reply_to(&mut client, "p1").await["type"], "pong");
+
+    assert!(connect_async(format!("ws://{}/ws", addr)).await.is_err());
+}
+
+/// WebSocketで取ったロックは、RESTやGraphQLからの更新・削除も止めること
+#[tokio::test]
+async fn test_ws_lock_blocks_other_protocols() {
+    let addr = start_server().await;
+    let mut alice = connect(&addr, "alice").await;
+    send(
+        &mut alice,
+        serde_json::json!({"type": "create_todo", "request_id": "c1", "title": "lock me"}),
+    )
+    .await;
+    let id = reply_to(&mut alice, "c1").await["result"]["id"].clone();
+    send(
+        &mut alice,
+        serde_json::json!({"type": "lock", "request_id": "l1", "todo_id": id}),
+    )
+    .await;
+    assert_eq!(reply_to(&mut alice, "l1").await["type"], "ack");
+
+    let client = reqwest::Client::new();
+    let url = format!("http://{}/api/v1/todos/{}", addr, id);
+    let response = client
+        .put(&url)
+        .json(&serde_json::json!({"completed": true}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::CONFLICT);
+    let problem: serde_json::Value = response.json().await.unwrap();
+    assert!(problem["detail"].as_str().unwrap().contains("alice"));
+    let response = client.delete(&url).send().await.unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::CONFLICT);
+
+    let query = format!("mutation {{ deleteTodo(id: {}) }}", id);
+    let response: serde_json::Value = client
+        .post(format!("http://{}/graphql", addr))
+        .json(&serde_json::json!({ "query": query }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(response["errors"][0]["extensions"]["code"], "todo_locked");
+
+    // ロックを持つ本人は更新できる
+    send(
+        &mut alice,
+        serde_json::json!({"type": "update_todo", "request_id": "u1", "id": id, "completed": true}),
+    )
+    .await;
+    assert_eq!(reply_to(&mut alice, "u1").await["type"], "ack");
+}
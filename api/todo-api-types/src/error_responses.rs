@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+pub const PROBLEM_CONTENT_TYPE: &str = "application/problem+json";
+
+/// エラー時のレスポンスボディ（RFC 7807 Problem Details）
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ProblemDetails {
+    /// エラーの種類を表すURI（例: `/problems/validation-failed`）
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    /// エラーの種類ごとの短い説明（`Accept-Language` に合わせて翻訳する）
+    pub title: String,
+    pub status: u16,
+    /// 言語によらないエラーコード（例: `invalid_fields`, `attachment_too_large`）
+    pub code: String,
+    /// このリクエストで起きたことの説明
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// 読み取りに失敗したときのライブラリの原文（翻訳しない。調査用）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// エラーになったリクエストのパス
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    /// 入力エラーの項目ごとの内訳
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<FieldErrorResponse>,
+    /// 問い合わせ時に伝えてもらうID（`X-Request-Id` と同じ値）
+    pub request_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct FieldErrorResponse {
+    pub field: String,
+    /// 言語によらないエラーコード（例: `length`, `url`）
+    pub code: String,
+    pub message: String,
+}
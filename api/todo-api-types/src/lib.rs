@@ -0,0 +1,10 @@
+//! TODOのAPIのリクエストとレスポンスの形。サーバ（`rust_todo_app`）とクライアント（`todo-client`）で共有する。
+//!
+//! 形はAPIのバージョンごとにモジュールを分ける（`/api/v1` は `v1`）。
+//! エラー（problem+json）の形はバージョンによらない。
+//! ドメインの型からの変換はサーバ側に置くので、このクレートはサーバに依存しない
+pub mod error_responses;
+pub mod v1;
+
+/// 現行バージョンのAPIのパスのプレフィックス
+pub const API_V1_PREFIX: &str = "/api/v1";
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+#[derive(Serialize, Deserialize, Validate, ToSchema)]
+pub struct CreateCommentRequest {
+    #[validate(length(min = 1, max = 100))]
+    #[schema(min_length = 1, max_length = 100)]
+    pub author: String,
+    /// Markdown
+    #[validate(length(min = 1, max = 10000))]
+    #[schema(min_length = 1, max_length = 10000)]
+    pub body: String,
+}
+
+#[derive(Serialize, Deserialize, Validate, ToSchema)]
+pub struct UpdateCommentRequest {
+    #[validate(length(min = 1, max = 10000))]
+    #[schema(min_length = 1, max_length = 10000)]
+    pub body: String,
+}
+
+#[derive(Default, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CommentListQuery {
+    /// 1始まり。省略時は1
+    #[param(minimum = 1)]
+    pub page: Option<u32>,
+    /// 省略時は20
+    #[param(minimum = 1, maximum = 100)]
+    pub per_page: Option<u32>,
+}
@@ -0,0 +1,22 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// `body` はMarkdownのまま返す（描画はクライアント側で行う）
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CommentResponse {
+    pub id: i64,
+    pub todo_id: i64,
+    pub author: String,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+    pub edited_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CommentPageResponse {
+    pub comments: Vec<CommentResponse>,
+    pub page: u32,
+    pub per_page: u32,
+    pub total: u64,
+}
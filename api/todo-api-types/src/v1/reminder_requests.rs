@@ -0,0 +1,28 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
+
+/// `remind_at`（絶対時刻）と `offset_minutes`（期限の何分前か）のどちらか一方を指定する
+#[derive(Serialize, Deserialize, Validate, ToSchema)]
+#[validate(schema(function = "validate_schedule"))]
+pub struct CreateReminderRequest {
+    pub remind_at: Option<DateTime<Utc>>,
+    // 最大1年前まで
+    #[validate(range(min = 0, max = 525600))]
+    #[schema(minimum = 0, maximum = 525600)]
+    pub offset_minutes: Option<i64>,
+}
+
+fn validate_schedule(request: &CreateReminderRequest) -> Result<(), ValidationError> {
+    if request.remind_at.is_some() == request.offset_minutes.is_some() {
+        return Err(ValidationError::new("reminder_schedule"));
+    }
+    Ok(())
+}
+
+impl CreateReminderRequest {
+    pub fn offset(&self) -> Option<chrono::Duration> {
+        self.offset_minutes.map(chrono::Duration::minutes)
+    }
+}
@@ -0,0 +1,17 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ReminderResponse {
+    pub id: i64,
+    pub todo_id: i64,
+    pub remind_at: Option<DateTime<Utc>>,
+    pub offset_minutes: Option<i64>,
+    /// 実際に通知する時刻。期限未設定の相対指定では null
+    pub fire_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+    pub attempts: u32,
+    pub last_error: Option<String>,
+}
@@ -0,0 +1,46 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+// 入力エラーの文言は、サーバが validator のコード（length など）で翻訳のカタログから引く
+#[derive(Serialize, Deserialize, Validate, ToSchema)]
+pub struct CreateTodoRequest {
+    #[validate(length(min = 1, max = 200))]
+    #[schema(min_length = 1, max_length = 200, example = "牛乳を買う")]
+    pub title: String,
+}
+
+// 送る側（CLIなど）では None の項目を省き、「変更なし」として扱わせる
+#[derive(Default, Serialize, Deserialize, Validate, ToSchema)]
+pub struct UpdateTodoRequest {
+    #[validate(length(min = 1, max = 200))]
+    #[schema(min_length = 1, max_length = 200)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed: Option<bool>,
+    /// 省略時は変更なし、`null` で期限を解除する
+    #[serde(
+        default,
+        deserialize_with = "deserialize_present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schema(value_type = Option<String>, format = DateTime, nullable)]
+    pub due_at: Option<Option<DateTime<Utc>>>,
+}
+
+// フィールドが存在すれば `Some`（値が null なら `Some(None)`）にする
+fn deserialize_present<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// 並べたい順にTODOのIDを列挙する
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct ReorderRequest {
+    pub ids: Vec<i64>,
+}
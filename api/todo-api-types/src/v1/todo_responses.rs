@@ -0,0 +1,12 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TodoResponse {
+    pub id: i64,
+    pub title: String,
+    pub completed: bool,
+    pub position: i64,
+    pub due_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+/// `events` を省略または空にすると全イベントを購読する
+#[derive(Serialize, Deserialize, Validate, ToSchema)]
+pub struct CreateWebhookRequest {
+    #[validate(url)]
+    #[schema(example = "https://example.com/hooks/todo")]
+    pub url: String,
+    /// 署名（HMAC-SHA256）の鍵
+    #[validate(length(min = 16, max = 256))]
+    #[schema(min_length = 16, max_length = 256)]
+    pub secret: String,
+    /// `todo.created` / `todo.updated` / `todo.deleted` / `todo.reordered`
+    #[serde(default)]
+    pub events: Vec<String>,
+}
+
+// 送る側では None の項目を省き、「変更なし」として扱わせる
+#[derive(Default, Serialize, Deserialize, Validate, ToSchema)]
+pub struct UpdateWebhookRequest {
+    #[validate(url)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[validate(length(min = 16, max = 256))]
+    #[schema(min_length = 16, max_length = 256)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub events: Option<Vec<String>>,
+}
+
+#[derive(Default, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DeliveryListQuery {
+    /// 1始まり。省略時は1
+    #[param(minimum = 1)]
+    pub page: Option<u32>,
+    /// 省略時は20
+    #[param(minimum = 1, maximum = 100)]
+    pub per_page: Option<u32>,
+}
@@ -0,0 +1,62 @@
+use std::fmt;
+use std::process::ExitCode;
+
+use todo_client::{ClientError, ProblemDetails, StatusCode};
+
+#[derive(Debug)]
+pub enum CliError {
+    /// APIの呼び出しに失敗した
+    Client(ClientError),
+    /// 指定したTODOが一覧にない（`mv` の移動先など）
+    MissingTodo(i64),
+}
+
+impl CliError {
+    /// APIが返したproblem+json
+    pub fn problem(&self) -> Option<&ProblemDetails> {
+        match self {
+            CliError::Client(error) => error.problem(),
+            CliError::MissingTodo(_) => None,
+        }
+    }
+
+    /// 終了コード。2は引数の誤りとしてclapが使う
+    pub fn exit_code(&self) -> ExitCode {
+        let status = match self {
+            CliError::Client(ClientError::NotFound(_)) | CliError::MissingTodo(_) => {
+                return ExitCode::from(3)
+            }
+            CliError::Client(
+                ClientError::InvalidInput(_)
+                | ClientError::PayloadTooLarge(_)
+                | ClientError::UnsupportedMediaType(_),
+            ) => return ExitCode::from(4),
+            CliError::Client(ClientError::Timeout(_) | ClientError::Transport(_)) => {
+                return ExitCode::from(5)
+            }
+            // `--url` が正しいURLでない
+            CliError::Client(ClientError::Request(_)) => return ExitCode::from(2),
+            CliError::Client(error) => error.status(),
+        };
+        match status {
+            Some(StatusCode::NOT_FOUND) => ExitCode::from(3),
+            Some(status) if status.is_client_error() => ExitCode::from(4),
+            _ => ExitCode::from(1),
+        }
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Client(error) => write!(f, "{}", error),
+            CliError::MissingTodo(id) => write!(f, "todo {} not found", id),
+        }
+    }
+}
+
+impl From<ClientError> for CliError {
+    fn from(error: ClientError) -> Self {
+        CliError::Client(error)
+    }
+}
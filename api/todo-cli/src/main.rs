@@ -0,0 +1,180 @@
+//! TODOをターミナルから操作するCLI。HTTPのAPI（`/api/v1`）を呼ぶ
+//!
+//! 終了コード: 0 成功 / 1 サーバ側のエラー / 2 引数の誤り / 3 TODOが見つからない /
+//! 4 入力エラー / 5 APIに接続できない
+mod error;
+mod output;
+
+use std::process::ExitCode;
+
+use clap::{Args, Parser, Subcommand};
+use todo_client::{CreateTodoRequest, ReorderRequest, TodoClient, TodoResponse, UpdateTodoRequest};
+
+use crate::error::CliError;
+
+#[derive(Parser)]
+#[command(name = "todo", about = "Manage todos from the terminal")]
+struct Cli {
+    /// APIのベースURL（`/api/v1` は付けない）
+    #[arg(
+        long,
+        env = "TODO_API_URL",
+        default_value = "http://localhost:3000",
+        global = true
+    )]
+    url: String,
+    /// `Authorization: Bearer` で送るトークン
+    #[arg(long, env = "TODO_API_TOKEN", hide_env_values = true, global = true)]
+    token: Option<String>,
+    /// 表ではなくJSONで出力する
+    #[arg(long, global = true)]
+    json: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// TODOを追加する
+    Add { title: String },
+    /// TODOを並び順で一覧する
+    Ls {
+        /// 未完了のものだけ
+        #[arg(long, conflicts_with = "done")]
+        pending: bool,
+        /// 完了したものだけ
+        #[arg(long)]
+        done: bool,
+    },
+    /// TODOを完了にする
+    Done { id: i64 },
+    /// TODOを別のTODOの前（または後ろ）へ移動する
+    Mv {
+        id: i64,
+        #[command(flatten)]
+        target: MoveTarget,
+    },
+    /// TODOを削除する
+    Rm { id: i64 },
+}
+
+#[derive(Args)]
+#[group(required = true, multiple = false)]
+struct MoveTarget {
+    /// このIDのTODOの前へ
+    #[arg(long)]
+    before: Option<i64>,
+    /// このIDのTODOの後ろへ
+    #[arg(long)]
+    after: Option<i64>,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let mut builder = TodoClient::builder(&cli.url);
+    if let Some(token) = cli.token {
+        builder = builder.token(token);
+    }
+
+    let result = match builder.build() {
+        Ok(client) => run(&client, cli.command, cli.json).await,
+        Err(error) => Err(error.into()),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            match (error.problem(), cli.json) {
+                (Some(problem), true) => eprintln!(
+                    "{}",
+                    serde_json::to_string_pretty(problem).expect("problem is serializable")
+                ),
+                _ => eprintln!("error: {}", error),
+            }
+            error.exit_code()
+        }
+    }
+}
+
+async fn run(client: &TodoClient, command: Command, json: bool) -> Result<(), CliError> {
+    match command {
+        Command::Add { title } => {
+            let todo = client.create_todo(&CreateTodoRequest { title }).await?;
+            print_todos(&[todo], json);
+        }
+        Command::Ls { pending, done } => {
+            let todos: Vec<TodoResponse> = client
+                .list_todos()
+                .await?
+                .into_iter()
+                .filter(|todo| !(pending && todo.completed || done && !todo.completed))
+                .collect();
+            print_todos(&todos, json);
+        }
+        Command::Done { id } => {
+            let update = UpdateTodoRequest {
+                completed: Some(true),
+                ..UpdateTodoRequest::default()
+            };
+            let todo = client.update_todo(id, &update).await?;
+            print_todos(&[todo], json);
+        }
+        Command::Mv { id, target } => {
+            let ids: Vec<i64> = client
+                .list_todos()
+                .await?
+                .iter()
+                .map(|todo| todo.id)
+                .collect();
+            let ids = moved(ids, id, target)?;
+            client.reorder_todos(&ReorderRequest { ids }).await?;
+            print_todos(&client.list_todos().await?, json);
+        }
+        Command::Rm { id } => {
+            client.delete_todo(id).await?;
+            if json {
+                println!("{}", serde_json::json!({ "id": id, "deleted": true }));
+            } else {
+                println!("Deleted todo {}", id);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// `id` を `target` の前後へ移した並び順
+fn moved(mut ids: Vec<i64>, id: i64, target: MoveTarget) -> Result<Vec<i64>, CliError> {
+    let (target_id, after) = match (target.before, target.after) {
+        (Some(before), _) => (before, false),
+        (None, Some(after)) => (after, true),
+        (None, None) => unreachable!("clap requires --before or --after"),
+    };
+    let from = ids
+        .iter()
+        .position(|&other| other == id)
+        .ok_or(CliError::MissingTodo(id))?;
+    if !ids.contains(&target_id) {
+        return Err(CliError::MissingTodo(target_id));
+    }
+    if id == target_id {
+        return Ok(ids);
+    }
+    ids.remove(from);
+    let to = ids
+        .iter()
+        .position(|&other| other == target_id)
+        .expect("target is still in the list");
+    ids.insert(if after { to + 1 } else { to }, id);
+    Ok(ids)
+}
+
+fn print_todos(todos: &[TodoResponse], json: bool) {
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(todos).expect("todos are serializable")
+        );
+    } else {
+        print!("{}", output::table(todos));
+    }
+}
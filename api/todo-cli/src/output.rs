@@ -0,0 +1,40 @@
+use todo_client::TodoResponse;
+use unicode_width::UnicodeWidthStr;
+
+const HEADERS: [&str; 4] = ["ID", "DONE", "TITLE", "DUE"];
+
+/// TODOを列を揃えた表にする。全角文字は2桁として数える
+pub fn table(todos: &[TodoResponse]) -> String {
+    let rows: Vec<[String; 4]> = todos
+        .iter()
+        .map(|todo| {
+            [
+                todo.id.to_string(),
+                if todo.completed { "[x]" } else { "[ ]" }.to_string(),
+                todo.title.clone(),
+                todo.due_at
+                    .map(|due_at| due_at.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_default(),
+            ]
+        })
+        .collect();
+
+    let mut widths = HEADERS.map(UnicodeWidthStr::width);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.width());
+        }
+    }
+
+    let mut out = String::new();
+    for cells in std::iter::once(HEADERS.map(str::to_string)).chain(rows) {
+        let line: Vec<String> = cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{}{}", cell, " ".repeat(width - cell.width())))
+            .collect();
+        out.push_str(line.join("  ").trim_end());
+        out.push('\n');
+    }
+    out
+}
@@ -0,0 +1,117 @@
+use rust_todo_app::create_test_app;
+use tokio::process::Command;
+
+/// テスト用にアプリを実際のポートで起動し、ベースURLを返す
+async fn start_server() -> String {
+    let app = create_test_app().await;
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{}", addr)
+}
+
+struct Output {
+    code: i32,
+    stdout: String,
+    stderr: String,
+}
+
+async fn todo(url: &str, args: &[&str]) -> Output {
+    let output = Command::new(env!("CARGO_BIN_EXE_todo"))
+        .env("TODO_API_URL", url)
+        .args(args)
+        .output()
+        .await
+        .unwrap();
+    Output {
+        code: output.status.code().unwrap(),
+        stdout: String::from_utf8(output.stdout).unwrap(),
+        stderr: String::from_utf8(output.stderr).unwrap(),
+    }
+}
+
+async fn add(url: &str, title: &str) -> i64 {
+    let output = todo(url, &["--json", "add", title]).await;
+    assert_eq!(output.code, 0, "{}", output.stderr);
+    let todos: serde_json::Value = serde_json::from_str(&output.stdout).unwrap();
+    todos[0]["id"].as_i64().unwrap()
+}
+
+async fn listed_ids(url: &str, args: &[&str]) -> Vec<i64> {
+    let output = todo(url, &[&["--json", "ls"], args].concat()).await;
+    assert_eq!(output.code, 0, "{}", output.stderr);
+    let todos: Vec<serde_json::Value> = serde_json::from_str(&output.stdout).unwrap();
+    todos
+        .iter()
+        .map(|todo| todo["id"].as_i64().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_cli_manages_todos() {
+    let url = start_server().await;
+    let milk = add(&url, "牛乳を買う").await;
+    let report = add(&url, "write report").await;
+    let call = add(&url, "call mom").await;
+
+    let output = todo(&url, &["done", &report.to_string()]).await;
+    assert_eq!(output.code, 0);
+    assert!(output.stdout.contains("[x]"));
+
+    assert_eq!(listed_ids(&url, &["--pending"]).await, vec![milk, call]);
+    assert_eq!(listed_ids(&url, &["--done"]).await, vec![report]);
+
+    let output = todo(
+        &url,
+        &["mv", &call.to_string(), "--before", &milk.to_string()],
+    )
+    .await;
+    assert_eq!(output.code, 0, "{}", output.stderr);
+    assert_eq!(listed_ids(&url, &[]).await, vec![call, milk, report]);
+    let output = todo(
+        &url,
+        &["mv", &call.to_string(), "--after", &report.to_string()],
+    )
+    .await;
+    assert_eq!(output.code, 0, "{}", output.stderr);
+    assert_eq!(listed_ids(&url, &[]).await, vec![milk, report, call]);
+
+    let output = todo(&url, &["rm", &milk.to_string()]).await;
+    assert_eq!(output.code, 0);
+    assert_eq!(listed_ids(&url, &[]).await, vec![report, call]);
+
+    // 表はヘッダの下に1行1件
+    add(&url, "全角").await;
+    let output = todo(&url, &["ls"]).await;
+    let lines: Vec<&str> = output.stdout.lines().collect();
+    assert!(lines[0].starts_with("ID  DONE  TITLE"));
+    assert_eq!(lines.len(), 4);
+}
+
+#[tokio::test]
+async fn test_cli_exit_codes_follow_api_errors() {
+    let url = start_server().await;
+
+    let output = todo(&url, &["done", "999"]).await;
+    assert_eq!(output.code, 3);
+    assert!(output.stderr.contains("code: not_found"));
+
+    let output = todo(&url, &["--json", "add", ""]).await;
+    assert_eq!(output.code, 4);
+    let problem: serde_json::Value = serde_json::from_str(&output.stderr).unwrap();
+    assert_eq!(problem["code"], "invalid_fields");
+
+    let id = add(&url, "exists").await;
+    let output = todo(&url, &["mv", &id.to_string(), "--before", "999"]).await;
+    assert_eq!(output.code, 3);
+
+    let output = todo(&url, &["mv", &id.to_string()]).await;
+    assert_eq!(output.code, 2);
+
+    // 誰も待ち受けていないポート
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let closed = format!("http://{}", listener.local_addr().unwrap());
+    drop(listener);
+    let output = todo(&closed, &["ls"]).await;
+    assert_eq!(output.code, 5);
+}
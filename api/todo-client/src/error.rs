@@ -0,0 +1,164 @@
+use std::fmt;
+
+use reqwest::{Response, StatusCode};
+use todo_api_types::error_responses::ProblemDetails;
+
+/// [`crate::TodoClient`] のエラー
+///
+/// APIがproblem+jsonで返したエラーはステータスごとの種類に振り分ける。
+/// 種類によらない判定には [`ClientError::code`]（RESTの `code`）を使う
+#[derive(Debug)]
+pub enum ClientError {
+    /// 404。TODOなどが見つからない
+    NotFound(Box<ProblemDetails>),
+    /// 400 / 422。項目ごとの内訳は `errors`
+    InvalidInput(Box<ProblemDetails>),
+    /// 413。添付が大きすぎる
+    PayloadTooLarge(Box<ProblemDetails>),
+    /// 415。許可されていない形式
+    UnsupportedMediaType(Box<ProblemDetails>),
+    /// 5xx
+    Server(Box<ProblemDetails>),
+    /// 上のどれにも当たらないproblem+json
+    Api(Box<ProblemDetails>),
+    /// problem+jsonではないエラー応答（プロキシなど）
+    UnexpectedStatus { status: StatusCode, body: String },
+    /// 時間内に応答がなかった
+    Timeout(reqwest::Error),
+    /// リクエストを組み立てられない（URLやMIMEタイプの誤り）
+    Request(reqwest::Error),
+    /// APIに接続できない、応答を最後まで読めない
+    Transport(reqwest::Error),
+    /// 成功の応答が期待した形のJSONではない
+    Decode(reqwest::Error),
+}
+
+impl ClientError {
+    /// APIが返したproblem+json
+    pub fn problem(&self) -> Option<&ProblemDetails> {
+        match self {
+            ClientError::NotFound(problem)
+            | ClientError::InvalidInput(problem)
+            | ClientError::PayloadTooLarge(problem)
+            | ClientError::UnsupportedMediaType(problem)
+            | ClientError::Server(problem)
+            | ClientError::Api(problem) => Some(problem),
+            _ => None,
+        }
+    }
+
+    /// 言語によらないエラーコード（例: `not_found`, `invalid_fields`）
+    pub fn code(&self) -> Option<&str> {
+        self.problem().map(|problem| problem.code.as_str())
+    }
+
+    /// エラー応答のステータス。応答がなかった場合は `None`
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            ClientError::UnexpectedStatus { status, .. } => Some(*status),
+            ClientError::Timeout(error)
+            | ClientError::Request(error)
+            | ClientError::Transport(error)
+            | ClientError::Decode(error) => error.status(),
+            _ => self
+                .problem()
+                .and_then(|problem| StatusCode::from_u16(problem.status).ok()),
+        }
+    }
+
+    pub(crate) async fn from_response(response: Response) -> Self {
+        let status = response.status();
+        let body = match response.text().await {
+            Ok(body) => body,
+            Err(error) => return error.into(),
+        };
+        let problem = match serde_json::from_str::<ProblemDetails>(&body) {
+            Ok(problem) => Box::new(problem),
+            Err(_) => return ClientError::UnexpectedStatus { status, body },
+        };
+        match status {
+            StatusCode::NOT_FOUND => ClientError::NotFound(problem),
+            StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY => {
+                ClientError::InvalidInput(problem)
+            }
+            StatusCode::PAYLOAD_TOO_LARGE => ClientError::PayloadTooLarge(problem),
+            StatusCode::UNSUPPORTED_MEDIA_TYPE => ClientError::UnsupportedMediaType(problem),
+            status if status.is_server_error() => ClientError::Server(problem),
+            _ => ClientError::Api(problem),
+        }
+    }
+
+    /// 送り直して成功する見込みがあるか。`idempotent` でない（POST）場合は、
+    /// サーバが処理していないと言える失敗だけを対象にする
+    pub(crate) fn is_retryable(&self, idempotent: bool) -> bool {
+        match self {
+            ClientError::Transport(error) if error.is_connect() => true,
+            ClientError::Transport(_) | ClientError::Timeout(_) => idempotent,
+            ClientError::Request(_) | ClientError::Decode(_) => false,
+            _ => match self.status() {
+                Some(StatusCode::TOO_MANY_REQUESTS) => true,
+                Some(
+                    StatusCode::BAD_GATEWAY
+                    | StatusCode::SERVICE_UNAVAILABLE
+                    | StatusCode::GATEWAY_TIMEOUT,
+                ) => idempotent,
+                _ => false,
+            },
+        }
+    }
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(problem) = self.problem() {
+            write!(
+                f,
+                "{} ({}, code: {}, request_id: {})",
+                problem.detail.as_ref().unwrap_or(&problem.title),
+                problem.status,
+                problem.code,
+                problem.request_id
+            )?;
+            for error in &problem.errors {
+                write!(f, "; {}: {}", error.field, error.message)?;
+            }
+            return Ok(());
+        }
+        match self {
+            ClientError::UnexpectedStatus { status, body } => {
+                write!(f, "HTTP {}: {}", status, body.trim())
+            }
+            ClientError::Timeout(error) => write!(f, "request timed out: {}", error),
+            ClientError::Request(error) => write!(f, "invalid request: {}", error),
+            ClientError::Transport(error) => write!(f, "failed to reach the API: {}", error),
+            ClientError::Decode(error) => write!(f, "unexpected response body: {}", error),
+            _ => unreachable!("problem variants are handled above"),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ClientError::Timeout(error)
+            | ClientError::Request(error)
+            | ClientError::Transport(error)
+            | ClientError::Decode(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for ClientError {
+    fn from(error: reqwest::Error) -> Self {
+        if error.is_timeout() {
+            ClientError::Timeout(error)
+        } else if error.is_builder() {
+            ClientError::Request(error)
+        } else if error.is_decode() {
+            ClientError::Decode(error)
+        } else {
+            ClientError::Transport(error)
+        }
+    }
+}
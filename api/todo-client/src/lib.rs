@@ -0,0 +1,517 @@
+//! TODOのAPI（`/api/v1`）を呼ぶ非同期クライアント
+//!
+//! リクエストとレスポンスの型はサーバと共有する `todo-api-types` のものをそのまま使う。
+//! エラー応答（problem+json）は [`ClientError`] に振り分けて返す。
+//!
+//! SSE（`/events`）とWebSocket（`/ws`）は対象外。変更を購読したい場合は
+//! GraphQLの `todoChanged` かgRPCの `WatchTodos` を使う
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), todo_client::ClientError> {
+//! use std::time::Duration;
+//! use todo_client::{CreateTodoRequest, TodoClient};
+//!
+//! let client = TodoClient::builder("http://localhost:3000")
+//!     .timeout(Duration::from_secs(5))
+//!     .build()?;
+//! let todo = client
+//!     .create_todo(&CreateTodoRequest {
+//!         title: "牛乳を買う".to_string(),
+//!     })
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+mod error;
+
+use std::time::Duration;
+
+use reqwest::multipart::{Form, Part};
+use reqwest::{Method, RequestBuilder, Response};
+use serde::de::DeserializeOwned;
+use todo_api_types::API_V1_PREFIX;
+
+pub use error::ClientError;
+pub use reqwest::StatusCode;
+pub use todo_api_types::error_responses::{FieldErrorResponse, ProblemDetails};
+pub use todo_api_types::v1::attachment_responses::AttachmentResponse;
+pub use todo_api_types::v1::comment_requests::{
+    CommentListQuery, CreateCommentRequest, UpdateCommentRequest,
+};
+pub use todo_api_types::v1::comment_responses::{CommentPageResponse, CommentResponse};
+pub use todo_api_types::v1::reminder_requests::CreateReminderRequest;
+pub use todo_api_types::v1::reminder_responses::ReminderResponse;
+pub use todo_api_types::v1::todo_requests::{CreateTodoRequest, ReorderRequest, UpdateTodoRequest};
+pub use todo_api_types::v1::todo_responses::TodoResponse;
+pub use todo_api_types::v1::webhook_requests::{
+    CreateWebhookRequest, DeliveryListQuery, UpdateWebhookRequest,
+};
+pub use todo_api_types::v1::webhook_responses::{
+    WebhookDeliveryPageResponse, WebhookDeliveryResponse, WebhookResponse,
+};
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 失敗したリクエストを送り直す方針
+///
+/// 送り直すのは接続できなかったとき、タイムアウト、429 / 502 / 503 / 504 のとき。
+/// `POST`（作成やアップロード）は二重に作らないよう、サーバに届いていないことが
+/// 確かな場合（接続失敗と429）に限る
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// 最初の1回を除いた再試行の回数
+    pub max_retries: u32,
+    /// 1回目の再試行までの待ち時間。以降は2倍ずつ延ばす
+    pub initial_backoff: Duration,
+    /// 待ち時間の上限
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 2,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// 再試行しない
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_backoff)
+    }
+}
+
+/// [`TodoClient`] の設定
+pub struct TodoClientBuilder {
+    base_url: String,
+    token: Option<String>,
+    language: Option<String>,
+    timeout: Duration,
+    connect_timeout: Duration,
+    retry: RetryPolicy,
+}
+
+impl TodoClientBuilder {
+    /// `Authorization: Bearer` で送るトークン
+    pub fn token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// `Accept-Language` で送る言語。エラーの `title` / `detail` の言語が変わる
+    pub fn language(mut self, language: impl Into<String>) -> Self {
+        self.language = Some(language.into());
+        self
+    }
+
+    /// 1回の試行にかける時間の上限（応答ボディの読み込みまで）。既定は30秒
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// 接続にかける時間の上限。既定は5秒
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    pub fn retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    pub fn build(self) -> Result<TodoClient, ClientError> {
+        let http = reqwest::Client::builder()
+            .timeout(self.timeout)
+            .connect_timeout(self.connect_timeout)
+            .build()?;
+        Ok(TodoClient {
+            http,
+            base_url: format!("{}{}", self.base_url.trim_end_matches('/'), API_V1_PREFIX),
+            token: self.token,
+            language: self.language,
+            retry: self.retry,
+        })
+    }
+}
+
+/// `/api/v1` の各ルートに対応するメソッドを持つクライアント。
+/// 内部で接続を使い回すので、clone して共有する
+#[derive(Clone)]
+pub struct TodoClient {
+    http: reqwest::Client,
+    base_url: String,
+    token: Option<String>,
+    language: Option<String>,
+    retry: RetryPolicy,
+}
+
+impl TodoClient {
+    /// 既定の設定で作る。`base_url` には `/api/v1` を付けない
+    pub fn new(base_url: &str) -> Result<Self, ClientError> {
+        Self::builder(base_url).build()
+    }
+
+    pub fn builder(base_url: &str) -> TodoClientBuilder {
+        TodoClientBuilder {
+            base_url: base_url.to_string(),
+            token: None,
+            language: None,
+            timeout: DEFAULT_TIMEOUT,
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            retry: RetryPolicy::default(),
+        }
+    }
+
+    // ---- TODO ----
+
+    /// 並び順で全件
+    pub async fn list_todos(&self) -> Result<Vec<TodoResponse>, ClientError> {
+        self.json(Method::GET, "/todos", |request| request).await
+    }
+
+    pub async fn create_todo(&self, body: &CreateTodoRequest) -> Result<TodoResponse, ClientError> {
+        self.json(Method::POST, "/todos", |request| request.json(body))
+            .await
+    }
+
+    pub async fn get_todo(&self, id: i64) -> Result<TodoResponse, ClientError> {
+        self.json(Method::GET, &format!("/todos/{}", id), |request| request)
+            .await
+    }
+
+    pub async fn update_todo(
+        &self,
+        id: i64,
+        body: &UpdateTodoRequest,
+    ) -> Result<TodoResponse, ClientError> {
+        self.json(Method::PUT, &format!("/todos/{}", id), |request| {
+            request.json(body)
+        })
+        .await
+    }
+
+    pub async fn delete_todo(&self, id: i64) -> Result<(), ClientError> {
+        self.empty(Method::DELETE, &format!("/todos/{}", id), |request| request)
+            .await
+    }
+
+    pub async fn reorder_todos(&self, body: &ReorderRequest) -> Result<(), ClientError> {
+        self.empty(Method::PUT, "/todos/reorder", |request| request.json(body))
+            .await
+    }
+
+    // ---- コメント ----
+
+    pub async fn list_comments(
+        &self,
+        todo_id: i64,
+        query: &CommentListQuery,
+    ) -> Result<CommentPageResponse, ClientError> {
+        self.json(
+            Method::GET,
+            &format!("/todos/{}/comments", todo_id),
+            |request| request.query(query),
+        )
+        .await
+    }
+
+    pub async fn create_comment(
+        &self,
+        todo_id: i64,
+        body: &CreateCommentRequest,
+    ) -> Result<CommentResponse, ClientError> {
+        self.json(
+            Method::POST,
+            &format!("/todos/{}/comments", todo_id),
+            |request| request.json(body),
+        )
+        .await
+    }
+
+    pub async fn get_comment(
+        &self,
+        todo_id: i64,
+        comment_id: i64,
+    ) -> Result<CommentResponse, ClientError> {
+        self.json(
+            Method::GET,
+            &format!("/todos/{}/comments/{}", todo_id, comment_id),
+            |request| request,
+        )
+        .await
+    }
+
+    pub async fn update_comment(
+        &self,
+        todo_id: i64,
+        comment_id: i64,
+        body: &UpdateCommentRequest,
+    ) -> Result<CommentResponse, ClientError> {
+        self.json(
+            Method::PUT,
+            &format!("/todos/{}/comments/{}", todo_id, comment_id),
+            |request| request.json(body),
+        )
+        .await
+    }
+
+    pub async fn delete_comment(&self, todo_id: i64, comment_id: i64) -> Result<(), ClientError> {
+        self.empty(
+            Method::DELETE,
+            &format!("/todos/{}/comments/{}", todo_id, comment_id),
+            |request| request,
+        )
+        .await
+    }
+
+    // ---- リマインダー ----
+
+    pub async fn list_reminders(&self, todo_id: i64) -> Result<Vec<ReminderResponse>, ClientError> {
+        self.json(
+            Method::GET,
+            &format!("/todos/{}/reminders", todo_id),
+            |request| request,
+        )
+        .await
+    }
+
+    pub async fn create_reminder(
+        &self,
+        todo_id: i64,
+        body: &CreateReminderRequest,
+    ) -> Result<ReminderResponse, ClientError> {
+        self.json(
+            Method::POST,
+            &format!("/todos/{}/reminders", todo_id),
+            |request| request.json(body),
+        )
+        .await
+    }
+
+    pub async fn delete_reminder(&self, todo_id: i64, reminder_id: i64) -> Result<(), ClientError> {
+        self.empty(
+            Method::DELETE,
+            &format!("/todos/{}/reminders/{}", todo_id, reminder_id),
+            |request| request,
+        )
+        .await
+    }
+
+    // ---- 添付 ----
+
+    pub async fn list_attachments(
+        &self,
+        todo_id: i64,
+    ) -> Result<Vec<AttachmentResponse>, ClientError> {
+        self.json(
+            Method::GET,
+            &format!("/todos/{}/attachments", todo_id),
+            |request| request,
+        )
+        .await
+    }
+
+    /// `file` フィールドにして `multipart/form-data` で送る
+    pub async fn upload_attachment(
+        &self,
+        todo_id: i64,
+        filename: &str,
+        content_type: &str,
+        data: Vec<u8>,
+    ) -> Result<AttachmentResponse, ClientError> {
+        Part::bytes(Vec::new()).mime_str(content_type)?;
+        // multipartのボディは送ると消費されるので、試行ごとに作り直す
+        self.json(
+            Method::POST,
+            &format!("/todos/{}/attachments", todo_id),
+            |request| {
+                let part = Part::bytes(data.clone())
+                    .file_name(filename.to_string())
+                    .mime_str(content_type)
+                    .expect("content type is checked above");
+                request.multipart(Form::new().part("file", part))
+            },
+        )
+        .await
+    }
+
+    pub async fn get_attachment(
+        &self,
+        todo_id: i64,
+        attachment_id: i64,
+    ) -> Result<AttachmentResponse, ClientError> {
+        self.json(
+            Method::GET,
+            &format!("/todos/{}/attachments/{}", todo_id, attachment_id),
+            |request| request,
+        )
+        .await
+    }
+
+    /// ファイル本体
+    pub async fn download_attachment(
+        &self,
+        todo_id: i64,
+        attachment_id: i64,
+    ) -> Result<Vec<u8>, ClientError> {
+        let response = self
+            .send(
+                Method::GET,
+                &format!("/todos/{}/attachments/{}/content", todo_id, attachment_id),
+                |request| request,
+            )
+            .await?;
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    pub async fn delete_attachment(
+        &self,
+        todo_id: i64,
+        attachment_id: i64,
+    ) -> Result<(), ClientError> {
+        self.empty(
+            Method::DELETE,
+            &format!("/todos/{}/attachments/{}", todo_id, attachment_id),
+            |request| request,
+        )
+        .await
+    }
+
+    // ---- Webhook ----
+
+    pub async fn list_webhooks(&self) -> Result<Vec<WebhookResponse>, ClientError> {
+        self.json(Method::GET, "/webhooks", |request| request).await
+    }
+
+    pub async fn create_webhook(
+        &self,
+        body: &CreateWebhookRequest,
+    ) -> Result<WebhookResponse, ClientError> {
+        self.json(Method::POST, "/webhooks", |request| request.json(body))
+            .await
+    }
+
+    pub async fn get_webhook(&self, id: i64) -> Result<WebhookResponse, ClientError> {
+        self.json(Method::GET, &format!("/webhooks/{}", id), |request| request)
+            .await
+    }
+
+    pub async fn update_webhook(
+        &self,
+        id: i64,
+        body: &UpdateWebhookRequest,
+    ) -> Result<WebhookResponse, ClientError> {
+        self.json(Method::PUT, &format!("/webhooks/{}", id), |request| {
+            request.json(body)
+        })
+        .await
+    }
+
+    pub async fn delete_webhook(&self, id: i64) -> Result<(), ClientError> {
+        self.empty(Method::DELETE, &format!("/webhooks/{}", id), |request| {
+            request
+        })
+        .await
+    }
+
+    pub async fn list_webhook_deliveries(
+        &self,
+        id: i64,
+        query: &DeliveryListQuery,
+    ) -> Result<WebhookDeliveryPageResponse, ClientError> {
+        self.json(
+            Method::GET,
+            &format!("/webhooks/{}/deliveries", id),
+            |request| request.query(query),
+        )
+        .await
+    }
+
+    // ---- 共通 ----
+
+    async fn json<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        build: impl Fn(RequestBuilder) -> RequestBuilder,
+    ) -> Result<T, ClientError> {
+        Ok(self.send(method, path, build).await?.json().await?)
+    }
+
+    async fn empty(
+        &self,
+        method: Method,
+        path: &str,
+        build: impl Fn(RequestBuilder) -> RequestBuilder,
+    ) -> Result<(), ClientError> {
+        self.send(method, path, build).await.map(drop)
+    }
+
+    /// 成功（2xx）の応答を返す。失敗は [`RetryPolicy`] に従って送り直す
+    async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        build: impl Fn(RequestBuilder) -> RequestBuilder,
+    ) -> Result<Response, ClientError> {
+        let url = format!("{}{}", self.base_url, path);
+        let idempotent = method != Method::POST;
+        let mut attempt = 0;
+        loop {
+            let error = match build(self.request(method.clone(), &url)).send().await {
+                Ok(response) if response.status().is_success() => return Ok(response),
+                Ok(response) => ClientError::from_response(response).await,
+                Err(error) => error.into(),
+            };
+            if attempt >= self.retry.max_retries || !error.is_retryable(idempotent) {
+                return Err(error);
+            }
+            tokio::time::sleep(self.retry.backoff(attempt)).await;
+            attempt += 1;
+        }
+    }
+
+    fn request(&self, method: Method, url: &str) -> RequestBuilder {
+        let mut request = self.http.request(method, url);
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        if let Some(language) = &self.language {
+            request = request.header(reqwest::header::ACCEPT_LANGUAGE, language);
+        }
+        request
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles_up_to_the_limit() {
+        let policy = RetryPolicy {
+            max_retries: 5,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(350),
+        };
+        assert_eq!(policy.backoff(0), Duration::from_millis(100));
+        assert_eq!(policy.backoff(1), Duration::from_millis(200));
+        assert_eq!(policy.backoff(2), Duration::from_millis(350));
+        assert_eq!(policy.backoff(40), Duration::from_millis(350));
+    }
+}
@@ -0,0 +1,285 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::http::StatusCode;
+use axum::routing::get;
+use axum::{Json, Router};
+use rust_todo_app::create_test_app;
+use todo_client::{
+    ClientError, CommentListQuery, CreateCommentRequest, CreateReminderRequest, CreateTodoRequest,
+    CreateWebhookRequest, DeliveryListQuery, ReorderRequest, RetryPolicy, TodoClient,
+    UpdateCommentRequest, UpdateTodoRequest, UpdateWebhookRequest,
+};
+
+/// ルーターを実際のポートで起動し、ベースURLを返す
+async fn serve(app: Router) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{}", addr)
+}
+
+fn fast_retry(max_retries: u32) -> RetryPolicy {
+    RetryPolicy {
+        max_retries,
+        initial_backoff: Duration::from_millis(10),
+        max_backoff: Duration::from_millis(50),
+    }
+}
+
+async fn create(client: &TodoClient, title: &str) -> i64 {
+    client
+        .create_todo(&CreateTodoRequest {
+            title: title.to_string(),
+        })
+        .await
+        .unwrap()
+        .id
+}
+
+#[tokio::test]
+async fn test_client_manages_todos() {
+    let client = TodoClient::new(&serve(create_test_app().await).await).unwrap();
+    let first = create(&client, "first").await;
+    let second = create(&client, "second").await;
+
+    let updated = client
+        .update_todo(
+            first,
+            &UpdateTodoRequest {
+                completed: Some(true),
+                ..UpdateTodoRequest::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert!(updated.completed);
+    assert_eq!(updated.title, "first");
+
+    client
+        .reorder_todos(&ReorderRequest {
+            ids: vec![second, first],
+        })
+        .await
+        .unwrap();
+    let ids: Vec<i64> = client
+        .list_todos()
+        .await
+        .unwrap()
+        .iter()
+        .map(|todo| todo.id)
+        .collect();
+    assert_eq!(ids, vec![second, first]);
+
+    client.delete_todo(second).await.unwrap();
+    assert!(matches!(
+        client.get_todo(second).await,
+        Err(ClientError::NotFound(_))
+    ));
+    assert_eq!(client.get_todo(first).await.unwrap().id, first);
+}
+
+#[tokio::test]
+async fn test_client_covers_nested_resources() {
+    let client = TodoClient::new(&serve(create_test_app().await).await).unwrap();
+    let todo = create(&client, "with extras").await;
+
+    let comment = client
+        .create_comment(
+            todo,
+            &CreateCommentRequest {
+                author: "alice".to_string(),
+                body: "**hello**".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+    let edited = client
+        .update_comment(
+            todo,
+            comment.id,
+            &UpdateCommentRequest {
+                body: "edited".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+    assert!(edited.edited_at.is_some());
+    let page = client
+        .list_comments(todo, &CommentListQuery::default())
+        .await
+        .unwrap();
+    assert_eq!(page.total, 1);
+    assert_eq!(
+        client.get_comment(todo, comment.id).await.unwrap().body,
+        "edited"
+    );
+    client.delete_comment(todo, comment.id).await.unwrap();
+
+    let reminder = client
+        .create_reminder(
+            todo,
+            &CreateReminderRequest {
+                remind_at: Some(chrono::Utc::now() + chrono::Duration::hours(1)),
+                offset_minutes: None,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(client.list_reminders(todo).await.unwrap().len(), 1);
+    client.delete_reminder(todo, reminder.id).await.unwrap();
+
+    assert!(client.list_attachments(todo).await.unwrap().is_empty());
+    let error = client
+        .upload_attachment(todo, "run.exe", "application/x-msdownload", vec![0; 8])
+        .await
+        .unwrap_err();
+    assert!(matches!(error, ClientError::UnsupportedMediaType(_)));
+
+    let webhook = client
+        .create_webhook(&CreateWebhookRequest {
+            url: "https://example.com/hooks/todo".to_string(),
+            secret: "0123456789abcdef".to_string(),
+            events: vec!["todo.created".to_string()],
+        })
+        .await
+        .unwrap();
+    let webhook = client
+        .update_webhook(
+            webhook.id,
+            &UpdateWebhookRequest {
+                events: Some(vec!["todo.deleted".to_string()]),
+                ..UpdateWebhookRequest::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(webhook.url, "https://example.com/hooks/todo");
+    assert_eq!(webhook.events, vec!["todo.deleted"]);
+    assert_eq!(client.list_webhooks().await.unwrap().len(), 1);
+    let deliveries = client
+        .list_webhook_deliveries(webhook.id, &DeliveryListQuery::default())
+        .await
+        .unwrap();
+    assert_eq!(deliveries.total, 0);
+    client.delete_webhook(webhook.id).await.unwrap();
+    assert!(matches!(
+        client.get_webhook(webhook.id).await,
+        Err(ClientError::NotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_client_maps_problem_details() {
+    let client = TodoClient::builder(&serve(create_test_app().await).await)
+        .language("en")
+        .build()
+        .unwrap();
+
+    let error = client.get_todo(999).await.unwrap_err();
+    assert!(matches!(error, ClientError::NotFound(_)));
+    assert_eq!(error.code(), Some("not_found"));
+    assert_eq!(error.status(), Some(StatusCode::NOT_FOUND));
+
+    let error = client
+        .create_todo(&CreateTodoRequest {
+            title: String::new(),
+        })
+        .await
+        .unwrap_err();
+    let ClientError::InvalidInput(problem) = &error else {
+        panic!("unexpected error: {:?}", error);
+    };
+    assert_eq!(problem.code, "invalid_fields");
+    assert_eq!(problem.errors[0].field, "title");
+    assert!(problem
+        .detail
+        .as_deref()
+        .unwrap()
+        .starts_with("One or more fields are invalid"));
+}
+
+#[tokio::test]
+async fn test_client_retries_idempotent_requests() {
+    // 最初の2回は503を返す
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let flaky = move || {
+        let counter = counter.clone();
+        async move {
+            if counter.fetch_add(1, Ordering::SeqCst) < 2 {
+                Err(StatusCode::SERVICE_UNAVAILABLE)
+            } else {
+                Ok(Json(serde_json::json!([])))
+            }
+        }
+    };
+    let url = serve(Router::new().route("/api/v1/todos", get(flaky.clone()).post(flaky))).await;
+
+    let client = TodoClient::builder(&url)
+        .retry(fast_retry(2))
+        .build()
+        .unwrap();
+    assert!(client.list_todos().await.unwrap().is_empty());
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+    // 作成は二重にならないよう送り直さない
+    calls.store(0, Ordering::SeqCst);
+    let error = client
+        .create_todo(&CreateTodoRequest {
+            title: "once".to_string(),
+        })
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        ClientError::UnexpectedStatus {
+            status: StatusCode::SERVICE_UNAVAILABLE,
+            ..
+        }
+    ));
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    // 回数を使い切ったら最後のエラーを返す
+    calls.store(0, Ordering::SeqCst);
+    let client = TodoClient::builder(&url)
+        .retry(fast_retry(1))
+        .build()
+        .unwrap();
+    assert!(client.list_todos().await.is_err());
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_client_times_out() {
+    let slow = || async {
+        tokio::time::sleep(Duration::from_secs(5)).await;
+        Json(serde_json::json!([]))
+    };
+    let url = serve(Router::new().route("/api/v1/todos", get(slow))).await;
+
+    let client = TodoClient::builder(&url)
+        .timeout(Duration::from_millis(100))
+        .retry(RetryPolicy::none())
+        .build()
+        .unwrap();
+    let error = client.list_todos().await.unwrap_err();
+    assert!(matches!(error, ClientError::Timeout(_)), "{:?}", error);
+}
+
+#[tokio::test]
+async fn test_client_retries_connection_failures() {
+    // 誰も待ち受けていないポート
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let closed = format!("http://{}", listener.local_addr().unwrap());
+    drop(listener);
+
+    let client = TodoClient::builder(&closed)
+        .retry(fast_retry(1))
+        .build()
+        .unwrap();
+    let error = client.list_todos().await.unwrap_err();
+    assert!(matches!(error, ClientError::Transport(_)), "{:?}", error);
+    assert_eq!(error.status(), None);
+}
@@ -0,0 +1,336 @@
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use todo_client::{TodoResponse, UpdateTodoRequest};
+
+/// 入力の受け付け方
+pub enum Mode {
+    Normal,
+    /// 選択中のTODOのタイトルを書き換えている
+    Editing {
+        input: String,
+    },
+    /// 絞り込みの文字列を入力している
+    Filtering,
+}
+
+/// キー操作の結果、バックエンドに頼むこと
+pub enum Command {
+    Update(i64, UpdateTodoRequest),
+    /// 全件の新しい並び順
+    Reorder(Vec<i64>),
+    Reload,
+    Quit,
+}
+
+/// 画面の状態。描画とバックエンドの呼び出しは持たず、キーから [`Command`] を決めるだけ
+pub struct App {
+    todos: Vec<TodoResponse>,
+    /// 選択中のTODOのID。一覧を読み直しても同じTODOを指し続ける
+    selected: Option<i64>,
+    pub filter: String,
+    pub mode: Mode,
+    /// 直前の操作のエラーなど
+    pub status: Option<String>,
+}
+
+impl App {
+    pub fn new(todos: Vec<TodoResponse>) -> Self {
+        let mut app = Self {
+            todos: Vec::new(),
+            selected: None,
+            filter: String::new(),
+            mode: Mode::Normal,
+            status: None,
+        };
+        app.set_todos(todos);
+        app
+    }
+
+    /// 読み直した一覧に差し替える。選択中のTODOが消えた場合は近い位置を選ぶ
+    pub fn set_todos(&mut self, todos: Vec<TodoResponse>) {
+        let index = self.selected_index().unwrap_or(0);
+        self.todos = todos;
+        if self.selected_index().is_none() {
+            self.select_at(index);
+        }
+    }
+
+    /// 絞り込み後の、並び順のTODO。大文字小文字は区別しない
+    pub fn visible(&self) -> Vec<&TodoResponse> {
+        let filter = self.filter.to_lowercase();
+        self.todos
+            .iter()
+            .filter(|todo| todo.title.to_lowercase().contains(&filter))
+            .collect()
+    }
+
+    /// `visible()` の中での選択位置
+    pub fn selected_index(&self) -> Option<usize> {
+        let selected = self.selected?;
+        self.visible().iter().position(|todo| todo.id == selected)
+    }
+
+    fn selected_todo(&self) -> Option<&TodoResponse> {
+        let selected = self.selected?;
+        self.todos.iter().find(|todo| todo.id == selected)
+    }
+
+    fn select_at(&mut self, index: usize) {
+        let visible = self.visible();
+        self.selected = visible
+            .get(index.min(visible.len().saturating_sub(1)))
+            .map(|todo| todo.id);
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Option<Command> {
+        if key.kind != KeyEventKind::Press {
+            return None;
+        }
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Some(Command::Quit);
+        }
+        match &mut self.mode {
+            Mode::Normal => self.handle_normal(key),
+            Mode::Editing { input } => match key.code {
+                KeyCode::Enter => {
+                    let title = input.trim().to_string();
+                    self.mode = Mode::Normal;
+                    let todo = self.selected_todo()?;
+                    if title == todo.title {
+                        return None;
+                    }
+                    Some(Command::Update(
+                        todo.id,
+                        UpdateTodoRequest {
+                            title: Some(title),
+                            ..UpdateTodoRequest::default()
+                        },
+                    ))
+                }
+                KeyCode::Esc => {
+                    self.mode = Mode::Normal;
+                    None
+                }
+                KeyCode::Backspace => {
+                    input.pop();
+                    None
+                }
+                KeyCode::Char(c) => {
+                    input.push(c);
+                    None
+                }
+                _ => None,
+            },
+            Mode::Filtering => {
+                match key.code {
+                    KeyCode::Enter => self.mode = Mode::Normal,
+                    KeyCode::Esc => {
+                        self.filter.clear();
+                        self.mode = Mode::Normal;
+                    }
+                    KeyCode::Backspace => {
+                        self.filter.pop();
+                    }
+                    KeyCode::Char(c) => self.filter.push(c),
+                    _ => return None,
+                }
+                // 選択中のTODOが絞り込みで隠れたら先頭を選ぶ
+                if self.selected_index().is_none() {
+                    self.select_at(0);
+                }
+                None
+            }
+        }
+    }
+
+    fn handle_normal(&mut self, key: KeyEvent) -> Option<Command> {
+        self.status = None;
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        let index = self.selected_index();
+        match key.code {
+            KeyCode::Char('q') => return Some(Command::Quit),
+            KeyCode::Esc if !self.filter.is_empty() => {
+                self.filter.clear();
+            }
+            KeyCode::Esc => return Some(Command::Quit),
+            KeyCode::Char('K') => return self.move_selected(-1),
+            KeyCode::Char('J') => return self.move_selected(1),
+            KeyCode::Up if shift => return self.move_selected(-1),
+            KeyCode::Down if shift => return self.move_selected(1),
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.select_at(index.unwrap_or(0).saturating_sub(1));
+            }
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.select_at(index.map_or(0, |index| index + 1));
+            }
+            KeyCode::Char('g') | KeyCode::Home => self.select_at(0),
+            KeyCode::Char('G') | KeyCode::End => self.select_at(usize::MAX),
+            KeyCode::Char(' ') | KeyCode::Char('x') => {
+                let todo = self.selected_todo()?;
+                return Some(Command::Update(
+                    todo.id,
+                    UpdateTodoRequest {
+                        completed: Some(!todo.completed),
+                        ..UpdateTodoRequest::default()
+                    },
+                ));
+            }
+            KeyCode::Char('e') | KeyCode::Enter => {
+                let input = self.selected_todo()?.title.clone();
+                self.mode = Mode::Editing { input };
+            }
+            KeyCode::Char('/') => self.mode = Mode::Filtering,
+            KeyCode::Char('r') => return Some(Command::Reload),
+            _ => {}
+        }
+        None
+    }
+
+    /// 選択中のTODOを、表示中の隣のTODOの前（上へ）か後ろ（下へ）へ移した全件の並び順。
+    /// 絞り込み中は隠れているTODOを飛び越えて動く
+    fn move_selected(&self, offset: isize) -> Option<Command> {
+        let selected = self.selected?;
+        let index = self.selected_index()?;
+        let neighbor = self.visible().get(index.checked_add_signed(offset)?)?.id;
+
+        let mut ids: Vec<i64> = self.todos.iter().map(|todo| todo.id).collect();
+        ids.retain(|&id| id != selected);
+        let at = ids.iter().position(|&id| id == neighbor)?;
+        ids.insert(if offset > 0 { at + 1 } else { at }, selected);
+        Some(Command::Reorder(ids))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn todo(id: i64, title: &str, completed: bool) -> TodoResponse {
+        TodoResponse {
+            id,
+            title: title.to_string(),
+            completed,
+            position: id,
+            due_at: None,
+        }
+    }
+
+    fn app() -> App {
+        App::new(vec![
+            todo(1, "Buy milk", false),
+            todo(2, "Write report", true),
+            todo(3, "Call mom", false),
+            todo(4, "buy bread", false),
+        ])
+    }
+
+    fn press(app: &mut App, code: KeyCode) -> Option<Command> {
+        app.handle_key(KeyEvent::from(code))
+    }
+
+    fn type_text(app: &mut App, text: &str) {
+        for c in text.chars() {
+            press(app, KeyCode::Char(c));
+        }
+    }
+
+    #[test]
+    fn test_toggle_flips_completion_of_selected() {
+        let mut app = app();
+        press(&mut app, KeyCode::Down);
+        assert!(matches!(
+            press(&mut app, KeyCode::Char(' ')),
+            Some(Command::Update(
+                2,
+                UpdateTodoRequest {
+                    completed: Some(false),
+                    title: None,
+                    ..
+                }
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_inline_edit_sends_new_title() {
+        let mut app = app();
+        press(&mut app, KeyCode::Char('e'));
+        for _ in 0.."milk".len() {
+            press(&mut app, KeyCode::Backspace);
+        }
+        type_text(&mut app, "eggs ");
+        let command = press(&mut app, KeyCode::Enter);
+        let Some(Command::Update(1, update)) = command else {
+            panic!("expected an update");
+        };
+        assert_eq!(update.title.as_deref(), Some("Buy eggs"));
+        assert!(matches!(app.mode, Mode::Normal));
+
+        // 変えずに確定、Escで取り消しなら何も送らない
+        press(&mut app, KeyCode::Enter);
+        assert!(press(&mut app, KeyCode::Enter).is_none());
+        press(&mut app, KeyCode::Enter);
+        type_text(&mut app, "!!");
+        assert!(press(&mut app, KeyCode::Esc).is_none());
+    }
+
+    #[test]
+    fn test_move_sends_full_order() {
+        let mut app = app();
+        // 先頭を下へ
+        let Some(Command::Reorder(ids)) = press(&mut app, KeyCode::Char('J')) else {
+            panic!("expected a reorder");
+        };
+        assert_eq!(ids, vec![2, 1, 3, 4]);
+
+        // 端ではそれ以上動かない
+        assert!(press(&mut app, KeyCode::Char('K')).is_none());
+        app.handle_key(KeyEvent::new(KeyCode::End, KeyModifiers::NONE));
+        assert!(app
+            .handle_key(KeyEvent::new(KeyCode::Down, KeyModifiers::SHIFT))
+            .is_none());
+        let Some(Command::Reorder(ids)) =
+            app.handle_key(KeyEvent::new(KeyCode::Up, KeyModifiers::SHIFT))
+        else {
+            panic!("expected a reorder");
+        };
+        assert_eq!(ids, vec![1, 2, 4, 3]);
+    }
+
+    #[test]
+    fn test_filter_narrows_list_and_moves_across_hidden() {
+        let mut app = app();
+        press(&mut app, KeyCode::Char('/'));
+        type_text(&mut app, "BUY");
+        press(&mut app, KeyCode::Enter);
+        let titles: Vec<&str> = app.visible().iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, vec!["Buy milk", "buy bread"]);
+
+        // 隠れている2と3を飛び越えて4の後ろへ
+        let Some(Command::Reorder(ids)) = press(&mut app, KeyCode::Char('J')) else {
+            panic!("expected a reorder");
+        };
+        assert_eq!(ids, vec![2, 3, 4, 1]);
+
+        // Escで絞り込みを外す（終了はしない）
+        assert!(press(&mut app, KeyCode::Esc).is_none());
+        assert_eq!(app.visible().len(), 4);
+        assert!(matches!(press(&mut app, KeyCode::Esc), Some(Command::Quit)));
+    }
+
+    #[test]
+    fn test_selection_follows_todo_across_reload() {
+        let mut app = app();
+        press(&mut app, KeyCode::Char('G'));
+        app.set_todos(vec![
+            todo(4, "buy bread", false),
+            todo(1, "Buy milk", false),
+        ]);
+        assert_eq!(app.selected_index(), Some(0));
+
+        // 選択中のTODOが消えたら同じ位置のTODOを選ぶ
+        app.set_todos(vec![todo(1, "Buy milk", false)]);
+        assert_eq!(app.selected_index(), Some(0));
+        app.set_todos(Vec::new());
+        assert_eq!(app.selected_index(), None);
+    }
+}
@@ -0,0 +1,187 @@
+use std::sync::Arc;
+
+use rust_todo_app::application::errors::AppError;
+use rust_todo_app::application::ports::edit_locks::NoEditLocks;
+use rust_todo_app::application::usecases::todo::edit_lock::Editor;
+use rust_todo_app::application::usecases::todo::{
+    list as list_usecase, reorder as reorder_usecase, update as update_usecase,
+};
+use rust_todo_app::infrastructure::clock::SystemClock;
+use rust_todo_app::infrastructure::events::fanout_publisher::FanoutPublisher;
+use rust_todo_app::infrastructure::persistence::sqlite_todo_repo::TodoStore;
+use sqlx::SqlitePool;
+use todo_client::{ProblemDetails, ReorderRequest, TodoClient, TodoResponse, UpdateTodoRequest};
+use validator::Validate;
+
+/// TODOの読み書き先。HTTPのAPIか、手元のSQLiteファイル
+pub enum Backend {
+    Http { client: TodoClient, url: String },
+    Local(LocalStore),
+}
+
+/// SQLiteを直接読み書きする。サーバと同じユースケースとストアを通すので、
+/// 変更はWebhookのアウトボックスにも積まれ、次にサーバを起動したときに送られる
+pub struct LocalStore {
+    todos: TodoStore,
+    /// このプロセスにはイベントを受け取る相手がいない
+    events: FanoutPublisher,
+    path: String,
+}
+
+impl LocalStore {
+    pub fn new(pool: SqlitePool, path: String) -> Self {
+        Self {
+            todos: TodoStore::with_webhook_outbox(pool, Arc::new(SystemClock)),
+            events: FanoutPublisher::new(Vec::new()),
+            path,
+        }
+    }
+}
+
+impl Backend {
+    /// 画面のタイトルに出す接続先
+    pub fn describe(&self) -> &str {
+        match self {
+            Backend::Http { url, .. } => url,
+            Backend::Local(store) => &store.path,
+        }
+    }
+
+    /// 並び順で全件
+    pub async fn list(&self) -> Result<Vec<TodoResponse>, String> {
+        match self {
+            Backend::Http { client, .. } => client.list_todos().await.map_err(describe_client),
+            Backend::Local(store) => list_usecase::execute(&store.todos)
+                .await
+                .map(|todos| todos.into_iter().map(Into::into).collect())
+                .map_err(|e| describe_app(&e)),
+        }
+    }
+
+    pub async fn update(&self, id: i64, update: UpdateTodoRequest) -> Result<(), String> {
+        match self {
+            Backend::Http { client, .. } => client
+                .update_todo(id, &update)
+                .await
+                .map(drop)
+                .map_err(describe_client),
+            Backend::Local(store) => {
+                // HTTPではハンドラが行う入力チェックをここで行う
+                update
+                    .validate()
+                    .map_err(|e| describe_app(&AppError::from(e)))?;
+                let id = u32::try_from(id).map_err(|_| describe_app(&AppError::NotFound))?;
+                match update_usecase::execute(
+                    &store.todos,
+                    &store.events,
+                    // 編集ロックはサーバのプロセス内にしかなく、ここからは見えない
+                    Editor::anonymous(&NoEditLocks),
+                    id,
+                    update.title,
+                    update.completed,
+                    update.due_at,
+                )
+                .await
+                {
+                    Ok(Some(_)) => Ok(()),
+                    Ok(None) => Err(describe_app(&AppError::NotFound)),
+                    Err(e) => Err(describe_app(&e)),
+                }
+            }
+        }
+    }
+
+    pub async fn reorder(&self, ids: Vec<i64>) -> Result<(), String> {
+        match self {
+            Backend::Http { client, .. } => client
+                .reorder_todos(&ReorderRequest { ids })
+                .await
+                .map_err(describe_client),
+            // 手元のファイルを直接並び替えるので、サーバのようなIDの数の上限は設けない
+            Backend::Local(store) => {
+                reorder_usecase::execute(&store.todos, &store.events, ids, usize::MAX)
+                    .await
+                    .map_err(|e| describe_app(&e))
+            }
+        }
+    }
+}
+
+fn describe_client(error: todo_client::ClientError) -> String {
+    match error.problem() {
+        Some(problem) => describe_problem(problem),
+        None => error.to_string(),
+    }
+}
+
+fn describe_app(error: &AppError) -> String {
+    describe_problem(&error.to_problem())
+}
+
+/// ステータス行に収まるよう、説明と項目ごとの内訳だけを1行にする
+fn describe_problem(problem: &ProblemDetails) -> String {
+    let mut message = problem.detail.clone().unwrap_or(problem.title.clone());
+    for error in &problem.errors {
+        message.push_str(&format!(" / {}: {}", error.field, error.message));
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_todo_app::application::ports::todo_repository::TodoRepository;
+    use rust_todo_app::config::DatabaseConfig;
+    use rust_todo_app::connect_database;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_local_store_updates_and_reorders() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("todos.db");
+        let pool = connect_database(&DatabaseConfig {
+            url: format!("sqlite:{}", path.display()),
+            ..DatabaseConfig::default()
+        })
+        .await
+        .unwrap();
+        let seed = TodoStore::new(pool.clone());
+        let first = seed.create("first".to_string()).await.unwrap();
+        let second = seed.create("second".to_string()).await.unwrap();
+        let backend = Backend::Local(LocalStore::new(pool, path.display().to_string()));
+
+        backend
+            .update(
+                first.id,
+                UpdateTodoRequest {
+                    completed: Some(true),
+                    ..UpdateTodoRequest::default()
+                },
+            )
+            .await
+            .unwrap();
+        backend.reorder(vec![second.id, first.id]).await.unwrap();
+
+        let todos = backend.list().await.unwrap();
+        let ids: Vec<i64> = todos.iter().map(|todo| todo.id).collect();
+        assert_eq!(ids, vec![second.id, first.id]);
+        assert!(todos[1].completed);
+
+        // HTTPと同じ入力チェックを通る
+        let error = backend
+            .update(
+                first.id,
+                UpdateTodoRequest {
+                    title: Some(String::new()),
+                    ..UpdateTodoRequest::default()
+                },
+            )
+            .await
+            .unwrap_err();
+        assert!(error.contains("title"), "{}", error);
+        assert!(backend
+            .update(999, UpdateTodoRequest::default())
+            .await
+            .is_err());
+    }
+}
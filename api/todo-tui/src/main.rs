@@ -0,0 +1,126 @@
+//! TODOをターミナルで一覧・編集するTUI。HTTPのAPI（`/api/v1`）か、
+//! `--db` で指定したSQLiteファイルを直接読み書きする（オフライン用）
+mod app;
+mod backend;
+mod ui;
+
+use std::io;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::Parser;
+use ratatui::crossterm::event::{self, Event};
+use ratatui::DefaultTerminal;
+use rust_todo_app::config::DatabaseConfig;
+use rust_todo_app::connect_database;
+use todo_client::TodoClient;
+use tokio::runtime::Runtime;
+
+use crate::app::{App, Command};
+use crate::backend::{Backend, LocalStore};
+
+#[derive(Parser)]
+#[command(name = "todo-tui", about = "Browse and edit todos in the terminal")]
+struct Cli {
+    /// APIのベースURL（`/api/v1` は付けない）
+    #[arg(long, env = "TODO_API_URL", default_value = "http://localhost:3000")]
+    url: String,
+    /// `Authorization: Bearer` で送るトークン
+    #[arg(long, env = "TODO_API_TOKEN", hide_env_values = true)]
+    token: Option<String>,
+    /// APIを使わず、このSQLiteファイルを直接開く。`--url` より優先する
+    #[arg(long, env = "TODO_DB")]
+    db: Option<PathBuf>,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let runtime = Runtime::new().expect("failed to start the async runtime");
+
+    let backend = match cli.db {
+        Some(path) => {
+            let database = DatabaseConfig {
+                url: format!("sqlite:{}", path.display()),
+                ..DatabaseConfig::default()
+            };
+            match runtime.block_on(connect_database(&database)) {
+                Ok(pool) => Backend::Local(LocalStore::new(pool, path.display().to_string())),
+                Err(error) => {
+                    eprintln!("error: failed to open {}: {}", path.display(), error);
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+        None => {
+            let mut builder = TodoClient::builder(&cli.url);
+            if let Some(token) = cli.token {
+                builder = builder.token(token);
+            }
+            match builder.build() {
+                Ok(client) => Backend::Http {
+                    client,
+                    url: cli.url,
+                },
+                Err(error) => {
+                    eprintln!("error: {}", error);
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+    };
+
+    // 最初の読み込みに失敗したら画面を開かずに終わる
+    let todos = match runtime.block_on(backend.list()) {
+        Ok(todos) => todos,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut terminal = ratatui::init();
+    let result = run(&mut terminal, &runtime, &backend, App::new(todos));
+    ratatui::restore();
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(
+    terminal: &mut DefaultTerminal,
+    runtime: &Runtime,
+    backend: &Backend,
+    mut app: App,
+) -> io::Result<()> {
+    loop {
+        terminal.draw(|frame| ui::draw(frame, &app, backend.describe()))?;
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        let command = match app.handle_key(key) {
+            Some(Command::Quit) => return Ok(()),
+            Some(command) => command,
+            None => continue,
+        };
+
+        // 操作のあとは成否によらず読み直し、ほかのクライアントの変更も取り込む
+        let result = runtime.block_on(async {
+            match command {
+                Command::Update(id, update) => backend.update(id, update).await,
+                Command::Reorder(ids) => backend.reorder(ids).await,
+                Command::Reload | Command::Quit => Ok(()),
+            }
+        });
+        match runtime.block_on(backend.list()) {
+            Ok(todos) => app.set_todos(todos),
+            Err(message) => app.status = Some(message),
+        }
+        if let Err(message) = result {
+            app.status = Some(message);
+        }
+    }
+}
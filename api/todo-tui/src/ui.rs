@@ -0,0 +1,71 @@
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph};
+use ratatui::Frame;
+
+use crate::app::{App, Mode};
+
+const HELP: &str = "j/k move  space toggle  e edit  J/K reorder  / filter  r reload  q quit";
+
+pub fn draw(frame: &mut Frame, app: &App, source: &str) {
+    let [list_area, footer_area] =
+        Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(frame.area());
+
+    let selected = app.selected_index();
+    let items: Vec<ListItem> = app
+        .visible()
+        .into_iter()
+        .enumerate()
+        .map(|(index, todo)| {
+            let check = if todo.completed { "[x] " } else { "[ ] " };
+            let mut spans = vec![Span::raw(check)];
+            match &app.mode {
+                // 編集中の行はタイトルの代わりに入力中の文字列を出す
+                Mode::Editing { input } if Some(index) == selected => {
+                    spans.push(Span::styled(
+                        format!("{}▏", input),
+                        Style::new().fg(Color::Yellow),
+                    ));
+                }
+                _ => {
+                    let style = if todo.completed {
+                        Style::new().add_modifier(Modifier::DIM | Modifier::CROSSED_OUT)
+                    } else {
+                        Style::new()
+                    };
+                    spans.push(Span::styled(todo.title.clone(), style));
+                }
+            }
+            if let Some(due_at) = todo.due_at {
+                spans.push(Span::styled(
+                    format!("  due {}", due_at.format("%Y-%m-%d %H:%M")),
+                    Style::new().fg(Color::DarkGray),
+                ));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let mut title = format!(" todo — {} ", source);
+    if !app.filter.is_empty() {
+        title.push_str(&format!("[filter: {}] ", app.filter));
+    }
+    let list = List::new(items)
+        .block(Block::bordered().title(title))
+        .highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+    frame.render_stateful_widget(
+        list,
+        list_area,
+        &mut ListState::default().with_selected(selected),
+    );
+
+    let footer = match (&app.mode, &app.status) {
+        (Mode::Editing { .. }, _) => Line::raw("Enter save  Esc cancel"),
+        (Mode::Filtering, _) => Line::raw(format!("/{}▏", app.filter)),
+        (Mode::Normal, Some(status)) => Line::styled(status.clone(), Style::new().fg(Color::Red)),
+        (Mode::Normal, None) => Line::styled(HELP, Style::new().fg(Color::DarkGray)),
+    };
+    frame.render_widget(Paragraph::new(footer), footer_area);
+}
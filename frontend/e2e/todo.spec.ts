@@ -1,6 +1,6 @@
 import { test, expect, APIRequestContext } from '@playwright/test';
 
-const apiUrl = process.env.API_URL ?? 'http://localhost:3000';
+const apiUrl = `${process.env.API_URL ?? 'http://localhost:3000'}/api/v1`;
 
 async function cleanupTodos(request: APIRequestContext) {
   const res = await request.get(`${apiUrl}/todos`);
@@ -7,8 +7,10 @@ const API_URL =
       'http://localhost:3000'
     : process.env.NEXT_PUBLIC_API_URL || 'http://localhost:3000';
 
+const API_BASE = `${API_URL}/api/v1`;
+
 export async function getTodos(): Promise<Todo[]> {
-  const response = await fetch(`${API_URL}/todos`);
+  const response = await fetch(`${API_BASE}/todos`);
   if (!response.ok) {
     throw new Error('Failed to fetch todos');
   }
@@ -16,7 +18,7 @@ export async function getTodos(): Promise<Todo[]> {
 }
 
 export async function createTodo(title: string): Promise<Todo> {
-  const response = await fetch(`${API_URL}/todos`, {
+  const response = await fetch(`${API_BASE}/todos`, {
     method: 'POST',
     headers: {
       'Content-Type': 'application/json',
@@ -34,7 +36,7 @@ export async function updateTodo(
   id: number,
   data: { title?: string; completed?: boolean }
 ): Promise<Todo> {
-  const response = await fetch(`${API_URL}/todos/${id}`, {
+  const response = await fetch(`${API_BASE}/todos/${id}`, {
     method: 'PUT',
     headers: {
       'Content-Type': 'application/json',
@@ -48,7 +50,7 @@ export async function updateTodo(
 }
 
 export async function deleteTodo(id: number): Promise<void> {
-  const response = await fetch(`${API_URL}/todos/${id}`, {
+  const response = await fetch(`${API_BASE}/todos/${id}`, {
     method: 'DELETE',
   })
   if (!response.ok) {
@@ -57,7 +59,7 @@ export async function deleteTodo(id: number): Promise<void> {
 }
 
 export async function reorderTodos(ids: number[]): Promise<void> {
-  const response = await fetch(`${API_URL}/todos/reorder`, {
+  const response = await fetch(`${API_BASE}/todos/reorder`, {
     method: 'PUT',
     headers: {
       'Content-Type': 'application/json',
@@ -25,7 +25,7 @@ docker compose -p "$PROJECT_NAME" -f "$COMPOSE_FILE" --env-file "$ENV_FILE" up -
 
 # Wait for API
 for _ in {1..30}; do
-  if curl -fsS "$API_URL/todos" >/dev/null; then
+  if curl -fsS "$API_URL/api/v1/todos" >/dev/null; then
     break
   fi
   sleep 2